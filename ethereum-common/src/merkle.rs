@@ -1,7 +1,7 @@
 use super::{
     beacon::{BlockHeader as BeaconBlockHeader, SyncCommittee},
-    network::Network,
-    utils, TreeHash,
+    network::{ForkName, Network},
+    TreeHash,
 };
 use ring::digest::{Context as RingContext, SHA256 as RingSHA256};
 
@@ -117,11 +117,8 @@ pub fn is_next_committee_proof_valid(
 ///
 /// Returns (`depth`, `index`) pair of the generalized merkle index to check a merkle proof of
 /// a current sync committee.
-pub const fn depth_index_current(network: &Network, slot: u64) -> (u32, u32) {
-    let epoch_electra = network.epoch_electra();
-    let epoch = utils::calculate_epoch(slot);
-
-    if epoch >= epoch_electra {
+pub fn depth_index_current(network: &Network, slot: u64) -> (u32, u32) {
+    if network.fork_at(slot).name >= ForkName::Electra {
         return (
             electra::DEPTH_CURRENT_SYNC_COMMITTEE,
             electra::INDEX_CURRENT_SYNC_COMMITTEE,
@@ -135,11 +132,8 @@ pub const fn depth_index_current(network: &Network, slot: u64) -> (u32, u32) {
 ///
 /// Returns (`depth`, `index`) pair of the generalized merkle index to check a merkle proof of
 /// a next sync committee.
-pub const fn depth_index_next(network: &Network, slot: u64) -> (u32, u32) {
-    let epoch_electra = network.epoch_electra();
-    let epoch = utils::calculate_epoch(slot);
-
-    if epoch >= epoch_electra {
+pub fn depth_index_next(network: &Network, slot: u64) -> (u32, u32) {
+    if network.fork_at(slot).name >= ForkName::Electra {
         return (
             electra::DEPTH_NEXT_SYNC_COMMITTEE,
             electra::INDEX_NEXT_SYNC_COMMITTEE,
@@ -153,11 +147,8 @@ pub const fn depth_index_next(network: &Network, slot: u64) -> (u32, u32) {
 ///
 /// Returns (`depth`, `index`) pair of the generalized merkle index to check a merkle proof of
 /// a finality update.
-pub const fn depth_index_finality(network: &Network, slot: u64) -> (u32, u32) {
-    let epoch_electra = network.epoch_electra();
-    let epoch = utils::calculate_epoch(slot);
-
-    if epoch >= epoch_electra {
+pub fn depth_index_finality(network: &Network, slot: u64) -> (u32, u32) {
+    if network.fork_at(slot).name >= ForkName::Electra {
         return (electra::DEPTH_FINALITY, electra::INDEX_FINALITY);
     }
 
@@ -9,9 +9,101 @@ pub enum Network {
     Sepolia,
     Holesky,
     Hoodi,
+    /// Network which parameters aren't known in advance, e.g. a private devnet.
+    Custom(CustomNetwork),
+}
+
+/// Hard forks known to the bridge. Forks are ordered by their activation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, TypeInfo)]
+pub enum ForkName {
+    Deneb,
+    Electra,
+    Fulu,
+}
+
+/// Fork activated at the specified epoch.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct Fork {
+    pub name: ForkName,
+    pub version: [u8; 4],
+    pub epoch: u64,
+}
+
+impl Fork {
+    const fn new(name: ForkName, version: [u8; 4], epoch: u64) -> Self {
+        Self {
+            name,
+            version,
+            epoch,
+        }
+    }
+}
+
+// According to https://github.com/eth-clients/mainnet/blob/main/metadata/config.yaml
+const FORKS_MAINNET: &[Fork] = &[
+    Fork::new(ForkName::Deneb, hex!("04000000"), 269_568),
+    Fork::new(ForkName::Electra, hex!("05000000"), 364_032),
+    Fork::new(ForkName::Fulu, hex!("06000000"), 411_392),
+];
+
+// According to https://github.com/eth-clients/sepolia/blob/main/metadata/config.yaml
+const FORKS_SEPOLIA: &[Fork] = &[
+    Fork::new(ForkName::Deneb, hex!("90000073"), 132_608),
+    Fork::new(ForkName::Electra, hex!("90000074"), 222_464),
+    Fork::new(ForkName::Fulu, hex!("90000075"), 272_640),
+];
+
+// According to https://github.com/eth-clients/holesky/blob/main/metadata/config.yaml
+const FORKS_HOLESKY: &[Fork] = &[
+    Fork::new(ForkName::Deneb, hex!("05017000"), 29_696),
+    Fork::new(ForkName::Electra, hex!("06017000"), 115_968),
+    Fork::new(ForkName::Fulu, hex!("07017000"), 165_120),
+];
+
+// According to https://github.com/eth-clients/hoodi/blob/main/metadata/config.yaml
+const FORKS_HOODI: &[Fork] = &[
+    Fork::new(ForkName::Deneb, hex!("50000910"), 0),
+    Fork::new(ForkName::Electra, hex!("60000910"), 2_048),
+    Fork::new(ForkName::Fulu, hex!("70000910"), 50_688),
+];
+
+/// Parameters of the network required by the light client.
+///
+/// Only networks with the mainnet preset are supported: the sync committee size
+/// ([`SYNC_COMMITTEE_SIZE`]) and the period length ([`EPOCHS_PER_SYNC_COMMITTEE`]) are
+/// fixed, so devnets running the minimal preset can't be tracked even if `slots_per_epoch`
+/// matches.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct CustomNetwork {
+    pub genesis_validators_root: [u8; 32],
+    /// Fork schedule ordered by the activation epoch. The first fork is assumed to be
+    /// active since the genesis.
+    pub forks: Vec<Fork>,
+    pub slots_per_epoch: u64,
+}
+
+impl CustomNetwork {
+    /// Checks that the fork schedule isn't empty and is ordered by both the activation
+    /// epoch and the fork name, and that the epoch length is non-zero.
+    pub fn is_valid(&self) -> bool {
+        self.slots_per_epoch > 0
+            && !self.forks.is_empty()
+            && self
+                .forks
+                .windows(2)
+                .all(|forks| forks[0].epoch < forks[1].epoch && forks[0].name < forks[1].name)
+    }
 }
 
 impl Network {
+    /// Checks parameters of the custom network. Predefined networks are always valid.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Custom(network) => network.is_valid(),
+            _ => true,
+        }
+    }
+
     pub fn genesis_validators_root(&self) -> Hash256 {
         match self {
             Mainnet => hex!("4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95"),
@@ -19,56 +111,151 @@ impl Network {
             Holesky => hex!("9143aa7c615a7f7115e2b6aac319c03529df8242ae705fba9df39b79c59fa8b1"),
             // According to https://github.com/eth-clients/hoodi/blob/2b03cffba84b50759b3476a69334fac8412e217c/metadata/genesis_validators_root.txt
             Hoodi => hex!("212f13fc4df078b6cb7db228f1c8307566dcecf900867401a92023d7ba99cb5f"),
+            Custom(network) => network.genesis_validators_root,
         }
         .into()
     }
 
-    pub fn fork_version(&self, slot: u64) -> [u8; 4] {
-        let epoch_electra = self.epoch_electra();
-        let epoch = utils::calculate_epoch(slot);
+    /// Fork schedule of the network ordered by the activation epoch.
+    pub fn forks(&self) -> &[Fork] {
         match self {
-            Mainnet => {
-                if epoch >= epoch_electra {
-                    return hex!("05000000");
-                }
-
-                hex!("04000000")
-            }
-
-            Sepolia => {
-                if epoch >= epoch_electra {
-                    return hex!("90000074");
-                }
-
-                hex!("90000073")
-            }
-
-            Holesky => {
-                if epoch >= epoch_electra {
-                    return hex!("06017000");
-                }
-
-                hex!("05017000")
-            }
-
-            Hoodi => {
-                // According to https://github.com/eth-clients/hoodi/blob/2b03cffba84b50759b3476a69334fac8412e217c/metadata/config.yaml
-                if epoch >= epoch_electra {
-                    return hex!("60000910");
-                }
-
-                hex!("50000910")
-            }
+            Mainnet => FORKS_MAINNET,
+            Sepolia => FORKS_SEPOLIA,
+            Holesky => FORKS_HOLESKY,
+            Hoodi => FORKS_HOODI,
+            Custom(network) => &network.forks,
         }
     }
 
+    /// Fork active at the epoch. The earliest known fork is returned for the epochs
+    /// preceding it.
+    pub fn fork_at_epoch(&self, epoch: u64) -> &Fork {
+        let forks = self.forks();
+
+        forks
+            .iter()
+            .rev()
+            .find(|fork| fork.epoch <= epoch)
+            .or_else(|| forks.first())
+            .expect("Fork schedule shouldn't be empty")
+    }
+
+    pub fn fork_at(&self, slot: u64) -> &Fork {
+        self.fork_at_epoch(self.calculate_epoch(slot))
+    }
+
+    pub fn fork_version(&self, slot: u64) -> [u8; 4] {
+        self.fork_at(slot).version
+    }
+
+    /// Activation epoch of the fork or `None` if the fork isn't scheduled.
+    pub fn fork_epoch(&self, name: ForkName) -> Option<u64> {
+        self.forks()
+            .iter()
+            .find(|fork| fork.name == name)
+            .map(|fork| fork.epoch)
+    }
+
     // https://github.com/ethereum/EIPs/blob/55ec2d12e4738585338acdabd1c6400dc7235144/EIPS/eip-7600.md#activation
-    pub const fn epoch_electra(&self) -> u64 {
+    pub fn epoch_electra(&self) -> u64 {
+        self.fork_epoch(ForkName::Electra).unwrap_or(u64::MAX)
+    }
+
+    pub fn slots_per_epoch(&self) -> u64 {
         match self {
-            Holesky => 115_968,
-            Sepolia => 222_464,
-            Hoodi => 2_048,
-            Mainnet => 364_032,
+            Custom(network) => network.slots_per_epoch,
+            _ => SLOTS_PER_EPOCH,
         }
     }
+
+    /// Network-aware version of [`utils::calculate_epoch`].
+    pub fn calculate_epoch(&self, slot: u64) -> u64 {
+        slot / self.slots_per_epoch()
+    }
+
+    /// Network-aware version of [`utils::calculate_period`].
+    pub fn calculate_period(&self, slot: u64) -> u64 {
+        self.calculate_epoch(slot) / EPOCHS_PER_SYNC_COMMITTEE
+    }
+
+    /// Network-aware version of [`utils::calculate_slot`].
+    pub fn calculate_slot(&self, period: u64) -> u64 {
+        period * self.slots_per_epoch() * EPOCHS_PER_SYNC_COMMITTEE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn devnet() -> Network {
+        Custom(CustomNetwork {
+            genesis_validators_root: [1; 32],
+            forks: vec![
+                Fork {
+                    name: ForkName::Deneb,
+                    version: hex!("40000038"),
+                    epoch: 0,
+                },
+                Fork {
+                    name: ForkName::Electra,
+                    version: hex!("50000038"),
+                    epoch: 10,
+                },
+            ],
+            slots_per_epoch: 8,
+        })
+    }
+
+    #[test]
+    fn custom_fork_schedule() {
+        let network = devnet();
+        assert!(network.is_valid());
+
+        assert_eq!(network.epoch_electra(), 10);
+        assert_eq!(network.fork_version(0), hex!("40000038"));
+        assert_eq!(network.fork_version(79), hex!("40000038"));
+        assert_eq!(network.fork_version(80), hex!("50000038"));
+
+        assert_eq!(network.calculate_epoch(80), 10);
+        assert_eq!(network.calculate_slot(1), 8 * EPOCHS_PER_SYNC_COMMITTEE);
+        assert_eq!(network.calculate_period(8 * EPOCHS_PER_SYNC_COMMITTEE), 1);
+    }
+
+    #[test]
+    fn predefined_networks_match_utils() {
+        let slot = 364_032 * SLOTS_PER_EPOCH + 5;
+        assert_eq!(Mainnet.calculate_epoch(slot), utils::calculate_epoch(slot));
+        assert_eq!(
+            Mainnet.calculate_period(slot),
+            utils::calculate_period(slot)
+        );
+        assert_eq!(Mainnet.fork_version(slot), hex!("05000000"));
+        assert_eq!(Mainnet.fork_version(slot - 6), hex!("04000000"));
+        // slots before Deneb get the earliest known fork
+        assert_eq!(Mainnet.fork_version(0), hex!("04000000"));
+
+        let slot_fulu = 411_392 * SLOTS_PER_EPOCH;
+        assert_eq!(Mainnet.fork_at(slot_fulu).name, ForkName::Fulu);
+        assert_eq!(Mainnet.fork_at(slot_fulu - 1).name, ForkName::Electra);
+    }
+
+    #[test]
+    fn invalid_custom_networks() {
+        let Custom(mut network) = devnet() else {
+            unreachable!()
+        };
+
+        network.forks.reverse();
+        assert!(!Custom(network.clone()).is_valid());
+
+        network.forks.clear();
+        assert!(!Custom(network.clone()).is_valid());
+
+        let Custom(mut network) = devnet() else {
+            unreachable!()
+        };
+        network.slots_per_epoch = 0;
+        assert!(!Custom(network).is_valid());
+    }
 }
@@ -24,6 +24,12 @@ sol!(
     "../../api/ethereum/BridgingPayment.json"
 );
 
+sol!(
+    #[sol(rpc)]
+    IVaraMessenger,
+    "../../api/ethereum/IVaraMessenger.json"
+);
+
 impl IMessageQueue::VaraMessage {
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut ret: Vec<u8> = Vec::with_capacity(32 + 32 + 20 + self.data.len());
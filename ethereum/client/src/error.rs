@@ -11,6 +11,14 @@ pub enum Error {
     WrongNodeUrl,
     #[error("Wrong private key")]
     WrongPrivateKey,
+    #[error("Wrong public key")]
+    WrongPublicKey,
+    #[error("Wrong remote signer URL")]
+    WrongSignerUrl,
+    #[error("Remote signer returned malformed signature")]
+    WrongSignature,
+    #[error("Remote signer request failed: {0}")]
+    RemoteSignerRequestFailed(alloy::transports::http::reqwest::Error),
     #[error("Error during contract execution: {0}")]
     ErrorDuringContractExecution(alloy::contract::Error),
     #[error("Error sending transaction: {0}")]
@@ -25,6 +33,8 @@ pub enum Error {
     ErrorFetchingTransactionReceipt,
     #[error("Error fetching block")]
     ErrorFetchingBlock,
+    #[error("Error fetching fee history")]
+    ErrorFetchingFeeHistory,
     #[error("Failed to build reqwest client")]
     FailedToBuildClient(alloy::transports::http::reqwest::Error),
 }
@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+/// Fees of an EIP-1559 transaction in wei.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fees {
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+}
+
+impl Fees {
+    /// Fees increased by `percent` but at least by 1 wei so the transaction
+    /// can replace the previous one with the same nonce.
+    pub fn bumped(&self, percent: u32) -> Self {
+        let bump = |fee: u128| {
+            let bumped = fee.saturating_mul(100 + percent as u128) / 100;
+
+            bumped.max(fee.saturating_add(1))
+        };
+
+        Self {
+            max_fee_per_gas: bump(self.max_fee_per_gas),
+            max_priority_fee_per_gas: bump(self.max_priority_fee_per_gas),
+        }
+    }
+
+    pub fn capped(&self, max_fee_per_gas: u128) -> Self {
+        let max_fee_per_gas = self.max_fee_per_gas.min(max_fee_per_gas);
+
+        Self {
+            max_fee_per_gas,
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas.min(max_fee_per_gas),
+        }
+    }
+
+    /// Fees that are not less than both `self` and `other`.
+    pub fn max(&self, other: &Self) -> Self {
+        Self {
+            max_fee_per_gas: self.max_fee_per_gas.max(other.max_fee_per_gas),
+            max_priority_fee_per_gas: self
+                .max_priority_fee_per_gas
+                .max(other.max_priority_fee_per_gas),
+        }
+    }
+
+    /// Fees from the fee history: priority fee is the average of the per-block rewards at the
+    /// requested percentile and max fee allows the base fee to double.
+    pub fn from_fee_history(next_base_fee_per_gas: u128, rewards: &[u128]) -> Self {
+        let max_priority_fee_per_gas = match rewards.len() {
+            0 => 0,
+            len => rewards.iter().sum::<u128>() / len as u128,
+        };
+
+        Self {
+            max_fee_per_gas: next_base_fee_per_gas
+                .saturating_mul(2)
+                .saturating_add(max_priority_fee_per_gas),
+            max_priority_fee_per_gas,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub enum GasPricing {
+    /// Fees are estimated by the provider.
+    #[default]
+    Provider,
+    /// Fees are estimated by the provider but never exceed the cap.
+    FixedCap { max_fee_per_gas: u128 },
+    /// Priority fee is taken from the given percentile of the priority fees
+    /// paid in the recent blocks.
+    Percentile {
+        percentile: f64,
+        blocks: u64,
+        max_fee_per_gas: Option<u128>,
+    },
+}
+
+impl GasPricing {
+    pub fn max_fee_per_gas(&self) -> Option<u128> {
+        match self {
+            Self::Provider => None,
+            Self::FixedCap { max_fee_per_gas } => Some(*max_fee_per_gas),
+            Self::Percentile {
+                max_fee_per_gas, ..
+            } => *max_fee_per_gas,
+        }
+    }
+}
+
+/// Policy for transactions that aren't included for too long.
+#[derive(Clone, Copy, Debug)]
+pub struct Replacement {
+    /// How long to wait for the transaction inclusion before replacing it.
+    pub timeout: Duration,
+    /// Fee increase of the replacement transaction in percents.
+    pub bump_percent: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GasStrategy {
+    pub pricing: GasPricing,
+    /// Stuck transactions aren't replaced if not set.
+    pub replacement: Option<Replacement>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fees() {
+        let fees = Fees {
+            max_fee_per_gas: 100,
+            max_priority_fee_per_gas: 5,
+        };
+
+        assert_eq!(
+            fees.bumped(10),
+            Fees {
+                max_fee_per_gas: 110,
+                max_priority_fee_per_gas: 6,
+            }
+        );
+        assert_eq!(
+            fees.capped(4),
+            Fees {
+                max_fee_per_gas: 4,
+                max_priority_fee_per_gas: 4,
+            }
+        );
+        assert_eq!(
+            fees.max(&Fees {
+                max_fee_per_gas: 50,
+                max_priority_fee_per_gas: 7,
+            }),
+            Fees {
+                max_fee_per_gas: 100,
+                max_priority_fee_per_gas: 7,
+            }
+        );
+
+        assert_eq!(
+            Fees::from_fee_history(30, &[1, 2, 6]),
+            Fees {
+                max_fee_per_gas: 63,
+                max_priority_fee_per_gas: 3,
+            }
+        );
+        assert_eq!(Fees::from_fee_history(30, &[]).max_fee_per_gas, 60);
+    }
+}
@@ -2,8 +2,8 @@ use std::{marker::PhantomData, str::FromStr, time::Duration};
 
 use alloy::{
     contract::Event,
-    network::{Ethereum, EthereumWallet},
-    primitives::{Address, Bytes, B256, U256},
+    network::{Ethereum, EthereumWallet, TransactionBuilder, TxSigner},
+    primitives::{Address, Bytes, Signature, B256, U256},
     providers::{
         fillers::{
             BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller,
@@ -13,7 +13,7 @@ use alloy::{
     },
     rpc::{
         client::RpcClient,
-        types::{BlockId, BlockNumberOrTag, Filter},
+        types::{BlockId, BlockNumberOrTag, Filter, TransactionRequest},
     },
     signers::local::PrivateKeySigner,
     sol_types::SolEvent,
@@ -31,11 +31,21 @@ mod abi;
 use abi::{
     BridgingPayment, IERC20Manager, IMessageQueue, IMessageQueue::IMessageQueueInstance,
     IMessageQueue::VaraMessage, IRelayer, IRelayer::IRelayerInstance, IRelayer::MerkleRoot,
+    IVaraMessenger,
 };
 
 pub mod error;
 pub use error::Error;
 
+pub mod gas;
+use gas::{Fees, GasPricing, GasStrategy};
+
+pub mod nonce;
+use nonce::NonceManager;
+
+pub mod signer;
+pub use signer::Web3Signer;
+
 type ProviderType = FillProvider<
     JoinFill<
         JoinFill<
@@ -54,6 +64,8 @@ pub struct Contracts<P, T, N> {
     provider: P,
     message_queue_instance: IMessageQueueInstance<T, P, N>,
     relayer_instance: IRelayerInstance<T, P, N>,
+    gas_strategy: GasStrategy,
+    nonce_manager: NonceManager,
     _phantom: PhantomData<(T, N)>,
 }
 
@@ -78,6 +90,33 @@ pub struct FeePaidEntry {
     pub tx_hash: TxHash,
 }
 
+#[derive(Debug, Clone)]
+pub struct MessageProcessedEntry {
+    /// Little-endian bytes of the nonce of the delivered message.
+    pub nonce_le: [u8; 32],
+    pub tx_hash: TxHash,
+}
+
+#[derive(Debug, Clone)]
+pub struct VaraMessageEventEntry {
+    pub from: H160,
+    pub destination: H256,
+    pub nonce: primitive_types::U256,
+    pub payload: Vec<u8>,
+
+    pub tx_hash: TxHash,
+}
+
+/// Message from the merkle tree to be submitted as a part of a batch.
+#[derive(Debug, Clone)]
+pub struct ContentMessage {
+    pub leaf_index: u32,
+    pub nonce: [u8; 32],
+    pub sender: [u8; 32],
+    pub receiver: [u8; 20],
+    pub payload: Vec<u8>,
+}
+
 #[derive(Debug)]
 pub enum TxStatus {
     Finalized,
@@ -111,9 +150,23 @@ impl EthApi {
             None => PrivateKeySigner::random(),
         };
 
+        Self::with_signer(url, message_queue_address, relayer_address, signer, timeout)
+    }
+
+    /// Create client which signs transactions with the provided signer, e.g. [`Web3Signer`].
+    pub fn with_signer<S>(
+        url: &str,
+        message_queue_address: &str,
+        relayer_address: &str,
+        signer: S,
+        timeout: Duration,
+    ) -> Result<EthApi, Error>
+    where
+        S: TxSigner<Signature> + Send + Sync + 'static,
+    {
         let public_key = signer.address();
 
-        let wallet = alloy::network::EthereumWallet::from(signer);
+        let wallet = EthereumWallet::from(signer);
 
         let message_queue_address: Address = message_queue_address
             .parse()
@@ -138,6 +191,7 @@ impl EthApi {
             provider,
             message_queue_address.into_array(),
             relayer_address.into_array(),
+            NonceManager::new(public_key),
         )?;
 
         Ok(EthApi {
@@ -149,6 +203,27 @@ impl EthApi {
         })
     }
 
+    pub fn with_gas_strategy(mut self, gas_strategy: GasStrategy) -> Self {
+        self.contracts.gas_strategy = gas_strategy;
+
+        self
+    }
+
+    /// How long to wait for a transaction inclusion before replacing it with a higher fee.
+    /// `None` if stuck transactions shouldn't be replaced.
+    pub fn replacement_timeout(&self) -> Option<Duration> {
+        self.contracts
+            .gas_strategy
+            .replacement
+            .map(|replacement| replacement.timeout)
+    }
+
+    /// Replace the transaction that isn't included yet with the same one but with higher fees.
+    /// Returns `None` if the transaction is already included or can't be replaced.
+    pub async fn replace_transaction(&self, tx_hash: TxHash) -> Result<Option<TxHash>, Error> {
+        self.contracts.replace_transaction(tx_hash).await
+    }
+
     pub fn reconnect(&self) -> Result<EthApi, Error> {
         let client_reqwest = Client::builder()
             .timeout(self.timeout)
@@ -162,11 +237,13 @@ impl EthApi {
             .wallet(self.wallet.clone())
             .on_client(rpc_client);
 
-        let contracts = Contracts::new(
+        let mut contracts = Contracts::new(
             provider,
             self.contracts.message_queue_instance.address().0 .0,
             self.contracts.relayer_instance.address().0 .0,
+            self.contracts.nonce_manager.clone(),
         )?;
+        contracts.gas_strategy = self.contracts.gas_strategy;
 
         Ok(EthApi {
             contracts,
@@ -177,6 +254,10 @@ impl EthApi {
         })
     }
 
+    pub fn nonce_manager(&self) -> &NonceManager {
+        &self.contracts.nonce_manager
+    }
+
     // TODO: Don't expose provider here.
     pub fn raw_provider(&self) -> &ProviderType {
         &self.contracts.provider
@@ -275,10 +356,73 @@ impl EthApi {
             .collect())
     }
 
+    /// Fetch messages from gear delivered by the `MessageQueue` contract in the `block`.
+    pub async fn fetch_message_processed_events(
+        &self,
+        block: u64,
+    ) -> Result<Vec<MessageProcessedEntry>, Error> {
+        Ok(self
+            .contracts
+            .fetch_message_processed_events(block)
+            .await?
+            .into_iter()
+            .map(
+                |(IMessageQueue::MessageProcessed { messageNonce, .. }, tx_hash)| {
+                    MessageProcessedEntry {
+                        nonce_le: messageNonce.0,
+                        tx_hash,
+                    }
+                },
+            )
+            .collect())
+    }
+
+    /// Fetch messages sent through the `VaraMessenger` contract in the `block`.
+    /// If `destination` is specified, only messages to this program are returned.
+    pub async fn fetch_vara_message_events(
+        &self,
+        contract_address: H160,
+        destination: Option<H256>,
+        block: u64,
+    ) -> Result<Vec<VaraMessageEventEntry>, Error> {
+        Ok(self
+            .contracts
+            .fetch_vara_message_events(
+                Address::from_slice(contract_address.as_bytes()),
+                destination.map(|destination| B256::from(destination.0)),
+                block,
+            )
+            .await?
+            .into_iter()
+            .map(
+                |(
+                    IVaraMessenger::MessageToVaraSent {
+                        from,
+                        destination,
+                        nonce,
+                        payload,
+                    },
+                    tx_hash,
+                )| VaraMessageEventEntry {
+                    from: H160(*from.0),
+                    destination: H256(destination.0),
+                    nonce: primitive_types::U256::from_little_endian(&nonce.to_le_bytes_vec()),
+                    payload: payload.to_vec(),
+                    tx_hash,
+                },
+            )
+            .collect())
+    }
+
     pub async fn block_number(&self) -> Result<u64, Error> {
         self.contracts.block_number().await
     }
 
+    /// Current gas price in wei.
+    pub async fn gas_price(&self) -> Result<u128, Error> {
+        self.contracts.gas_price().await
+    }
+
     pub async fn finalized_block_number(&self) -> Result<u64, Error> {
         self.contracts.finalized_block_number().await
     }
@@ -309,6 +453,39 @@ impl EthApi {
             .await
     }
 
+    /// Submit several messages that belong to the same merkle tree in a single transaction.
+    /// Messages should be sorted by their leaf indices and `proof` is a multiproof of their
+    /// inclusion.
+    pub async fn provide_content_messages(
+        &self,
+        block_number: u32,
+        total_leaves: u32,
+        messages: Vec<ContentMessage>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<TxHash, Error> {
+        let mut leaf_indices = Vec::with_capacity(messages.len());
+        let mut vara_messages = Vec::with_capacity(messages.len());
+        for message in messages {
+            leaf_indices.push(U256::from(message.leaf_index));
+            vara_messages.push(VaraMessage {
+                nonce: B256::from(message.nonce),
+                sender: B256::from(message.sender),
+                receiver: Address::from(message.receiver),
+                data: Bytes::from(message.payload),
+            });
+        }
+
+        self.contracts
+            .provide_content_messages(
+                U256::from(block_number),
+                U256::from(total_leaves),
+                leaf_indices,
+                vara_messages,
+                proof.into_iter().map(B256::from).collect(),
+            )
+            .await
+    }
+
     pub async fn is_message_processed(&self, nonce: [u8; 32]) -> Result<bool, Error> {
         self.contracts.is_message_processed(B256::from(nonce)).await
     }
@@ -323,6 +500,7 @@ where
         provider: P,
         message_queue_address: [u8; 20],
         relayer_address: [u8; 20],
+        nonce_manager: NonceManager,
     ) -> Result<Self, Error> {
         let relayer_address = Address::from(relayer_address);
         let message_queue_address = Address::from(message_queue_address);
@@ -334,10 +512,141 @@ where
             provider,
             relayer_instance,
             message_queue_instance,
+            gas_strategy: Default::default(),
+            nonce_manager,
             _phantom: PhantomData,
         })
     }
 
+    /// Fees for a new transaction according to the gas strategy. `None` means that
+    /// fees are filled by the provider.
+    async fn fees(&self) -> Result<Option<Fees>, Error> {
+        match self.gas_strategy.pricing {
+            GasPricing::Provider => Ok(None),
+
+            GasPricing::FixedCap { max_fee_per_gas } => {
+                let estimation = self.provider.estimate_eip1559_fees(None).await?;
+                let fees = Fees {
+                    max_fee_per_gas: estimation.max_fee_per_gas,
+                    max_priority_fee_per_gas: estimation.max_priority_fee_per_gas,
+                };
+
+                Ok(Some(fees.capped(max_fee_per_gas)))
+            }
+
+            GasPricing::Percentile {
+                percentile,
+                blocks,
+                max_fee_per_gas,
+            } => {
+                let history = self
+                    .provider
+                    .get_fee_history(blocks, BlockNumberOrTag::Latest, &[percentile])
+                    .await?;
+
+                let next_base_fee_per_gas = history
+                    .next_block_base_fee()
+                    .ok_or(Error::ErrorFetchingFeeHistory)?;
+                let rewards: Vec<_> = history
+                    .reward
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|rewards| rewards.first().copied())
+                    .collect();
+
+                let fees = Fees::from_fee_history(next_base_fee_per_gas, &rewards);
+
+                Ok(Some(match max_fee_per_gas {
+                    Some(max_fee_per_gas) => fees.capped(max_fee_per_gas),
+                    None => fees,
+                }))
+            }
+        }
+    }
+
+    pub async fn replace_transaction(&self, tx_hash: TxHash) -> Result<Option<TxHash>, Error> {
+        let Some(replacement) = self.gas_strategy.replacement else {
+            return Ok(None);
+        };
+
+        let tx = self
+            .provider
+            .get_transaction_by_hash(tx_hash)
+            .await
+            .map_err(|_| Error::ErrorFetchingTransaction)?
+            .ok_or(Error::ErrorFetchingTransaction)?;
+
+        if tx.block_hash.is_some() {
+            return Ok(None);
+        }
+
+        let max_fee_per_gas = tx
+            .max_fee_per_gas
+            .or(tx.gas_price)
+            .ok_or(Error::ErrorFetchingTransaction)?;
+        let fees_old = Fees {
+            max_fee_per_gas,
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas.unwrap_or(max_fee_per_gas),
+        };
+
+        let fees_current = match self.fees().await? {
+            Some(fees) => fees,
+            None => {
+                let estimation = self.provider.estimate_eip1559_fees(None).await?;
+                Fees {
+                    max_fee_per_gas: estimation.max_fee_per_gas,
+                    max_priority_fee_per_gas: estimation.max_priority_fee_per_gas,
+                }
+            }
+        };
+
+        let mut fees = fees_old.bumped(replacement.bump_percent).max(&fees_current);
+        if let Some(max_fee_per_gas) = self.gas_strategy.pricing.max_fee_per_gas() {
+            fees = fees.capped(max_fee_per_gas);
+        }
+
+        if fees.max_fee_per_gas <= fees_old.max_fee_per_gas {
+            log::warn!(
+                "Unable to replace transaction {tx_hash}: fee cap of {} wei is reached",
+                fees_old.max_fee_per_gas
+            );
+
+            return Ok(None);
+        }
+
+        let mut request = TransactionRequest::default()
+            .with_from(tx.from)
+            .with_nonce(tx.nonce)
+            .with_value(tx.value)
+            .with_input(tx.input)
+            .with_gas_limit(tx.gas)
+            .with_max_fee_per_gas(fees.max_fee_per_gas)
+            .with_max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+        if let Some(to) = tx.to {
+            request = request.with_to(to);
+        }
+
+        let Some(nonce) = self
+            .nonce_manager
+            .reserve_replacement(&self.provider, tx.nonce)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        match self.provider.send_transaction(request).await {
+            Ok(pending_tx) => {
+                nonce.submitted();
+
+                Ok(Some(*pending_tx.tx_hash()))
+            }
+            Err(e) => {
+                log::error!("Sending error: {e:?}");
+                Err(Error::ErrorInHTTPTransport(e))
+            }
+        }
+    }
+
     pub async fn get_approx_balance(&self, address: Address) -> Result<f64, Error> {
         let balance = self.provider.get_balance(address).latest().await?;
         let balance: f64 = balance.into();
@@ -350,21 +659,26 @@ where
         merkle_root: B256,
         proof: Bytes,
     ) -> Result<TxHash, Error> {
-        match self
+        let mut call = self
             .relayer_instance
-            .submitMerkleRoot(block_number, merkle_root, proof.clone())
-            .estimate_gas()
-            .await
-        {
+            .submitMerkleRoot(block_number, merkle_root, proof);
+        if let Some(fees) = self.fees().await? {
+            call = call
+                .max_fee_per_gas(fees.max_fee_per_gas)
+                .max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+        }
+
+        match call.estimate_gas().await {
             Ok(gas_used) => {
                 log::info!("Gas used: {gas_used}");
-                match self
-                    .relayer_instance
-                    .submitMerkleRoot(block_number, merkle_root, proof.clone())
-                    .send()
-                    .await
-                {
-                    Ok(pending_tx) => Ok(*pending_tx.tx_hash()),
+
+                let nonce = self.nonce_manager.reserve(&self.provider).await?;
+                match call.nonce(nonce.nonce()).send().await {
+                    Ok(pending_tx) => {
+                        nonce.submitted();
+
+                        Ok(*pending_tx.tx_hash())
+                    }
                     Err(e) => {
                         log::error!("Sending error: {e:?}");
                         Err(Error::ErrorSendingTransaction(e))
@@ -379,6 +693,10 @@ where
         self.provider.get_block_number().await.map_err(|e| e.into())
     }
 
+    pub async fn gas_price(&self) -> Result<u128, Error> {
+        self.provider.get_gas_price().await.map_err(|e| e.into())
+    }
+
     pub async fn finalized_block_number(&self) -> Result<u64, Error> {
         Ok(self
             .provider
@@ -480,6 +798,64 @@ where
             .collect()
     }
 
+    pub async fn fetch_message_processed_events(
+        &self,
+        block: u64,
+    ) -> Result<Vec<(IMessageQueue::MessageProcessed, TxHash)>, Error> {
+        let filter = Filter::new()
+            .address(*self.message_queue_instance.address())
+            .event_signature(IMessageQueue::MessageProcessed::SIGNATURE_HASH)
+            .from_block(block)
+            .to_block(block);
+
+        let event: Event<T, P, IMessageQueue::MessageProcessed, Ethereum> =
+            Event::new(self.provider.clone(), filter);
+
+        let logs = event.query().await.map_err(Error::ErrorQueryingEvent)?;
+
+        logs.into_iter()
+            .map(|(event, log)| {
+                Ok((
+                    event,
+                    log.transaction_hash
+                        .ok_or(Error::ErrorFetchingTransaction)?,
+                ))
+            })
+            .collect()
+    }
+
+    pub async fn fetch_vara_message_events(
+        &self,
+        contract_address: Address,
+        destination: Option<B256>,
+        block: u64,
+    ) -> Result<Vec<(IVaraMessenger::MessageToVaraSent, TxHash)>, Error> {
+        let mut filter = Filter::new()
+            .address(contract_address)
+            .event_signature(IVaraMessenger::MessageToVaraSent::SIGNATURE_HASH)
+            .from_block(block)
+            .to_block(block);
+
+        if let Some(destination) = destination {
+            filter = filter.topic2(destination);
+        }
+
+        let event: Event<T, P, IVaraMessenger::MessageToVaraSent, Ethereum> =
+            Event::new(self.provider.clone(), filter);
+
+        let logs = event.query().await.map_err(Error::ErrorQueryingEvent)?;
+
+        logs.into_iter()
+            .map(|(event, log)| {
+                Ok((
+                    event,
+                    log.transaction_hash
+                        .ok_or(Error::ErrorFetchingTransaction)?,
+                ))
+            })
+            .collect()
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn provide_content_message(
         &self,
@@ -492,7 +868,7 @@ where
         data: Bytes,
         proof: Vec<B256>,
     ) -> Result<TxHash, Error> {
-        let call = self.message_queue_instance.processMessage(
+        let mut call = self.message_queue_instance.processMessage(
             block_number,
             total_leaves,
             leaf_index,
@@ -504,15 +880,67 @@ where
             },
             proof,
         );
+        if let Some(fees) = self.fees().await? {
+            call = call
+                .max_fee_per_gas(fees.max_fee_per_gas)
+                .max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+        }
 
         match call.estimate_gas().await {
-            Ok(_gas_used) => match call.send().await {
-                Ok(pending_tx) => Ok(*pending_tx.tx_hash()),
-                Err(e) => {
-                    log::error!("Sending error: {e:?}");
-                    Err(Error::ErrorSendingTransaction(e))
+            Ok(_gas_used) => {
+                let nonce = self.nonce_manager.reserve(&self.provider).await?;
+                match call.nonce(nonce.nonce()).send().await {
+                    Ok(pending_tx) => {
+                        nonce.submitted();
+
+                        Ok(*pending_tx.tx_hash())
+                    }
+                    Err(e) => {
+                        log::error!("Sending error: {e:?}");
+                        Err(Error::ErrorSendingTransaction(e))
+                    }
                 }
-            },
+            }
+            Err(e) => Err(Error::ErrorDuringContractExecution(e)),
+        }
+    }
+
+    pub async fn provide_content_messages(
+        &self,
+        block_number: U256,
+        total_leaves: U256,
+        leaf_indices: Vec<U256>,
+        messages: Vec<VaraMessage>,
+        proof: Vec<B256>,
+    ) -> Result<TxHash, Error> {
+        let mut call = self.message_queue_instance.processMessages(
+            block_number,
+            total_leaves,
+            leaf_indices,
+            messages,
+            proof,
+        );
+        if let Some(fees) = self.fees().await? {
+            call = call
+                .max_fee_per_gas(fees.max_fee_per_gas)
+                .max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+        }
+
+        match call.estimate_gas().await {
+            Ok(_gas_used) => {
+                let nonce = self.nonce_manager.reserve(&self.provider).await?;
+                match call.nonce(nonce.nonce()).send().await {
+                    Ok(pending_tx) => {
+                        nonce.submitted();
+
+                        Ok(*pending_tx.tx_hash())
+                    }
+                    Err(e) => {
+                        log::error!("Sending error: {e:?}");
+                        Err(Error::ErrorSendingTransaction(e))
+                    }
+                }
+            }
             Err(e) => Err(Error::ErrorDuringContractExecution(e)),
         }
     }
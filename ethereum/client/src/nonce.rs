@@ -0,0 +1,319 @@
+use std::{collections::BTreeSet, sync::Arc};
+
+use alloy::{network::Ethereum, primitives::Address, providers::Provider, transports::Transport};
+use prometheus::{IntCounter, IntGauge};
+use tokio::sync::{Mutex, MutexGuard};
+use utils_prometheus::{impl_metered_service, MeteredService};
+
+use crate::Error;
+
+#[derive(Debug, Default)]
+struct State {
+    /// Nonce of the next transaction. `None` if it should be fetched from the chain.
+    next: Option<u64>,
+    /// Nonces of the sent transactions that aren't included yet.
+    pending: BTreeSet<u64>,
+}
+
+/// Outcome of the synchronization of the local state with the chain.
+#[derive(Debug, PartialEq, Eq)]
+enum NonceSync {
+    /// Local state matches the chain.
+    InSync,
+    /// Nonce of the next transaction is fetched from the chain.
+    Fetched,
+    /// The transactions with the nonces in the range are unknown to the node. They aren't
+    /// resubmitted by the manager: the senders detect the dropped transactions by their
+    /// status and submit them again.
+    Gap { from: u64, to: u64 },
+    /// The transactions with the nonces in the range are sent by someone else.
+    External { from: u64, to: u64 },
+}
+
+impl State {
+    /// Synchronize the local state with the amount of the included transactions of the account
+    /// and the amount including the ones in the mempool. Returns nonce for the next transaction.
+    fn sync(&mut self, confirmed: u64, pending: u64) -> (u64, NonceSync) {
+        self.pending.retain(|nonce| *nonce >= confirmed);
+
+        let pending = pending.max(confirmed);
+        let (next, sync) = match self.next {
+            None => (pending, NonceSync::Fetched),
+            Some(next) if next == pending => (next, NonceSync::InSync),
+            Some(next) if next > pending => {
+                // Node doesn't know about some of our transactions, so the nonces starting
+                // from the first missing one are reused by the next transactions.
+                self.pending.retain(|nonce| *nonce < pending);
+
+                (
+                    pending,
+                    NonceSync::Gap {
+                        from: pending,
+                        to: next,
+                    },
+                )
+            }
+            Some(next) => (
+                pending,
+                NonceSync::External {
+                    from: next,
+                    to: pending,
+                },
+            ),
+        };
+
+        self.next = Some(next);
+
+        (next, sync)
+    }
+
+    fn submitted(&mut self, nonce: u64) {
+        self.next = Some(nonce + 1);
+        self.pending.insert(nonce);
+    }
+
+    /// Checks whether the transaction with the nonce can still be replaced, i.e. none of
+    /// the transactions with this nonce is included.
+    fn replaceable(&mut self, confirmed: u64, nonce: u64) -> bool {
+        self.pending.retain(|nonce| *nonce >= confirmed);
+
+        nonce >= confirmed
+    }
+
+    fn reset(&mut self) {
+        self.next = None;
+    }
+}
+
+impl_metered_service! {
+    struct Metrics {
+        pending_nonces: IntGauge = IntGauge::new(
+            "ethereum_nonce_manager_pending_nonces",
+            "Amount of nonces used by the sent transactions that aren't included yet",
+        ),
+        next_nonce: IntGauge = IntGauge::new(
+            "ethereum_nonce_manager_next_nonce",
+            "Nonce to be used by the next transaction",
+        ),
+        total_resyncs: IntCounter = IntCounter::new(
+            "ethereum_nonce_manager_total_resyncs",
+            "Total amount of times the local nonce diverged from the chain",
+        ),
+    }
+}
+
+/// Assigns nonces to the transactions sent from the same account by several tasks.
+///
+/// Nonce is reserved for the whole time a transaction is being sent, so the concurrent
+/// submissions are serialized. Before the reservation the local state is checked against
+/// the chain and re-synced if transactions were dropped or sent by someone else.
+/// Failed submission makes the next one fetch nonce from the chain.
+///
+/// Replacements of the stuck transactions reserve the nonce of the replaced transaction so they
+/// don't race with the new submissions.
+#[derive(Clone)]
+pub struct NonceManager {
+    address: Address,
+    state: Arc<Mutex<State>>,
+    metrics: Metrics,
+}
+
+impl MeteredService for NonceManager {
+    fn get_sources(&self) -> impl IntoIterator<Item = Box<dyn prometheus::core::Collector>> {
+        self.metrics.get_sources()
+    }
+}
+
+impl NonceManager {
+    pub fn new(address: Address) -> Self {
+        Self {
+            address,
+            state: Arc::new(Mutex::new(State::default())),
+            metrics: Metrics::new(),
+        }
+    }
+
+    pub async fn reserve<P, T>(&self, provider: &P) -> Result<NonceGuard<'_>, Error>
+    where
+        T: Transport + Clone,
+        P: Provider<T, Ethereum>,
+    {
+        let mut state = self.state.lock().await;
+
+        let result = async {
+            let confirmed = provider
+                .get_transaction_count(self.address)
+                .latest()
+                .await?;
+            let pending = provider
+                .get_transaction_count(self.address)
+                .pending()
+                .await?;
+
+            Ok::<_, Error>((confirmed, pending))
+        }
+        .await;
+        let (confirmed, pending) = match result {
+            Ok(counts) => counts,
+            Err(e) => {
+                state.reset();
+
+                return Err(e);
+            }
+        };
+
+        let (nonce, sync) = state.sync(confirmed, pending);
+        match sync {
+            NonceSync::InSync | NonceSync::Fetched => {}
+            NonceSync::Gap { from, to } => {
+                self.metrics.total_resyncs.inc();
+
+                log::warn!(
+                    "Transactions with nonces {from}..{to} are unknown to the node. \
+                    Reusing nonces starting from {from}"
+                );
+            }
+            NonceSync::External { from, to } => {
+                self.metrics.total_resyncs.inc();
+
+                log::warn!(
+                    "Nonces {from}..{to} are used by transactions sent outside of the relayer"
+                );
+            }
+        }
+
+        self.update_metrics(&state);
+
+        Ok(NonceGuard {
+            manager: self,
+            state,
+            nonce,
+            replacement: false,
+            submitted: false,
+        })
+    }
+
+    /// Reserve the nonce of the sent transaction to replace it. Returns `None` if a transaction
+    /// with the nonce is already included.
+    pub async fn reserve_replacement<P, T>(
+        &self,
+        provider: &P,
+        nonce: u64,
+    ) -> Result<Option<NonceGuard<'_>>, Error>
+    where
+        T: Transport + Clone,
+        P: Provider<T, Ethereum>,
+    {
+        let mut state = self.state.lock().await;
+
+        let confirmed = provider
+            .get_transaction_count(self.address)
+            .latest()
+            .await?;
+        if !state.replaceable(confirmed, nonce) {
+            self.update_metrics(&state);
+
+            return Ok(None);
+        }
+
+        Ok(Some(NonceGuard {
+            manager: self,
+            state,
+            nonce,
+            replacement: true,
+            submitted: false,
+        }))
+    }
+
+    fn update_metrics(&self, state: &State) {
+        self.metrics.pending_nonces.set(state.pending.len() as i64);
+        if let Some(next) = state.next {
+            self.metrics.next_nonce.set(next as i64);
+        }
+    }
+}
+
+/// Nonce reserved for a transaction. Other submissions wait until it is dropped.
+pub struct NonceGuard<'a> {
+    manager: &'a NonceManager,
+    state: MutexGuard<'a, State>,
+    nonce: u64,
+    replacement: bool,
+    submitted: bool,
+}
+
+impl NonceGuard<'_> {
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Should be called once the transaction is accepted by the node.
+    pub fn submitted(mut self) {
+        self.submitted = true;
+        match self.replacement {
+            // the replaced transaction has already advanced the next nonce
+            true => {
+                self.state.pending.insert(self.nonce);
+            }
+            false => self.state.submitted(self.nonce),
+        }
+    }
+}
+
+impl Drop for NonceGuard<'_> {
+    fn drop(&mut self) {
+        // failed replacement doesn't affect the next nonce
+        if !self.submitted && !self.replacement {
+            self.state.reset();
+        }
+
+        self.manager.update_metrics(&self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync() {
+        let mut state = State::default();
+
+        assert_eq!(state.sync(3, 5), (5, NonceSync::Fetched));
+        state.submitted(5);
+        state.submitted(6);
+        assert_eq!(state.sync(3, 7), (7, NonceSync::InSync));
+
+        // Transaction with nonce 6 is dropped by the node.
+        state.submitted(7);
+        assert_eq!(state.sync(5, 6), (6, NonceSync::Gap { from: 6, to: 8 }));
+        assert_eq!(state.pending, BTreeSet::from([5]));
+
+        state.submitted(6);
+        assert_eq!(
+            state.sync(7, 9),
+            (9, NonceSync::External { from: 7, to: 9 })
+        );
+        assert!(state.pending.is_empty());
+
+        state.reset();
+        assert_eq!(state.sync(9, 9), (9, NonceSync::Fetched));
+    }
+
+    #[test]
+    fn replacement() {
+        let mut state = State::default();
+
+        assert_eq!(state.sync(3, 3), (3, NonceSync::Fetched));
+        state.submitted(3);
+        state.submitted(4);
+
+        assert!(state.replaceable(3, 3));
+        assert!(state.replaceable(3, 4));
+
+        // Transaction with nonce 3 is included so it can't be replaced anymore.
+        assert!(!state.replaceable(4, 3));
+        assert_eq!(state.pending, BTreeSet::from([4]));
+        assert_eq!(state.sync(4, 5), (5, NonceSync::InSync));
+    }
+}
@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use alloy::{
+    network::TxSigner,
+    primitives::{hex, Address, Signature},
+    transports::http::reqwest::{header::CONTENT_TYPE, Client, Url},
+};
+use alloy_consensus::SignableTransaction;
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::Error;
+
+#[derive(Serialize)]
+struct SignRequest {
+    data: String,
+}
+
+/// Signer that delegates signing to a remote service implementing the
+/// [Web3Signer](https://docs.web3signer.consensys.io) eth1 API, so the private
+/// key never leaves the service.
+#[derive(Clone, Debug)]
+pub struct Web3Signer {
+    client: Client,
+    sign_url: Url,
+    address: Address,
+}
+
+impl Web3Signer {
+    /// `public_key` is the hex encoded uncompressed secp256k1 public key that
+    /// identifies the key in the signer, with or without `04` prefix.
+    pub fn new(url: &str, public_key: &str, timeout: Duration) -> Result<Self, Error> {
+        let public_key = public_key.trim_start_matches("0x");
+        let public_key_bytes = hex::decode(public_key).map_err(|_| Error::WrongPublicKey)?;
+        let public_key_bytes = match public_key_bytes.len() {
+            64 => &public_key_bytes[..],
+            65 if public_key_bytes[0] == 0x04 => &public_key_bytes[1..],
+            _ => return Err(Error::WrongPublicKey),
+        };
+
+        let address = Address::from_raw_public_key(public_key_bytes);
+
+        let identifier = hex::encode(public_key_bytes);
+        let sign_url = Url::parse(url)
+            .and_then(|url| url.join(&format!("api/v1/eth1/sign/0x{identifier}")))
+            .map_err(|_| Error::WrongSignerUrl)?;
+
+        let client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(Error::FailedToBuildClient)?;
+
+        Ok(Self {
+            client,
+            sign_url,
+            address,
+        })
+    }
+
+    /// Sign `keccak256(data)`.
+    pub async fn sign(&self, data: &[u8]) -> Result<Signature, Error> {
+        let request = serde_json::to_vec(&SignRequest {
+            data: format!("0x{}", hex::encode(data)),
+        })
+        .expect("Sign request is serializable");
+
+        let response = self
+            .client
+            .post(self.sign_url.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .body(request)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::RemoteSignerRequestFailed)?;
+
+        let signature = response
+            .text()
+            .await
+            .map_err(Error::RemoteSignerRequestFailed)?;
+        let signature = hex::decode(signature.trim().trim_start_matches("0x"))
+            .map_err(|_| Error::WrongSignature)?;
+
+        Signature::try_from(&signature[..]).map_err(|_| Error::WrongSignature)
+    }
+}
+
+#[async_trait]
+impl TxSigner<Signature> for Web3Signer {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> alloy::signers::Result<Signature> {
+        self.sign(&tx.encoded_for_signing())
+            .await
+            .map_err(alloy::signers::Error::other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{
+        primitives::{keccak256, TxKind, U256},
+        signers::{local::PrivateKeySigner, SignerSync},
+    };
+    use alloy_consensus::TxEip1559;
+    use axum::{extract::State, routing::post, Json, Router};
+    use serde::Deserialize;
+    use tokio::net::TcpListener;
+
+    #[derive(Deserialize)]
+    struct MockSignRequest {
+        data: String,
+    }
+
+    async fn mock_sign(
+        State(signer): State<PrivateKeySigner>,
+        Json(request): Json<MockSignRequest>,
+    ) -> String {
+        let data = hex::decode(request.data.trim_start_matches("0x")).unwrap();
+        let signature = signer.sign_hash_sync(&keccak256(data)).unwrap();
+
+        format!("0x{}", hex::encode(signature.as_bytes()))
+    }
+
+    /// Start a mock signer holding the key and return its URL.
+    async fn spawn_mock_signer(signer: PrivateKeySigner) -> String {
+        let public_key = signer.credential().verifying_key().to_encoded_point(false);
+        let path = format!(
+            "/api/v1/eth1/sign/0x{}",
+            hex::encode(&public_key.as_bytes()[1..])
+        );
+
+        let app = Router::new()
+            .route(&path, post(mock_sign))
+            .with_state(signer);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        format!("http://{address}")
+    }
+
+    #[tokio::test]
+    async fn signs_with_remote_key() {
+        let key = PrivateKeySigner::random();
+        let public_key = key.credential().verifying_key().to_encoded_point(false);
+        let url = spawn_mock_signer(key.clone()).await;
+
+        let signer = Web3Signer::new(
+            &url,
+            &hex::encode(public_key.as_bytes()),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        assert_eq!(TxSigner::address(&signer), key.address());
+
+        let mut tx = TxEip1559 {
+            chain_id: 1,
+            nonce: 7,
+            gas_limit: 21_000,
+            max_fee_per_gas: 100,
+            max_priority_fee_per_gas: 2,
+            to: TxKind::Call(Address::repeat_byte(1)),
+            value: U256::from(1),
+            ..Default::default()
+        };
+        let signature = signer.sign_transaction(&mut tx).await.unwrap();
+
+        let recovered = signature
+            .recover_address_from_prehash(&tx.signature_hash())
+            .unwrap();
+        assert_eq!(recovered, key.address());
+    }
+}
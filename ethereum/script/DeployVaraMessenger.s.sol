@@ -0,0 +1,22 @@
+pragma solidity ^0.8.24;
+
+import {Script, console} from "forge-std/Script.sol";
+
+import {VaraMessenger} from "../src/VaraMessenger.sol";
+
+contract Deploy is Script {
+    function setUp() public {}
+
+    function run() public {
+        vm.startBroadcast(vm.envUint("ETHEREUM_DEPLOYMENT_PRIVATE_KEY"));
+
+        address vara_messenger_admin = vm.envAddress("VARA_MESSENGER_ADMIN");
+        uint256 fee = vm.envUint("VARA_MESSENGER_FEE");
+
+        VaraMessenger vara_messenger = new VaraMessenger(fee, vara_messenger_admin);
+
+        console.log("VaraMessenger:", address(vara_messenger));
+
+        vm.stopBroadcast();
+    }
+}
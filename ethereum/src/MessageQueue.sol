@@ -8,6 +8,7 @@ import {IRelayer} from "./interfaces/IRelayer.sol";
 
 import {VaraMessage, VaraMessage, IMessageQueue, IMessageQueueReceiver, Hasher} from "./interfaces/IMessageQueue.sol";
 import {MerkleProof} from "openzeppelin-contracts/contracts/utils/cryptography/MerkleProof.sol";
+import {MerkleMultiProof} from "./libraries/MerkleMultiProof.sol";
 
 contract MessageQueue is IMessageQueue {
     using Address for address;
@@ -51,22 +52,99 @@ contract MessageQueue is IMessageQueue {
         VaraMessage calldata message,
         bytes32[] calldata proof
     ) public {
-        if (_processed_messages[message.nonce])
-            revert MessageAlreadyProcessed(message.nonce);
+        bytes32 merkle_root = _getMerkleRoot(block_number);
 
-        bytes32 msg_hash = hash_vara_msg(message);
+        _processMessage(
+            block_number,
+            merkle_root,
+            total_leaves,
+            leaf_index,
+            message,
+            proof
+        );
+    }
+
+    /**
+     * @dev Unpack several messages from the same merkle tree and relay them to the receivers.
+     *  Reverts if any of the messages can't be processed.
+     *
+     * @param block_number - Block number of block containing target merkle tree.
+     * @param total_leaves - Number of leaves in target merkle tree.
+     * @param leaf_indices - Indices of leaves containing target messages in ascending order.
+     * @param messages - Target messages in the same order as `leaf_indices`.
+     * @param proof - Merkle multiproof of inclusion of leaves #`leaf_indices` into target merkle
+     *  tree that was included into `block_number`. See `MerkleMultiProof` for reference.
+     */
+    function processMessages(
+        uint256 block_number,
+        uint256 total_leaves,
+        uint256[] calldata leaf_indices,
+        VaraMessage[] calldata messages,
+        bytes32[] calldata proof
+    ) public {
+        if (leaf_indices.length != messages.length)
+            revert BatchLengthMismatch();
 
+        bytes32 merkle_root = _getMerkleRoot(block_number);
+
+        bytes32[] memory msg_hashes = new bytes32[](messages.length);
+        for (uint256 i = 0; i < messages.length; i++) {
+            msg_hashes[i] = hash_vara_msg(messages[i]);
+        }
+
+        // `calculateRoot` overwrites the leaves so a copy is passed.
+        if (
+            MerkleMultiProof.calculateRoot(
+                proof,
+                _copy(msg_hashes),
+                leaf_indices,
+                total_leaves
+            ) != merkle_root
+        ) revert BadProof();
+
+        for (uint256 i = 0; i < messages.length; i++) {
+            _deliverMessage(block_number, msg_hashes[i], messages[i]);
+        }
+    }
+
+    function _getMerkleRoot(
+        uint256 block_number
+    ) internal view returns (bytes32) {
         bytes32 merkle_root = IRelayer(RELAYER_ADDRESS).getMerkleRoot(
             block_number
         );
 
         if (merkle_root == bytes32(0)) revert MerkleRootNotSet(block_number);
 
+        return merkle_root;
+    }
+
+    function _processMessage(
+        uint256 block_number,
+        bytes32 merkle_root,
+        uint256 total_leaves,
+        uint256 leaf_index,
+        VaraMessage calldata message,
+        bytes32[] calldata proof
+    ) internal {
+        bytes32 msg_hash = hash_vara_msg(message);
+
         if (
             _calculateMerkleRoot(proof, msg_hash, total_leaves, leaf_index) !=
             merkle_root
         ) revert BadProof();
 
+        _deliverMessage(block_number, msg_hash, message);
+    }
+
+    function _deliverMessage(
+        uint256 block_number,
+        bytes32 msg_hash,
+        VaraMessage calldata message
+    ) internal {
+        if (_processed_messages[message.nonce])
+            revert MessageAlreadyProcessed(message.nonce);
+
         _processed_messages[message.nonce] = true;
 
         if (
@@ -81,6 +159,15 @@ contract MessageQueue is IMessageQueue {
         }
     }
 
+    function _copy(
+        bytes32[] memory data
+    ) private pure returns (bytes32[] memory result) {
+        result = new bytes32[](data.length);
+        for (uint256 i = 0; i < data.length; i++) {
+            result[i] = data[i];
+        }
+    }
+
     /**
      * @dev Calculated merkle tree root for a provided merkle proof.
      *
@@ -98,6 +185,29 @@ contract MessageQueue is IMessageQueue {
         return _calculateMerkleRoot(proof, leaf_hash, total_leaves, leaf_index);
     }
 
+    /**
+     * @dev Calculated merkle tree root for a provided merkle multiproof.
+     *
+     * @param proof - Merkle multiproof.
+     * @param leaf_hashes - Hashes of data stored in target leaves.
+     * @param total_leaves - Number of leaves in merkle tree.
+     * @param leaf_indices - Indices of target leaves in ascending order.
+     */
+    function calculateMerkleRootMultiProof(
+        bytes32[] calldata proof,
+        bytes32[] memory leaf_hashes,
+        uint256 total_leaves,
+        uint256[] calldata leaf_indices
+    ) public pure returns (bytes32) {
+        return
+            MerkleMultiProof.calculateRoot(
+                proof,
+                leaf_hashes,
+                leaf_indices,
+                total_leaves
+            );
+    }
+
     /**
      * @dev Checks if `VaraMessage` already was processed.
      *
@@ -0,0 +1,47 @@
+pragma solidity ^0.8.24;
+
+import {IVaraMessenger} from "./interfaces/IVaraMessenger.sol";
+import {Ownable} from "@openzeppelin/contracts/access/Ownable.sol";
+
+contract VaraMessenger is IVaraMessenger, Ownable {
+    uint256 public nonce;
+    uint256 public fee;
+
+    constructor(uint256 _fee, address initialOwner) Ownable(initialOwner) {
+        fee = _fee;
+    }
+
+    /** @dev Send arbitrary message to the program on gear. `MessageToVaraSent` event is emitted
+     * that later can be verified on other side of bridge and delivered to the `destination`
+     * program through the `historical-proxy`.
+     *
+     * Every message gets unique `nonce` so several messages sent in the same transaction
+     * can be distinguished by the receiver.
+     *
+     * Exactly `fee` should be attached to the call. It's transferred to the owner and
+     * covers the relaying of the message to gear.
+     *
+     * @param destination program on gear that should receive the message.
+     * @param payload payload of the message.
+     * @return nonce of the sent message.
+     */
+    function sendMessageToVara(
+        bytes32 destination,
+        bytes calldata payload
+    ) external payable returns (uint256) {
+        require(msg.value == fee, "incorrect fee amount");
+
+        uint256 _nonce = nonce;
+        nonce = _nonce + 1;
+
+        payable(owner()).transfer(msg.value);
+
+        emit MessageToVaraSent(msg.sender, destination, _nonce, payload);
+
+        return _nonce;
+    }
+
+    function setFee(uint256 _fee) external onlyOwner {
+        fee = _fee;
+    }
+}
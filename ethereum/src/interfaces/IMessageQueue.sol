@@ -13,6 +13,7 @@ interface IMessageQueue {
     error MessageNotProcessed();
     error MerkleRootNotSet(uint256 blockNumber);
     error BadProof();
+    error BatchLengthMismatch();
 
     event RelayerAddressUpdated(address relayer);
     event ProoverAddressUpdated(address proover);
@@ -37,6 +38,14 @@ interface IMessageQueue {
         VaraMessage calldata message,
         bytes32[] calldata proof
     ) external;
+
+    function processMessages(
+        uint256 block_number,
+        uint256 total_leaves,
+        uint256[] calldata leaf_indices,
+        VaraMessage[] calldata messages,
+        bytes32[] calldata proof
+    ) external;
 }
 
 interface IMessageQueueReceiver {
@@ -0,0 +1,21 @@
+pragma solidity ^0.8.24;
+
+interface IVaraMessenger {
+    event MessageToVaraSent(
+        address indexed from,
+        bytes32 indexed destination,
+        uint256 indexed nonce,
+        bytes payload
+    );
+
+    function sendMessageToVara(
+        bytes32 destination,
+        bytes calldata payload
+    ) external payable returns (uint256);
+
+    function setFee(uint256 newFee) external;
+
+    function fee() external view returns (uint256);
+
+    function nonce() external view returns (uint256);
+}
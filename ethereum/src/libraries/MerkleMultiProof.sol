@@ -0,0 +1,93 @@
+pragma solidity ^0.8.24;
+
+/**
+ * @dev Verification of compact proofs of inclusion of several leaves into the binary merkle
+ *  tree of messages sent from Vara. Last node of a tree level with an odd number of nodes is
+ *  promoted to the upper level as is.
+ *
+ *  Proof contains only the nodes that can't be calculated from the leaves themselves, in the
+ *  order they're consumed when going through the tree bottom-up and left-to-right.
+ */
+library MerkleMultiProof {
+    error InvalidMultiProof();
+
+    /**
+     * @dev Calculate root of the merkle tree from the leaves and their multiproof.
+     *
+     * @param proof - Nodes required to calculate the root.
+     * @param leaves - Hashes of the leaves. Overwritten during calculation.
+     * @param leaf_indices - Indices of the leaves in strictly ascending order.
+     * @param total_leaves - Number of leaves in the merkle tree.
+     */
+    function calculateRoot(
+        bytes32[] calldata proof,
+        bytes32[] memory leaves,
+        uint256[] calldata leaf_indices,
+        uint256 total_leaves
+    ) internal pure returns (bytes32) {
+        uint256 count = leaves.length;
+        if (count == 0 || count != leaf_indices.length)
+            revert InvalidMultiProof();
+
+        uint256[] memory indices = new uint256[](count);
+        for (uint256 i = 0; i < count; i++) {
+            if (
+                leaf_indices[i] >= total_leaves ||
+                (i > 0 && leaf_indices[i] <= leaf_indices[i - 1])
+            ) revert InvalidMultiProof();
+
+            indices[i] = leaf_indices[i];
+        }
+
+        uint256 width = total_leaves;
+        uint256 proof_index = 0;
+
+        while (width > 1) {
+            uint256 count_next = 0;
+
+            for (uint256 i = 0; i < count; i++) {
+                uint256 index = indices[i];
+                bytes32 hash = leaves[i];
+
+                if (index % 2 == 1) {
+                    if (proof_index >= proof.length) revert InvalidMultiProof();
+
+                    hash = _hashPair(proof[proof_index++], hash);
+                } else if (index + 1 < width) {
+                    if (i + 1 < count && indices[i + 1] == index + 1) {
+                        hash = _hashPair(hash, leaves[i + 1]);
+                        i++;
+                    } else {
+                        if (proof_index >= proof.length)
+                            revert InvalidMultiProof();
+
+                        hash = _hashPair(hash, proof[proof_index++]);
+                    }
+                }
+
+                // Nodes of the next level are written in place since `count_next <= i`.
+                indices[count_next] = index / 2;
+                leaves[count_next] = hash;
+                count_next++;
+            }
+
+            count = count_next;
+            width = ((width - 1) / 2) + 1;
+        }
+
+        if (proof_index != proof.length) revert InvalidMultiProof();
+
+        return leaves[0];
+    }
+
+    function _hashPair(
+        bytes32 left,
+        bytes32 right
+    ) private pure returns (bytes32 hash) {
+        assembly {
+            mstore(0x00, left)
+            mstore(0x20, right)
+            hash := keccak256(0x00, 0x40)
+        }
+    }
+}
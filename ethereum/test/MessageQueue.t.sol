@@ -13,13 +13,27 @@ import {ERC20Manager} from "../src/ERC20Manager.sol";
 import {IERC20Manager, Packer, WithdrawMessage} from "../src/interfaces/IERC20Manager.sol";
 
 import {MessageQueue} from "../src/MessageQueue.sol";
-import {IMessageQueue, VaraMessage, Hasher} from "../src/interfaces/IMessageQueue.sol";
+import {IMessageQueue, IMessageQueueReceiver, VaraMessage, Hasher} from "../src/interfaces/IMessageQueue.sol";
 import {ProxyContract} from "../src/ProxyContract.sol";
+import {MerkleMultiProof} from "../src/libraries/MerkleMultiProof.sol";
 
 import {TestHelper, OWNER, USER, VARA_ADDRESS_3, VARA_ADDRESS_7, ETH_ADDRESS_3, ETH_ADDRESS_5, VFT_MANAGER_ADDRESS} from "./TestHelper.t.sol";
 
 import {ERC20Mock} from "../src/mocks/ERC20Mock.sol";
 
+contract MessageQueueReceiverMock is IMessageQueueReceiver {
+    bytes[] public payloads;
+
+    function processVaraMessage(
+        bytes32,
+        bytes calldata payload
+    ) external returns (bool) {
+        payloads.push(payload);
+
+        return true;
+    }
+}
+
 contract MessageQueueTest is TestHelper {
     using Address for address;
     using Hasher for VaraMessage;
@@ -447,4 +461,243 @@ contract MessageQueueTest is TestHelper {
         vm.expectRevert(IRelayer.EmergencyStop.selector);
         relayer.getMerkleRoot(BLOCK_ID);
     }
+
+    function test_calculate_root_multi_proof() public view {
+        bytes32[] memory leaf_hashes = new bytes32[](2);
+        leaf_hashes[0] = bytes32(
+            0x57caf83a5d10cdf3f3a28cdc6426da6a94ce5c2b966a8d08f948470358be53a8
+        );
+        leaf_hashes[1] = bytes32(
+            0xac9f1d13ebef420edd0101b06f534ec2495ca41af6c23cf14bc94f67bae8dfe1
+        );
+
+        uint256[] memory leaf_indices = new uint256[](2);
+        leaf_indices[0] = 2;
+        leaf_indices[1] = 3;
+
+        // Leaves #2 and #3 are siblings so they share the rest of the path.
+        bytes32[] memory proof = new bytes32[](6);
+        proof[0] = bytes32(
+            0x30cdfaedf81fed4b4564ef0e8c04c56d3481e0121501c2dcc12288e01f3ceb94
+        );
+        proof[1] = bytes32(
+            0xf87bc57ba7962a2b733f78df0e777ca31499b78c4d6f64c6d49ab0fd1dc60f44
+        );
+        proof[2] = bytes32(
+            0xed0dcf662c10b0827133e6e99e415b0d97da1a92ce69eb717838d55cc9067c49
+        );
+        proof[3] = bytes32(
+            0x2387406c963403e53d56621d1cef73b80089994ee4c5866ae2d21eaa9fcdfe01
+        );
+        proof[4] = bytes32(
+            0x08ab6b1030ad30cece656ac2638a8aed651bd759a6486241a293610f84927f52
+        );
+        proof[5] = bytes32(
+            0xe7e9ede5fe38231d6c068bc8f5d95b76eed9b255f9b892f77c4f640cc86514ac
+        );
+
+        bytes32 root = message_queue.calculateMerkleRootMultiProof(
+            proof,
+            leaf_hashes,
+            101,
+            leaf_indices
+        );
+
+        assertEq(
+            root,
+            bytes32(
+                0xbd18567f3cd28d09dc4f8b0f367415dc19f0e32d47424015eaf22103a4bf4cb3
+            )
+        );
+    }
+
+    function test_calculate_root_multi_proof_single_leaf() public view {
+        bytes32[] memory leaf_hashes = new bytes32[](1);
+        leaf_hashes[0] = bytes32(
+            0xcee28748a98c81d3eb24f23af4876c8d71c75efc61416bfd2bb018390b138794
+        );
+
+        uint256[] memory leaf_indices = new uint256[](1);
+        leaf_indices[0] = 100;
+
+        bytes32[] memory proof = new bytes32[](3);
+        proof[0] = bytes32(
+            0x69b655dccf32e0c3e4d4f427875a09b8cde36a2e6d1b980a8b1f8b134425652f
+        );
+        proof[1] = bytes32(
+            0x6d6e07bcb08ba34a789918ab09f0a8aabd1c42a1e7b8625448dab3ed03a02b59
+        );
+        proof[2] = bytes32(
+            0xbdfbb5c1b5550cf03c9819c027ee7d51d3153d372968cdfae6f01d261cb6877b
+        );
+
+        bytes32 root = message_queue.calculateMerkleRootMultiProof(
+            proof,
+            leaf_hashes,
+            101,
+            leaf_indices
+        );
+
+        assertEq(
+            root,
+            bytes32(
+                0x8db8d383e63f1ff7bbd1b35d7d1f240f6fce68aa12e60cd3a446021f8cd04226
+            )
+        );
+    }
+
+    function test_calculate_root_multi_proof_unsorted_leaves() public {
+        bytes32[] memory leaf_hashes = new bytes32[](2);
+        uint256[] memory leaf_indices = new uint256[](2);
+        leaf_indices[0] = 1;
+        leaf_indices[1] = 0;
+
+        vm.expectRevert(MerkleMultiProof.InvalidMultiProof.selector);
+        message_queue.calculateMerkleRootMultiProof(
+            new bytes32[](0),
+            leaf_hashes,
+            2,
+            leaf_indices
+        );
+    }
+
+    function test_process_messages_length_mismatch() public {
+        uint256[] memory leaf_indices = new uint256[](2);
+        VaraMessage[] memory messages = new VaraMessage[](1);
+
+        vm.expectRevert(IMessageQueue.BatchLengthMismatch.selector);
+        message_queue.processMessages(
+            BLOCK_ID,
+            2,
+            leaf_indices,
+            messages,
+            new bytes32[](0)
+        );
+    }
+
+    function test_process_messages_merkle_root_not_set() public {
+        uint256[] memory leaf_indices = new uint256[](0);
+        VaraMessage[] memory messages = new VaraMessage[](0);
+
+        vm.expectRevert(
+            abi.encodeWithSelector(
+                IMessageQueue.MerkleRootNotSet.selector,
+                BLOCK_ID + 1
+            )
+        );
+        message_queue.processMessages(
+            BLOCK_ID + 1,
+            2,
+            leaf_indices,
+            messages,
+            new bytes32[](0)
+        );
+    }
+
+    function test_process_messages_bad_proof() public {
+        uint256[] memory leaf_indices = new uint256[](2);
+        leaf_indices[0] = 0;
+        leaf_indices[1] = 1;
+
+        VaraMessage[] memory messages = new VaraMessage[](2);
+        messages[0] = VaraMessage({
+            sender: VARA_ADDRESS_3,
+            receiver: ETH_ADDRESS_3,
+            nonce: bytes32(uint256(0x03)),
+            data: hex"0303"
+        });
+        messages[1] = VaraMessage({
+            sender: VARA_ADDRESS_7,
+            receiver: ETH_ADDRESS_5,
+            nonce: bytes32(uint256(0x07)),
+            data: hex"0707"
+        });
+
+        vm.expectRevert(IMessageQueue.BadProof.selector);
+        message_queue.processMessages(
+            BLOCK_ID,
+            2,
+            leaf_indices,
+            messages,
+            new bytes32[](0)
+        );
+    }
+
+    function test_process_messages() public {
+        MessageQueueReceiverMock receiver = new MessageQueueReceiverMock();
+
+        VaraMessage[] memory messages = new VaraMessage[](2);
+        messages[0] = VaraMessage({
+            sender: VARA_ADDRESS_3,
+            receiver: address(receiver),
+            nonce: bytes32(uint256(0x03)),
+            data: hex"0303"
+        });
+        messages[1] = VaraMessage({
+            sender: VARA_ADDRESS_7,
+            receiver: address(receiver),
+            nonce: bytes32(uint256(0x07)),
+            data: hex"0707"
+        });
+
+        // Tree of 3 leaves where the messages are leaves #0 and #2. Leaf #2 is promoted
+        // to the upper level as is.
+        bytes32 leaf_1 = bytes32(uint256(0x01));
+        bytes32 hash_0 = messages[0].hash();
+        bytes32 hash_2 = messages[1].hash();
+        bytes32 root = keccak256(
+            abi.encodePacked(keccak256(abi.encodePacked(hash_0, leaf_1)), hash_2)
+        );
+
+        uint256 block_number = BLOCK_ID + 1;
+        relayer.submitMerkleRoot(block_number, root, bytes(hex"00"));
+
+        uint256[] memory leaf_indices = new uint256[](2);
+        leaf_indices[0] = 0;
+        leaf_indices[1] = 2;
+
+        bytes32[] memory proof = new bytes32[](1);
+        proof[0] = leaf_1;
+
+        vm.expectEmit(true, true, true, false);
+        emit IMessageQueue.MessageProcessed(
+            block_number,
+            hash_0,
+            messages[0].nonce
+        );
+        vm.expectEmit(true, true, true, false);
+        emit IMessageQueue.MessageProcessed(
+            block_number,
+            hash_2,
+            messages[1].nonce
+        );
+
+        message_queue.processMessages(
+            block_number,
+            3,
+            leaf_indices,
+            messages,
+            proof
+        );
+
+        assertEq(receiver.payloads(0), hex"0303");
+        assertEq(receiver.payloads(1), hex"0707");
+        assert(message_queue.isProcessed(messages[0]));
+        assert(message_queue.isProcessed(messages[1]));
+
+        // the whole batch is reverted if any of the messages is already processed
+        vm.expectRevert(
+            abi.encodeWithSelector(
+                IMessageQueue.MessageAlreadyProcessed.selector,
+                messages[0].nonce
+            )
+        );
+        message_queue.processMessages(
+            block_number,
+            3,
+            leaf_indices,
+            messages,
+            proof
+        );
+    }
 }
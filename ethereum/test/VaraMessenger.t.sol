@@ -0,0 +1,72 @@
+pragma solidity ^0.8.13;
+
+import {Test} from "forge-std/Test.sol";
+import {IVaraMessenger} from "../src/interfaces/IVaraMessenger.sol";
+import {VaraMessenger} from "../src/VaraMessenger.sol";
+
+contract VaraMessengerTest is Test {
+    uint256 constant NOT_ENOUGH_FEE = 99;
+    uint256 constant FEE = 100;
+    uint256 constant USER_BALANCE = 10 ether;
+
+    address constant ADMIN = address(42);
+    address constant USER = address(69);
+
+    bytes32 constant DESTINATION = bytes32(0x0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A0A);
+
+    VaraMessenger public vara_messenger;
+
+    function setUp() public {
+        vara_messenger = new VaraMessenger(FEE, ADMIN);
+    }
+
+    function test_sendMessageToVara() public {
+        vm.startPrank(USER, USER);
+        vm.deal(USER, USER_BALANCE);
+        vm.deal(ADMIN, 0);
+
+        bytes memory payload = hex"deadbeef";
+
+        vm.expectEmit(address(vara_messenger));
+        emit IVaraMessenger.MessageToVaraSent(USER, DESTINATION, 0, payload);
+
+        uint256 nonce = vara_messenger.sendMessageToVara{value: FEE}(DESTINATION, payload);
+        assertEq(nonce, 0);
+
+        vm.expectEmit(address(vara_messenger));
+        emit IVaraMessenger.MessageToVaraSent(USER, DESTINATION, 1, "");
+
+        nonce = vara_messenger.sendMessageToVara{value: FEE}(DESTINATION, "");
+        assertEq(nonce, 1);
+
+        assertEq(vara_messenger.nonce(), 2);
+        assertEq(ADMIN.balance, 2 * FEE);
+    }
+
+    function test_revertWhenIncorrectFee() public {
+        vm.startPrank(USER, USER);
+        vm.deal(USER, USER_BALANCE);
+
+        vm.expectRevert();
+        vara_messenger.sendMessageToVara{value: NOT_ENOUGH_FEE}(DESTINATION, "");
+
+        vm.expectRevert();
+        vara_messenger.sendMessageToVara(DESTINATION, "");
+
+        assertEq(vara_messenger.nonce(), 0);
+    }
+
+    function test_setFee() public {
+        vm.prank(USER);
+        vm.expectRevert();
+        vara_messenger.setFee(0);
+
+        vm.prank(ADMIN);
+        vara_messenger.setFee(0);
+        assertEq(vara_messenger.fee(), 0);
+
+        vm.prank(USER);
+        uint256 nonce = vara_messenger.sendMessageToVara(DESTINATION, "");
+        assertEq(nonce, 0);
+    }
+}
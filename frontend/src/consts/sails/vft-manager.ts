@@ -105,7 +105,13 @@ export type Error =
   | 'notSupportedEvent'
   | 'transactionTooOld'
   | 'alreadyProcessed'
-  | 'paused';
+  | 'paused'
+  | 'tokenPaused'
+  | 'transferLimitExceeded'
+  | 'windowCapExceeded'
+  | 'deliveryFailed'
+  | 'noFailedDelivery'
+  | 'refundAlreadyRequested';
 
 /**
  * State in which message processing can be.
@@ -160,6 +166,158 @@ export interface TxDetails {
    * [TokenSupply] type of the token being bridged.
    */
   token_supply: TokenSupply;
+  /**
+   * Block the bridging was requested at. Transfer is accounted in the rate limits at it.
+   */
+  requested_at: number;
+}
+
+/**
+ * Direction of the bridging.
+ */
+export type Direction = 'ethToVara' | 'varaToEth';
+
+/**
+ * Admin operation that is subject to the timelock.
+ */
+export type AdminOperation =
+  /**
+   * See [super::VftManager::map_vara_to_eth_address].
+   */
+  | { mapVaraToEthAddress: { vara_token_id: ActorId; eth_token_id: H160; supply_type: TokenSupply } }
+  /**
+   * See [super::VftManager::update_historical_proxy_address].
+   */
+  | { updateHistoricalProxyAddress: ActorId }
+  /**
+   * See [super::VftManager::update_erc20_manager_address].
+   */
+  | { updateErc20ManagerAddress: H160 }
+  /**
+   * See [super::VftManager::update_checkpoint_light_client_address].
+   */
+  | { updateCheckpointLightClientAddress: ActorId }
+  /**
+   * See [super::VftManager::upgrade].
+   */
+  | { upgrade: ActorId }
+  /**
+   * See [super::VftManager::update_timelock_delay].
+   */
+  | { updateTimelockDelay: number }
+  /**
+   * See [super::VftManager::remove_vara_to_eth_address].
+   */
+  | { removeVaraToEthAddress: ActorId }
+  /**
+   * See [super::VftManager::update_config].
+   */
+  | { updateConfig: Config }
+  /**
+   * See [super::VftManager::update_vfts].
+   */
+  | { updateVfts: Array<[ActorId, ActorId]> }
+  /**
+   * See [super::VftManager::set_rate_limit].
+   */
+  | { setRateLimit: { vara_token_id: ActorId; direction: Direction; limit: RateLimit | null } }
+  /**
+   * See [super::VftManager::set_admin].
+   */
+  | { setAdmin: ActorId }
+  /**
+   * See [super::VftManager::set_pause_admin].
+   */
+  | { setPauseAdmin: ActorId };
+
+/**
+ * Limits of the amount of tokens bridged in one direction.
+ */
+export interface RateLimit {
+  /**
+   * Maximum amount of tokens in a single transfer. Unlimited if `None`.
+   */
+  max_per_transfer: number | string | bigint | null;
+  /**
+   * Maximum amount of tokens transferred during the last [RateLimit::window] blocks.
+   * Unlimited if `None`.
+   */
+  window_cap: number | string | bigint | null;
+  /**
+   * Length of the rolling window in blocks.
+   */
+  window: number;
+}
+
+/**
+ * Deposit which tokens the `VFT` program has refused to mint/unlock.
+ */
+export interface FailedDelivery {
+  /**
+   * Details of the deposit.
+   */
+  deposit: Deposit;
+  /**
+   * State of the refund to the sender on Ethereum.
+   */
+  refund: RefundStatus;
+}
+
+/**
+ * Tokens deposited on Ethereum that should be delivered to the receiver on Gear.
+ */
+export interface Deposit {
+  /**
+   * `VFT` token address that should be minted/unlocked.
+   */
+  vara_token_id: ActorId;
+  /**
+   * `ERC20` token address that was locked/burned on Ethereum.
+   */
+  eth_token_id: H160;
+  /**
+   * Original token owner on the Ethereum side.
+   */
+  sender: H160;
+  /**
+   * Receiver of the tokens on the Gear side.
+   */
+  receiver: ActorId;
+  /**
+   * Deposited amount of tokens.
+   */
+  amount: number | string | bigint;
+}
+
+/**
+ * State of the refund of a [FailedDelivery].
+ */
+export type RefundStatus =
+  /**
+   * Refund can be claimed.
+   */
+  | { notRequested: null }
+  /**
+   * Message to the `gear-eth-bridge` built-in actor is sent, waiting for the reply.
+   */
+  | { requested: null }
+  /**
+   * Refund message is queued by the `gear-eth-bridge` built-in actor with the nonce.
+   */
+  | { completed: number | string | bigint };
+
+/**
+ * Operation waiting for its delay to pass.
+ */
+export interface PendingOperation {
+  /**
+   * Scheduled operation.
+   */
+  operation: AdminOperation;
+  /**
+   * Block starting from which the operation can be executed.
+   */
+  executable_at: number;
 }
 
 /**
@@ -178,6 +336,20 @@ export interface MessageInfo {
 
 export type Order = 'direct' | 'reverse';
 
+/**
+ * Directions in which bridging of the token pair is paused.
+ */
+export interface PausedDirections {
+  /**
+   * Bridging from Ethereum to Gear is paused.
+   */
+  eth_to_vara: boolean;
+  /**
+   * Bridging from Gear to Ethereum is paused.
+   */
+  vara_to_eth: boolean;
+}
+
 export class SailsProgram {
   public readonly registry: TypeRegistry;
   public readonly vftManager: VftManager;
@@ -224,6 +396,12 @@ export class SailsProgram {
           'TransactionTooOld',
           'AlreadyProcessed',
           'Paused',
+          'TokenPaused',
+          'TransferLimitExceeded',
+          'WindowCapExceeded',
+          'DeliveryFailed',
+          'NoFailedDelivery',
+          'RefundAlreadyRequested',
         ],
       },
       MessageStatus: {
@@ -242,9 +420,33 @@ export class SailsProgram {
         amount: 'U256',
         receiver: 'H160',
         token_supply: 'TokenSupply',
+        requested_at: 'u32',
+      },
+      Direction: { _enum: ['EthToVara', 'VaraToEth'] },
+      AdminOperation: {
+        _enum: {
+          MapVaraToEthAddress: { vara_token_id: '[u8;32]', eth_token_id: 'H160', supply_type: 'TokenSupply' },
+          UpdateHistoricalProxyAddress: '[u8;32]',
+          UpdateErc20ManagerAddress: 'H160',
+          UpdateCheckpointLightClientAddress: '[u8;32]',
+          Upgrade: '[u8;32]',
+          UpdateTimelockDelay: 'u32',
+          RemoveVaraToEthAddress: '[u8;32]',
+          UpdateConfig: 'Config',
+          UpdateVfts: 'Vec<([u8;32], [u8;32])>',
+          SetRateLimit: { vara_token_id: '[u8;32]', direction: 'Direction', limit: 'Option<RateLimit>' },
+          SetAdmin: '[u8;32]',
+          SetPauseAdmin: '[u8;32]',
+        },
       },
+      RateLimit: { max_per_transfer: 'Option<U256>', window_cap: 'Option<U256>', window: 'u32' },
+      FailedDelivery: { deposit: 'Deposit', refund: 'RefundStatus' },
+      Deposit: { vara_token_id: '[u8;32]', eth_token_id: 'H160', sender: 'H160', receiver: '[u8;32]', amount: 'U256' },
+      RefundStatus: { _enum: { NotRequested: 'Null', Requested: 'Null', Completed: 'U256' } },
+      PendingOperation: { operation: 'AdminOperation', executable_at: 'u32' },
       MessageInfo: { status: 'MessageStatus', details: 'TxDetails' },
       Order: { _enum: ['Direct', 'Reverse'] },
+      PausedDirections: { eth_to_vara: 'bool', vara_to_eth: 'bool' },
     };
 
     this.registry = new TypeRegistry();
@@ -390,6 +592,64 @@ export class VftManager {
     );
   }
 
+  /**
+   * Cancel scheduled admin operation.
+   *
+   * Can be called only by a [State::admin] or [State::pause_admin].
+   */
+  public cancelOperation(id: number | string | bigint): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'CancelOperation', id],
+      '(String, String, u64)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Request refund of the tokens which have failed to be delivered to the receiver
+   * on Gear. Tokens are returned to the original sender on Ethereum.
+   *
+   * Can be called by anyone since the refund receiver is fixed by the deposit.
+   */
+  public claimRefund(
+    slot: number | string | bigint,
+    transaction_index: number | string | bigint,
+  ): TransactionBuilder<{ ok: number | string | bigint } | { err: Error }> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<{ ok: number | string | bigint } | { err: Error }>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'ClaimRefund', slot, transaction_index],
+      '(String, String, u64, u64)',
+      'Result<U256, Error>',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Execute scheduled admin operation which delay has passed.
+   *
+   * Can be called only by a [State::admin].
+   */
+  public executeOperation(id: number | string | bigint): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'ExecuteOperation', id],
+      '(String, String, u64)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
   /**
    * The method is intended for tests and is available only when the feature `mocks`
    * is enabled. Populates the collection with processed transactions.
@@ -452,7 +712,29 @@ export class VftManager {
   }
 
   /**
-   * Add a new token pair to a [State::token_map]. Can be called only by a [State::admin].
+   * Insert already processed Ethereum transactions. It's intended to migrate them from
+   * the previous `vft-manager` program, see [VftManager::transactions].
+   *
+   * Can be called only by a [State::admin] while the program is paused.
+   */
+  public insertTransactions(
+    transactions: Array<[number | string | bigint, number | string | bigint]>,
+  ): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'InsertTransactions', transactions],
+      '(String, String, Vec<(u64, u64)>)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Add a new token pair to a [State::token_map]. Can be called only by a [State::admin]
+   * when the [State::timelock] is disabled.
    */
   public mapVaraToEthAddress(
     vara_token_id: ActorId,
@@ -494,7 +776,50 @@ export class VftManager {
   }
 
   /**
-   * Remove the token pair from [State::token_map]. Can be called only by a [State::admin].
+   * Pause bridging of the token pair in the specified direction.
+   *
+   * Unlike [VftManager::pause] it affects only requests to `submit_receipt` (for
+   * [Direction::EthToVara]) or `request_bridging` (for [Direction::VaraToEth]) with
+   * the specified token.
+   *
+   * Can be called only by a [State::admin] or [State::pause_admin].
+   */
+  public pauseToken(vara_token_id: ActorId, direction: Direction): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'PauseToken', vara_token_id, direction],
+      '(String, String, [u8;32], Direction)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Remove up to `count` processed Ethereum transactions from the slots that can't be
+   * proven anymore by the [State::checkpoint_light_client_address] program. Such
+   * transactions are rejected by `submit_receipt` afterwards.
+   *
+   * Returns the amount of removed transactions. Can be called by anyone.
+   */
+  public pruneTransactions(count: number): TransactionBuilder<number> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<number>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'PruneTransactions', count],
+      '(String, String, u32)',
+      'u32',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Remove the token pair from [State::token_map]. Can be called only by a [State::admin]
+   * when the [State::timelock] is disabled.
    */
   public removeVaraToEthAddress(vara_token_id: ActorId): TransactionBuilder<null> {
     if (!this._program.programId) throw new Error('Program ID is not set');
@@ -533,7 +858,26 @@ export class VftManager {
   }
 
   /**
-   * Change [State::admin]. Can be called only by a [State::admin].
+   * Schedule admin operation to be executed after the delay of the [State::timelock].
+   *
+   * Can be called only by a [State::admin].
+   */
+  public scheduleOperation(operation: AdminOperation): TransactionBuilder<number | string | bigint> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<number | string | bigint>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'ScheduleOperation', operation],
+      '(String, String, AdminOperation)',
+      'u64',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Change [State::admin]. Can be called only by a [State::admin] when the
+   * [State::timelock] is disabled.
    */
   public setAdmin(new_admin: ActorId): TransactionBuilder<null> {
     if (!this._program.programId) throw new Error('Program ID is not set');
@@ -549,7 +893,8 @@ export class VftManager {
   }
 
   /**
-   * Change [State::pause_admin]. Can be called only by a [State::admin].
+   * Change [State::pause_admin]. Can be called only by a [State::admin] when the
+   * [State::timelock] is disabled.
    */
   public setPauseAdmin(new_pause_admin: ActorId): TransactionBuilder<null> {
     if (!this._program.programId) throw new Error('Program ID is not set');
@@ -564,6 +909,24 @@ export class VftManager {
     );
   }
 
+  /**
+   * Set rate limit of the token in the specified direction or remove it if `limit`
+   * is `None`. Can be called only by a [State::admin] when the [State::timelock]
+   * is disabled.
+   */
+  public setRateLimit(vara_token_id: ActorId, direction: Direction, limit: RateLimit | null): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'SetRateLimit', vara_token_id, direction, limit],
+      '(String, String, [u8;32], Direction, Option<RateLimit>)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
   /**
    * Submit rlp-encoded transaction receipt.
    *
@@ -610,7 +973,45 @@ export class VftManager {
   }
 
   /**
-   * Change [Config]. Can be called only by a [State::admin].
+   * Unpause bridging of the token pair in the specified direction.
+   *
+   * It will effectively cancel effect of the [VftManager::pause_token].
+   *
+   * Can be called only by a [State::admin] or [State::pause_admin].
+   */
+  public unpauseToken(vara_token_id: ActorId, direction: Direction): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'UnpauseToken', vara_token_id, direction],
+      '(String, String, [u8;32], Direction)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Change [State::checkpoint_light_client_address]. Can be called only by a [State::admin]
+   * when the [State::timelock] is disabled.
+   */
+  public updateCheckpointLightClientAddress(checkpoint_light_client_address_new: ActorId): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'UpdateCheckpointLightClientAddress', checkpoint_light_client_address_new],
+      '(String, String, [u8;32])',
+      'Null',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Change [Config]. Can be called only by a [State::admin] when the [State::timelock]
+   * is disabled.
    *
    * For more info see [Config] docs.
    */
@@ -628,7 +1029,8 @@ export class VftManager {
   }
 
   /**
-   * Change [State::erc20_manager_address]. Can be called only by a [State::admin].
+   * Change [State::erc20_manager_address]. Can be called only by a [State::admin]
+   * when the [State::timelock] is disabled.
    */
   public updateErc20ManagerAddress(new_erc20_manager_address: H160): TransactionBuilder<null> {
     if (!this._program.programId) throw new Error('Program ID is not set');
@@ -644,7 +1046,8 @@ export class VftManager {
   }
 
   /**
-   * Change [State::historical_proxy_address]. Can be called only by a [State::admin].
+   * Change [State::historical_proxy_address]. Can be called only by a [State::admin]
+   * when the [State::timelock] is disabled.
    */
   public updateHistoricalProxyAddress(historical_proxy_address_new: ActorId): TransactionBuilder<null> {
     if (!this._program.programId) throw new Error('Program ID is not set');
@@ -659,6 +1062,30 @@ export class VftManager {
     );
   }
 
+  /**
+   * Change delay of the [State::timelock]. Zero delay disables the timelock.
+   *
+   * Can be called only by a [State::admin] when the [State::timelock] is disabled.
+   */
+  public updateTimelockDelay(delay: number): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'UpdateTimelockDelay', delay],
+      '(String, String, u32)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Replace `VFT` programs that have exited with the new ones in the [State::token_map].
+   * `vft_map` contains pairs of the old and the new `VFT` addresses.
+   *
+   * Can be called only by a [State::admin] when the [State::timelock] is disabled.
+   */
   public updateVfts(vft_map: Array<[ActorId, ActorId]>): TransactionBuilder<null> {
     if (!this._program.programId) throw new Error('Program ID is not set');
     return new TransactionBuilder<null>(
@@ -672,6 +1099,12 @@ export class VftManager {
     );
   }
 
+  /**
+   * Upgrade to the new `vft-manager` program transferring all the locked tokens to it.
+   *
+   * Can be called only by a [State::admin] when the [State::timelock] is disabled. If it
+   * was interrupted, it also can be called directly to continue the same upgrade.
+   */
   public upgrade(vft_manager_new: ActorId): TransactionBuilder<null> {
     if (!this._program.programId) throw new Error('Program ID is not set');
     return new TransactionBuilder<null>(
@@ -708,15 +1141,15 @@ export class VftManager {
   }
 
   /**
-   * Get current [State::erc20_manager_address] address.
+   * Get current [State::checkpoint_light_client_address].
    */
-  public async erc20ManagerAddress(
+  public async checkpointLightClientAddress(
     originAddress?: string,
     value?: number | string | bigint,
     atBlock?: `0x${string}`,
-  ): Promise<H160> {
+  ): Promise<ActorId> {
     const payload = this._program.registry
-      .createType('(String, String)', ['VftManager', 'Erc20ManagerAddress'])
+      .createType('(String, String)', ['VftManager', 'CheckpointLightClientAddress'])
       .toHex();
     const reply = await this._program.api.message.calculateReply({
       destination: this._program.programId,
@@ -727,14 +1160,69 @@ export class VftManager {
       at: atBlock,
     });
     throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
-    const result = this._program.registry.createType('(String, String, H160)', reply.payload);
-    return result[2].toJSON() as unknown as H160;
+    const result = this._program.registry.createType('(String, String, [u8;32])', reply.payload);
+    return result[2].toJSON() as unknown as ActorId;
   }
 
   /**
-   * Get current [State::gear_bridge_builtin] address.
+   * Get current [State::erc20_manager_address] address.
    */
-  public async gearBridgeBuiltin(
+  public async erc20ManagerAddress(
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<H160> {
+    const payload = this._program.registry
+      .createType('(String, String)', ['VftManager', 'Erc20ManagerAddress'])
+      .toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType('(String, String, H160)', reply.payload);
+    return result[2].toJSON() as unknown as H160;
+  }
+
+  /**
+   * Get Ethereum transactions which tokens have failed to be delivered.
+   */
+  public async failedDeliveries(
+    start: number,
+    count: number,
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<Array<[[number | string | bigint, number | string | bigint], FailedDelivery]>> {
+    const payload = this._program.registry
+      .createType('(String, String, u32, u32)', ['VftManager', 'FailedDeliveries', start, count])
+      .toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType(
+      '(String, String, Vec<((u64, u64), FailedDelivery)>)',
+      reply.payload,
+    );
+    return result[2].toJSON() as unknown as Array<
+      [[number | string | bigint, number | string | bigint], FailedDelivery]
+    >;
+  }
+
+  /**
+   * Get current [State::gear_bridge_builtin] address.
+   */
+  public async gearBridgeBuiltin(
     originAddress?: string,
     value?: number | string | bigint,
     atBlock?: `0x${string}`,
@@ -844,17 +1332,128 @@ export class VftManager {
   }
 
   /**
-   * Get state of a `request_bridging` message tracker.
+   * Get admin operations scheduled in the [State::timelock].
    */
-  public async requestBridingMsgTrackerState(
-    start: number,
+  public async pendingOperations(
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<Array<[number | string | bigint, PendingOperation]>> {
+    const payload = this._program.registry.createType('(String, String)', ['VftManager', 'PendingOperations']).toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType('(String, String, Vec<(u64, PendingOperation)>)', reply.payload);
+    return result[2].toJSON() as unknown as Array<[number | string | bigint, PendingOperation]>;
+  }
+
+  /**
+   * Get amount of tokens bridged in the specified direction during the current
+   * rolling window of the [rate limit](State::rate_limits).
+   */
+  public async rateLimitUsage(
+    vara_token_id: ActorId,
+    direction: Direction,
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<number | string | bigint> {
+    const payload = this._program.registry
+      .createType('(String, String, [u8;32], Direction)', ['VftManager', 'RateLimitUsage', vara_token_id, direction])
+      .toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType('(String, String, U256)', reply.payload);
+    return result[2].toJSON() as unknown as number | string | bigint;
+  }
+
+  /**
+   * Get current [rate limits](State::rate_limits).
+   */
+  public async rateLimits(
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<Array<[ActorId, Direction, RateLimit]>> {
+    const payload = this._program.registry.createType('(String, String)', ['VftManager', 'RateLimits']).toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType(
+      '(String, String, Vec<([u8;32], Direction, RateLimit)>)',
+      reply.payload,
+    );
+    return result[2].toJSON() as unknown as Array<[ActorId, Direction, RateLimit]>;
+  }
+
+  /**
+   * Get state of the message in a `request_bridging` message tracker.
+   */
+  public async requestBridgingMsgTrackerMessage(
+    msg_id: MessageId,
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<MessageInfo | null> {
+    const payload = this._program.registry
+      .createType('(String, String, [u8;32])', ['VftManager', 'RequestBridgingMsgTrackerMessage', msg_id])
+      .toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType('(String, String, Option<MessageInfo>)', reply.payload);
+    return result[2].toJSON() as unknown as MessageInfo | null;
+  }
+
+  /**
+   * Get page of a `request_bridging` message tracker state.
+   *
+   * Returns up to `count` messages ordered by [MessageId] starting right after the `after`
+   * message (or from the first one if it's `None`). The next page can be requested by
+   * passing the last [MessageId] of the current page.
+   *
+   * Messages that have reached the terminal state are removed from the tracker
+   * after the retention window.
+   */
+  public async requestBridgingMsgTrackerPage(
+    after: MessageId | null,
     count: number,
     originAddress?: string,
     value?: number | string | bigint,
     atBlock?: `0x${string}`,
   ): Promise<Array<[MessageId, MessageInfo]>> {
     const payload = this._program.registry
-      .createType('(String, String, u32, u32)', ['VftManager', 'RequestBridingMsgTrackerState', start, count])
+      .createType('(String, String, Option<[u8;32]>, u32)', [
+        'VftManager',
+        'RequestBridgingMsgTrackerPage',
+        after,
+        count,
+      ])
       .toHex();
     const reply = await this._program.api.message.calculateReply({
       destination: this._program.programId,
@@ -869,6 +1468,28 @@ export class VftManager {
     return result[2].toJSON() as unknown as Array<[MessageId, MessageInfo]>;
   }
 
+  /**
+   * Get current delay of the [State::timelock] in blocks.
+   */
+  public async timelockDelay(
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<number> {
+    const payload = this._program.registry.createType('(String, String)', ['VftManager', 'TimelockDelay']).toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType('(String, String, u32)', reply.payload);
+    return result[2].toJSON() as unknown as number;
+  }
+
   public async transactions(
     order: Order,
     start: number,
@@ -893,14 +1514,40 @@ export class VftManager {
     return result[2].toJSON() as unknown as Array<[number | string | bigint, number | string | bigint]>;
   }
 
+  /**
+   * Get the slot starting from which Ethereum transactions are accepted by `submit_receipt`.
+   */
+  public async transactionsSlotFloor(
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<number | string | bigint> {
+    const payload = this._program.registry
+      .createType('(String, String)', ['VftManager', 'TransactionsSlotFloor'])
+      .toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType('(String, String, u64)', reply.payload);
+    return result[2].toJSON() as unknown as number | string | bigint;
+  }
+
   /**
    * Get current [token mapping](State::token_map).
+   *
+   * Every entry also contains the directions in which bridging of the pair is paused.
    */
   public async varaToEthAddresses(
     originAddress?: string,
     value?: number | string | bigint,
     atBlock?: `0x${string}`,
-  ): Promise<Array<[ActorId, H160, TokenSupply]>> {
+  ): Promise<Array<[ActorId, H160, TokenSupply, PausedDirections]>> {
     const payload = this._program.registry.createType('(String, String)', ['VftManager', 'VaraToEthAddresses']).toHex();
     const reply = await this._program.api.message.calculateReply({
       destination: this._program.programId,
@@ -912,10 +1559,10 @@ export class VftManager {
     });
     throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
     const result = this._program.registry.createType(
-      '(String, String, Vec<([u8;32], H160, TokenSupply)>)',
+      '(String, String, Vec<([u8;32], H160, TokenSupply, PausedDirections)>)',
       reply.payload,
     );
-    return result[2].toJSON() as unknown as Array<[ActorId, H160, TokenSupply]>;
+    return result[2].toJSON() as unknown as Array<[ActorId, H160, TokenSupply, PausedDirections]>;
   }
 
   /**
@@ -1015,6 +1662,263 @@ export class VftManager {
     });
   }
 
+  /**
+   * Rate limit of the token was changed by an admin.
+   */
+  public subscribeToRateLimitUpdatedEvent(
+    callback: (data: { vara_token_id: ActorId; direction: Direction; limit: RateLimit | null }) => void | Promise<void>,
+  ): Promise<() => void> {
+    return this._program.api.gearEvents.subscribeToGearEvent('UserMessageSent', ({ data: { message } }) => {
+      if (!message.source.eq(this._program.programId) || !message.destination.eq(ZERO_ADDRESS)) {
+        return;
+      }
+
+      const payload = message.payload.toHex();
+      if (getServiceNamePrefix(payload) === 'VftManager' && getFnNamePrefix(payload) === 'RateLimitUpdated') {
+        callback(
+          this._program.registry
+            .createType(
+              '(String, String, {"vara_token_id":"[u8;32]","direction":"Direction","limit":"Option<RateLimit>"})',
+              message.payload,
+            )[2]
+            .toJSON() as unknown as { vara_token_id: ActorId; direction: Direction; limit: RateLimit | null },
+        );
+      }
+    });
+  }
+
+  /**
+   * Transfer was rejected because it exceeds the rate limit of the token.
+   */
+  public subscribeToRateLimitExceededEvent(
+    callback: (data: {
+      vara_token_id: ActorId;
+      direction: Direction;
+      amount: number | string | bigint;
+    }) => void | Promise<void>,
+  ): Promise<() => void> {
+    return this._program.api.gearEvents.subscribeToGearEvent('UserMessageSent', ({ data: { message } }) => {
+      if (!message.source.eq(this._program.programId) || !message.destination.eq(ZERO_ADDRESS)) {
+        return;
+      }
+
+      const payload = message.payload.toHex();
+      if (getServiceNamePrefix(payload) === 'VftManager' && getFnNamePrefix(payload) === 'RateLimitExceeded') {
+        callback(
+          this._program.registry
+            .createType(
+              '(String, String, {"vara_token_id":"[u8;32]","direction":"Direction","amount":"U256"})',
+              message.payload,
+            )[2]
+            .toJSON() as unknown as { vara_token_id: ActorId; direction: Direction; amount: number | string | bigint },
+        );
+      }
+    });
+  }
+
+  /**
+   * Bridging of the token pair was paused in the specified direction.
+   *
+   * It means that user requests to bridge this token in the specified direction
+   * will be rejected while the other token pairs keep working.
+   */
+  public subscribeToTokenPausedEvent(
+    callback: (data: { vara_token_id: ActorId; direction: Direction }) => void | Promise<void>,
+  ): Promise<() => void> {
+    return this._program.api.gearEvents.subscribeToGearEvent('UserMessageSent', ({ data: { message } }) => {
+      if (!message.source.eq(this._program.programId) || !message.destination.eq(ZERO_ADDRESS)) {
+        return;
+      }
+
+      const payload = message.payload.toHex();
+      if (getServiceNamePrefix(payload) === 'VftManager' && getFnNamePrefix(payload) === 'TokenPaused') {
+        callback(
+          this._program.registry
+            .createType('(String, String, {"vara_token_id":"[u8;32]","direction":"Direction"})', message.payload)[2]
+            .toJSON() as unknown as { vara_token_id: ActorId; direction: Direction },
+        );
+      }
+    });
+  }
+
+  /**
+   * Bridging of the token pair was unpaused in the specified direction.
+   */
+  public subscribeToTokenUnpausedEvent(
+    callback: (data: { vara_token_id: ActorId; direction: Direction }) => void | Promise<void>,
+  ): Promise<() => void> {
+    return this._program.api.gearEvents.subscribeToGearEvent('UserMessageSent', ({ data: { message } }) => {
+      if (!message.source.eq(this._program.programId) || !message.destination.eq(ZERO_ADDRESS)) {
+        return;
+      }
+
+      const payload = message.payload.toHex();
+      if (getServiceNamePrefix(payload) === 'VftManager' && getFnNamePrefix(payload) === 'TokenUnpaused') {
+        callback(
+          this._program.registry
+            .createType('(String, String, {"vara_token_id":"[u8;32]","direction":"Direction"})', message.payload)[2]
+            .toJSON() as unknown as { vara_token_id: ActorId; direction: Direction },
+        );
+      }
+    });
+  }
+
+  /**
+   * Admin operation was scheduled for the delayed execution.
+   */
+  public subscribeToOperationScheduledEvent(
+    callback: (data: {
+      id: number | string | bigint;
+      operation: AdminOperation;
+      executable_at: number;
+    }) => void | Promise<void>,
+  ): Promise<() => void> {
+    return this._program.api.gearEvents.subscribeToGearEvent('UserMessageSent', ({ data: { message } }) => {
+      if (!message.source.eq(this._program.programId) || !message.destination.eq(ZERO_ADDRESS)) {
+        return;
+      }
+
+      const payload = message.payload.toHex();
+      if (getServiceNamePrefix(payload) === 'VftManager' && getFnNamePrefix(payload) === 'OperationScheduled') {
+        callback(
+          this._program.registry
+            .createType(
+              '(String, String, {"id":"u64","operation":"AdminOperation","executable_at":"u32"})',
+              message.payload,
+            )[2]
+            .toJSON() as unknown as { id: number | string | bigint; operation: AdminOperation; executable_at: number },
+        );
+      }
+    });
+  }
+
+  /**
+   * Scheduled admin operation was executed.
+   */
+  public subscribeToOperationExecutedEvent(
+    callback: (data: { id: number | string | bigint }) => void | Promise<void>,
+  ): Promise<() => void> {
+    return this._program.api.gearEvents.subscribeToGearEvent('UserMessageSent', ({ data: { message } }) => {
+      if (!message.source.eq(this._program.programId) || !message.destination.eq(ZERO_ADDRESS)) {
+        return;
+      }
+
+      const payload = message.payload.toHex();
+      if (getServiceNamePrefix(payload) === 'VftManager' && getFnNamePrefix(payload) === 'OperationExecuted') {
+        callback(
+          this._program.registry
+            .createType('(String, String, {"id":"u64"})', message.payload)[2]
+            .toJSON() as unknown as { id: number | string | bigint },
+        );
+      }
+    });
+  }
+
+  /**
+   * Scheduled admin operation was cancelled.
+   */
+  public subscribeToOperationCancelledEvent(
+    callback: (data: { id: number | string | bigint }) => void | Promise<void>,
+  ): Promise<() => void> {
+    return this._program.api.gearEvents.subscribeToGearEvent('UserMessageSent', ({ data: { message } }) => {
+      if (!message.source.eq(this._program.programId) || !message.destination.eq(ZERO_ADDRESS)) {
+        return;
+      }
+
+      const payload = message.payload.toHex();
+      if (getServiceNamePrefix(payload) === 'VftManager' && getFnNamePrefix(payload) === 'OperationCancelled') {
+        callback(
+          this._program.registry
+            .createType('(String, String, {"id":"u64"})', message.payload)[2]
+            .toJSON() as unknown as { id: number | string | bigint },
+        );
+      }
+    });
+  }
+
+  /**
+   * `VFT` program has refused to mint/unlock tokens deposited on Ethereum.
+   *
+   * Tokens can be refunded to the sender on Ethereum by calling `claim_refund`.
+   */
+  public subscribeToDeliveryFailedEvent(
+    callback: (data: {
+      slot: number | string | bigint;
+      transaction_index: number | string | bigint;
+      vara_token_id: ActorId;
+      sender: H160;
+      receiver: ActorId;
+      amount: number | string | bigint;
+    }) => void | Promise<void>,
+  ): Promise<() => void> {
+    return this._program.api.gearEvents.subscribeToGearEvent('UserMessageSent', ({ data: { message } }) => {
+      if (!message.source.eq(this._program.programId) || !message.destination.eq(ZERO_ADDRESS)) {
+        return;
+      }
+
+      const payload = message.payload.toHex();
+      if (getServiceNamePrefix(payload) === 'VftManager' && getFnNamePrefix(payload) === 'DeliveryFailed') {
+        callback(
+          this._program.registry
+            .createType(
+              '(String, String, {"slot":"u64","transaction_index":"u64","vara_token_id":"[u8;32]","sender":"H160","receiver":"[u8;32]","amount":"U256"})',
+              message.payload,
+            )[2]
+            .toJSON() as unknown as {
+            slot: number | string | bigint;
+            transaction_index: number | string | bigint;
+            vara_token_id: ActorId;
+            sender: H160;
+            receiver: ActorId;
+            amount: number | string | bigint;
+          },
+        );
+      }
+    });
+  }
+
+  /**
+   * Refund of the failed delivery was requested.
+   *
+   * When this event is emitted it means that a message returning tokens to the original
+   * sender was successfully submitted to the gear-eth-bridge built-in actor.
+   */
+  public subscribeToRefundRequestedEvent(
+    callback: (data: {
+      slot: number | string | bigint;
+      transaction_index: number | string | bigint;
+      nonce: number | string | bigint;
+      eth_token_id: H160;
+      receiver: H160;
+      amount: number | string | bigint;
+    }) => void | Promise<void>,
+  ): Promise<() => void> {
+    return this._program.api.gearEvents.subscribeToGearEvent('UserMessageSent', ({ data: { message } }) => {
+      if (!message.source.eq(this._program.programId) || !message.destination.eq(ZERO_ADDRESS)) {
+        return;
+      }
+
+      const payload = message.payload.toHex();
+      if (getServiceNamePrefix(payload) === 'VftManager' && getFnNamePrefix(payload) === 'RefundRequested') {
+        callback(
+          this._program.registry
+            .createType(
+              '(String, String, {"slot":"u64","transaction_index":"u64","nonce":"U256","eth_token_id":"H160","receiver":"H160","amount":"U256"})',
+              message.payload,
+            )[2]
+            .toJSON() as unknown as {
+            slot: number | string | bigint;
+            transaction_index: number | string | bigint;
+            nonce: number | string | bigint;
+            eth_token_id: H160;
+            receiver: H160;
+            amount: number | string | bigint;
+          },
+        );
+      }
+    });
+  }
+
   /**
    * Vft-manager was paused by an admin.
    *
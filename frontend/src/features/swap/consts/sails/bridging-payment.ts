@@ -5,26 +5,125 @@ import { TypeRegistry } from '@polkadot/types';
 import {
   TransactionBuilder,
   ActorId,
+  H160,
   throwOnErrorReply,
   getServiceNamePrefix,
   getFnNamePrefix,
   ZERO_ADDRESS,
 } from 'sails-js';
 
-interface State {
+/**
+ * Global state of the Bridging Payment service.
+ */
+export interface State {
   /**
    * Admin of this service. Admin is in charge of:
-   * - Changing fee
-   * - Withdrawing collected fees from the program address
+   * - Changing fees and the fee formulas
+   * - Managing the set of gas price oracles
+   * - Withdrawing fees of the delivered messages from the program address
+   * - Attesting delivery of the paid messages and configuring delivery proofs
    * - Updating [State] of this service
    */
   admin_address: ActorId;
   /**
-   * Fee amount that will be charged from users.
+   * Fee amount that will be charged from users for the messages sent to Ethereum
+   * when the dynamic fee is unavailable.
    */
   fee: number | string | bigint;
 }
 
+/**
+ * Direction of the bridging. Only the messages sent to Ethereum are paid
+ * through this program.
+ */
+export type Direction = 'varaToEth';
+
+/**
+ * Formula of the dynamic fee:
+ * `base_fee + gas_per_message * gas_price * (1 + markup_bps / 10_000)`.
+ */
+export interface FeeFormula {
+  /**
+   * Flat part of the fee.
+   */
+  base_fee: number | string | bigint;
+  /**
+   * Amount of gas a relayer spends to deliver a message on the destination chain.
+   */
+  gas_per_message: number | string | bigint;
+  /**
+   * Markup over the gas cost in basis points.
+   */
+  markup_bps: number;
+  /**
+   * Age in blocks after which the gas price is considered stale and
+   * the fallback fee is charged instead.
+   */
+  max_gas_price_age: number;
+}
+
+export type Error =
+  | 'decodeReceiptEnvelopeFailure'
+  | 'failedEthTransaction'
+  | 'messageNotFound'
+  | 'notHistoricalProxy'
+  | 'transactionTooOld'
+  | 'alreadyProcessed';
+
+/**
+ * State of the fee oracle.
+ */
+export interface FeeOracleState {
+  /**
+   * Accounts allowed to report gas prices.
+   */
+  oracles: Array<ActorId>;
+  /**
+   * Static fees charged when the dynamic fee is unavailable.
+   */
+  fallback_fees: Array<[Direction, number | string | bigint]>;
+  /**
+   * Dynamic fee formulas that are set.
+   */
+  formulas: Array<[Direction, FeeFormula]>;
+  /**
+   * Latest reported gas prices.
+   */
+  gas_prices: Array<[Direction, GasPrice]>;
+}
+
+/**
+ * Gas price on the destination chain reported by a relayer.
+ */
+export interface GasPrice {
+  /**
+   * Price of one unit of gas converted to the smallest units of VARA.
+   */
+  price: number | string | bigint;
+  /**
+   * Block the price was reported at.
+   */
+  updated_at: number;
+}
+
+/**
+ * Fee paid for the message that isn't delivered yet.
+ */
+export interface PaidMessage {
+  /**
+   * Account that has paid the fee.
+   */
+  payer: ActorId;
+  /**
+   * Amount of the fee.
+   */
+  fee: number | string | bigint;
+  /**
+   * Block after which the payer can claim a refund.
+   */
+  deadline: number;
+}
+
 export class SailsProgram {
   public readonly registry: TypeRegistry;
   public readonly bridgingPayment: BridgingPayment;
@@ -36,6 +135,26 @@ export class SailsProgram {
   ) {
     const types: Record<string, any> = {
       State: { admin_address: '[u8;32]', fee: 'u128' },
+      Direction: { _enum: ['VaraToEth'] },
+      FeeFormula: { base_fee: 'u128', gas_per_message: 'u64', markup_bps: 'u16', max_gas_price_age: 'u32' },
+      Error: {
+        _enum: [
+          'DecodeReceiptEnvelopeFailure',
+          'FailedEthTransaction',
+          'MessageNotFound',
+          'NotHistoricalProxy',
+          'TransactionTooOld',
+          'AlreadyProcessed',
+        ],
+      },
+      FeeOracleState: {
+        oracles: 'Vec<[u8;32]>',
+        fallback_fees: 'Vec<(Direction, u128)>',
+        formulas: 'Vec<(Direction, FeeFormula)>',
+        gas_prices: 'Vec<(Direction, GasPrice)>',
+      },
+      GasPrice: { price: 'u128', updated_at: 'u32' },
+      PaidMessage: { payer: '[u8;32]', fee: 'u128', deadline: 'u32' },
     };
 
     this.registry = new TypeRegistry();
@@ -95,13 +214,36 @@ export class SailsProgram {
 export class BridgingPayment {
   constructor(private _program: SailsProgram) {}
 
+  /**
+   * Attest that the messages were delivered to Ethereum so their fees can be withdrawn.
+   *
+   * Messages without pending payment are skipped.
+   *
+   * This method can be called only by admin.
+   */
+  public attestDelivery(nonces: Array<number | string | bigint>): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'AttestDelivery', nonces],
+      '(String, String, Vec<U256>)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
   /**
    * Pay fees for message processing to the admin.
    *
-   * This method requires that **exactly** [Config::fee] must
-   * be attached as a value when sending message to this method.
+   * This method requires that at least the fee returned by `quote_fee`
+   * for [Direction::VaraToEth] must be attached as a value when sending
+   * message to this method. The excess is sent back to the payer since
+   * the fee may change between the quote and the payment.
    *
-   * Current fee amount can be retreived by calling `get_state`.
+   * Fee is held by the program until the message is delivered. If it isn't
+   * delivered before the deadline, the fee can be taken back with `refund`.
    */
   public payFees(nonce: number | string | bigint): TransactionBuilder<null> {
     if (!this._program.programId) throw new Error('Program ID is not set');
@@ -109,7 +251,7 @@ export class BridgingPayment {
       this._program.api,
       this._program.registry,
       'send_message',
-      ['BridgingPayment', 'PayFees', nonce],
+      ['VftManager', 'PayFees', nonce],
       '(String, String, U256)',
       'Null',
       this._program.programId,
@@ -117,7 +259,10 @@ export class BridgingPayment {
   }
 
   /**
-   * Withdraw fees that were collected from user requests.
+   * Withdraw fees of the messages which delivery was proven or attested.
+   *
+   * Fees of the messages that aren't delivered yet stay in the program
+   * since they can be refunded.
    *
    * This method can be called only by admin.
    */
@@ -127,13 +272,31 @@ export class BridgingPayment {
       this._program.api,
       this._program.registry,
       'send_message',
-      ['BridgingPayment', 'ReclaimFee'],
+      ['VftManager', 'ReclaimFee'],
       '(String, String)',
       'Null',
       this._program.programId,
     );
   }
 
+  /**
+   * Return the fee paid for the message that wasn't delivered before the deadline.
+   *
+   * This method can be called only by the account that has paid the fee.
+   */
+  public refund(nonce: number | string | bigint): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'Refund', nonce],
+      '(String, String, U256)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
   /**
    * Set new admin.
    *
@@ -145,7 +308,7 @@ export class BridgingPayment {
       this._program.api,
       this._program.registry,
       'send_message',
-      ['BridgingPayment', 'SetAdmin', new_admin],
+      ['VftManager', 'SetAdmin', new_admin],
       '(String, String, [u8;32])',
       'Null',
       this._program.programId,
@@ -153,7 +316,49 @@ export class BridgingPayment {
   }
 
   /**
-   * Set fee that this program will take from incoming requests.
+   * Set `historical-proxy` program which submits delivery proofs and
+   * address of the `MessageQueue` contract on Ethereum.
+   *
+   * This method can be called only by admin.
+   */
+  public setDeliveryProofConfig(
+    historical_proxy_address: ActorId,
+    message_queue_address: H160,
+  ): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'SetDeliveryProofConfig', historical_proxy_address, message_queue_address],
+      '(String, String, [u8;32], H160)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Set fee charged for the messages in the specified direction when
+   * the dynamic fee is unavailable.
+   *
+   * This method can be called only by admin.
+   */
+  public setFallbackFee(direction: Direction, fee: number | string | bigint): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'SetFallbackFee', direction, fee],
+      '(String, String, Direction, u128)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Set fee that this program will take from incoming requests when
+   * the dynamic fee is unavailable.
    *
    * This method can be called only by admin.
    */
@@ -163,13 +368,184 @@ export class BridgingPayment {
       this._program.api,
       this._program.registry,
       'send_message',
-      ['BridgingPayment', 'SetFee', fee],
+      ['VftManager', 'SetFee', fee],
       '(String, String, u128)',
       'Null',
       this._program.programId,
     );
   }
 
+  /**
+   * Set or remove (if `formula` is `None`) formula of the dynamic fee
+   * for the specified direction.
+   *
+   * This method can be called only by admin.
+   */
+  public setFeeFormula(direction: Direction, formula: FeeFormula | null): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'SetFeeFormula', direction, formula],
+      '(String, String, Direction, Option<FeeFormula>)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Allow or disallow `oracle` to report gas prices.
+   *
+   * This method can be called only by admin.
+   */
+  public setGasPriceOracle(oracle: ActorId, allowed: boolean): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'SetGasPriceOracle', oracle, allowed],
+      '(String, String, [u8;32], bool)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Set amount of blocks a paid message should be delivered in before its
+   * fee can be refunded. Applies only to the messages paid after the change.
+   *
+   * This method can be called only by admin.
+   */
+  public setPaymentTimeout(timeout: number): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'SetPaymentTimeout', timeout],
+      '(String, String, u32)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Claim fees of the messages delivered to Ethereum by the `MessageQueue` transaction.
+   *
+   * This method is called by the `historical-proxy` program after the receipt is
+   * verified, so relayers prove delivery by relaying `MessageQueue` transactions to
+   * this route. Messages that aren't paid through this program are skipped.
+   */
+  public submitDeliveryProof(
+    slot: number | string | bigint,
+    transaction_index: number | string | bigint,
+    receipt_rlp: `0x${string}`,
+  ): TransactionBuilder<{ ok: null } | { err: Error }> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<{ ok: null } | { err: Error }>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'SubmitDeliveryProof', slot, transaction_index, receipt_rlp],
+      '(String, String, u64, u64, Vec<u8>)',
+      'Result<Null, Error>',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Report gas price on the destination chain of the specified direction.
+   *
+   * `gas_price` is a price of one unit of gas converted to the smallest units of VARA.
+   *
+   * This method can be called only by gas price oracles.
+   */
+  public updateGasPrice(direction: Direction, gas_price: number | string | bigint): TransactionBuilder<null> {
+    if (!this._program.programId) throw new Error('Program ID is not set');
+    return new TransactionBuilder<null>(
+      this._program.api,
+      this._program.registry,
+      'send_message',
+      ['VftManager', 'UpdateGasPrice', direction, gas_price],
+      '(String, String, Direction, u128)',
+      'Null',
+      this._program.programId,
+    );
+  }
+
+  /**
+   * Get fees of the delivered messages that can be withdrawn by the admin.
+   */
+  public async collectedFees(
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<number | string | bigint> {
+    const payload = this._program.registry.createType('(String, String)', ['BridgingPayment', 'CollectedFees']).toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType('(String, String, u128)', reply.payload);
+    return result[2].toJSON() as unknown as number | string | bigint;
+  }
+
+  /**
+   * Get `historical-proxy` program and `MessageQueue` contract addresses used
+   * to verify delivery proofs, if configured.
+   */
+  public async deliveryProofConfig(
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<[ActorId, H160] | null> {
+    const payload = this._program.registry
+      .createType('(String, String)', ['BridgingPayment', 'DeliveryProofConfig'])
+      .toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType('(String, String, Option<([u8;32], H160)>)', reply.payload);
+    return result[2].toJSON() as unknown as [ActorId, H160] | null;
+  }
+
+  /**
+   * Get current state of the fee oracle.
+   */
+  public async feeOracleState(
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<FeeOracleState> {
+    const payload = this._program.registry
+      .createType('(String, String)', ['BridgingPayment', 'FeeOracleState'])
+      .toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType('(String, String, FeeOracleState)', reply.payload);
+    return result[2].toJSON() as unknown as FeeOracleState;
+  }
+
   /**
    * Get current service [State].
    */
@@ -192,11 +568,115 @@ export class BridgingPayment {
     return result[2].toJSON() as unknown as State;
   }
 
+  /**
+   * Get pending payment for the message.
+   */
+  public async paidMessage(
+    nonce: number | string | bigint,
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<PaidMessage | null> {
+    const payload = this._program.registry
+      .createType('(String, String, U256)', ['BridgingPayment', 'PaidMessage', nonce])
+      .toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType('(String, String, Option<PaidMessage>)', reply.payload);
+    return result[2].toJSON() as unknown as PaidMessage | null;
+  }
+
+  /**
+   * Get pending payments ordered by the message nonce.
+   */
+  public async paidMessages(
+    start: number,
+    count: number,
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<Array<[number | string | bigint, PaidMessage]>> {
+    const payload = this._program.registry
+      .createType('(String, String, u32, u32)', ['BridgingPayment', 'PaidMessages', start, count])
+      .toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType('(String, String, Vec<(U256, PaidMessage)>)', reply.payload);
+    return result[2].toJSON() as unknown as Array<[number | string | bigint, PaidMessage]>;
+  }
+
+  /**
+   * Get amount of blocks a paid message should be delivered in.
+   */
+  public async paymentTimeout(
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<number> {
+    const payload = this._program.registry
+      .createType('(String, String)', ['BridgingPayment', 'PaymentTimeout'])
+      .toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType('(String, String, u32)', reply.payload);
+    return result[2].toJSON() as unknown as number;
+  }
+
+  /**
+   * Get fee that will be charged at the current block for the message
+   * in the specified direction.
+   *
+   * Dynamic fee is returned if the fee formula is set and the reported gas price
+   * isn't stale, otherwise the fallback fee is returned.
+   */
+  public async quoteFee(
+    direction: Direction,
+    originAddress?: string,
+    value?: number | string | bigint,
+    atBlock?: `0x${string}`,
+  ): Promise<number | string | bigint> {
+    const payload = this._program.registry
+      .createType('(String, String, Direction)', ['BridgingPayment', 'QuoteFee', direction])
+      .toHex();
+    const reply = await this._program.api.message.calculateReply({
+      destination: this._program.programId,
+      origin: originAddress ? decodeAddress(originAddress) : ZERO_ADDRESS,
+      payload,
+      value: value || 0,
+      gasLimit: this._program.api.blockGasLimit.toBigInt(),
+      at: atBlock,
+    });
+    throwOnErrorReply(reply.code, reply.payload.toU8a(), this._program.api.specVersion, this._program.registry);
+    const result = this._program.registry.createType('(String, String, u128)', reply.payload);
+    return result[2].toJSON() as unknown as number | string | bigint;
+  }
+
   /**
    * Fee for the message processing by relayer was paid.
    */
   public subscribeToBridgingPaidEvent(
-    callback: (data: { nonce: number | string | bigint }) => void | Promise<void>,
+    callback: (data: { nonce: number | string | bigint; deadline: number }) => void | Promise<void>,
   ): Promise<() => void> {
     return this._program.api.gearEvents.subscribeToGearEvent('UserMessageSent', ({ data: { message } }) => {
       if (!message.source.eq(this._program.programId) || !message.destination.eq(ZERO_ADDRESS)) {
@@ -207,8 +687,78 @@ export class BridgingPayment {
       if (getServiceNamePrefix(payload) === 'BridgingPayment' && getFnNamePrefix(payload) === 'BridgingPaid') {
         callback(
           this._program.registry
-            .createType('(String, String, {"nonce":"U256"})', message.payload)[2]
-            .toJSON() as unknown as { nonce: number | string | bigint },
+            .createType('(String, String, {"nonce":"U256","deadline":"u32"})', message.payload)[2]
+            .toJSON() as unknown as { nonce: number | string | bigint; deadline: number },
+        );
+      }
+    });
+  }
+
+  /**
+   * Message was delivered to Ethereum so its fee can be withdrawn by the admin.
+   */
+  public subscribeToFeeClaimedEvent(
+    callback: (data: { nonce: number | string | bigint; fee: number | string | bigint }) => void | Promise<void>,
+  ): Promise<() => void> {
+    return this._program.api.gearEvents.subscribeToGearEvent('UserMessageSent', ({ data: { message } }) => {
+      if (!message.source.eq(this._program.programId) || !message.destination.eq(ZERO_ADDRESS)) {
+        return;
+      }
+
+      const payload = message.payload.toHex();
+      if (getServiceNamePrefix(payload) === 'BridgingPayment' && getFnNamePrefix(payload) === 'FeeClaimed') {
+        callback(
+          this._program.registry
+            .createType('(String, String, {"nonce":"U256","fee":"u128"})', message.payload)[2]
+            .toJSON() as unknown as { nonce: number | string | bigint; fee: number | string | bigint },
+        );
+      }
+    });
+  }
+
+  /**
+   * Message wasn't delivered before the deadline and its fee was returned to the payer.
+   */
+  public subscribeToFeeRefundedEvent(
+    callback: (data: {
+      nonce: number | string | bigint;
+      payer: ActorId;
+      fee: number | string | bigint;
+    }) => void | Promise<void>,
+  ): Promise<() => void> {
+    return this._program.api.gearEvents.subscribeToGearEvent('UserMessageSent', ({ data: { message } }) => {
+      if (!message.source.eq(this._program.programId) || !message.destination.eq(ZERO_ADDRESS)) {
+        return;
+      }
+
+      const payload = message.payload.toHex();
+      if (getServiceNamePrefix(payload) === 'BridgingPayment' && getFnNamePrefix(payload) === 'FeeRefunded') {
+        callback(
+          this._program.registry
+            .createType('(String, String, {"nonce":"U256","payer":"[u8;32]","fee":"u128"})', message.payload)[2]
+            .toJSON() as unknown as { nonce: number | string | bigint; payer: ActorId; fee: number | string | bigint },
+        );
+      }
+    });
+  }
+
+  /**
+   * Gas price on the destination chain was reported by a relayer.
+   */
+  public subscribeToGasPriceUpdatedEvent(
+    callback: (data: { direction: Direction; gas_price: number | string | bigint }) => void | Promise<void>,
+  ): Promise<() => void> {
+    return this._program.api.gearEvents.subscribeToGearEvent('UserMessageSent', ({ data: { message } }) => {
+      if (!message.source.eq(this._program.programId) || !message.destination.eq(ZERO_ADDRESS)) {
+        return;
+      }
+
+      const payload = message.payload.toHex();
+      if (getServiceNamePrefix(payload) === 'BridgingPayment' && getFnNamePrefix(payload) === 'GasPriceUpdated') {
+        callback(
+          this._program.registry
+            .createType('(String, String, {"direction":"Direction","gas_price":"u128"})', message.payload)[2]
+            .toJSON() as unknown as { direction: Direction; gas_price: number | string | bigint },
         );
       }
     });
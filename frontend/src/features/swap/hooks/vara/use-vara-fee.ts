@@ -12,16 +12,18 @@ function useVaraFee() {
     id: BRIDGING_PAYMENT_CONTRACT_ADDRESS,
   });
 
-  const { data: config, isPending } = useProgramQuery({
+  // fee depends on the reported gas price, so it's requested on every block
+  const { data: quote, isPending } = useProgramQuery({
     program,
     serviceName: 'bridgingPayment',
-    functionName: 'getState',
-    args: [],
+    functionName: 'quoteFee',
+    args: ['varaToEth'],
+    watch: true,
   });
 
   const fee = {
-    value: !isUndefined(config?.fee) ? BigInt(config.fee) : undefined,
-    formattedValue: !isUndefined(config?.fee) ? getFormattedBalanceValue(config.fee.toString()).toFixed() : undefined,
+    value: !isUndefined(quote) ? BigInt(quote) : undefined,
+    formattedValue: !isUndefined(quote) ? getFormattedBalanceValue(quote.toString()).toFixed() : undefined,
   };
 
   const isLoading = isPending;
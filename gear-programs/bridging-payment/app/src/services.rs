@@ -1,11 +1,23 @@
 //! Bridging Payment service implementation.
 
+use eth_message_receiver::ReceiverState;
 use gstd::{exec, static_mut, static_ref};
 use sails_rs::{
     gstd::{msg, ExecContext},
     prelude::*,
 };
 
+pub mod fee_oracle;
+pub mod payments;
+
+use fee_oracle::FeeOracle;
+pub use fee_oracle::{Direction, FeeFormula, FeeOracleState, GasPrice};
+pub use payments::PaidMessage;
+use payments::{abi::IMessageQueue, Payments};
+
+/// Amount of `MessageQueue` transactions remembered to reject repeated delivery proofs.
+const PROCESSED_TRANSACTIONS_CAPACITY: usize = 4_096;
+
 /// Bridging Payment service.
 pub struct BridgingPayment<ExecContext> {
     exec_context: ExecContext,
@@ -18,20 +30,51 @@ pub enum BridgingPaymentEvents {
     BridgingPaid {
         /// Nonce of the message that was paid for.
         nonce: U256,
+        /// Block after which the fee can be refunded if the message isn't delivered.
+        deadline: u32,
+    },
+    /// Message was delivered to Ethereum so its fee can be withdrawn by the admin.
+    FeeClaimed {
+        /// Nonce of the delivered message.
+        nonce: U256,
+        /// Fee paid for the message.
+        fee: u128,
+    },
+    /// Message wasn't delivered before the deadline and its fee was returned to the payer.
+    FeeRefunded {
+        /// Nonce of the message.
+        nonce: U256,
+        /// Account the fee is returned to.
+        payer: ActorId,
+        /// Fee paid for the message.
+        fee: u128,
+    },
+    /// Gas price on the destination chain was reported by a relayer.
+    GasPriceUpdated {
+        /// Direction of the bridging the gas price is used for.
+        direction: Direction,
+        /// Price of one unit of gas converted to the smallest units of VARA.
+        gas_price: u128,
     },
 }
 
 static mut STATE: Option<State> = None;
+static mut FEE_ORACLE: Option<FeeOracle> = None;
+static mut PAYMENTS: Option<Payments> = None;
+static mut DELIVERY_PROOFS: Option<ReceiverState> = None;
 
 /// Global state of the Bridging Payment service.
 #[derive(Debug, Decode, Encode, TypeInfo, Clone)]
 pub struct State {
     /// Admin of this service. Admin is in charge of:
-    /// - Changing fee
-    /// - Withdrawing collected fees from the program address
+    /// - Changing fees and the fee formulas
+    /// - Managing the set of gas price oracles
+    /// - Withdrawing fees of the delivered messages from the program address
+    /// - Attesting delivery of the paid messages and configuring delivery proofs
     /// - Updating [State] of this service
     pub admin_address: ActorId,
-    /// Fee amount that will be charged from users.
+    /// Fee amount that will be charged from users for the messages sent to Ethereum
+    /// when the dynamic fee is unavailable.
     pub fee: u128,
 }
 
@@ -41,8 +84,13 @@ where
 {
     /// Initialize state of the Bridging Payment service.
     pub fn seed(initial_state: State) {
+        let mut fee_oracle = FeeOracle::default();
+        fee_oracle.set_fallback_fee(Direction::VaraToEth, initial_state.fee);
+
         unsafe {
             STATE = Some(initial_state);
+            FEE_ORACLE = Some(fee_oracle);
+            PAYMENTS = Some(Payments::default());
         }
     }
 
@@ -58,6 +106,42 @@ where
     fn state_mut(&mut self) -> &mut State {
         unsafe { static_mut!(STATE).as_mut() }.expect("BridgingPayment::seed() should be called")
     }
+
+    fn fee_oracle(&self) -> &FeeOracle {
+        unsafe { static_ref!(FEE_ORACLE).as_ref() }
+            .expect("BridgingPayment::seed() should be called")
+    }
+
+    fn fee_oracle_mut(&mut self) -> &mut FeeOracle {
+        unsafe { static_mut!(FEE_ORACLE).as_mut() }
+            .expect("BridgingPayment::seed() should be called")
+    }
+
+    fn payments(&self) -> &Payments {
+        unsafe { static_ref!(PAYMENTS).as_ref() }.expect("BridgingPayment::seed() should be called")
+    }
+
+    fn payments_mut(&mut self) -> &mut Payments {
+        unsafe { static_mut!(PAYMENTS).as_mut() }.expect("BridgingPayment::seed() should be called")
+    }
+
+    fn delivery_proofs(&self) -> Option<&ReceiverState> {
+        unsafe { static_ref!(DELIVERY_PROOFS).as_ref() }
+    }
+
+    fn delivery_proofs_mut(&mut self) -> Option<&mut ReceiverState> {
+        unsafe { static_mut!(DELIVERY_PROOFS).as_mut() }
+    }
+
+    fn claim_fee(&mut self, nonce: U256) {
+        // Messages paid with other means or already refunded ones are skipped.
+        let Some(fee) = self.payments_mut().settle(&nonce) else {
+            return;
+        };
+
+        self.notify_on(BridgingPaymentEvents::FeeClaimed { nonce, fee })
+            .expect("Error depositing event");
+    }
 }
 
 #[service(events = BridgingPaymentEvents)]
@@ -65,25 +149,200 @@ impl<T> BridgingPayment<T>
 where
     T: ExecContext,
 {
-    /// Set fee that this program will take from incoming requests.
+    /// Set fee that this program will take from incoming requests when
+    /// the dynamic fee is unavailable.
     ///
     /// This method can be called only by admin.
     pub fn set_fee(&mut self, fee: u128) {
+        self.set_fallback_fee(Direction::VaraToEth, fee);
+    }
+
+    /// Set fee charged for the messages in the specified direction when
+    /// the dynamic fee is unavailable.
+    ///
+    /// This method can be called only by admin.
+    pub fn set_fallback_fee(&mut self, direction: Direction, fee: u128) {
+        self.ensure_admin();
+
+        if direction == Direction::VaraToEth {
+            self.state_mut().fee = fee;
+        }
+
+        self.fee_oracle_mut().set_fallback_fee(direction, fee);
+    }
+
+    /// Set or remove (if `formula` is `None`) formula of the dynamic fee
+    /// for the specified direction.
+    ///
+    /// This method can be called only by admin.
+    pub fn set_fee_formula(&mut self, direction: Direction, formula: Option<FeeFormula>) {
+        self.ensure_admin();
+
+        self.fee_oracle_mut().set_formula(direction, formula);
+    }
+
+    /// Allow or disallow `oracle` to report gas prices.
+    ///
+    /// This method can be called only by admin.
+    pub fn set_gas_price_oracle(&mut self, oracle: ActorId, allowed: bool) {
         self.ensure_admin();
 
-        self.state_mut().fee = fee;
+        self.fee_oracle_mut().set_oracle(oracle, allowed);
+    }
+
+    /// Report gas price on the destination chain of the specified direction.
+    ///
+    /// `gas_price` is a price of one unit of gas converted to the smallest units of VARA.
+    ///
+    /// This method can be called only by gas price oracles.
+    pub fn update_gas_price(&mut self, direction: Direction, gas_price: u128) {
+        let source = self.exec_context.actor_id();
+        if !self.fee_oracle().is_oracle(&source) {
+            panic!("Not a gas price oracle");
+        }
+
+        if gas_price == 0 {
+            panic!("Gas price should be non-zero");
+        }
+
+        self.fee_oracle_mut()
+            .update_gas_price(direction, gas_price, exec::block_height());
+
+        self.notify_on(BridgingPaymentEvents::GasPriceUpdated {
+            direction,
+            gas_price,
+        })
+        .expect("Error depositing event");
     }
 
-    /// Withdraw fees that were collected from user requests.
+    /// Withdraw fees of the messages which delivery was proven or attested.
+    ///
+    /// Fees of the messages that aren't delivered yet stay in the program
+    /// since they can be refunded.
     ///
     /// This method can be called only by admin.
     pub fn reclaim_fee(&mut self) {
         self.ensure_admin();
 
-        let fee_balance = exec::value_available();
+        let fee_balance = self.payments_mut().take_collected();
         msg::send(self.state().admin_address, "", fee_balance).expect("Failed to reclaim fees");
     }
 
+    /// Set amount of blocks a paid message should be delivered in before its
+    /// fee can be refunded. Applies only to the messages paid after the change.
+    ///
+    /// This method can be called only by admin.
+    pub fn set_payment_timeout(&mut self, timeout: u32) {
+        self.ensure_admin();
+
+        if timeout == 0 {
+            panic!("Payment timeout should be non-zero");
+        }
+
+        self.payments_mut().set_timeout(timeout);
+    }
+
+    /// Set `historical-proxy` program which submits delivery proofs and
+    /// address of the `MessageQueue` contract on Ethereum.
+    ///
+    /// This method can be called only by admin.
+    pub fn set_delivery_proof_config(
+        &mut self,
+        historical_proxy_address: ActorId,
+        message_queue_address: H160,
+    ) {
+        self.ensure_admin();
+
+        let admin = self.state().admin_address;
+        match self.delivery_proofs_mut() {
+            Some(receiver) => {
+                receiver.historical_proxy_address = historical_proxy_address;
+                receiver.emitter_address = message_queue_address;
+            }
+            None => unsafe {
+                DELIVERY_PROOFS = Some(ReceiverState::new(
+                    admin,
+                    historical_proxy_address,
+                    message_queue_address,
+                    PROCESSED_TRANSACTIONS_CAPACITY,
+                ));
+            },
+        }
+    }
+
+    /// Claim fees of the messages delivered to Ethereum by the `MessageQueue` transaction.
+    ///
+    /// This method is called by the `historical-proxy` program after the receipt is
+    /// verified, so relayers prove delivery by relaying `MessageQueue` transactions to
+    /// this route. Messages that aren't paid through this program are skipped.
+    pub fn submit_delivery_proof(
+        &mut self,
+        slot: u64,
+        transaction_index: u64,
+        receipt_rlp: Vec<u8>,
+    ) -> Result<(), eth_message_receiver::Error> {
+        let source = self.exec_context.actor_id();
+        let events = self
+            .delivery_proofs_mut()
+            .expect("Delivery proofs are not configured")
+            .accept::<IMessageQueue::MessageProcessed>(
+                source,
+                slot,
+                transaction_index,
+                &receipt_rlp,
+            )?;
+
+        for event in events {
+            // MessageQueue uses little-endian bytes of the nonce.
+            self.claim_fee(U256::from_little_endian(event.messageNonce.as_slice()));
+        }
+
+        Ok(())
+    }
+
+    /// Attest that the messages were delivered to Ethereum so their fees can be withdrawn.
+    ///
+    /// Messages without pending payment are skipped.
+    ///
+    /// This method can be called only by admin.
+    pub fn attest_delivery(&mut self, nonces: Vec<U256>) {
+        self.ensure_admin();
+
+        for nonce in nonces {
+            self.claim_fee(nonce);
+        }
+    }
+
+    /// Return the fee paid for the message that wasn't delivered before the deadline.
+    ///
+    /// This method can be called only by the account that has paid the fee.
+    pub fn refund(&mut self, nonce: U256) {
+        let paid_message = self
+            .payments()
+            .get(&nonce)
+            .unwrap_or_else(|| panic!("Message {} has no pending payment", nonce));
+
+        let payer = paid_message.payer;
+        if payer != self.exec_context.actor_id() {
+            panic!("Not a payer");
+        }
+
+        if exec::block_height() <= paid_message.deadline {
+            panic!(
+                "Message can be refunded after block #{}",
+                paid_message.deadline
+            );
+        }
+
+        let fee = paid_message.fee;
+        self.payments_mut().refund(&nonce);
+
+        msg::send(payer, "", fee).expect("Failed to refund fee");
+
+        self.notify_on(BridgingPaymentEvents::FeeRefunded { nonce, payer, fee })
+            .expect("Error depositing event");
+    }
+
     /// Set new admin.
     ///
     /// This method can be called only by admin.
@@ -101,24 +360,84 @@ where
 
     /// Pay fees for message processing to the admin.
     ///
-    /// This method requires that **exactly** [Config::fee] must
-    /// be attached as a value when sending message to this method.
+    /// This method requires that at least the fee returned by `quote_fee`
+    /// for [Direction::VaraToEth] must be attached as a value when sending
+    /// message to this method. The excess is sent back to the payer since
+    /// the fee may change between the quote and the payment.
     ///
-    /// Current fee amount can be retreived by calling `get_state`.
+    /// Fee is held by the program until the message is delivered. If it isn't
+    /// delivered before the deadline, the fee can be taken back with `refund`.
     pub async fn pay_fees(&mut self, nonce: U256) {
-        let fee = self.state().fee;
+        let fee = self.quote_fee(Direction::VaraToEth);
 
         let attached_value = msg::value();
-        if attached_value != fee {
-            panic!("Please attach exactly {} value", fee);
+        if attached_value < fee {
+            panic!("Please attach at least {} value", fee);
         }
 
-        self.notify_on(BridgingPaymentEvents::BridgingPaid { nonce })
+        let payer = self.exec_context.actor_id();
+        let deadline = self
+            .payments_mut()
+            .pay(nonce, payer, fee, exec::block_height())
+            .unwrap_or_else(|| panic!("Message {} is already paid", nonce));
+
+        let change = attached_value - fee;
+        if change > 0 {
+            msg::send(payer, "", change).expect("Failed to return change");
+        }
+
+        self.notify_on(BridgingPaymentEvents::BridgingPaid { nonce, deadline })
             .expect("Error depositing event");
     }
 
+    /// Get fee that will be charged at the current block for the message
+    /// in the specified direction.
+    ///
+    /// Dynamic fee is returned if the fee formula is set and the reported gas price
+    /// isn't stale, otherwise the fallback fee is returned.
+    pub fn quote_fee(&self, direction: Direction) -> u128 {
+        self.fee_oracle().quote(direction, exec::block_height())
+    }
+
     /// Get current service [State].
     pub fn get_state(&self) -> State {
         self.state().clone()
     }
+
+    /// Get current state of the fee oracle.
+    pub fn fee_oracle_state(&self) -> FeeOracleState {
+        self.fee_oracle().read_state()
+    }
+
+    /// Get pending payment for the message.
+    pub fn paid_message(&self, nonce: U256) -> Option<PaidMessage> {
+        self.payments().get(&nonce).cloned()
+    }
+
+    /// Get pending payments ordered by the message nonce.
+    pub fn paid_messages(&self, start: u32, count: u32) -> Vec<(U256, PaidMessage)> {
+        self.payments()
+            .iter()
+            .skip(start as usize)
+            .take(count as usize)
+            .map(|(nonce, paid_message)| (*nonce, paid_message.clone()))
+            .collect()
+    }
+
+    /// Get amount of blocks a paid message should be delivered in.
+    pub fn payment_timeout(&self) -> u32 {
+        self.payments().timeout()
+    }
+
+    /// Get fees of the delivered messages that can be withdrawn by the admin.
+    pub fn collected_fees(&self) -> u128 {
+        self.payments().collected()
+    }
+
+    /// Get `historical-proxy` program and `MessageQueue` contract addresses used
+    /// to verify delivery proofs, if configured.
+    pub fn delivery_proof_config(&self) -> Option<(ActorId, H160)> {
+        self.delivery_proofs()
+            .map(|receiver| (receiver.historical_proxy_address, receiver.emitter_address))
+    }
 }
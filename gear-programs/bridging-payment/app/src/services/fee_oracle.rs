@@ -0,0 +1,212 @@
+//! Dynamic fees based on the gas price on the destination chain.
+//!
+//! Gas price is submitted by the trusted relayers. When it's fresh enough, the fee is
+//! calculated with [FeeFormula], otherwise the static fallback fee is charged.
+
+use collections::{BTreeMap, BTreeSet};
+use sails_rs::prelude::*;
+
+/// Markup is specified in basis points, i.e. `10_000` is 100%.
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Direction of the bridging. Only the messages sent to Ethereum are paid
+/// through this program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, TypeInfo)]
+pub enum Direction {
+    /// Messages sent from Gear to Ethereum.
+    VaraToEth,
+}
+
+/// Formula of the dynamic fee:
+/// `base_fee + gas_per_message * gas_price * (1 + markup_bps / 10_000)`.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct FeeFormula {
+    /// Flat part of the fee.
+    pub base_fee: u128,
+    /// Amount of gas a relayer spends to deliver a message on the destination chain.
+    pub gas_per_message: u64,
+    /// Markup over the gas cost in basis points.
+    pub markup_bps: u16,
+    /// Age in blocks after which the gas price is considered stale and
+    /// the fallback fee is charged instead.
+    pub max_gas_price_age: u32,
+}
+
+impl FeeFormula {
+    fn fee(&self, gas_price: u128) -> u128 {
+        let gas_cost = gas_price.saturating_mul(self.gas_per_message.into());
+        let markup = gas_cost.saturating_mul(self.markup_bps.into()) / BPS_DENOMINATOR;
+
+        self.base_fee
+            .saturating_add(gas_cost)
+            .saturating_add(markup)
+    }
+}
+
+/// Gas price on the destination chain reported by a relayer.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct GasPrice {
+    /// Price of one unit of gas converted to the smallest units of VARA.
+    pub price: u128,
+    /// Block the price was reported at.
+    pub updated_at: u32,
+}
+
+/// State of the fee oracle.
+#[derive(Debug, Clone, Default, Encode, Decode, TypeInfo)]
+pub struct FeeOracleState {
+    /// Accounts allowed to report gas prices.
+    pub oracles: Vec<ActorId>,
+    /// Static fees charged when the dynamic fee is unavailable.
+    pub fallback_fees: Vec<(Direction, u128)>,
+    /// Dynamic fee formulas that are set.
+    pub formulas: Vec<(Direction, FeeFormula)>,
+    /// Latest reported gas prices.
+    pub gas_prices: Vec<(Direction, GasPrice)>,
+}
+
+/// Gas prices reported by the relayers and formulas to calculate fees from them.
+#[derive(Debug, Default)]
+pub struct FeeOracle {
+    oracles: BTreeSet<ActorId>,
+    fallback_fees: BTreeMap<Direction, u128>,
+    formulas: BTreeMap<Direction, FeeFormula>,
+    gas_prices: BTreeMap<Direction, GasPrice>,
+}
+
+impl FeeOracle {
+    pub fn is_oracle(&self, actor: &ActorId) -> bool {
+        self.oracles.contains(actor)
+    }
+
+    /// Allow or disallow `actor` to report gas prices.
+    pub fn set_oracle(&mut self, actor: ActorId, allowed: bool) {
+        if allowed {
+            self.oracles.insert(actor);
+        } else {
+            self.oracles.remove(&actor);
+        }
+    }
+
+    /// Set static fee charged when the dynamic fee is unavailable.
+    pub fn set_fallback_fee(&mut self, direction: Direction, fee: u128) {
+        self.fallback_fees.insert(direction, fee);
+    }
+
+    /// Set or remove (if `formula` is `None`) formula of the dynamic fee.
+    pub fn set_formula(&mut self, direction: Direction, formula: Option<FeeFormula>) {
+        match formula {
+            Some(formula) => self.formulas.insert(direction, formula),
+            None => self.formulas.remove(&direction),
+        };
+    }
+
+    pub fn update_gas_price(&mut self, direction: Direction, price: u128, now: u32) {
+        self.gas_prices.insert(
+            direction,
+            GasPrice {
+                price,
+                updated_at: now,
+            },
+        );
+    }
+
+    /// Calculate fee at the block `now`. Fallback fee is returned if there's no formula
+    /// for the direction or the gas price is stale.
+    pub fn quote(&self, direction: Direction, now: u32) -> u128 {
+        self.dynamic_fee(direction, now).unwrap_or_else(|| {
+            self.fallback_fees
+                .get(&direction)
+                .copied()
+                .unwrap_or_default()
+        })
+    }
+
+    fn dynamic_fee(&self, direction: Direction, now: u32) -> Option<u128> {
+        let formula = self.formulas.get(&direction)?;
+        let gas_price = self.gas_prices.get(&direction)?;
+
+        if now.saturating_sub(gas_price.updated_at) > formula.max_gas_price_age {
+            return None;
+        }
+
+        Some(formula.fee(gas_price.price))
+    }
+
+    pub fn read_state(&self) -> FeeOracleState {
+        FeeOracleState {
+            oracles: self.oracles.iter().copied().collect(),
+            fallback_fees: self
+                .fallback_fees
+                .iter()
+                .map(|(direction, fee)| (*direction, *fee))
+                .collect(),
+            formulas: self
+                .formulas
+                .iter()
+                .map(|(direction, formula)| (*direction, formula.clone()))
+                .collect(),
+            gas_prices: self
+                .gas_prices
+                .iter()
+                .map(|(direction, gas_price)| (*direction, gas_price.clone()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FALLBACK_FEE: u128 = 500;
+    const FORMULA: FeeFormula = FeeFormula {
+        base_fee: 1_000,
+        gas_per_message: 100,
+        markup_bps: 1_000,
+        max_gas_price_age: 10,
+    };
+
+    #[test]
+    fn quote() {
+        let mut fee_oracle = FeeOracle::default();
+        assert_eq!(fee_oracle.quote(Direction::VaraToEth, 0), 0);
+
+        fee_oracle.set_fallback_fee(Direction::VaraToEth, FALLBACK_FEE);
+        assert_eq!(fee_oracle.quote(Direction::VaraToEth, 0), FALLBACK_FEE);
+
+        // there's no gas price for the formula yet
+        fee_oracle.set_formula(Direction::VaraToEth, Some(FORMULA));
+        assert_eq!(fee_oracle.quote(Direction::VaraToEth, 0), FALLBACK_FEE);
+
+        // 1_000 + 100 * 20 + 10%
+        fee_oracle.update_gas_price(Direction::VaraToEth, 20, 100);
+        assert_eq!(fee_oracle.quote(Direction::VaraToEth, 100), 3_200);
+
+        fee_oracle.set_formula(Direction::VaraToEth, None);
+        assert_eq!(fee_oracle.quote(Direction::VaraToEth, 100), FALLBACK_FEE);
+    }
+
+    #[test]
+    fn stale_gas_price() {
+        let mut fee_oracle = FeeOracle::default();
+        fee_oracle.set_fallback_fee(Direction::VaraToEth, FALLBACK_FEE);
+        fee_oracle.set_formula(Direction::VaraToEth, Some(FORMULA));
+        fee_oracle.update_gas_price(Direction::VaraToEth, 20, 100);
+
+        assert_eq!(fee_oracle.quote(Direction::VaraToEth, 110), 3_200);
+        assert_eq!(fee_oracle.quote(Direction::VaraToEth, 111), FALLBACK_FEE);
+
+        fee_oracle.update_gas_price(Direction::VaraToEth, 30, 111);
+        assert_eq!(fee_oracle.quote(Direction::VaraToEth, 111), 4_300);
+    }
+
+    #[test]
+    fn fee_saturates() {
+        let mut fee_oracle = FeeOracle::default();
+        fee_oracle.set_formula(Direction::VaraToEth, Some(FORMULA));
+        fee_oracle.update_gas_price(Direction::VaraToEth, u128::MAX / 2, 0);
+
+        assert_eq!(fee_oracle.quote(Direction::VaraToEth, 0), u128::MAX);
+    }
+}
@@ -0,0 +1,116 @@
+//! Escrow of the fees paid for the messages sent to Ethereum.
+//!
+//! Fee stays in the escrow until the message delivery is either proven with the receipt
+//! of the `MessageQueue` transaction or attested by the admin. Only then the fee can be
+//! withdrawn by the admin. If the message isn't delivered before the deadline, the payer
+//! can take the fee back.
+
+use collections::BTreeMap;
+use sails_rs::prelude::*;
+
+/// Default amount of blocks a message should be delivered in, about a day.
+pub const DEFAULT_PAYMENT_TIMEOUT: u32 = 28_800;
+
+pub mod abi {
+    alloy_sol_types::sol! {
+        #[allow(missing_docs)]
+        interface IMessageQueue {
+            event MessageProcessed(
+                uint256 indexed blockNumber,
+                bytes32 indexed messageHash,
+                bytes32 indexed messageNonce
+            );
+        }
+    }
+}
+
+/// Fee paid for the message that isn't delivered yet.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct PaidMessage {
+    /// Account that has paid the fee.
+    pub payer: ActorId,
+    /// Amount of the fee.
+    pub fee: u128,
+    /// Block after which the payer can claim a refund.
+    pub deadline: u32,
+}
+
+/// Fees held for the messages that aren't delivered yet.
+#[derive(Debug)]
+pub struct Payments {
+    timeout: u32,
+    messages: BTreeMap<U256, PaidMessage>,
+    collected: u128,
+}
+
+impl Default for Payments {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_PAYMENT_TIMEOUT,
+            messages: Default::default(),
+            collected: 0,
+        }
+    }
+}
+
+impl Payments {
+    pub fn timeout(&self) -> u32 {
+        self.timeout
+    }
+
+    pub fn set_timeout(&mut self, timeout: u32) {
+        self.timeout = timeout;
+    }
+
+    /// Fees of the delivered messages that aren't withdrawn yet.
+    pub fn collected(&self) -> u128 {
+        self.collected
+    }
+
+    pub fn get(&self, nonce: &U256) -> Option<&PaidMessage> {
+        self.messages.get(nonce)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&U256, &PaidMessage)> {
+        self.messages.iter()
+    }
+
+    /// Record the fee paid at the block `now`. Returns the deadline of the payment
+    /// or `None` if the message is already paid.
+    pub fn pay(&mut self, nonce: U256, payer: ActorId, fee: u128, now: u32) -> Option<u32> {
+        if self.messages.contains_key(&nonce) {
+            return None;
+        }
+
+        let deadline = now.saturating_add(self.timeout);
+        self.messages.insert(
+            nonce,
+            PaidMessage {
+                payer,
+                fee,
+                deadline,
+            },
+        );
+
+        Some(deadline)
+    }
+
+    /// Mark the message as delivered so its fee can be withdrawn. Returns the fee or
+    /// `None` if there's no pending payment for the message.
+    pub fn settle(&mut self, nonce: &U256) -> Option<u128> {
+        let paid_message = self.messages.remove(nonce)?;
+        self.collected = self.collected.saturating_add(paid_message.fee);
+
+        Some(paid_message.fee)
+    }
+
+    /// Remove the payment to give the fee back to the payer.
+    pub fn refund(&mut self, nonce: &U256) -> Option<PaidMessage> {
+        self.messages.remove(nonce)
+    }
+
+    /// Take all the collected fees to withdraw them.
+    pub fn take_collected(&mut self) -> u128 {
+        core::mem::take(&mut self.collected)
+    }
+}
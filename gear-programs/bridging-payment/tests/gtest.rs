@@ -0,0 +1,466 @@
+use alloy_consensus::{Receipt, ReceiptEnvelope, ReceiptWithBloom};
+use bridging_payment_app::services::payments::abi::IMessageQueue;
+use bridging_payment_client::{
+    traits::*, BridgingPayment as BridgingPaymentC,
+    BridgingPaymentFactory as BridgingPaymentFactoryC, Direction, Error, FeeFormula, State,
+};
+use gtest::System;
+use sails_rs::{calls::*, gtest::calls::*, prelude::*};
+
+const ADMIN_ID: u64 = 1_000;
+const USER_ID: u64 = 1_001;
+const ORACLE_ID: u64 = 1_002;
+const HISTORICAL_PROXY_ID: u64 = 500;
+
+const MESSAGE_QUEUE_ADDRESS: H160 = H160([1; 20]);
+
+const UNITS: u128 = 1_000_000_000_000;
+const FEE: u128 = 10 * UNITS;
+
+const GAS_PER_MESSAGE: u64 = 100_000;
+const GAS_PRICE: u128 = 1_000_000;
+const MAX_GAS_PRICE_AGE: u32 = 10;
+
+struct Fixture {
+    remoting: GTestRemoting,
+    bridging_payment_program_id: ActorId,
+}
+
+async fn setup_for_test() -> Fixture {
+    let system = System::new();
+    system.init_logger();
+    system.mint_to(ADMIN_ID, 100_000_000_000_000);
+    system.mint_to(USER_ID, 100_000_000_000_000);
+    system.mint_to(ORACLE_ID, 100_000_000_000_000);
+    system.mint_to(HISTORICAL_PROXY_ID, 100_000_000_000_000);
+
+    let remoting = GTestRemoting::new(system, ADMIN_ID.into());
+
+    let code_id = remoting.system().submit_code(bridging_payment::WASM_BINARY);
+    let bridging_payment_program_id = BridgingPaymentFactoryC::new(remoting.clone())
+        .new(State {
+            admin_address: ADMIN_ID.into(),
+            fee: FEE,
+        })
+        .send_recv(code_id, b"salt")
+        .await
+        .unwrap();
+
+    Fixture {
+        remoting,
+        bridging_payment_program_id,
+    }
+}
+
+async fn quote_fee(remoting: &GTestRemoting, program_id: ActorId, direction: Direction) -> u128 {
+    BridgingPaymentC::new(remoting.clone())
+        .quote_fee(direction)
+        .recv(program_id)
+        .await
+        .unwrap()
+}
+
+async fn pay_fees(remoting: &GTestRemoting, program_id: ActorId, nonce: U256) {
+    BridgingPaymentC::new(remoting.clone().with_actor_id(USER_ID.into()))
+        .pay_fees(nonce)
+        .with_value(FEE)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+}
+
+async fn collected_fees(remoting: &GTestRemoting, program_id: ActorId) -> u128 {
+    BridgingPaymentC::new(remoting.clone())
+        .collected_fees()
+        .recv(program_id)
+        .await
+        .unwrap()
+}
+
+fn create_receipt_rlp(emitter: H160, nonces: &[U256]) -> Vec<u8> {
+    let logs = nonces
+        .iter()
+        .map(|nonce| {
+            let event = IMessageQueue::MessageProcessed {
+                blockNumber: alloy_primitives::U256::from(1),
+                messageHash: [2u8; 32].into(),
+                messageNonce: {
+                    // MessageQueue uses little-endian bytes of the nonce.
+                    let mut bytes = [0u8; 32];
+                    nonce.to_little_endian(&mut bytes[..]);
+
+                    bytes.into()
+                },
+            };
+
+            alloy_primitives::Log {
+                address: emitter.0.into(),
+                data: Into::into(&event),
+            }
+        })
+        .collect();
+
+    let receipt = ReceiptWithBloom::from(Receipt {
+        status: true.into(),
+        cumulative_gas_used: 100_000u128,
+        logs,
+    });
+
+    let receipt = ReceiptEnvelope::Eip2930(receipt);
+
+    let mut receipt_rlp = vec![];
+    alloy_rlp::Encodable::encode(&receipt, &mut receipt_rlp);
+
+    receipt_rlp
+}
+
+#[tokio::test]
+async fn test_quote_fee() {
+    let Fixture {
+        remoting,
+        bridging_payment_program_id,
+    } = setup_for_test().await;
+
+    let mut bridging_payment = BridgingPaymentC::new(remoting.clone());
+    let mut oracle = BridgingPaymentC::new(remoting.clone().with_actor_id(ORACLE_ID.into()));
+
+    let fee = quote_fee(&remoting, bridging_payment_program_id, Direction::VaraToEth).await;
+    assert_eq!(fee, FEE);
+
+    bridging_payment
+        .set_fee_formula(
+            Direction::VaraToEth,
+            Some(FeeFormula {
+                base_fee: FEE,
+                gas_per_message: GAS_PER_MESSAGE,
+                markup_bps: 1_000,
+                max_gas_price_age: MAX_GAS_PRICE_AGE,
+            }),
+        )
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+
+    // the gas price isn't reported yet
+    let fee = quote_fee(&remoting, bridging_payment_program_id, Direction::VaraToEth).await;
+    assert_eq!(fee, FEE);
+
+    // only the oracles may report the gas price
+    let result = oracle
+        .update_gas_price(Direction::VaraToEth, GAS_PRICE)
+        .send_recv(bridging_payment_program_id)
+        .await;
+    assert!(result.is_err());
+
+    bridging_payment
+        .set_gas_price_oracle(ORACLE_ID.into(), true)
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    oracle
+        .update_gas_price(Direction::VaraToEth, GAS_PRICE)
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+
+    let gas_cost = GAS_PER_MESSAGE as u128 * GAS_PRICE;
+    let fee = quote_fee(&remoting, bridging_payment_program_id, Direction::VaraToEth).await;
+    assert_eq!(fee, FEE + gas_cost + gas_cost / 10);
+
+    // fallback fee is charged while the gas price is stale
+    let system = remoting.system();
+    system.run_to_block(system.block_height() + MAX_GAS_PRICE_AGE + 1);
+
+    let fee = quote_fee(&remoting, bridging_payment_program_id, Direction::VaraToEth).await;
+    assert_eq!(fee, FEE);
+
+    oracle
+        .update_gas_price(Direction::VaraToEth, 2 * GAS_PRICE)
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+
+    let fee = quote_fee(&remoting, bridging_payment_program_id, Direction::VaraToEth).await;
+    assert_eq!(fee, FEE + 2 * gas_cost + 2 * gas_cost / 10);
+}
+
+#[tokio::test]
+async fn test_pay_fees() {
+    let Fixture {
+        remoting,
+        bridging_payment_program_id,
+    } = setup_for_test().await;
+
+    let mut user = BridgingPaymentC::new(remoting.clone().with_actor_id(USER_ID.into()));
+    let nonce = U256::from(1);
+
+    let result = user
+        .pay_fees(nonce)
+        .with_value(FEE - 1)
+        .send_recv(bridging_payment_program_id)
+        .await;
+    assert!(result.is_err());
+
+    let paid_message = user
+        .paid_message(nonce)
+        .recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    assert!(paid_message.is_none());
+
+    // the excess value is returned to the payer
+    let balance = remoting.system().balance_of(bridging_payment_program_id);
+    user.pay_fees(nonce)
+        .with_value(FEE + UNITS)
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        remoting.system().balance_of(bridging_payment_program_id),
+        balance + FEE
+    );
+
+    let paid_message = user
+        .paid_message(nonce)
+        .recv(bridging_payment_program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(paid_message.payer, USER_ID.into());
+    assert_eq!(paid_message.fee, FEE);
+
+    // the message can be paid only once
+    let result = user
+        .pay_fees(nonce)
+        .with_value(FEE)
+        .send_recv(bridging_payment_program_id)
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_fee_escrow() {
+    let Fixture {
+        remoting,
+        bridging_payment_program_id,
+    } = setup_for_test().await;
+
+    let mut bridging_payment = BridgingPaymentC::new(remoting.clone());
+    let mut user = BridgingPaymentC::new(remoting.clone().with_actor_id(USER_ID.into()));
+    let nonce = U256::from(1);
+
+    pay_fees(&remoting, bridging_payment_program_id, nonce).await;
+
+    // the fee is held until the message is delivered
+    assert_eq!(
+        collected_fees(&remoting, bridging_payment_program_id).await,
+        0
+    );
+
+    let balance = remoting.system().balance_of(bridging_payment_program_id);
+    bridging_payment
+        .reclaim_fee()
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        remoting.system().balance_of(bridging_payment_program_id),
+        balance
+    );
+
+    // only admin may attest delivery
+    let result = user
+        .attest_delivery(vec![nonce])
+        .send_recv(bridging_payment_program_id)
+        .await;
+    assert!(result.is_err());
+
+    // messages without pending payment are skipped
+    bridging_payment
+        .attest_delivery(vec![nonce, U256::from(2)])
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        collected_fees(&remoting, bridging_payment_program_id).await,
+        FEE
+    );
+
+    let paid_message = user
+        .paid_message(nonce)
+        .recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    assert!(paid_message.is_none());
+
+    // fee of the delivered message can't be refunded
+    let result = user
+        .refund(nonce)
+        .send_recv(bridging_payment_program_id)
+        .await;
+    assert!(result.is_err());
+
+    bridging_payment
+        .reclaim_fee()
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        remoting.system().balance_of(bridging_payment_program_id),
+        balance - FEE
+    );
+    assert_eq!(
+        collected_fees(&remoting, bridging_payment_program_id).await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_refund() {
+    let Fixture {
+        remoting,
+        bridging_payment_program_id,
+    } = setup_for_test().await;
+
+    let mut bridging_payment = BridgingPaymentC::new(remoting.clone());
+    let mut user = BridgingPaymentC::new(remoting.clone().with_actor_id(USER_ID.into()));
+    let nonce = U256::from(1);
+
+    bridging_payment
+        .set_payment_timeout(10)
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+
+    pay_fees(&remoting, bridging_payment_program_id, nonce).await;
+
+    let paid_message = user
+        .paid_message(nonce)
+        .recv(bridging_payment_program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // the message can still be delivered
+    let result = user
+        .refund(nonce)
+        .send_recv(bridging_payment_program_id)
+        .await;
+    assert!(result.is_err());
+
+    let system = remoting.system();
+    system.run_to_block(paid_message.deadline + 1);
+
+    // only the payer may take the fee back
+    let result = bridging_payment
+        .refund(nonce)
+        .send_recv(bridging_payment_program_id)
+        .await;
+    assert!(result.is_err());
+
+    let balance = remoting.system().balance_of(bridging_payment_program_id);
+    user.refund(nonce)
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        remoting.system().balance_of(bridging_payment_program_id),
+        balance - FEE
+    );
+
+    let paid_message = user
+        .paid_message(nonce)
+        .recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    assert!(paid_message.is_none());
+
+    // the refunded fee can't be claimed anymore
+    bridging_payment
+        .attest_delivery(vec![nonce])
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        collected_fees(&remoting, bridging_payment_program_id).await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_submit_delivery_proof() {
+    let Fixture {
+        remoting,
+        bridging_payment_program_id,
+    } = setup_for_test().await;
+
+    let mut bridging_payment = BridgingPaymentC::new(remoting.clone());
+    let mut historical_proxy =
+        BridgingPaymentC::new(remoting.clone().with_actor_id(HISTORICAL_PROXY_ID.into()));
+
+    bridging_payment
+        .set_delivery_proof_config(HISTORICAL_PROXY_ID.into(), MESSAGE_QUEUE_ADDRESS)
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+
+    pay_fees(&remoting, bridging_payment_program_id, U256::from(1)).await;
+    pay_fees(&remoting, bridging_payment_program_id, U256::from(2)).await;
+
+    // message 3 isn't paid through the program so it's skipped
+    let receipt_rlp = create_receipt_rlp(MESSAGE_QUEUE_ADDRESS, &[U256::from(1), U256::from(3)]);
+
+    let result = bridging_payment
+        .submit_delivery_proof(1_000, 0, receipt_rlp.clone())
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    assert_eq!(result, Err(Error::NotHistoricalProxy));
+
+    let result = historical_proxy
+        .submit_delivery_proof(
+            1_000,
+            0,
+            create_receipt_rlp(H160([2; 20]), &[U256::from(1)]),
+        )
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    assert_eq!(result, Err(Error::MessageNotFound));
+
+    historical_proxy
+        .submit_delivery_proof(1_000, 0, receipt_rlp.clone())
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        collected_fees(&remoting, bridging_payment_program_id).await,
+        FEE
+    );
+
+    let paid_message = bridging_payment
+        .paid_message(U256::from(1))
+        .recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    assert!(paid_message.is_none());
+
+    let paid_message = bridging_payment
+        .paid_message(U256::from(2))
+        .recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    assert!(paid_message.is_some());
+
+    let result = historical_proxy
+        .submit_delivery_proof(1_000, 0, receipt_rlp)
+        .send_recv(bridging_payment_program_id)
+        .await
+        .unwrap();
+    assert_eq!(result, Err(Error::AlreadyProcessed));
+    assert_eq!(
+        collected_fees(&remoting, bridging_payment_program_id).await,
+        FEE
+    );
+}
@@ -0,0 +1,194 @@
+#![no_std]
+
+use cell::RefCell;
+use sails_rs::{gstd::msg, prelude::*};
+
+pub type Slot = u64;
+
+/// Errors returned by the Checkpoint Archive service.
+#[derive(Clone, Debug, PartialEq, Decode, Encode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub enum ArchiveError {
+    /// Checkpoints should be ordered by slot and follow the already archived ones.
+    Unordered,
+}
+
+/// Checkpoints evicted from the window of the `checkpoint-light-client` program.
+///
+/// ### Invariant
+///
+/// Checkpoints are stored in ascending order, sorted by slot.
+pub struct Archive {
+    pub checkpoint_light_client: ActorId,
+    checkpoints: Vec<(Slot, H256)>,
+}
+
+impl Archive {
+    pub fn new(checkpoint_light_client: ActorId) -> Self {
+        Self {
+            checkpoint_light_client,
+            checkpoints: Vec::new(),
+        }
+    }
+
+    /// Append the checkpoints evicted by the light client. Nothing is appended if they
+    /// aren't ordered.
+    pub fn append(&mut self, checkpoints: Vec<(Slot, H256)>) -> Result<(), ArchiveError> {
+        let slot_last = self.checkpoints.last().map(|(slot, _checkpoint)| *slot);
+        let ordered = checkpoints.windows(2).all(|items| items[0].0 < items[1].0)
+            && match (slot_last, checkpoints.first()) {
+                (Some(slot_last), Some((slot_first, _checkpoint))) => slot_last < *slot_first,
+                _ => true,
+            };
+
+        if !ordered {
+            return Err(ArchiveError::Unordered);
+        }
+
+        self.checkpoints.extend(checkpoints);
+
+        Ok(())
+    }
+
+    /// Get the first archived checkpoint with slot greater than or equal to the `slot`.
+    ///
+    /// The checkpoint preceding the oldest archived one isn't known so `None` is returned
+    /// for the slots before the oldest checkpoint.
+    pub fn get(&self, slot: Slot) -> Option<(Slot, H256)> {
+        let (slot_first, _checkpoint) = self.checkpoints.first()?;
+        if slot < *slot_first {
+            return None;
+        }
+
+        let index = self
+            .checkpoints
+            .partition_point(|(slot_current, _checkpoint)| *slot_current < slot);
+
+        self.checkpoints.get(index).copied()
+    }
+
+    pub fn checkpoints(&self, index_start: u32, count: u32) -> Vec<(Slot, H256)> {
+        self.checkpoints
+            .iter()
+            .skip(index_start as usize)
+            .take(count as usize)
+            .copied()
+            .collect()
+    }
+
+    pub fn count(&self) -> u32 {
+        self.checkpoints.len() as u32
+    }
+}
+
+pub struct CheckpointArchiveProgram(RefCell<Archive>);
+
+#[sails_rs::program]
+impl CheckpointArchiveProgram {
+    pub fn new(checkpoint_light_client: ActorId) -> Self {
+        Self(RefCell::new(Archive::new(checkpoint_light_client)))
+    }
+
+    pub fn checkpoint_archive(&self) -> CheckpointArchive {
+        CheckpointArchive::new(&self.0)
+    }
+}
+
+/// Checkpoint Archive service.
+///
+/// `checkpoint-light-client` keeps only a bounded window of the latest checkpoints. The
+/// evicted checkpoints are exported to this service so the light client can still find
+/// them when requested.
+pub struct CheckpointArchive<'a> {
+    state: &'a RefCell<Archive>,
+}
+
+#[sails_rs::service]
+impl<'a> CheckpointArchive<'a> {
+    pub fn new(state: &'a RefCell<Archive>) -> Self {
+        Self { state }
+    }
+
+    /// Get the light client which checkpoints are archived.
+    pub fn checkpoint_light_client(&self) -> ActorId {
+        self.state.borrow().checkpoint_light_client
+    }
+
+    /// Append the evicted checkpoints.
+    ///
+    /// This function can be called only by the light client.
+    pub fn append(&mut self, checkpoints: Vec<(Slot, H256)>) -> Result<(), ArchiveError> {
+        let mut state = self.state.borrow_mut();
+        if msg::source() != state.checkpoint_light_client {
+            panic!("Access forbidden");
+        }
+
+        state.append(checkpoints)
+    }
+
+    /// Get the first archived checkpoint with slot greater than or equal to the `slot`.
+    pub fn get(&self, slot: Slot) -> Option<(Slot, H256)> {
+        self.state.borrow().get(slot)
+    }
+
+    /// Get `count` archived checkpoints starting from `index_start`.
+    pub fn checkpoints(&self, index_start: u32, count: u32) -> Vec<(Slot, H256)> {
+        self.state.borrow().checkpoints(index_start, count)
+    }
+
+    /// Get the amount of archived checkpoints.
+    pub fn count(&self) -> u32 {
+        self.state.borrow().count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint(slot: Slot) -> (Slot, H256) {
+        (slot, H256::from_low_u64_be(slot))
+    }
+
+    #[test]
+    fn append_ordered() {
+        let mut archive = Archive::new(ActorId::zero());
+
+        assert_eq!(archive.append(vec![checkpoint(32), checkpoint(64)]), Ok(()));
+        assert_eq!(archive.append(vec![]), Ok(()));
+        assert_eq!(
+            archive.append(vec![checkpoint(64)]),
+            Err(ArchiveError::Unordered)
+        );
+        assert_eq!(
+            archive.append(vec![checkpoint(128), checkpoint(96)]),
+            Err(ArchiveError::Unordered)
+        );
+        assert_eq!(archive.append(vec![checkpoint(96)]), Ok(()));
+
+        assert_eq!(archive.count(), 3);
+        assert_eq!(
+            archive.checkpoints(1, 10),
+            vec![checkpoint(64), checkpoint(96)]
+        );
+    }
+
+    #[test]
+    fn get() {
+        let mut archive = Archive::new(ActorId::zero());
+        assert_eq!(archive.get(0), None);
+
+        archive
+            .append(vec![checkpoint(32), checkpoint(64), checkpoint(95)])
+            .unwrap();
+
+        // the checkpoint preceding the oldest one is unknown
+        assert_eq!(archive.get(31), None);
+        assert_eq!(archive.get(32), Some(checkpoint(32)));
+        assert_eq!(archive.get(33), Some(checkpoint(64)));
+        assert_eq!(archive.get(64), Some(checkpoint(64)));
+        assert_eq!(archive.get(70), Some(checkpoint(95)));
+        assert_eq!(archive.get(96), None);
+    }
+}
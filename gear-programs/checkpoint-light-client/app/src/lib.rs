@@ -7,13 +7,16 @@ mod utils;
 
 use cell::RefCell;
 use checkpoint_light_client_io::Init;
-use ethereum_common::{merkle, tree_hash::TreeHash, utils as eth_utils};
-use sails_rs::prelude::*;
-
+use ethereum_common::{merkle, tree_hash::TreeHash};
+use sails_rs::{
+    gstd::{exec, msg},
+    prelude::*,
+};
+use state::State;
+
+/// Default amount of the stored checkpoints.
 const STORED_CHECKPOINTS_COUNT: usize = 150_000;
 
-type State = state::State<STORED_CHECKPOINTS_COUNT>;
-
 pub struct CheckpointLightClientProgram(RefCell<State>);
 
 #[sails_rs::program]
@@ -26,8 +29,20 @@ impl CheckpointLightClientProgram {
             sync_committee_current_branch,
             update,
             sync_aggregate_encoded,
+            checkpoints_window,
         } = init;
 
+        if !network.is_valid() {
+            panic!("Invalid parameters of the custom network");
+        }
+
+        let checkpoints_window = checkpoints_window
+            .map(|window| window as usize)
+            .unwrap_or(STORED_CHECKPOINTS_COUNT);
+        if checkpoints_window == 0 {
+            panic!("Checkpoints window should be non-zero");
+        }
+
         let sync_aggregate = Decode::decode(&mut &sync_aggregate_encoded[..])
             .expect("Correctly scale-encoded SyncAggregate");
 
@@ -47,34 +62,53 @@ impl CheckpointLightClientProgram {
             panic!("Current sync committee proof is not valid");
         }
 
-        let period = eth_utils::calculate_period(update.finalized_header.slot) - 1;
+        let period = network.calculate_period(update.finalized_header.slot) - 1;
         match services::sync_update::verify(
             &network,
-            eth_utils::calculate_slot(period),
+            network.calculate_slot(period),
             &sync_committee_current_pub_keys,
             &sync_committee_current_pub_keys,
             update,
             sync_aggregate,
+            false,
         )
         .await
         {
             Err(e) => panic!("Failed to verify sync committee update: {e:?}"),
 
-            Ok((Some(finalized_header), Some(sync_committee_next))) => Self(RefCell::new(State {
+            Ok(services::sync_update::Verified {
+                finalized_header: Some(finalized_header),
+                sync_committee_next: Some(sync_committee_next),
+                participation,
+                ..
+            }) => Self(RefCell::new(State {
+                admin: msg::source(),
                 network,
                 sync_committee_current: sync_committee_current_pub_keys.into(),
                 sync_committee_next,
                 checkpoints: {
-                    let mut checkpoints = state::Checkpoints::new();
+                    let mut checkpoints = state::Checkpoints::new(checkpoints_window);
                     checkpoints.push(finalized_header.slot, finalized_header.tree_hash_root());
 
                     checkpoints
                 },
                 finalized_header,
+                archive: None,
                 replay_back: None,
+                participation: {
+                    let mut history = state::ParticipationHistory::default();
+                    history.push(participation);
+
+                    history
+                },
+                force_update: state::ForceUpdate::new(exec::block_height()),
             })),
 
-            Ok((finalized_header, sync_committee_next)) => panic!(
+            Ok(services::sync_update::Verified {
+                finalized_header,
+                sync_committee_next,
+                ..
+            }) => panic!(
                 "Incorrect initial sync committee update ({}, {})",
                 finalized_header.is_some(),
                 sync_committee_next.is_some()
@@ -82,11 +116,21 @@ impl CheckpointLightClientProgram {
         }
     }
 
+    #[export(route = "service_archive")]
+    pub fn archive(&self) -> services::Archive {
+        services::Archive::new(&self.0)
+    }
+
     #[export(route = "service_checkpoint_for")]
     pub fn checkpoint_for(&self) -> services::CheckpointFor {
         services::CheckpointFor::new(&self.0)
     }
 
+    #[export(route = "service_force_update")]
+    pub fn force_update(&self) -> services::ForceUpdate {
+        services::ForceUpdate::new(&self.0)
+    }
+
     #[export(route = "service_replay_back")]
     pub fn replay_back(&self) -> services::ReplayBack {
         services::ReplayBack::new(&self.0)
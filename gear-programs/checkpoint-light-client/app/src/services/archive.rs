@@ -0,0 +1,103 @@
+use crate::State;
+use cell::RefCell;
+use checkpoint_archive_client::{traits::CheckpointArchive as _, CheckpointArchive};
+use sails_rs::{calls::*, gstd::calls::GStdRemoting, gstd::msg, prelude::*};
+
+/// Management of the checkpoint window and the export of the evicted checkpoints
+/// to the `checkpoint-archive` program.
+pub struct Archive<'a> {
+    state: &'a RefCell<State>,
+}
+
+#[sails_rs::service]
+impl<'a> Archive<'a> {
+    pub fn new(state: &'a RefCell<State>) -> Self {
+        Self { state }
+    }
+
+    /// Maximum amount of the stored checkpoints.
+    pub fn window(&self) -> u32 {
+        self.state.borrow().checkpoints.window() as u32
+    }
+
+    /// Change the maximum amount of the stored checkpoints. If the window shrinks, the
+    /// oldest checkpoints are evicted.
+    ///
+    /// This function can be called only by the admin.
+    pub fn set_window(&mut self, window: u32) {
+        let mut state = self.state.borrow_mut();
+        if msg::source() != state.admin {
+            panic!("Not an admin");
+        }
+
+        if window == 0 {
+            panic!("Checkpoints window should be non-zero");
+        }
+
+        state.checkpoints.set_window(window as usize);
+    }
+
+    /// Program the evicted checkpoints are exported to.
+    pub fn address(&self) -> Option<ActorId> {
+        self.state.borrow().archive
+    }
+
+    /// Set the program the evicted checkpoints are exported to. If `None`, the evicted
+    /// checkpoints are dropped.
+    ///
+    /// This function can be called only by the admin.
+    pub fn set_address(&mut self, archive: Option<ActorId>) {
+        let mut state = self.state.borrow_mut();
+        if msg::source() != state.admin {
+            panic!("Not an admin");
+        }
+
+        if state.archive != archive {
+            state.archive = archive;
+            state.checkpoints.set_archived(archive.is_some());
+        }
+    }
+
+    /// Amount of the evicted checkpoints that aren't exported yet. At most `UNARCHIVED_MAX`
+    /// of them are kept so the oldest ones are dropped if they aren't exported in time.
+    pub fn unarchived_count(&self) -> u32 {
+        self.state.borrow().checkpoints.unarchived_count() as u32
+    }
+
+    /// Export up to `count` oldest evicted checkpoints to the archive. Returns the amount
+    /// of the exported checkpoints.
+    ///
+    /// Can be called by anyone.
+    pub async fn export(&mut self, count: u32) -> u32 {
+        let (archive, checkpoints) = {
+            let state = self.state.borrow();
+            let Some(archive) = state.archive else {
+                panic!("Archive isn't set");
+            };
+
+            (archive, state.checkpoints.unarchived(count))
+        };
+
+        let (Some(&(slot_first, _)), Some(&(slot_last, _))) =
+            (checkpoints.first(), checkpoints.last())
+        else {
+            return 0;
+        };
+
+        let exported = checkpoints.len() as u32;
+        CheckpointArchive::new(GStdRemoting)
+            .append(checkpoints)
+            .send_recv(archive)
+            .await
+            .expect("Failed to send checkpoints to the archive")
+            .unwrap_or_else(|e| panic!("Archive refused checkpoints: {e:?}"));
+
+        let mut state = self.state.borrow_mut();
+        // the archive might be changed while the checkpoints were exported
+        if state.archive == Some(archive) {
+            state.checkpoints.exported(slot_first, slot_last);
+        }
+
+        exported
+    }
+}
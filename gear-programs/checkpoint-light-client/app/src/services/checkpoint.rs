@@ -1,8 +1,9 @@
 use crate::{state::CheckpointError, State};
 use cell::RefCell;
+use checkpoint_archive_client::{traits::CheckpointArchive as _, CheckpointArchive};
 use checkpoint_light_client_io::Slot;
 use ethereum_common::Hash256;
-use sails_rs::prelude::*;
+use sails_rs::{calls::*, gstd::calls::GStdRemoting, prelude::*};
 
 pub struct CheckpointFor<'a> {
     state: &'a RefCell<State>,
@@ -14,9 +15,46 @@ impl<'a> CheckpointFor<'a> {
         Self { state }
     }
 
-    pub fn get(&self, slot: Slot) -> Result<(Slot, Hash256), CheckpointError> {
-        let state = self.state.borrow();
+    /// Get the first checkpoint with slot greater than or equal to the `slot`. The checkpoints
+    /// exported to the archive are requested from it.
+    pub async fn get(&self, slot: Slot) -> Result<(Slot, Hash256), CheckpointError> {
+        let result = self.state.borrow().checkpoints.checkpoint(slot);
 
-        state.checkpoints.checkpoint(slot)
+        self.with_archive(slot, result).await
+    }
+
+    /// The same as `get` but ignores the checkpoints accepted by the forced update so
+    /// the returned checkpoint is always finalized.
+    pub async fn get_finalized(&self, slot: Slot) -> Result<(Slot, Hash256), CheckpointError> {
+        let result = self.state.borrow().checkpoints.checkpoint_finalized(slot);
+
+        // optimistic checkpoints aren't archived
+        self.with_archive(slot, result).await
+    }
+
+    /// The first slot the checkpoint can be requested for with `get`, including the archived
+    /// ones. Blocks before it can't be proven anymore.
+    pub fn first_provable_slot(&self) -> Slot {
+        self.state.borrow().checkpoints.slot_provable_first()
+    }
+}
+
+impl CheckpointFor<'_> {
+    async fn with_archive(
+        &self,
+        slot: Slot,
+        result: Result<(Slot, Hash256), CheckpointError>,
+    ) -> Result<(Slot, Hash256), CheckpointError> {
+        let archive = self.state.borrow().archive;
+        match (result, archive) {
+            (Err(CheckpointError::OutDated), Some(archive)) => CheckpointArchive::new(GStdRemoting)
+                .get(slot)
+                .recv(archive)
+                .await
+                .ok()
+                .flatten()
+                .ok_or(CheckpointError::OutDated),
+            (result, _) => result,
+        }
     }
 }
@@ -0,0 +1,104 @@
+use crate::State;
+use cell::RefCell;
+use checkpoint_light_client_io::{ForceUpdateConfig, Participation, Slot};
+use sails_rs::{gstd::msg, prelude::*};
+
+/// Administration of the forced update mode. While Ethereum doesn't finalize, the
+/// program keeps the best-participating non-finalized update and accepts it as an
+/// optimistic checkpoint once the timeout elapses. The mode is disabled by default.
+pub struct ForceUpdate<'a> {
+    state: &'a RefCell<State>,
+}
+
+#[sails_rs::service]
+impl<'a> ForceUpdate<'a> {
+    pub fn new(state: &'a RefCell<State>) -> Self {
+        Self { state }
+    }
+
+    pub fn admin(&self) -> ActorId {
+        self.state.borrow().admin
+    }
+
+    /// Update the current admin to `admin_new`.
+    ///
+    /// This function can be called only by the admin.
+    pub fn update_admin(&mut self, admin_new: ActorId) {
+        let mut state = self.state.borrow_mut();
+        if msg::source() != state.admin {
+            panic!("Not an admin");
+        }
+
+        state.admin = admin_new;
+    }
+
+    /// Enable the mode or change its parameters.
+    ///
+    /// This function can be called only by the admin.
+    pub fn enable(&mut self, config: ForceUpdateConfig) {
+        let mut state = self.state.borrow_mut();
+        if msg::source() != state.admin {
+            panic!("Not an admin");
+        }
+
+        if !config.is_valid() {
+            panic!("Invalid forced update config");
+        }
+
+        state.force_update.config = Some(config);
+    }
+
+    /// Disable the mode and drop the kept non-finalized update.
+    ///
+    /// This function can be called only by the admin.
+    pub fn disable(&mut self) {
+        let mut state = self.state.borrow_mut();
+        if msg::source() != state.admin {
+            panic!("Not an admin");
+        }
+
+        state.force_update.config = None;
+        state.force_update.best = None;
+    }
+
+    /// Parameters of the mode or `None` if it is disabled.
+    pub fn config(&self) -> Option<ForceUpdateConfig> {
+        self.state.borrow().force_update.config
+    }
+
+    /// Participation of the best non-finalized update that will be accepted after the timeout.
+    pub fn best_update(&self) -> Option<Participation> {
+        self.state
+            .borrow()
+            .force_update
+            .best
+            .as_ref()
+            .map(|candidate| candidate.participation)
+    }
+
+    /// Gear block the timeout is counted from.
+    pub fn last_update_block(&self) -> u32 {
+        self.state.borrow().force_update.last_update_block
+    }
+
+    /// Whether the latest stored checkpoint is accepted by the forced update.
+    pub fn is_optimistic(&self) -> bool {
+        let state = self.state.borrow();
+
+        state
+            .checkpoints
+            .last()
+            .map(|(slot, _checkpoint)| state.checkpoints.is_optimistic(slot))
+            .unwrap_or(false)
+    }
+
+    /// Slots of the stored checkpoints accepted by the forced update.
+    pub fn optimistic_checkpoints(&self) -> Vec<Slot> {
+        self.state
+            .borrow()
+            .checkpoints
+            .optimistic()
+            .copied()
+            .collect()
+    }
+}
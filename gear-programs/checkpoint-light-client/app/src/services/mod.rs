@@ -1,9 +1,13 @@
+mod archive;
 mod checkpoint;
+mod force_update;
 mod replay_back;
 mod state;
 pub mod sync_update;
 
+pub use archive::Archive;
 pub use checkpoint::CheckpointFor;
+pub use force_update::ForceUpdate;
 pub use replay_back::ReplayBack;
 pub use state::State;
 pub use sync_update::SyncUpdate;
@@ -1,10 +1,11 @@
+use super::sync_update::Verified;
 use crate::{state::ReplayBackState, State};
 use cell::RefCell;
 use checkpoint_light_client_io::{
     BeaconBlockHeader, Error, ReplayBackError, ReplayBackStatus, Update,
 };
-use ethereum_common::{tree_hash::TreeHash, EPOCHS_PER_SYNC_COMMITTEE, SLOTS_PER_EPOCH};
-use sails_rs::{prelude::*, rc::Rc};
+use ethereum_common::{tree_hash::TreeHash, EPOCHS_PER_SYNC_COMMITTEE};
+use sails_rs::{gstd::exec, prelude::*, rc::Rc};
 
 pub struct ReplayBack<'a> {
     state: &'a RefCell<State>,
@@ -38,13 +39,19 @@ impl<'a> ReplayBack<'a> {
 
         let sync_aggregate = Decode::decode(&mut &sync_aggregate_encoded[..])
             .map_err(|_| Error::InvalidSyncAggregate)?;
-        let (finalized_header_update, committee_update) = super::sync_update::verify(
+        // Participation of the replayed update isn't recorded since it's historical.
+        let Verified {
+            finalized_header: finalized_header_update,
+            sync_committee_next: committee_update,
+            ..
+        } = super::sync_update::verify(
             &network,
             slot,
             &sync_committee_current,
             &sync_committee_next,
             sync_update,
             sync_aggregate,
+            false,
         )
         .await?;
 
@@ -87,6 +94,7 @@ impl<'a> ReplayBack<'a> {
 fn process_headers(state: &mut State, mut headers: Vec<BeaconBlockHeader>) -> bool {
     headers.sort_unstable_by(|a, b| a.slot.cmp(&b.slot));
 
+    let slots_per_epoch = state.network.slots_per_epoch();
     let replay_back = state.replay_back.as_mut().expect("Checked by the caller");
     let (slot_last, checkpoint_last) = state
         .checkpoints
@@ -111,7 +119,7 @@ fn process_headers(state: &mut State, mut headers: Vec<BeaconBlockHeader>) -> bo
             .checkpoints
             .last()
             .expect("At least contains finalized header; qed");
-        if slot % SLOTS_PER_EPOCH == 0 || slot + SLOTS_PER_EPOCH < *slot_next {
+        if slot % slots_per_epoch == 0 || slot + slots_per_epoch < *slot_next {
             replay_back.checkpoints.push((slot, hash));
         }
     }
@@ -132,6 +140,7 @@ fn process_headers(state: &mut State, mut headers: Vec<BeaconBlockHeader>) -> bo
 
     state.finalized_header = replay_back.finalized_header.clone();
     state.replay_back = None;
+    state.force_update.reset(exec::block_height());
 
     true
 }
@@ -1,6 +1,6 @@
 use cell::RefCell;
-use checkpoint_light_client_io::{ReplayBack, Slot};
-use ethereum_common::Hash256;
+use checkpoint_light_client_io::{Participation, ReplayBack, Slot};
+use ethereum_common::{network::Network, Hash256};
 use sails_rs::prelude::*;
 
 #[derive(Clone, Debug, Decode, Encode, TypeInfo)]
@@ -11,6 +11,9 @@ pub struct StateData {
     /// The field contains the data if the program is
     /// replaying checkpoints back.
     pub replay_back: Option<ReplayBack>,
+    /// Slots of the returned checkpoints that are accepted by the forced update
+    /// so aren't finalized.
+    pub optimistic: Vec<Slot>,
 }
 
 #[derive(Clone, Debug, Decode, TypeInfo)]
@@ -32,17 +35,6 @@ impl<'a> State<'a> {
     }
 
     pub fn get(&self, order: Order, index_start: u32, count: u32) -> StateData {
-        fn collect<'a, T: 'a + Copy>(
-            index_start: u32,
-            count: u32,
-            iter: impl DoubleEndedIterator<Item = &'a T>,
-        ) -> Vec<T> {
-            iter.skip(index_start as usize)
-                .take(count as usize)
-                .copied()
-                .collect()
-        }
-
         let state = self.state.borrow();
         let checkpoints = match order {
             Order::Direct => collect(index_start, count, state.checkpoints.iter()),
@@ -54,9 +46,44 @@ impl<'a> State<'a> {
             last_header: replay_back.last_header.slot,
         });
 
+        let optimistic = checkpoints
+            .iter()
+            .filter_map(|(slot, _checkpoint)| {
+                state.checkpoints.is_optimistic(*slot).then_some(*slot)
+            })
+            .collect();
+
         StateData {
             checkpoints,
             replay_back,
+            optimistic,
         }
     }
+
+    /// Ethereum network the program is initialized for. Relayers use it to calculate
+    /// epochs and sync committee periods of the network.
+    pub fn network(&self) -> Network {
+        self.state.borrow().network.clone()
+    }
+
+    /// Sync committee participation in the latest accepted updates. The history is bounded
+    /// so only the latest updates are available. Updates used to replay back aren't included.
+    pub fn participation(&self, order: Order, index_start: u32, count: u32) -> Vec<Participation> {
+        let state = self.state.borrow();
+        match order {
+            Order::Direct => collect(index_start, count, state.participation.iter()),
+            Order::Reverse => collect(index_start, count, state.participation.iter().rev()),
+        }
+    }
+}
+
+fn collect<'a, T: 'a + Copy>(
+    index_start: u32,
+    count: u32,
+    iter: impl DoubleEndedIterator<Item = &'a T>,
+) -> Vec<T> {
+    iter.skip(index_start as usize)
+        .take(count as usize)
+        .copied()
+        .collect()
 }
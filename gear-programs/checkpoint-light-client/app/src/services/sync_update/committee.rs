@@ -21,15 +21,16 @@ pub struct Update<'a>(Status<'a>);
 
 impl<'a> Update<'a> {
     pub fn new(
+        network: &Network,
         attested_header: &'a BeaconBlockHeader,
         update_slot_finalized: u64,
         sync_committee_next_aggregate_pubkey: Option<BLSPubKey>,
         sync_committee_next_pub_keys: Option<Box<SyncCommitteeKeys>>,
         sync_committee_next_branch: Option<Vec<[u8; 32]>>,
     ) -> Self {
-        let update_period_finalized = eth_utils::calculate_period(update_slot_finalized);
+        let update_period_finalized = network.calculate_period(update_slot_finalized);
         match (
-            eth_utils::calculate_period(attested_header.slot) == update_period_finalized,
+            network.calculate_period(attested_header.slot) == update_period_finalized,
             sync_committee_next_aggregate_pubkey,
             sync_committee_next_pub_keys,
             sync_committee_next_branch,
@@ -1,10 +1,11 @@
 mod committee;
 
+use crate::state::Candidate;
 use crate::State;
 use crate::{crypto, utils};
 use cell::RefCell;
 use checkpoint_light_client_io::{
-    Error as SyncCommitteeUpdateError, ReplayBack, Slot, SyncCommitteeKeys,
+    Error as SyncCommitteeUpdateError, Participation, ReplayBack, Slot, SyncCommitteeKeys,
     Update as SyncCommitteeUpdate, MAX_EPOCHS_GAP,
 };
 use ethereum_common::{
@@ -12,10 +13,21 @@ use ethereum_common::{
     merkle,
     network::Network,
     tree_hash::TreeHash,
-    utils as eth_utils, SYNC_COMMITTEE_SIZE,
+    SYNC_COMMITTEE_SIZE,
 };
-use sails_rs::{prelude::*, rc::Rc};
+use sails_rs::{gstd::exec, prelude::*, rc::Rc};
 
+/// Result of the successful update verification.
+pub struct Verified {
+    pub finalized_header: Option<BeaconBlockHeader>,
+    pub sync_committee_next: Option<Rc<SyncCommitteeKeys>>,
+    pub participation: Participation,
+    /// The update doesn't advance finality so `finalized_header` is its attested header.
+    pub optimistic: bool,
+}
+
+/// Verify the update against the stored state. If `accept_optimistic` is set, the update
+/// without a newer finalized header is treated as if its attested header were finalized.
 pub async fn verify(
     network: &Network,
     stored_finalized_slot: Slot,
@@ -23,7 +35,8 @@ pub async fn verify(
     stored_sync_committee_next: &SyncCommitteeKeys,
     sync_update: SyncCommitteeUpdate,
     sync_aggregate: SyncAggregate,
-) -> Result<(Option<BeaconBlockHeader>, Option<Rc<SyncCommitteeKeys>>), SyncCommitteeUpdateError> {
+    accept_optimistic: bool,
+) -> Result<Verified, SyncCommitteeUpdateError> {
     let SyncCommitteeUpdate {
         signature_slot,
         attested_header,
@@ -40,8 +53,8 @@ pub async fn verify(
         return Err(SyncCommitteeUpdateError::InvalidTimestamp);
     }
 
-    let store_period = eth_utils::calculate_period(stored_finalized_slot);
-    let update_sig_period = eth_utils::calculate_period(signature_slot);
+    let store_period = network.calculate_period(stored_finalized_slot);
+    let update_sig_period = network.calculate_period(signature_slot);
     let sync_committee = if update_sig_period == store_period + 1 {
         stored_sync_committee_next
     } else if update_sig_period == store_period {
@@ -59,9 +72,17 @@ pub async fn verify(
         return Err(SyncCommitteeUpdateError::LowVoteCount);
     }
 
+    let optimistic = accept_optimistic
+        && update_slot_finalized <= stored_finalized_slot
+        && attested_header.slot > stored_finalized_slot;
+
     let committee_update = committee::Update::new(
+        network,
         &attested_header,
-        update_slot_finalized,
+        match optimistic {
+            true => attested_header.slot,
+            false => update_slot_finalized,
+        },
         sync_committee_next_aggregate_pubkey,
         sync_committee_next_pub_keys,
         sync_committee_next_branch,
@@ -91,6 +112,8 @@ pub async fn verify(
         } else {
             return Err(SyncCommitteeUpdateError::InvalidFinalityProof);
         }
+    } else if optimistic {
+        finalized_header_update = Some(attested_header.clone());
     }
 
     let committee_update = match committee_update.verify(network, store_period) {
@@ -107,7 +130,16 @@ pub async fn verify(
         return Err(SyncCommitteeUpdateError::NotActual);
     }
 
-    Ok((finalized_header_update, committee_update))
+    Ok(Verified {
+        finalized_header: finalized_header_update,
+        sync_committee_next: committee_update,
+        participation: Participation {
+            bit_count: committee_count as u16,
+            signature_slot,
+            attested_slot: attested_header.slot,
+        },
+        optimistic,
+    })
 }
 
 pub struct SyncUpdate<'a> {
@@ -125,7 +157,7 @@ impl<'a> SyncUpdate<'a> {
         sync_update: SyncCommitteeUpdate,
         sync_aggregate_encoded: Vec<u8>,
     ) -> Result<(), SyncCommitteeUpdateError> {
-        let (network, slot, sync_committee_current, sync_committee_next) = {
+        let (network, slot, sync_committee_current, sync_committee_next, accept_optimistic) = {
             let state = self.state.borrow();
 
             (
@@ -133,11 +165,12 @@ impl<'a> SyncUpdate<'a> {
                 state.finalized_header.slot,
                 Rc::clone(&state.sync_committee_current),
                 Rc::clone(&state.sync_committee_next),
+                state.force_update.config.is_some(),
             )
         };
 
-        if eth_utils::calculate_epoch(slot) + MAX_EPOCHS_GAP
-            <= eth_utils::calculate_epoch(sync_update.finalized_header.slot)
+        if network.calculate_epoch(slot) + MAX_EPOCHS_GAP
+            <= network.calculate_epoch(sync_update.finalized_header.slot)
         {
             let state = self.state.borrow();
             return Err(SyncCommitteeUpdateError::ReplayBackRequired {
@@ -154,30 +187,92 @@ impl<'a> SyncUpdate<'a> {
 
         let sync_aggregate = Decode::decode(&mut &sync_aggregate_encoded[..])
             .map_err(|_| SyncCommitteeUpdateError::InvalidSyncAggregate)?;
-        let (finalized_header_update, committee_update) = verify(
+        let Verified {
+            finalized_header: finalized_header_update,
+            sync_committee_next: committee_update,
+            participation,
+            optimistic,
+        } = verify(
             &network,
             slot,
             &sync_committee_current,
             &sync_committee_next,
             sync_update,
             sync_aggregate,
+            accept_optimistic,
         )
         .await?;
 
         let mut state = self.state.borrow_mut();
+        let block = exec::block_height();
+
+        if !optimistic {
+            state.participation.push(participation);
+            if finalized_header_update.is_some() {
+                state.force_update.reset(block);
+            }
+
+            apply(&mut state, finalized_header_update, committee_update, false);
 
-        if let Some(finalized_header) = finalized_header_update {
-            state
-                .checkpoints
-                .push(finalized_header.slot, finalized_header.tree_hash_root());
-            state.finalized_header = finalized_header;
+            return Ok(());
         }
 
-        if let Some(sync_committee_next) = committee_update {
-            state.sync_committee_current =
-                core::mem::replace(&mut state.sync_committee_next, sync_committee_next);
+        // the forced update mode might be disabled while the update was verified
+        let Some(config) = state.force_update.config else {
+            return Err(SyncCommitteeUpdateError::NotActual);
+        };
+
+        if participation.bit_count < config.min_participation {
+            return Err(SyncCommitteeUpdateError::LowVoteCount);
+        }
+
+        state.force_update.offer(Candidate {
+            attested_header: finalized_header_update.expect("Optimistic update has the header"),
+            sync_committee_next: committee_update,
+            participation,
+        });
+
+        let Some(best) = state.force_update.take_timed_out(block) else {
+            return Ok(());
+        };
+
+        // the state might be updated while the update was verified
+        if best.attested_header.slot <= state.finalized_header.slot {
+            return Err(SyncCommitteeUpdateError::NotActual);
         }
 
+        state.participation.push(best.participation);
+        state.force_update.reset(block);
+
+        apply(
+            &mut state,
+            Some(best.attested_header),
+            best.sync_committee_next,
+            true,
+        );
+
         Ok(())
     }
 }
+
+fn apply(
+    state: &mut State,
+    finalized_header: Option<BeaconBlockHeader>,
+    sync_committee_next: Option<Rc<SyncCommitteeKeys>>,
+    optimistic: bool,
+) {
+    if let Some(finalized_header) = finalized_header {
+        let (slot, checkpoint) = (finalized_header.slot, finalized_header.tree_hash_root());
+        match optimistic {
+            true => state.checkpoints.push_optimistic(slot, checkpoint),
+            false => state.checkpoints.push(slot, checkpoint),
+        }
+
+        state.finalized_header = finalized_header;
+    }
+
+    if let Some(sync_committee_next) = sync_committee_next {
+        state.sync_committee_current =
+            core::mem::replace(&mut state.sync_committee_next, sync_committee_next);
+    }
+}
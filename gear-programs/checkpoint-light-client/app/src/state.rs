@@ -1,9 +1,21 @@
-use checkpoint_light_client_io::{Slot, SyncCommitteeKeys};
-use circular_buffer::CircularBuffer;
+use checkpoint_light_client_io::{ForceUpdateConfig, Participation, Slot, SyncCommitteeKeys};
 use ethereum_common::{beacon::BlockHeader as BeaconBlockHeader, network::Network, Hash256};
-use sails_rs::{prelude::*, rc::Rc};
-
-#[derive(Clone, Debug, Decode, Encode, TypeInfo)]
+use sails_rs::{
+    prelude::{
+        collections::{BTreeSet, VecDeque},
+        *,
+    },
+    rc::Rc,
+};
+
+/// Amount of the latest accepted updates which sync committee participation is kept.
+pub const PARTICIPATION_HISTORY_SIZE: usize = 1_024;
+
+/// Maximum amount of the evicted checkpoints waiting for the export to the archive. The
+/// oldest ones are dropped if they aren't exported in time.
+pub const UNARCHIVED_MAX: usize = 8_192;
+
+#[derive(Clone, Debug, PartialEq, Decode, Encode, TypeInfo)]
 #[codec(crate = sails_rs::scale_codec)]
 #[scale_info(crate = sails_rs::scale_info)]
 pub enum CheckpointError {
@@ -11,13 +23,18 @@ pub enum CheckpointError {
     NotPresent,
 }
 
-pub struct State<const N: usize> {
+pub struct State {
+    pub admin: ActorId,
     pub network: Network,
     pub finalized_header: BeaconBlockHeader,
     pub sync_committee_current: Rc<SyncCommitteeKeys>,
     pub sync_committee_next: Rc<SyncCommitteeKeys>,
-    pub checkpoints: Checkpoints<N>,
+    pub checkpoints: Checkpoints,
+    /// Program the evicted checkpoints are exported to.
+    pub archive: Option<ActorId>,
     pub replay_back: Option<ReplayBackState>,
+    pub participation: ParticipationHistory,
+    pub force_update: ForceUpdate,
 }
 
 pub struct ReplayBackState {
@@ -27,29 +44,254 @@ pub struct ReplayBackState {
     pub last_header: BeaconBlockHeader,
 }
 
+/// Bounded history of the sync committee participation in the accepted updates.
+/// Only the updates that advance the store are recorded, the ones used to replay
+/// back are not.
+#[derive(Debug, Clone, Default)]
+pub struct ParticipationHistory(VecDeque<Participation>);
+
+impl ParticipationHistory {
+    pub fn push(&mut self, participation: Participation) {
+        if self.0.len() >= PARTICIPATION_HISTORY_SIZE {
+            self.0.pop_front();
+        }
+
+        self.0.push_back(participation);
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Participation> {
+        self.0.iter()
+    }
+}
+
+/// Verified non-finalized update which may be accepted by the forced update.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub attested_header: BeaconBlockHeader,
+    pub sync_committee_next: Option<Rc<SyncCommitteeKeys>>,
+    pub participation: Participation,
+}
+
+impl Candidate {
+    /// Prefer the update with the higher participation and then the more recent one.
+    fn is_better(&self, other: &Self) -> bool {
+        (self.participation.bit_count, self.attested_header.slot)
+            > (other.participation.bit_count, other.attested_header.slot)
+    }
+}
+
+/// State of the forced update mode. The mode is disabled if there is no config.
 #[derive(Debug, Clone)]
-pub struct Checkpoints<const N: usize>(Box<CircularBuffer<N, (Slot, Hash256)>>);
+pub struct ForceUpdate {
+    pub config: Option<ForceUpdateConfig>,
+    /// Gear block the latest finalized or forced update was accepted at.
+    pub last_update_block: u32,
+    pub best: Option<Candidate>,
+}
+
+impl ForceUpdate {
+    pub fn new(block: u32) -> Self {
+        Self {
+            config: None,
+            last_update_block: block,
+            best: None,
+        }
+    }
+
+    /// Keep the candidate if it is better than the current one.
+    pub fn offer(&mut self, candidate: Candidate) {
+        match &self.best {
+            Some(best) if !candidate.is_better(best) => {}
+            _ => self.best = Some(candidate),
+        }
+    }
 
-impl<const N: usize> Default for Checkpoints<N> {
-    fn default() -> Self {
-        Self::new()
+    /// Take the best candidate if the mode is enabled and the timeout has elapsed since
+    /// the latest accepted update.
+    pub fn take_timed_out(&mut self, block: u32) -> Option<Candidate> {
+        let config = self.config?;
+        if block < self.last_update_block.saturating_add(config.timeout) {
+            return None;
+        }
+
+        self.best.take()
+    }
+
+    /// Restart the timeout since a new update is accepted.
+    pub fn reset(&mut self, block: u32) {
+        self.last_update_block = block;
+        self.best = None;
     }
 }
 
-impl<const N: usize> Checkpoints<N> {
-    pub fn new() -> Self {
-        Self(CircularBuffer::boxed())
+/// Bounded window of the latest checkpoints ordered by slot.
+#[derive(Debug, Clone)]
+pub struct Checkpoints {
+    buffer: VecDeque<(Slot, Hash256)>,
+    /// Maximum amount of the checkpoints in the window.
+    window: usize,
+    /// Slots of the stored checkpoints that are accepted by the forced update, i.e.
+    /// without the finality proof.
+    optimistic: BTreeSet<Slot>,
+    /// Slot of the latest checkpoint evicted from the window.
+    evicted_last: Option<Slot>,
+    /// Evicted finalized checkpoints that aren't exported to the archive yet. `None` if
+    /// there is no archive so the evicted checkpoints are dropped.
+    unarchived: Option<VecDeque<(Slot, Hash256)>>,
+    /// Maximum amount of the unarchived checkpoints.
+    unarchived_max: usize,
+    /// Slot of the latest unarchived checkpoint dropped because of the limit. The archive
+    /// misses the checkpoints up to it.
+    dropped_last: Option<Slot>,
+    /// Slots of the first and the latest checkpoints exported to the archive.
+    exported: Option<(Slot, Slot)>,
+}
+
+impl Checkpoints {
+    pub fn new(window: usize) -> Self {
+        Self {
+            buffer: VecDeque::new(),
+            window,
+            optimistic: BTreeSet::new(),
+            evicted_last: None,
+            unarchived: None,
+            unarchived_max: UNARCHIVED_MAX,
+            dropped_last: None,
+            exported: None,
+        }
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Change the window size. If the window shrinks, the oldest checkpoints are evicted.
+    pub fn set_window(&mut self, window: usize) {
+        self.window = window;
+        self.evict();
     }
 
     pub fn push(&mut self, slot: Slot, checkpoint: Hash256) {
-        self.0.push_back((slot, checkpoint))
+        self.buffer.push_back((slot, checkpoint));
+        self.evict();
+    }
+
+    pub fn push_optimistic(&mut self, slot: Slot, checkpoint: Hash256) {
+        self.optimistic.insert(slot);
+        self.push(slot, checkpoint);
+    }
+
+    fn evict(&mut self) {
+        while self.buffer.len() > self.window {
+            let Some((slot, checkpoint)) = self.buffer.pop_front() else {
+                break;
+            };
+
+            self.evicted_last = Some(slot);
+
+            // optimistic checkpoints aren't archived
+            if self.optimistic.remove(&slot) {
+                continue;
+            }
+
+            let Some(unarchived) = self.unarchived.as_mut() else {
+                continue;
+            };
+
+            unarchived.push_back((slot, checkpoint));
+            if unarchived.len() > self.unarchived_max {
+                self.dropped_last = unarchived.pop_front().map(|(slot, _checkpoint)| slot);
+            }
+        }
+    }
+
+    /// Start or stop keeping the evicted checkpoints for the archive. The archive
+    /// is considered new so the previously exported checkpoints are forgotten.
+    pub fn set_archived(&mut self, archived: bool) {
+        self.exported = None;
+        self.dropped_last = None;
+        match archived {
+            true if self.unarchived.is_none() => self.unarchived = Some(VecDeque::new()),
+            true => {}
+            false => self.unarchived = None,
+        }
+    }
+
+    /// Get up to `count` oldest evicted checkpoints that aren't exported yet.
+    pub fn unarchived(&self, count: u32) -> Vec<(Slot, Hash256)> {
+        self.unarchived
+            .iter()
+            .flatten()
+            .take(count as usize)
+            .copied()
+            .collect()
+    }
+
+    pub fn unarchived_count(&self) -> usize {
+        self.unarchived.as_ref().map(VecDeque::len).unwrap_or(0)
+    }
+
+    /// Forget the evicted checkpoints from `slot_first` to `slot_last` since they're exported
+    /// to the archive. Some of them might be already dropped while the export was in progress.
+    pub fn exported(&mut self, slot_first: Slot, slot_last: Slot) {
+        let Some(unarchived) = self.unarchived.as_mut() else {
+            return;
+        };
+
+        while unarchived
+            .front()
+            .is_some_and(|(slot, _checkpoint)| *slot <= slot_last)
+        {
+            unarchived.pop_front();
+        }
+
+        let slot_first = self
+            .exported
+            .map(|(slot_first, _slot_last)| slot_first)
+            .unwrap_or(slot_first);
+        self.exported = Some((slot_first, slot_last));
+    }
+
+    /// The first slot the checkpoint can be requested for, including the archived ones.
+    /// Blocks before it can't be proven anymore.
+    ///
+    /// Since the first checkpoint with slot greater than or equal to the requested one is
+    /// returned, the slots after the latest dropped checkpoint are still covered by the window.
+    /// If the unarchived checkpoints were dropped, the archive has a gap so the exported ones
+    /// before it are ignored as well.
+    pub fn slot_provable_first(&self) -> Slot {
+        self.dropped_last
+            .map(|slot| slot + 1)
+            .or_else(|| self.exported.map(|(slot_first, _slot_last)| slot_first))
+            .or_else(|| {
+                self.unarchived
+                    .as_ref()
+                    .and_then(VecDeque::front)
+                    .map(|(slot, _checkpoint)| *slot)
+            })
+            .or_else(|| self.evicted_last.map(|slot| slot + 1))
+            .unwrap_or(0)
+    }
+
+    pub fn is_optimistic(&self, slot: Slot) -> bool {
+        self.optimistic.contains(&slot)
+    }
+
+    pub fn optimistic(&self) -> impl DoubleEndedIterator<Item = &Slot> {
+        self.optimistic.iter()
     }
 
     pub fn checkpoints(&self) -> Vec<(Slot, Hash256)> {
-        self.0.to_vec()
+        self.buffer.iter().copied().collect()
     }
 
+    /// Get the first checkpoint with slot greater than or equal to the `slot`. Returns
+    /// `OutDated` if the checkpoint is evicted and should be requested from the archive.
     pub fn checkpoint(&self, slot: Slot) -> Result<(Slot, Hash256), CheckpointError> {
+        if self.is_evicted(slot) {
+            return self.checkpoint_evicted(slot);
+        }
+
         let search = |slice: &[(Slot, Hash256)]| match slice
             .binary_search_by(|(slot_current, _checkpoint)| slot_current.cmp(&slot))
         {
@@ -60,27 +302,73 @@ impl<const N: usize> Checkpoints<N> {
             },
         };
 
-        let (left, right) = self.0.as_slices();
+        let (left, right) = self.buffer.as_slices();
 
         search(left).or(search(right))
     }
 
+    /// The same as [`Self::checkpoint`] but skips the optimistic checkpoints.
+    pub fn checkpoint_finalized(&self, slot: Slot) -> Result<(Slot, Hash256), CheckpointError> {
+        if self.is_evicted(slot) {
+            return self.checkpoint_evicted(slot);
+        }
+
+        let (left, right) = self.buffer.as_slices();
+        let start = |slice: &[(Slot, Hash256)]| {
+            slice.partition_point(|(slot_current, _checkpoint)| *slot_current < slot)
+        };
+
+        left[start(left)..]
+            .iter()
+            .chain(&right[start(right)..])
+            .find(|(slot_current, _checkpoint)| !self.optimistic.contains(slot_current))
+            .copied()
+            .ok_or(CheckpointError::NotPresent)
+    }
+
+    fn is_evicted(&self, slot: Slot) -> bool {
+        self.evicted_last
+            .map(|slot_evicted| slot <= slot_evicted)
+            .unwrap_or(false)
+    }
+
+    fn checkpoint_evicted(&self, slot: Slot) -> Result<(Slot, Hash256), CheckpointError> {
+        let unarchived = self.unarchived.as_ref().ok_or(CheckpointError::OutDated)?;
+        let (slot_first, _checkpoint) = unarchived.front().ok_or(CheckpointError::OutDated)?;
+
+        // the checkpoint preceding the first unarchived one is either exported to
+        // the archive, dropped or unknown
+        let exported_last = self.exported.map(|(_slot_first, slot_last)| slot_last);
+        let slot_preceding = exported_last.max(self.dropped_last);
+        if slot < *slot_first && !slot_preceding.is_some_and(|slot_preceding| slot > slot_preceding)
+        {
+            return Err(CheckpointError::OutDated);
+        }
+
+        let index = unarchived.partition_point(|(slot_current, _checkpoint)| *slot_current < slot);
+
+        unarchived
+            .get(index)
+            .copied()
+            .ok_or(CheckpointError::OutDated)
+    }
+
     pub fn checkpoint_by_index(&self, index: usize) -> Option<(Slot, Hash256)> {
-        self.0.get(index).copied()
+        self.buffer.get(index).copied()
     }
 
     pub fn last(&self) -> Option<(Slot, Hash256)> {
-        self.0.back().copied()
+        self.buffer.back().copied()
     }
 
     pub fn iter(&self) -> impl DoubleEndedIterator<Item = &(Slot, Hash256)> {
-        self.0.iter()
+        self.buffer.iter()
     }
 }
 
 #[test]
 fn empty_checkpoints() {
-    let checkpoints = Checkpoints::<3>::new();
+    let checkpoints = Checkpoints::new(3);
 
     assert!(checkpoints.checkpoints().is_empty());
     assert!(matches!(
@@ -102,10 +390,7 @@ fn empty_checkpoints() {
 
 #[cfg(test)]
 #[track_caller]
-fn compare_checkpoints<const COUNT: usize>(
-    data: &[(Slot, Hash256)],
-    checkpoints: &Checkpoints<COUNT>,
-) {
+fn compare_checkpoints(data: &[(Slot, Hash256)], checkpoints: &Checkpoints) {
     for items in data.windows(2) {
         let (slot_start, checkpoint_start) = items[0];
         let (slot_end, checkpoint_end) = items[1];
@@ -114,7 +399,7 @@ fn compare_checkpoints<const COUNT: usize>(
         assert_eq!(
             slot, slot_start,
             "start; slot = {slot}, {:?}, {data:?}",
-            checkpoints.0
+            checkpoints.buffer
         );
         assert_eq!(checkpoint, checkpoint_start);
 
@@ -124,7 +409,7 @@ fn compare_checkpoints<const COUNT: usize>(
             assert_eq!(
                 slot, slot_end,
                 "slot = {slot}, slot_requested = {slot_requested}, {:?}, {data:?}",
-                checkpoints.0
+                checkpoints.buffer
             );
             assert_eq!(checkpoint, checkpoint_end);
         }
@@ -171,7 +456,7 @@ fn checkpoints() {
     ];
     assert_eq!(data.len(), COUNT);
 
-    let mut checkpoints = Checkpoints::<COUNT>::new();
+    let mut checkpoints = Checkpoints::new(COUNT);
 
     for (slot, checkpoint) in &data {
         checkpoints.push(*slot, *checkpoint);
@@ -283,7 +568,7 @@ fn checkpoints_with_gaps() {
     ];
     assert_eq!(data.len(), COUNT);
 
-    let mut checkpoints = Checkpoints::<COUNT>::new();
+    let mut checkpoints = Checkpoints::new(COUNT);
 
     for (slot, checkpoint) in &data {
         checkpoints.push(*slot, *checkpoint);
@@ -349,7 +634,7 @@ fn checkpoints_get() {
     ];
     assert_eq!(data.len(), COUNT);
 
-    let mut checkpoints = Checkpoints::<COUNT>::new();
+    let mut checkpoints = Checkpoints::new(COUNT);
 
     for (slot, checkpoint) in &data {
         checkpoints.push(*slot, *checkpoint);
@@ -367,3 +652,211 @@ fn checkpoints_get() {
         }
     }
 }
+
+#[test]
+fn participation_history_is_bounded() {
+    let mut history = ParticipationHistory::default();
+    for slot in 0..(PARTICIPATION_HISTORY_SIZE as u64 + 10) {
+        history.push(Participation {
+            bit_count: 512,
+            signature_slot: slot + 1,
+            attested_slot: slot,
+        });
+    }
+
+    assert_eq!(history.iter().count(), PARTICIPATION_HISTORY_SIZE);
+    assert_eq!(history.iter().next().map(|p| p.attested_slot), Some(10));
+    assert_eq!(
+        history.iter().next_back().map(|p| p.attested_slot),
+        Some(PARTICIPATION_HISTORY_SIZE as u64 + 9)
+    );
+}
+
+#[test]
+fn optimistic_checkpoints() {
+    let mut checkpoints = Checkpoints::new(3);
+
+    checkpoints.push(32, [1; 32].into());
+    checkpoints.push_optimistic(64, [2; 32].into());
+    checkpoints.push_optimistic(96, [3; 32].into());
+
+    assert!(!checkpoints.is_optimistic(32));
+    assert!(checkpoints.is_optimistic(64));
+    assert_eq!(checkpoints.checkpoint(33).unwrap().0, 64);
+    assert!(matches!(
+        checkpoints.checkpoint_finalized(33),
+        Err(CheckpointError::NotPresent),
+    ));
+    assert_eq!(checkpoints.checkpoint_finalized(32).unwrap().0, 32);
+
+    checkpoints.push(128, [4; 32].into());
+    assert_eq!(checkpoints.checkpoint_finalized(33).unwrap().0, 128);
+    assert_eq!(checkpoints.optimistic().count(), 2);
+
+    // the optimistic checkpoints are forgotten after eviction
+    checkpoints.push(160, [5; 32].into());
+    checkpoints.push(192, [6; 32].into());
+    assert_eq!(checkpoints.optimistic().count(), 0);
+}
+
+#[test]
+fn evicted_checkpoints() {
+    let checkpoint = |slot: Slot| (slot, Hash256::from_low_u64_be(slot));
+
+    let mut checkpoints = Checkpoints::new(3);
+    checkpoints.push(checkpoint(32).0, checkpoint(32).1);
+
+    // blocks before the first checkpoint are proven with it
+    assert_eq!(checkpoints.slot_provable_first(), 0);
+
+    // evicted checkpoints are dropped without the archive
+    checkpoints.set_window(0);
+    assert!(matches!(
+        checkpoints.checkpoint(32),
+        Err(CheckpointError::OutDated),
+    ));
+    assert_eq!(checkpoints.unarchived_count(), 0);
+    assert_eq!(checkpoints.slot_provable_first(), 33);
+
+    checkpoints.set_window(3);
+    checkpoints.set_archived(true);
+    for slot in [64, 96, 128, 160, 192] {
+        checkpoints.push(slot, checkpoint(slot).1);
+    }
+    checkpoints.push_optimistic(224, checkpoint(224).1);
+    checkpoints.push(256, checkpoint(256).1);
+
+    // 64, 96, 128 and 160 are evicted
+    assert_eq!(
+        checkpoints.unarchived(10),
+        vec![
+            checkpoint(64),
+            checkpoint(96),
+            checkpoint(128),
+            checkpoint(160)
+        ]
+    );
+    // the checkpoint preceding the first unarchived one isn't known
+    assert!(matches!(
+        checkpoints.checkpoint(63),
+        Err(CheckpointError::OutDated),
+    ));
+    assert_eq!(checkpoints.checkpoint(64), Ok(checkpoint(64)));
+    assert_eq!(checkpoints.checkpoint(100), Ok(checkpoint(128)));
+    assert_eq!(checkpoints.checkpoint(161), Ok(checkpoint(192)));
+    assert_eq!(checkpoints.slot_provable_first(), 64);
+
+    checkpoints.exported(64, 96);
+    assert_eq!(
+        checkpoints.unarchived(10),
+        vec![checkpoint(128), checkpoint(160)]
+    );
+    // the exported checkpoints should be requested from the archive
+    assert!(matches!(
+        checkpoints.checkpoint(96),
+        Err(CheckpointError::OutDated),
+    ));
+    assert_eq!(checkpoints.checkpoint(97), Ok(checkpoint(128)));
+    assert_eq!(checkpoints.slot_provable_first(), 64);
+
+    // the optimistic checkpoint is evicted but not archived
+    checkpoints.set_window(1);
+    assert_eq!(
+        checkpoints.unarchived(10),
+        vec![checkpoint(128), checkpoint(160), checkpoint(192)]
+    );
+    assert_eq!(checkpoints.optimistic().count(), 0);
+    assert_eq!(checkpoints.checkpoints(), vec![checkpoint(256)]);
+}
+
+#[test]
+fn unarchived_checkpoints_are_bounded() {
+    let checkpoint = |slot: Slot| (slot, Hash256::from_low_u64_be(slot));
+
+    let mut checkpoints = Checkpoints::new(1);
+    checkpoints.unarchived_max = 2;
+    checkpoints.set_archived(true);
+    for slot in [32, 64, 96] {
+        checkpoints.push(slot, checkpoint(slot).1);
+    }
+
+    checkpoints.exported(32, 32);
+    assert_eq!(checkpoints.unarchived(10), vec![checkpoint(64)]);
+    assert_eq!(checkpoints.slot_provable_first(), 32);
+
+    // 64 and 96 are dropped without the export
+    for slot in [128, 160, 192] {
+        checkpoints.push(slot, checkpoint(slot).1);
+    }
+    assert_eq!(
+        checkpoints.unarchived(10),
+        vec![checkpoint(128), checkpoint(160)]
+    );
+    // the archive has a gap so the blocks before it can't be proven
+    assert_eq!(checkpoints.slot_provable_first(), 97);
+    assert!(matches!(
+        checkpoints.checkpoint(96),
+        Err(CheckpointError::OutDated),
+    ));
+    assert_eq!(checkpoints.checkpoint(97), Ok(checkpoint(128)));
+
+    // 128 is dropped while being exported
+    let exported = checkpoints.unarchived(10);
+    checkpoints.push(224, checkpoint(224).1);
+    checkpoints.exported(exported[0].0, exported[1].0);
+    assert_eq!(checkpoints.unarchived(10), vec![checkpoint(192)]);
+    assert_eq!(checkpoints.slot_provable_first(), 129);
+    assert!(matches!(
+        checkpoints.checkpoint(160),
+        Err(CheckpointError::OutDated),
+    ));
+    assert_eq!(checkpoints.checkpoint(161), Ok(checkpoint(192)));
+
+    // the dropped checkpoints are forgotten together with the archive
+    checkpoints.set_archived(false);
+    assert_eq!(checkpoints.slot_provable_first(), 193);
+}
+
+#[test]
+fn force_update_takes_best_candidate() {
+    let candidate = |bit_count, slot| Candidate {
+        attested_header: BeaconBlockHeader {
+            slot,
+            proposer_index: 0,
+            parent_root: Default::default(),
+            state_root: Default::default(),
+            body_root: Default::default(),
+        },
+        sync_committee_next: None,
+        participation: Participation {
+            bit_count,
+            signature_slot: slot + 1,
+            attested_slot: slot,
+        },
+    };
+
+    let mut force_update = ForceUpdate::new(10);
+    force_update.offer(candidate(400, 100));
+    force_update.offer(candidate(450, 90));
+    force_update.offer(candidate(420, 110));
+    force_update.offer(candidate(450, 95));
+
+    // the mode is disabled
+    assert!(force_update.take_timed_out(1_000).is_none());
+
+    force_update.config = Some(ForceUpdateConfig {
+        timeout: 100,
+        min_participation: 400,
+    });
+    assert!(force_update.take_timed_out(109).is_none());
+
+    let best = force_update.take_timed_out(110).unwrap();
+    assert_eq!(best.participation.bit_count, 450);
+    assert_eq!(best.attested_header.slot, 95);
+    assert!(force_update.best.is_none());
+
+    force_update.offer(candidate(500, 120));
+    force_update.reset(110);
+    assert!(force_update.best.is_none());
+    assert_eq!(force_update.last_update_block, 110);
+}
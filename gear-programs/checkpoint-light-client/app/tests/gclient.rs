@@ -1,8 +1,11 @@
+use checkpoint_archive_client::traits::{CheckpointArchive as _, CheckpointArchiveFactory as _};
 use checkpoint_light_client::WASM_BINARY;
 use checkpoint_light_client_client::{
     checkpoint_light_client_factory::io as factory_io, traits::*,
 };
-use checkpoint_light_client_io::{Error, Init, ReplayBackError, ReplayBackStatus, G2};
+use checkpoint_light_client_io::{
+    Error, ForceUpdateConfig, Init, ReplayBackError, ReplayBackStatus, G2,
+};
 use ethereum_beacon_client::utils;
 use ethereum_common::{
     base_types::BytesFixed,
@@ -19,7 +22,7 @@ use gclient::{GearApi, Result, WSAddress};
 use ruzstd::StreamingDecoder;
 use sails_rs::{calls::*, gclient::calls::*, prelude::*};
 use sp_core::crypto::DEV_PHRASE;
-use std::io::Read;
+use std::{io::Read, thread, time::Duration};
 use tokio::sync::Mutex;
 
 const SEPOLIA_FINALITY_UPDATE_5_263_072: &[u8; 4_941] =
@@ -127,6 +130,7 @@ fn construct_init(network: Network, update: Update, bootstrap: Bootstrap) -> Ini
             .collect(),
         update: sync_update,
         sync_aggregate_encoded,
+        checkpoints_window: None,
     }
 }
 
@@ -404,6 +408,19 @@ async fn replay_back_and_updating() -> Result<()> {
         "result = {result:?}"
     );
 
+    // the replayed update isn't recorded in the participation history
+    let service_state =
+        checkpoint_light_client_client::ServiceState::new(GClientRemoting::new(api.clone()));
+    let participation = service_state
+        .participation(checkpoint_light_client_client::Order::Direct, 0, 10)
+        .recv(program_id)
+        .await
+        .unwrap();
+    assert!(
+        participation.is_empty(),
+        "participation = {participation:?}"
+    );
+
     // updating
     let mut service =
         checkpoint_light_client_client::ServiceSyncUpdate::new(GClientRemoting::new(api.clone()));
@@ -427,6 +444,7 @@ async fn replay_back_and_updating() -> Result<()> {
             update.finalized_header.slot, update.attested_header.slot, update.signature_slot
         );
 
+        let attested_slot = update.attested_header.slot;
         let (gas_limit, (update, sync_aggregate_encoded)) = {
             let sync_aggregate_encoded = update.sync_aggregate.encode();
             let params = (
@@ -452,9 +470,328 @@ async fn replay_back_and_updating() -> Result<()> {
             "result = {result:?}"
         );
 
+        if result.is_ok() {
+            let participation = service_state
+                .participation(checkpoint_light_client_client::Order::Reverse, 0, 1)
+                .recv(program_id)
+                .await
+                .unwrap();
+            assert_eq!(participation[0].attested_slot, attested_slot);
+        }
+
         println!();
         println!();
     }
 
     Ok(())
 }
+
+async fn replay_back_holesky(api: &GearApi, program_id: ActorId) -> Result<()> {
+    let finality_update: FinalityUpdateResponse =
+        serde_json::from_slice(HOLESKY_FINALITY_UPDATE_3_014_736).unwrap();
+    let finality_update = finality_update.data;
+
+    let mut decoder = StreamingDecoder::new(&HOLESKY_HEADERS[..]).unwrap();
+    let mut headers = Vec::new();
+    decoder.read_to_end(&mut headers).unwrap();
+
+    let headers_all: Vec<BeaconBlockHeaderResponse> = serde_json::from_slice(&headers[..]).unwrap();
+    let size_batch = 30 * SLOTS_PER_EPOCH as usize;
+    let mut service =
+        checkpoint_light_client_client::ServiceReplayBack::new(GClientRemoting::new(api.clone()));
+
+    let sync_aggregate_encoded = finality_update.sync_aggregate.encode();
+    let signature = decode_signature(&finality_update.sync_aggregate);
+    let result = service
+        .start(
+            utils::sync_update_from_finality(signature, finality_update),
+            sync_aggregate_encoded,
+            headers_all
+                .iter()
+                .rev()
+                .take(size_batch)
+                .map(|r| r.data.header.message.clone())
+                .collect(),
+        )
+        .send_recv(program_id)
+        .await
+        .unwrap();
+    assert!(
+        matches!(result, Ok(ReplayBackStatus::InProcess)),
+        "result = {result:?}"
+    );
+
+    let result = service
+        .process(
+            headers_all
+                .iter()
+                .rev()
+                .skip(size_batch)
+                .map(|r| r.data.header.message.clone())
+                .collect(),
+        )
+        .send_recv(program_id)
+        .await
+        .unwrap();
+    assert!(
+        matches!(result, Ok(ReplayBackStatus::Finished)),
+        "result = {result:?}"
+    );
+
+    Ok(())
+}
+
+async fn process_finality_update(
+    api: &GearApi,
+    program_id: ActorId,
+    finality_update: &[u8],
+) -> Result<(), Error> {
+    let finality_update: FinalityUpdateResponse = serde_json::from_slice(finality_update).unwrap();
+    let finality_update = finality_update.data;
+
+    let sync_aggregate_encoded = finality_update.sync_aggregate.encode();
+    let signature = decode_signature(&finality_update.sync_aggregate);
+
+    checkpoint_light_client_client::ServiceSyncUpdate::new(GClientRemoting::new(api.clone()))
+        .process(
+            utils::sync_update_from_finality(signature, finality_update),
+            sync_aggregate_encoded,
+        )
+        .send_recv(program_id)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn forced_update() -> Result<()> {
+    // Slots of the headers from the finality update 3_016_736.
+    const SLOT_FINALIZED: u64 = 3_016_736;
+    const SLOT_ATTESTED: u64 = 3_016_814;
+    // Amount of Gear blocks without finalized updates to accept the optimistic one.
+    const TIMEOUT: u32 = 10;
+
+    let (bootstrap, update) = get_bootstrap_and_update();
+
+    let (api, _admin, code_id, _gas_limit, salt) = connect_to_node().await;
+    let factory = checkpoint_light_client_client::CheckpointLightClientFactory::new(
+        GClientRemoting::new(api.clone()),
+    );
+
+    let init = construct_init(Network::Holesky, update, bootstrap);
+    let gas_limit = calculate_upload_gas(&api, code_id, &init).await?;
+    let program_id = factory
+        .init(init)
+        .with_gas_limit(gas_limit)
+        .send_recv(code_id, salt)
+        .await
+        .unwrap();
+
+    replay_back_holesky(&api, program_id).await?;
+
+    let mut force_update =
+        checkpoint_light_client_client::ServiceForceUpdate::new(GClientRemoting::new(api.clone()));
+    force_update
+        .enable(ForceUpdateConfig {
+            timeout: TIMEOUT,
+            min_participation: 342,
+        })
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    // the update doesn't advance finality so its attested header is kept until the timeout
+    let result = process_finality_update(&api, program_id, HOLESKY_FINALITY_UPDATE_3_014_736).await;
+    assert!(matches!(result, Ok(())), "result = {result:?}");
+
+    let best_update = force_update.best_update().recv(program_id).await.unwrap();
+    assert_eq!(
+        best_update.map(|participation| participation.attested_slot),
+        Some(SLOT_ATTESTED)
+    );
+    assert!(!force_update.is_optimistic().recv(program_id).await.unwrap());
+
+    let last_update_block = force_update
+        .last_update_block()
+        .recv(program_id)
+        .await
+        .unwrap();
+    while api.last_block_number().await? < last_update_block + TIMEOUT {
+        thread::sleep(Duration::from_secs(1));
+    }
+
+    // the timeout has elapsed so the kept update is accepted
+    let result = process_finality_update(&api, program_id, HOLESKY_FINALITY_UPDATE_3_014_736).await;
+    assert!(matches!(result, Ok(())), "result = {result:?}");
+
+    assert!(force_update.is_optimistic().recv(program_id).await.unwrap());
+    let optimistic = force_update
+        .optimistic_checkpoints()
+        .recv(program_id)
+        .await
+        .unwrap();
+    assert_eq!(optimistic, vec![SLOT_ATTESTED]);
+
+    let checkpoint_for = checkpoint_light_client_client::ServiceCheckpointFor::new(
+        GClientRemoting::new(api.clone()),
+    );
+    let result = checkpoint_for
+        .get(SLOT_ATTESTED)
+        .recv(program_id)
+        .await
+        .unwrap();
+    assert!(
+        matches!(result, Ok((slot, _)) if slot == SLOT_ATTESTED),
+        "result = {result:?}"
+    );
+
+    // the optimistic checkpoint is ignored by the finalized-only requests
+    let result = checkpoint_for
+        .get_finalized(SLOT_ATTESTED)
+        .recv(program_id)
+        .await
+        .unwrap();
+    assert!(result.is_err(), "result = {result:?}");
+
+    let result = checkpoint_for
+        .get_finalized(SLOT_FINALIZED)
+        .recv(program_id)
+        .await
+        .unwrap();
+    assert!(
+        matches!(result, Ok((slot, _)) if slot == SLOT_FINALIZED),
+        "result = {result:?}"
+    );
+
+    // finalized updates behind the optimistic header are refused
+    force_update.disable().send_recv(program_id).await.unwrap();
+
+    for finality_update in [
+        HOLESKY_FINALITY_UPDATE_3_014_768,
+        HOLESKY_FINALITY_UPDATE_3_014_799,
+    ] {
+        let result = process_finality_update(&api, program_id, finality_update).await;
+        assert!(
+            matches!(result, Err(Error::NotActual | Error::LowVoteCount)),
+            "result = {result:?}"
+        );
+    }
+
+    let service_state =
+        checkpoint_light_client_client::ServiceState::new(GClientRemoting::new(api.clone()));
+    let state = service_state
+        .get(checkpoint_light_client_client::Order::Reverse, 0, 1)
+        .recv(program_id)
+        .await
+        .unwrap();
+    assert_eq!(state.checkpoints[0].0, SLOT_ATTESTED);
+    assert_eq!(state.optimistic, vec![SLOT_ATTESTED]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn archived_checkpoints() -> Result<()> {
+    use checkpoint_light_client_client::service_checkpoint_for::io as checkpoint_for_io;
+
+    let (bootstrap, update) = get_bootstrap_and_update();
+
+    let (api, _admin, code_id, gas_limit, salt) = connect_to_node().await;
+    let factory = checkpoint_light_client_client::CheckpointLightClientFactory::new(
+        GClientRemoting::new(api.clone()),
+    );
+
+    let init = construct_init(Network::Holesky, update, bootstrap);
+    let gas_limit_upload = calculate_upload_gas(&api, code_id, &init).await?;
+    let program_id = factory
+        .init(init)
+        .with_gas_limit(gas_limit_upload)
+        .send_recv(code_id, salt)
+        .await
+        .unwrap();
+
+    replay_back_holesky(&api, program_id).await?;
+
+    let (code_id_archive, _) = api.upload_code(checkpoint_archive::WASM_BINARY).await?;
+    let archive_id =
+        checkpoint_archive_client::CheckpointArchiveFactory::new(GClientRemoting::new(api.clone()))
+            .new(program_id)
+            .send_recv(code_id_archive, salt)
+            .await
+            .unwrap();
+
+    let mut service_archive =
+        checkpoint_light_client_client::ServiceArchive::new(GClientRemoting::new(api.clone()));
+    service_archive
+        .set_address(Some(archive_id))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+    service_archive
+        .set_window(1)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    let unarchived_count = service_archive
+        .unarchived_count()
+        .recv(program_id)
+        .await
+        .unwrap();
+    assert!(unarchived_count > 0);
+
+    let exported = service_archive
+        .export(unarchived_count)
+        .with_gas_limit(gas_limit / 100 * 95)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+    assert_eq!(exported, unarchived_count);
+    assert_eq!(
+        service_archive
+            .unarchived_count()
+            .recv(program_id)
+            .await
+            .unwrap(),
+        0
+    );
+
+    let archive =
+        checkpoint_archive_client::CheckpointArchive::new(GClientRemoting::new(api.clone()));
+    let checkpoints = archive.checkpoints(0, 2).recv(archive_id).await.unwrap();
+    assert_eq!(
+        archive.count().recv(archive_id).await.unwrap(),
+        unarchived_count
+    );
+
+    let checkpoint_for = checkpoint_light_client_client::ServiceCheckpointFor::new(
+        GClientRemoting::new(api.clone()),
+    );
+    let slot_first = checkpoint_for
+        .first_provable_slot()
+        .recv(program_id)
+        .await
+        .unwrap();
+    assert_eq!(slot_first, checkpoints[0].0);
+
+    // the light client can't request the archive in a query so the message is sent
+    for (slot, expected) in [
+        (checkpoints[0].0, checkpoints[0]),
+        (checkpoints[0].0 + 1, checkpoints[1]),
+    ] {
+        let mut listener = api.subscribe().await?;
+        let (message_id, _) = api
+            .send_message_bytes(
+                program_id,
+                checkpoint_for_io::Get::encode_call(&slot),
+                gas_limit / 100 * 95,
+                0,
+            )
+            .await?;
+        let (_, reply, _) = listener.reply_bytes_on(message_id).await?;
+        let result = checkpoint_for_io::Get::decode_reply(reply.unwrap()).unwrap();
+
+        assert_eq!(result, Ok(expected));
+    }
+
+    Ok(())
+}
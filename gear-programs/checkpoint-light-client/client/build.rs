@@ -18,9 +18,18 @@ fn main() {
             "ethereum_common::beacon::light::ExecutionPayload",
         )
         .with_external_type("Init", "checkpoint_light_client_io::Init")
+        .with_external_type("Network", "ethereum_common::network::Network")
+        .with_external_type("CustomNetwork", "ethereum_common::network::CustomNetwork")
+        .with_external_type("Fork", "ethereum_common::network::Fork")
+        .with_external_type("ForkName", "ethereum_common::network::ForkName")
         .with_external_type("Update", "checkpoint_light_client_io::Update")
         .with_external_type("Error", "checkpoint_light_client_io::Error")
         .with_external_type("ReplayBack", "checkpoint_light_client_io::ReplayBack")
+        .with_external_type("Participation", "checkpoint_light_client_io::Participation")
+        .with_external_type(
+            "ForceUpdateConfig",
+            "checkpoint_light_client_io::ForceUpdateConfig",
+        )
         .with_external_type(
             "ReplayBackError",
             "checkpoint_light_client_io::ReplayBackError",
@@ -66,6 +66,43 @@ pub struct Update {
     pub finality_branch: Vec<[u8; 32]>,
 }
 
+/// Sync committee participation in the accepted update.
+#[derive(Clone, Copy, Debug, Decode, Encode, PartialEq, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct Participation {
+    /// Amount of the sync committee members that signed the update.
+    pub bit_count: u16,
+    pub signature_slot: Slot,
+    pub attested_slot: Slot,
+}
+
+/// Parameters of the forced update mode. If Ethereum doesn't finalize for `timeout`
+/// blocks, the program accepts the best-participating non-finalized update as an
+/// optimistic checkpoint. It follows `process_light_client_store_force_update` of
+/// the Altair light client specification.
+#[derive(Clone, Copy, Debug, Decode, Encode, PartialEq, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct ForceUpdateConfig {
+    /// Amount of Gear blocks without finalized updates after which the best
+    /// non-finalized update is accepted.
+    pub timeout: u32,
+    /// Minimal amount of the sync committee members that should sign a non-finalized
+    /// update. Shouldn't be less than the supermajority of the committee.
+    pub min_participation: u16,
+}
+
+impl ForceUpdateConfig {
+    pub fn is_valid(&self) -> bool {
+        let min_participation = self.min_participation as usize;
+
+        self.timeout > 0
+            && min_participation * 3 >= SYNC_COMMITTEE_SIZE * 2
+            && min_participation <= SYNC_COMMITTEE_SIZE
+    }
+}
+
 #[derive(Clone, Debug, Decode, Encode, TypeInfo)]
 #[codec(crate = sails_rs::scale_codec)]
 #[scale_info(crate = sails_rs::scale_info)]
@@ -76,6 +113,8 @@ pub struct Init {
     pub sync_committee_current_branch: Vec<[u8; 32]>,
     pub update: Update,
     pub sync_aggregate_encoded: Vec<u8>,
+    /// Maximum amount of the stored checkpoints. The default window is used if `None`.
+    pub checkpoints_window: Option<u32>,
 }
 
 #[derive(Clone, Debug, Decode, Encode, TypeInfo)]
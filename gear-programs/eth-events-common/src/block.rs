@@ -0,0 +1,189 @@
+//! Block inclusion proofs generic over the fork-specific beacon block body.
+//!
+//! To support a new fork, add it to the fork schedule in `ethereum_common::network`. If the
+//! fork changes the layout of the block body, add the lightened body type to
+//! `ethereum_common::beacon::light`, implement [ForkBlockBody] for it and add a program
+//! that includes `lib-template.rs` with the new body (see `eth-events-electra`). Otherwise
+//! just list the fork in [ForkBlockBody::FORKS] of the existing body.
+
+use ethereum_common::{
+    beacon::{
+        light::{self, BlockGeneric},
+        BlockHeader as BeaconBlockHeader,
+    },
+    network::{ForkName, Network},
+    tree_hash::TreeHash,
+    H256,
+};
+use sails_rs::prelude::*;
+
+use super::{Error, State};
+
+/// Lightened beacon block body of the specific forks.
+pub trait ForkBlockBody: TreeHash {
+    /// Forks which beacon blocks have this body.
+    const FORKS: &'static [ForkName];
+
+    fn receipts_root(&self) -> H256;
+
+    fn block_number(&self) -> u64;
+}
+
+impl ForkBlockBody for light::BlockBody {
+    const FORKS: &'static [ForkName] = &[ForkName::Deneb];
+
+    fn receipts_root(&self) -> H256 {
+        H256::from(self.execution_payload.receipts_root.0 .0)
+    }
+
+    fn block_number(&self) -> u64 {
+        self.execution_payload.block_number
+    }
+}
+
+impl ForkBlockBody for light::electra::BlockBody {
+    // Fulu doesn't change the block body.
+    const FORKS: &'static [ForkName] = &[ForkName::Electra, ForkName::Fulu];
+
+    fn receipts_root(&self) -> H256 {
+        H256::from(self.execution_payload.receipts_root.0 .0)
+    }
+
+    fn block_number(&self) -> u64 {
+        self.execution_payload.block_number
+    }
+}
+
+/// Whether the block at `slot` belongs to one of [ForkBlockBody::FORKS] of the `Body`
+/// according to the fork schedule of the `network`.
+pub fn is_supported<Body: ForkBlockBody>(network: &Network, slot: u64) -> bool {
+    Body::FORKS.contains(&network.fork_at(slot).name)
+}
+
+/// Check that the block at `slot` has the `Body` according to the fork schedule of the
+/// light client network. The network is requested from the light client once.
+pub async fn check_fork<Body: ForkBlockBody>(
+    state: &cell::RefCell<State>,
+    slot: u64,
+) -> Result<(), Error> {
+    let network = state.borrow().network.clone();
+    let network = match network {
+        Some(network) => network,
+        None => {
+            let checkpoint_light_client_address = state.borrow().checkpoint_light_client_address;
+            let network = super::request_network(checkpoint_light_client_address).await?;
+            state.borrow_mut().network = Some(network.clone());
+
+            network
+        }
+    };
+
+    match is_supported::<Body>(&network, slot) {
+        true => Ok(()),
+        false => Err(Error::UnsupportedFork),
+    }
+}
+
+impl super::Proofs {
+    /// Construct proofs of the transaction receipt included into the beacon `block`
+    /// to be checked against the checkpoints of the light client from `state`.
+    pub fn for_block<Body: ForkBlockBody>(
+        state: &State,
+        block: &BlockGeneric<Body>,
+        headers: Vec<BeaconBlockHeader>,
+        proof: Vec<Vec<u8>>,
+        transaction_index: u64,
+        receipt_rlp: Vec<u8>,
+    ) -> Self {
+        Self {
+            checkpoint_light_client_address: state.checkpoint_light_client_address,
+            finalized_only: state.finalized_only,
+            slot: block.slot,
+            block_root: block.tree_hash_root(),
+            receipts_root: block.body.receipts_root(),
+            block_number: block.body.block_number(),
+            headers,
+            proof,
+            transaction_index,
+            receipt_rlp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Proofs;
+    use ethereum_common::{
+        beacon::{Block, BlockBody},
+        SLOTS_PER_EPOCH,
+    };
+    use hex_literal::hex;
+
+    const ETHEREUM_9_230_177: &[u8; 133_287] =
+        include_bytes!("../../../ethereum-common/src/beacon/chain-data/ethereum-9_230_177.json");
+
+    #[test]
+    fn forks_of_block_bodies() {
+        let network = Network::Mainnet;
+        let slot_deneb = 269_568 * SLOTS_PER_EPOCH;
+        let slot_electra = 364_032 * SLOTS_PER_EPOCH;
+        let slot_fulu = 411_392 * SLOTS_PER_EPOCH;
+
+        assert!(is_supported::<light::BlockBody>(&network, slot_deneb));
+        assert!(is_supported::<light::BlockBody>(&network, slot_electra - 1));
+        assert!(!is_supported::<light::BlockBody>(&network, slot_electra));
+        assert!(!is_supported::<light::BlockBody>(&network, slot_fulu));
+
+        assert!(!is_supported::<light::electra::BlockBody>(
+            &network, slot_deneb
+        ));
+        assert!(is_supported::<light::electra::BlockBody>(
+            &network,
+            slot_electra
+        ));
+        assert!(is_supported::<light::electra::BlockBody>(
+            &network, slot_fulu
+        ));
+    }
+
+    #[test]
+    fn proofs_for_block() {
+        // https://beaconcha.in/slot/9230177
+        // https://etherscan.io/block/20025266
+        let body: BlockBody = serde_json::from_slice(ETHEREUM_9_230_177.as_ref()).unwrap();
+        let receipts_root = H256::from(body.execution_payload.receipts_root.0 .0);
+        let block: light::Block = Block {
+            slot: 9_230_177,
+            proposer_index: 404_728,
+            parent_root: hex!("c00156add0e86e806b98c9a6367942d451770febb9a3dfcc79f6364863b749fa")
+                .into(),
+            state_root: hex!("01556da441fbe34b5c60c5192790358456228733f3ee874c32920a400771d1c1")
+                .into(),
+            body,
+        }
+        .into();
+
+        let state = State {
+            checkpoint_light_client_address: ActorId::from(1),
+            finalized_only: true,
+            network: None,
+        };
+
+        let proofs = Proofs::for_block(&state, &block, vec![], vec![vec![1]], 5, vec![2]);
+
+        assert_eq!(proofs.checkpoint_light_client_address, ActorId::from(1));
+        assert!(proofs.finalized_only);
+        assert_eq!(proofs.slot, 9_230_177);
+        // root of the lightened block equals to the root of the full one
+        assert_eq!(
+            proofs.block_root,
+            hex!("8a71cd9567f3ef85fc5e0dae0fcc6acd707f87b63e9a3174d4ec80395ab31763").into()
+        );
+        assert_eq!(proofs.receipts_root, receipts_root);
+        assert_eq!(proofs.block_number, 20_025_266);
+        assert_eq!(proofs.proof, vec![vec![1]]);
+        assert_eq!(proofs.transaction_index, 5);
+        assert_eq!(proofs.receipt_rlp, vec![2]);
+    }
+}
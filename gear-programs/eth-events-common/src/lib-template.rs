@@ -1,15 +1,33 @@
 use cell::RefCell;
-use eth_events_common::{CheckedProofs, Error, Proofs, State};
-use ethereum_common::{beacon::BlockHeader as BeaconBlockHeader, tree_hash::TreeHash, H256};
+use eth_events_common::{check_fork, CheckedProofs, Error, ForkBlockBody, Proofs, State};
+use ethereum_common::{
+    beacon::{light::BlockGeneric, BlockHeader as BeaconBlockHeader},
+    network::ForkName,
+};
 use sails_rs::prelude::*;
 
+type LightBeaconBlock = BlockGeneric<LightBeaconBlockBody>;
+
 pub struct Program(RefCell<State>);
 
 #[sails_rs::program]
 impl Program {
+    /// The program checks proofs only against finalized checkpoints of the light client.
     pub fn new(checkpoint_light_client_address: ActorId) -> Self {
         Self(RefCell::new(State {
             checkpoint_light_client_address,
+            finalized_only: true,
+            network: None,
+        }))
+    }
+
+    /// The program also accepts optimistic checkpoints of the light client, i.e. the ones
+    /// accepted by the forced update during long non-finality.
+    pub fn new_accepting_optimistic(checkpoint_light_client_address: ActorId) -> Self {
+        Self(RefCell::new(State {
+            checkpoint_light_client_address,
+            finalized_only: false,
+            network: None,
         }))
     }
 
@@ -50,6 +68,16 @@ impl<'a> Service<'a> {
         self.state.borrow().checkpoint_light_client_address
     }
 
+    /// Whether proofs are checked only against finalized checkpoints.
+    pub fn finalized_only(&self) -> bool {
+        self.state.borrow().finalized_only
+    }
+
+    /// Forks which blocks are accepted by the program.
+    pub fn forks(&self) -> Vec<ForkName> {
+        LightBeaconBlockBody::FORKS.to_vec()
+    }
+
     pub async fn check_proofs(&mut self, message: EthToVaraEvent) -> Result<CheckedProofs, Error> {
         let EthToVaraEvent {
             proof_block: BlockInclusionProof { block, headers },
@@ -58,18 +86,17 @@ impl<'a> Service<'a> {
             receipt_rlp,
         } = message;
 
-        Proofs {
-            checkpoint_light_client_address: self.checkpoint_light_client_address(),
-            slot: block.slot,
-            block_root: block.tree_hash_root(),
-            receipts_root: H256::from(block.body.execution_payload.receipts_root.0 .0),
-            block_number: block.body.execution_payload.block_number,
+        check_fork::<LightBeaconBlockBody>(self.state, block.slot).await?;
+
+        let proofs = Proofs::for_block(
+            &self.state.borrow(),
+            &block,
             headers,
             proof,
             transaction_index,
             receipt_rlp,
-        }
-        .check()
-        .await
+        );
+
+        proofs.check().await
     }
 }
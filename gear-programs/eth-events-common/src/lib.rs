@@ -1,9 +1,13 @@
 #![no_std]
 
-use checkpoint_light_client_client::{traits::ServiceCheckpointFor as _, ServiceCheckpointFor};
+use checkpoint_light_client_client::{
+    traits::{ServiceCheckpointFor as _, ServiceState as _},
+    ServiceCheckpointFor, ServiceState,
+};
 use ethereum_common::{
     beacon::BlockHeader as BeaconBlockHeader,
     hash_db, memory_db,
+    network::Network,
     patricia_trie::TrieDB,
     tree_hash::TreeHash,
     trie_db::{HashDB, Trie},
@@ -13,6 +17,10 @@ use ethereum_common::{
 use ops::ControlFlow::*;
 use sails_rs::{calls::*, gstd::calls::GStdRemoting, prelude::*};
 
+pub mod block;
+
+pub use block::{check_fork, ForkBlockBody};
+
 #[derive(Clone, Debug, Encode, Decode, TypeInfo)]
 #[codec(crate = sails_rs::scale_codec)]
 #[scale_info(crate = sails_rs::scale_info)]
@@ -26,10 +34,16 @@ pub enum Error {
     InvalidBlockProof,
     TrieDbFailure,
     InvalidReceiptProof,
+    UnsupportedFork,
 }
 
 pub struct State {
     pub checkpoint_light_client_address: ActorId,
+    /// Refuse the checkpoints accepted by the forced update of the light client,
+    /// i.e. check proofs only against finalized checkpoints.
+    pub finalized_only: bool,
+    /// Network of the light client. Requested on the first check of the proofs.
+    pub network: Option<Network>,
 }
 
 #[derive(Clone, Debug, Encode, Decode, TypeInfo)]
@@ -45,6 +59,7 @@ pub struct CheckedProofs {
 #[derive(Clone, Debug)]
 pub struct Proofs {
     pub checkpoint_light_client_address: ActorId,
+    pub finalized_only: bool,
     pub slot: u64,
     pub block_root: H256,
     pub receipts_root: H256,
@@ -60,6 +75,7 @@ impl Proofs {
     pub async fn check(self) -> Result<CheckedProofs, Error> {
         let Proofs {
             checkpoint_light_client_address,
+            finalized_only,
             slot,
             block_root,
             receipts_root,
@@ -73,7 +89,8 @@ impl Proofs {
         let receipt = decode_and_check_receipt(&receipt_rlp)?;
 
         // verify the proof of block inclusion
-        let checkpoint = request_checkpoint(checkpoint_light_client_address, slot).await?;
+        let checkpoint =
+            request_checkpoint(checkpoint_light_client_address, slot, finalized_only).await?;
 
         headers.sort_unstable_by(|a, b| a.slot.cmp(&b.slot));
         let Continue(block_root_parent) =
@@ -133,16 +150,35 @@ fn decode_and_check_receipt(receipt_rlp: &[u8]) -> Result<ReceiptEnvelope, Error
 async fn request_checkpoint(
     checkpoint_light_client_address: ActorId,
     slot: u64,
+    finalized_only: bool,
 ) -> Result<H256, Error> {
     let service = ServiceCheckpointFor::new(GStdRemoting);
-    let result = service
-        .get(slot)
-        .recv(checkpoint_light_client_address)
-        .await
-        .map_err(|_| Error::SendFailure)?;
+    let result = match finalized_only {
+        true => {
+            service
+                .get_finalized(slot)
+                .recv(checkpoint_light_client_address)
+                .await
+        }
+        false => {
+            service
+                .get(slot)
+                .recv(checkpoint_light_client_address)
+                .await
+        }
+    }
+    .map_err(|_| Error::SendFailure)?;
 
     match result {
         Ok((_slot, hash)) => Ok(hash),
         Err(_) => Err(Error::MissingCheckpoint),
     }
 }
+
+async fn request_network(checkpoint_light_client_address: ActorId) -> Result<Network, Error> {
+    ServiceState::new(GStdRemoting)
+        .network()
+        .recv(checkpoint_light_client_address)
+        .await
+        .map_err(|_| Error::SendFailure)
+}
@@ -1,5 +1,5 @@
 #![no_std]
 
-use ethereum_common::beacon::light::Block as LightBeaconBlock;
+use ethereum_common::beacon::light::BlockBody as LightBeaconBlockBody;
 
 include!("../../../eth-events-common/src/lib-template.rs");
@@ -1,4 +1,5 @@
 #![no_std]
-use ethereum_common::beacon::light::electra::Block as LightBeaconBlock;
+
+use ethereum_common::beacon::light::electra::BlockBody as LightBeaconBlockBody;
 
 include!("../../../eth-events-common/src/lib-template.rs");
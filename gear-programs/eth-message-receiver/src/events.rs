@@ -0,0 +1,35 @@
+//! Typed extraction of the events from Ethereum transaction receipts.
+
+use alloy_rlp::Decodable;
+use alloy_sol_types::SolEvent;
+use ethereum_common::utils::ReceiptEnvelope;
+use sails_rs::prelude::*;
+
+use super::Error;
+
+/// Decode RLP-encoded transaction receipt and check that the transaction has succeeded.
+pub fn decode_receipt(receipt_rlp: &[u8]) -> Result<ReceiptEnvelope, Error> {
+    let receipt = ReceiptEnvelope::decode(&mut &receipt_rlp[..])
+        .map_err(|_| Error::DecodeReceiptEnvelopeFailure)?;
+
+    if !receipt.is_success() {
+        return Err(Error::FailedEthTransaction);
+    }
+
+    Ok(receipt)
+}
+
+/// Extract all the events of type `E` emitted by the contract at the `emitter` address
+/// from the RLP-encoded transaction receipt.
+///
+/// Logs of other contracts and other events are skipped.
+pub fn extract_events<E: SolEvent>(receipt_rlp: &[u8], emitter: H160) -> Result<Vec<E>, Error> {
+    let receipt = decode_receipt(receipt_rlp)?;
+
+    Ok(receipt
+        .logs()
+        .iter()
+        .filter(|log| H160::from(log.address.0 .0) == emitter)
+        .filter_map(|log| E::decode_log_data(log, true).ok())
+        .collect())
+}
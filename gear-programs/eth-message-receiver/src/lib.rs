@@ -0,0 +1,41 @@
+//! SDK for the Gear programs receiving events from Ethereum through `historical-proxy`.
+//!
+//! `historical-proxy` calls the receiver program with `(slot, transaction_index, receipt_rlp)`
+//! after the receipt is verified by the corresponding `eth-events-*` program. The receiver
+//! should check that the call comes from the trusted proxy, that the event is emitted by
+//! the trusted contract and that the transaction isn't processed yet. [`ReceiverState`]
+//! does all of this, see also [`service::Service`] exposing its state.
+//!
+//! Messages sent through the `VaraMessenger` contract can be decoded with [`decode_messages`].
+
+#![no_std]
+
+use sails_rs::prelude::*;
+
+pub mod events;
+pub mod messages;
+pub mod replay;
+pub mod service;
+
+pub use events::extract_events;
+pub use messages::{abi, decode_messages, decode_messages_for, EthMessage};
+pub use replay::ProcessedTransactions;
+pub use service::ReceiverState;
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub enum Error {
+    /// Failed to decode receipt from RLP.
+    DecodeReceiptEnvelopeFailure,
+    /// Ethereum transaction has failed so its logs are invalid.
+    FailedEthTransaction,
+    /// There's no expected event for the receiver in the receipt.
+    MessageNotFound,
+    /// Receipt can only be submitted by `historical-proxy` program.
+    NotHistoricalProxy,
+    /// Ethereum transaction is too old and already have been removed from storage.
+    TransactionTooOld,
+    /// Ethereum transaction was already processed.
+    AlreadyProcessed,
+}
@@ -0,0 +1,171 @@
+//! Messages sent from Ethereum to Gear programs through the `VaraMessenger` contract.
+
+use sails_rs::prelude::*;
+
+use super::{events, Error};
+
+pub mod abi {
+    alloy_sol_types::sol!(
+        #[allow(missing_docs)]
+        IVaraMessenger,
+        "../../api/ethereum/IVaraMessenger.json"
+    );
+}
+
+/// Message sent through the `VaraMessenger` contract.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+pub struct EthMessage {
+    /// Account or contract that has sent the message on Ethereum.
+    pub sender: H160,
+    /// Program that should receive the message on Gear.
+    pub destination: ActorId,
+    /// Nonce assigned by the `VaraMessenger` contract.
+    pub nonce: U256,
+    /// Payload of the message.
+    pub payload: Vec<u8>,
+}
+
+impl From<abi::IVaraMessenger::MessageToVaraSent> for EthMessage {
+    fn from(event: abi::IVaraMessenger::MessageToVaraSent) -> Self {
+        Self {
+            sender: H160::from(event.from.0 .0),
+            destination: ActorId::from(event.destination.0),
+            nonce: U256::from_little_endian(event.nonce.as_le_slice()),
+            payload: event.payload.to_vec(),
+        }
+    }
+}
+
+/// Decode all the messages emitted by the `VaraMessenger` contract at the `messenger` address
+/// from the RLP-encoded transaction receipt.
+pub fn decode_messages(receipt_rlp: &[u8], messenger: H160) -> Result<Vec<EthMessage>, Error> {
+    Ok(
+        events::extract_events::<abi::IVaraMessenger::MessageToVaraSent>(receipt_rlp, messenger)?
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    )
+}
+
+/// Decode the messages emitted by the `VaraMessenger` contract at the `messenger` address
+/// and destined to the `destination` program, usually the current one.
+///
+/// Returns [`Error::MessageNotFound`] if there're no such messages in the receipt.
+pub fn decode_messages_for(
+    receipt_rlp: &[u8],
+    messenger: H160,
+    destination: ActorId,
+) -> Result<Vec<EthMessage>, Error> {
+    let messages: Vec<_> = decode_messages(receipt_rlp, messenger)?
+        .into_iter()
+        .filter(|message| message.destination == destination)
+        .collect();
+
+    if messages.is_empty() {
+        return Err(Error::MessageNotFound);
+    }
+
+    Ok(messages)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloy_consensus::{Receipt, ReceiptWithBloom};
+    use alloy_primitives::Log;
+    use ethereum_common::utils::ReceiptEnvelope;
+
+    pub(crate) const MESSENGER: H160 = H160([1; 20]);
+    const SENDER: H160 = H160([2; 20]);
+
+    pub(crate) fn message_log(
+        address: H160,
+        destination: ActorId,
+        nonce: u64,
+        payload: &[u8],
+    ) -> Log {
+        let event = abi::IVaraMessenger::MessageToVaraSent {
+            from: SENDER.0.into(),
+            destination: destination.into_bytes().into(),
+            nonce: alloy_primitives::U256::from(nonce),
+            payload: payload.to_vec().into(),
+        };
+
+        Log {
+            address: address.0.into(),
+            data: Into::into(&event),
+        }
+    }
+
+    pub(crate) fn receipt_rlp(status: bool, logs: Vec<Log>) -> Vec<u8> {
+        let receipt = ReceiptEnvelope::Eip1559(ReceiptWithBloom::from(Receipt {
+            status: status.into(),
+            cumulative_gas_used: 100_000u128,
+            logs,
+        }));
+
+        let mut receipt_rlp = vec![];
+        alloy_rlp::Encodable::encode(&receipt, &mut receipt_rlp);
+
+        receipt_rlp
+    }
+
+    #[test]
+    fn decodes_messages_for_destination() {
+        let destination = ActorId::from(10);
+        let other = ActorId::from(11);
+
+        let receipt_rlp = receipt_rlp(
+            true,
+            vec![
+                message_log(MESSENGER, destination, 0, b"first"),
+                message_log(MESSENGER, other, 1, b"other"),
+                message_log(H160([3; 20]), destination, 2, b"untrusted"),
+                message_log(MESSENGER, destination, 3, b"second"),
+            ],
+        );
+
+        let messages = decode_messages_for(&receipt_rlp, MESSENGER, destination).unwrap();
+        assert_eq!(
+            messages,
+            vec![
+                EthMessage {
+                    sender: SENDER,
+                    destination,
+                    nonce: 0.into(),
+                    payload: b"first".to_vec(),
+                },
+                EthMessage {
+                    sender: SENDER,
+                    destination,
+                    nonce: 3.into(),
+                    payload: b"second".to_vec(),
+                },
+            ]
+        );
+
+        assert_eq!(
+            decode_messages_for(&receipt_rlp, MESSENGER, ActorId::from(12)),
+            Err(Error::MessageNotFound)
+        );
+    }
+
+    #[test]
+    fn rejects_failed_transaction() {
+        let receipt_rlp = receipt_rlp(
+            false,
+            vec![message_log(MESSENGER, ActorId::from(10), 0, b"payload")],
+        );
+
+        assert_eq!(
+            decode_messages(&receipt_rlp, MESSENGER),
+            Err(Error::FailedEthTransaction)
+        );
+        assert_eq!(
+            decode_messages(b"garbage", MESSENGER),
+            Err(Error::DecodeReceiptEnvelopeFailure)
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Replay protection of the processed Ethereum transactions.
+
+use collections::BTreeSet;
+use sails_rs::prelude::*;
+
+use super::Error;
+
+/// Bounded storage of the processed Ethereum transactions identified by
+/// `(slot, transaction_index)`.
+///
+/// When the storage is full, the oldest transaction is evicted and all the transactions
+/// older than the remaining ones are rejected as [too old](Error::TransactionTooOld).
+#[derive(Debug)]
+pub struct ProcessedTransactions {
+    capacity: usize,
+    transactions: BTreeSet<(u64, u64)>,
+}
+
+impl ProcessedTransactions {
+    /// Create storage keeping at most `capacity` transactions.
+    ///
+    /// Will panic if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "Capacity should be non-zero");
+
+        Self {
+            capacity,
+            transactions: BTreeSet::new(),
+        }
+    }
+
+    /// Check that the transaction can be processed.
+    pub fn check(&self, slot: u64, transaction_index: u64) -> Result<(), Error> {
+        let key = (slot, transaction_index);
+        if self.transactions.contains(&key) {
+            return Err(Error::AlreadyProcessed);
+        }
+
+        if self.transactions.len() >= self.capacity
+            && self
+                .transactions
+                .first()
+                .map(|first| &key < first)
+                .unwrap_or(false)
+        {
+            return Err(Error::TransactionTooOld);
+        }
+
+        Ok(())
+    }
+
+    /// Mark the transaction as processed, evicting the oldest one if the storage is full.
+    pub fn insert(&mut self, slot: u64, transaction_index: u64) -> Result<(), Error> {
+        self.check(slot, transaction_index)?;
+
+        if self.transactions.len() >= self.capacity {
+            self.transactions.pop_first();
+        }
+
+        self.transactions.insert((slot, transaction_index));
+
+        Ok(())
+    }
+
+    /// Check whether the transaction is processed and still stored.
+    pub fn contains(&self, slot: u64, transaction_index: u64) -> bool {
+        self.transactions.contains(&(slot, transaction_index))
+    }
+
+    /// Get amount of the stored transactions.
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// Get maximum amount of the stored transactions.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_oldest_transactions() {
+        let mut transactions = ProcessedTransactions::new(2);
+
+        transactions.insert(10, 1).unwrap();
+        assert_eq!(transactions.insert(10, 1), Err(Error::AlreadyProcessed));
+
+        transactions.insert(5, 0).unwrap();
+        transactions.insert(11, 0).unwrap();
+        assert_eq!(transactions.len(), 2);
+        assert!(!transactions.contains(5, 0));
+
+        assert_eq!(transactions.check(5, 0), Err(Error::TransactionTooOld));
+        assert_eq!(transactions.check(10, 2), Ok(()));
+    }
+}
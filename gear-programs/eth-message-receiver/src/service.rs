@@ -0,0 +1,234 @@
+//! State and service of the program receiving events from Ethereum.
+//!
+//! Program embeds [ReceiverState] into its own state, calls [ReceiverState::accept] from
+//! the method that `historical-proxy` is configured to call and exposes [Service] to
+//! manage the receiver:
+//!
+//! ```ignore
+//! pub struct Program(RefCell<ReceiverState>);
+//!
+//! #[program]
+//! impl Program {
+//!     pub fn receiver(&self) -> Service<GStdExecContext> {
+//!         Service::new(&self.0, GStdExecContext::new())
+//!     }
+//! }
+//! ```
+
+use alloy_sol_types::SolEvent;
+use cell::RefCell;
+use sails_rs::{gstd::ExecContext, prelude::*};
+
+use super::{events, messages, Error, EthMessage, ProcessedTransactions};
+
+/// State of the program receiving events from Ethereum.
+#[derive(Debug)]
+pub struct ReceiverState {
+    /// Account allowed to change the receiver configuration.
+    pub admin: ActorId,
+    /// Address of the `historical-proxy` program. Receipts are accepted only from it.
+    pub historical_proxy_address: ActorId,
+    /// Address of the contract on Ethereum which events are accepted.
+    pub emitter_address: H160,
+    /// Transactions that were already processed.
+    pub transactions: ProcessedTransactions,
+}
+
+impl ReceiverState {
+    pub fn new(
+        admin: ActorId,
+        historical_proxy_address: ActorId,
+        emitter_address: H160,
+        capacity: usize,
+    ) -> Self {
+        Self {
+            admin,
+            historical_proxy_address,
+            emitter_address,
+            transactions: ProcessedTransactions::new(capacity),
+        }
+    }
+
+    /// Accept receipt submitted by `source` and extract the events of type `E`
+    /// emitted by [ReceiverState::emitter_address] from it.
+    ///
+    /// Transaction is marked as processed so the state modifications should be reverted
+    /// (e.g. by panicking) if the program fails to handle the events.
+    pub fn accept<E: SolEvent>(
+        &mut self,
+        source: ActorId,
+        slot: u64,
+        transaction_index: u64,
+        receipt_rlp: &[u8],
+    ) -> Result<Vec<E>, Error> {
+        self.accept_with(source, slot, transaction_index, |emitter| {
+            let events = events::extract_events::<E>(receipt_rlp, emitter)?;
+            if events.is_empty() {
+                return Err(Error::MessageNotFound);
+            }
+
+            Ok(events)
+        })
+    }
+
+    /// Accept receipt submitted by `source` and extract the messages sent to the `destination`
+    /// program through the `VaraMessenger` contract at [ReceiverState::emitter_address].
+    ///
+    /// See [ReceiverState::accept] for details.
+    pub fn accept_messages(
+        &mut self,
+        source: ActorId,
+        slot: u64,
+        transaction_index: u64,
+        receipt_rlp: &[u8],
+        destination: ActorId,
+    ) -> Result<Vec<EthMessage>, Error> {
+        self.accept_with(source, slot, transaction_index, |emitter| {
+            messages::decode_messages_for(receipt_rlp, emitter, destination)
+        })
+    }
+
+    fn accept_with<T>(
+        &mut self,
+        source: ActorId,
+        slot: u64,
+        transaction_index: u64,
+        extract: impl FnOnce(H160) -> Result<Vec<T>, Error>,
+    ) -> Result<Vec<T>, Error> {
+        if source != self.historical_proxy_address {
+            return Err(Error::NotHistoricalProxy);
+        }
+
+        self.transactions.check(slot, transaction_index)?;
+
+        let events = extract(self.emitter_address)?;
+
+        self.transactions.insert(slot, transaction_index)?;
+
+        Ok(events)
+    }
+}
+
+/// Service to inspect and manage [ReceiverState].
+pub struct Service<'a, ExecContext> {
+    state: &'a RefCell<ReceiverState>,
+    exec_context: ExecContext,
+}
+
+#[sails_rs::service]
+impl<'a, T> Service<'a, T>
+where
+    T: ExecContext,
+{
+    pub fn new(state: &'a RefCell<ReceiverState>, exec_context: T) -> Self {
+        Self {
+            state,
+            exec_context,
+        }
+    }
+
+    /// Change admin of the receiver.
+    ///
+    /// This function can be called only by the admin.
+    pub fn update_admin(&mut self, admin_new: ActorId) {
+        self.ensure_admin().admin = admin_new;
+    }
+
+    /// Change address of the `historical-proxy` program.
+    ///
+    /// This function can be called only by the admin.
+    pub fn update_historical_proxy_address(&mut self, historical_proxy_address_new: ActorId) {
+        self.ensure_admin().historical_proxy_address = historical_proxy_address_new;
+    }
+
+    /// Change address of the contract on Ethereum which events are accepted.
+    ///
+    /// This function can be called only by the admin.
+    pub fn update_emitter_address(&mut self, emitter_address_new: H160) {
+        self.ensure_admin().emitter_address = emitter_address_new;
+    }
+
+    pub fn admin(&self) -> ActorId {
+        self.state.borrow().admin
+    }
+
+    pub fn historical_proxy_address(&self) -> ActorId {
+        self.state.borrow().historical_proxy_address
+    }
+
+    pub fn emitter_address(&self) -> H160 {
+        self.state.borrow().emitter_address
+    }
+
+    /// Check whether the Ethereum transaction is processed.
+    ///
+    /// Returns `false` for the processed transactions that were evicted from the storage,
+    /// they're rejected as too old anyway.
+    pub fn is_processed(&self, slot: u64, transaction_index: u64) -> bool {
+        self.state
+            .borrow()
+            .transactions
+            .contains(slot, transaction_index)
+    }
+}
+
+impl<T> Service<'_, T>
+where
+    T: ExecContext,
+{
+    fn ensure_admin(&self) -> cell::RefMut<'_, ReceiverState> {
+        let state = self.state.borrow_mut();
+        if self.exec_context.actor_id() != state.admin {
+            panic!("Not an admin");
+        }
+
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::tests::{message_log, receipt_rlp, MESSENGER};
+
+    const PROXY: u64 = 500;
+
+    #[test]
+    fn accepts_messages_from_proxy_once() {
+        let destination = ActorId::from(10);
+        let mut state = ReceiverState::new(ActorId::from(1), PROXY.into(), MESSENGER, 10);
+
+        let receipt_rlp = receipt_rlp(true, vec![message_log(MESSENGER, destination, 0, b"1")]);
+
+        assert_eq!(
+            state.accept_messages(ActorId::from(2), 1, 0, &receipt_rlp, destination),
+            Err(Error::NotHistoricalProxy)
+        );
+        assert_eq!(
+            state.accept_messages(PROXY.into(), 1, 0, &receipt_rlp, ActorId::from(11)),
+            Err(Error::MessageNotFound)
+        );
+        assert!(!state.transactions.contains(1, 0));
+
+        let messages = state
+            .accept_messages(PROXY.into(), 1, 0, &receipt_rlp, destination)
+            .unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].payload, b"1".to_vec());
+
+        assert_eq!(
+            state.accept_messages(PROXY.into(), 1, 0, &receipt_rlp, destination),
+            Err(Error::AlreadyProcessed)
+        );
+
+        let events = state
+            .accept::<crate::abi::IVaraMessenger::MessageToVaraSent>(
+                PROXY.into(),
+                1,
+                1,
+                &receipt_rlp,
+            )
+            .unwrap();
+        assert_eq!(events.len(), 1);
+    }
+}
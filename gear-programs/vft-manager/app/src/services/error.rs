@@ -46,4 +46,18 @@ pub enum Error {
 
     /// Vft-manager is paused and cannot process the request.
     Paused,
+    /// Bridging of the token pair is paused in the requested direction.
+    TokenPaused,
+
+    /// Amount of tokens exceeds the per-transfer limit of the token.
+    TransferLimitExceeded,
+    /// Amount of tokens exceeds the rolling window cap of the token.
+    WindowCapExceeded,
+
+    /// `VFT` program has refused to mint/unlock tokens. They can be refunded on Ethereum.
+    DeliveryFailed,
+    /// There's no failed delivery for the specified Ethereum transaction.
+    NoFailedDelivery,
+    /// Refund of the failed delivery was already requested.
+    RefundAlreadyRequested,
 }
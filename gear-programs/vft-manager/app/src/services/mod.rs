@@ -1,3 +1,4 @@
+use checkpoint_light_client_client::traits::ServiceCheckpointFor as _;
 use gstd::{exec, static_mut, static_ref};
 use sails_rs::{
     calls::*,
@@ -7,16 +8,24 @@ use sails_rs::{
 use vft_client::traits::*;
 
 mod error;
+mod rate_limit;
+mod refund;
+mod timelock;
 mod token_mapping;
 
 use error::Error;
+use rate_limit::RateLimits;
 use request_bridging::{MessageStatus, TxDetails};
+use timelock::Timelock;
 use token_mapping::TokenMap;
 
 mod request_bridging;
 pub mod submit_receipt;
 
-pub use submit_receipt::abi as eth_abi;
+pub use rate_limit::RateLimit;
+pub use submit_receipt::{abi as eth_abi, Deposit, FailedDelivery, RefundStatus};
+pub use timelock::{AdminOperation, OperationId, PendingOperation};
+pub use token_mapping::PausedDirections;
 
 pub const SIZE_FILL_TRANSACTIONS_STEP: usize = 50_000;
 
@@ -59,6 +68,15 @@ pub enum TokenSupply {
     Gear = 1,
 }
 
+/// Direction of the bridging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Encode, Decode, TypeInfo)]
+pub enum Direction {
+    /// Tokens are bridged from Ethereum to Gear with [VftManager::submit_receipt].
+    EthToVara,
+    /// Tokens are bridged from Gear to Ethereum with [VftManager::request_bridging].
+    VaraToEth,
+}
+
 /// Events emitted by VFT Manager service.
 #[derive(Encode, TypeInfo)]
 #[codec(crate = sails_rs::scale_codec)]
@@ -105,6 +123,95 @@ enum Event {
         /// Receiver of the tokens on the Ethereum side.
         receiver: H160,
     },
+    /// Rate limit of the token was changed by an admin.
+    RateLimitUpdated {
+        /// `VFT` token address the limit is applied to.
+        vara_token_id: ActorId,
+        /// Direction of the bridging the limit is applied to.
+        direction: Direction,
+        /// New limit. `None` if the limit was removed.
+        limit: Option<RateLimit>,
+    },
+    /// Transfer was rejected because it exceeds the rate limit of the token.
+    RateLimitExceeded {
+        /// `VFT` token address which limit was hit.
+        vara_token_id: ActorId,
+        /// Direction of the rejected transfer.
+        direction: Direction,
+        /// Amount of tokens in the rejected transfer.
+        amount: U256,
+    },
+    /// Bridging of the token pair was paused in the specified direction.
+    ///
+    /// It means that user requests to bridge this token in the specified direction
+    /// will be rejected while the other token pairs keep working.
+    TokenPaused {
+        /// `VFT` token address of the paused pair.
+        vara_token_id: ActorId,
+        /// Paused direction of the bridging.
+        direction: Direction,
+    },
+    /// Bridging of the token pair was unpaused in the specified direction.
+    TokenUnpaused {
+        /// `VFT` token address of the unpaused pair.
+        vara_token_id: ActorId,
+        /// Unpaused direction of the bridging.
+        direction: Direction,
+    },
+    /// Admin operation was scheduled for the delayed execution.
+    OperationScheduled {
+        /// Id of the scheduled operation.
+        id: OperationId,
+        /// Scheduled operation.
+        operation: AdminOperation,
+        /// Block starting from which the operation can be executed.
+        executable_at: u32,
+    },
+    /// Scheduled admin operation was executed.
+    OperationExecuted {
+        /// Id of the executed operation.
+        id: OperationId,
+    },
+    /// Scheduled admin operation was cancelled.
+    OperationCancelled {
+        /// Id of the cancelled operation.
+        id: OperationId,
+    },
+    /// `VFT` program has refused to mint/unlock tokens deposited on Ethereum.
+    ///
+    /// Tokens can be refunded to the sender on Ethereum by calling `claim_refund`.
+    DeliveryFailed {
+        /// Slot of the Ethereum block with the deposit transaction.
+        slot: u64,
+        /// Index of the deposit transaction in the block.
+        transaction_index: u64,
+        /// `VFT` token address that should have been minted/unlocked.
+        vara_token_id: ActorId,
+        /// Original token owner on the Ethereum side.
+        sender: H160,
+        /// Receiver of the tokens on the Gear side.
+        receiver: ActorId,
+        /// Amount of tokens that failed to be delivered.
+        amount: U256,
+    },
+    /// Refund of the failed delivery was requested.
+    ///
+    /// When this event is emitted it means that a message returning tokens to the original
+    /// sender was successfully submitted to the gear-eth-bridge built-in actor.
+    RefundRequested {
+        /// Slot of the Ethereum block with the deposit transaction.
+        slot: u64,
+        /// Index of the deposit transaction in the block.
+        transaction_index: u64,
+        /// Nonce that gear-eth-bridge built-in actor have returned.
+        nonce: U256,
+        /// `ERC20` token address that should be refunded.
+        eth_token_id: H160,
+        /// Receiver of the refund on the Ethereum side.
+        receiver: H160,
+        /// Amount of tokens that should be refunded.
+        amount: U256,
+    },
     /// Vft-manager was paused by an admin.
     ///
     /// It means that any user requests to it will be rejected.
@@ -127,13 +234,17 @@ pub struct State {
     /// - Changing [Config]
     /// - Updating [State::erc20_manager_address]
     /// - Updating [State::historical_proxy_address]
+    /// - Updating [State::checkpoint_light_client_address]
     /// - Managing token mapping in [State::token_map]
-    /// - Pausing/unpausing the current program
+    /// - Managing rate limits in [State::rate_limits]
+    /// - Pausing/unpausing the current program or the specific token pairs
     /// - Changing [State::pause_admin]
     /// - Changing [State::admin]
+    /// - Scheduling and executing operations in [State::timelock]
     admin: ActorId,
     /// Governance of this program. This address is in charge of
-    /// pausing and unpausing the current program.
+    /// pausing and unpausing the current program and the specific token pairs
+    /// and cancelling operations scheduled in [State::timelock].
     pause_admin: ActorId,
     /// Address of the `ERC20Manager` contract address on Ethereum.
     ///
@@ -143,6 +254,10 @@ pub struct State {
     ///
     /// Can be adjusted by the [State::admin].
     token_map: TokenMap,
+    /// Per-token limits of the bridged amounts.
+    ///
+    /// Can be adjusted by the [State::admin].
+    rate_limits: RateLimits,
     /// Address of the `historical-proxy` program.
     ///
     /// VFT Manager service will only accept incoming requests on token withdrawals
@@ -150,8 +265,24 @@ pub struct State {
     ///
     /// Can be adjusted by the [State::admin].
     historical_proxy_address: ActorId,
+    /// Address of the `checkpoint-light-client` program.
+    ///
+    /// Its first provable slot is used to prune processed Ethereum transactions that can't be
+    /// replayed anymore. Can be adjusted by the [State::admin].
+    checkpoint_light_client_address: ActorId,
     /// Is the `vft-manager` currently on pause.
     is_paused: bool,
+    /// Admin operations scheduled for the delayed execution.
+    ///
+    /// While its delay is non-zero, the following operations can't be applied directly
+    /// and should be scheduled with [VftManager::schedule_operation] instead:
+    /// - [VftManager::update_erc20_manager_address]
+    /// - [VftManager::update_historical_proxy_address]
+    /// - [VftManager::update_checkpoint_light_client_address]
+    /// - [VftManager::map_vara_to_eth_address]
+    /// - [VftManager::upgrade]
+    /// - [VftManager::update_timelock_delay]
+    timelock: Timelock,
     /// Address of the new vft-manager program which the current should upgrade to.
     /// It is required to handle cases when gas exhausted during execution of `upgrade` method.
     vft_manager_new: Option<ActorId>,
@@ -205,21 +336,38 @@ impl<T> VftManager<T>
 where
     T: ExecContext,
 {
-    /// Change [State::erc20_manager_address]. Can be called only by a [State::admin].
+    /// Change [State::erc20_manager_address]. Can be called only by a [State::admin]
+    /// when the [State::timelock] is disabled.
     pub fn update_erc20_manager_address(&mut self, new_erc20_manager_address: H160) {
         self.ensure_admin();
+        self.ensure_not_timelocked();
 
         self.state_mut().erc20_manager_address = new_erc20_manager_address;
     }
 
-    /// Change [State::historical_proxy_address]. Can be called only by a [State::admin].
+    /// Change [State::historical_proxy_address]. Can be called only by a [State::admin]
+    /// when the [State::timelock] is disabled.
     pub fn update_historical_proxy_address(&mut self, historical_proxy_address_new: ActorId) {
         self.ensure_admin();
+        self.ensure_not_timelocked();
 
         self.state_mut().historical_proxy_address = historical_proxy_address_new;
     }
 
-    /// Add a new token pair to a [State::token_map]. Can be called only by a [State::admin].
+    /// Change [State::checkpoint_light_client_address]. Can be called only by a [State::admin]
+    /// when the [State::timelock] is disabled.
+    pub fn update_checkpoint_light_client_address(
+        &mut self,
+        checkpoint_light_client_address_new: ActorId,
+    ) {
+        self.ensure_admin();
+        self.ensure_not_timelocked();
+
+        self.state_mut().checkpoint_light_client_address = checkpoint_light_client_address_new;
+    }
+
+    /// Add a new token pair to a [State::token_map]. Can be called only by a [State::admin]
+    /// when the [State::timelock] is disabled.
     pub fn map_vara_to_eth_address(
         &mut self,
         vara_token_id: ActorId,
@@ -227,58 +375,133 @@ where
         supply_type: TokenSupply,
     ) {
         self.ensure_admin();
+        self.ensure_not_timelocked();
 
-        self.state_mut()
-            .token_map
-            .insert(vara_token_id, eth_token_id, supply_type);
-
-        self.notify_on(Event::TokenMappingAdded {
-            vara_token_id,
-            eth_token_id,
-            supply_type,
-        })
-        .expect("Failed to emit event");
+        self.insert_token_mapping(vara_token_id, eth_token_id, supply_type);
     }
 
-    /// Remove the token pair from [State::token_map]. Can be called only by a [State::admin].
+    /// Remove the token pair from [State::token_map]. Can be called only by a [State::admin]
+    /// when the [State::timelock] is disabled.
     pub fn remove_vara_to_eth_address(&mut self, vara_token_id: ActorId) {
         self.ensure_admin();
+        self.ensure_not_timelocked();
 
-        let (eth_token_id, supply_type) = self.state_mut().token_map.remove(vara_token_id);
+        self.remove_token_mapping(vara_token_id);
+    }
 
-        self.notify_on(Event::TokenMappingRemoved {
-            vara_token_id,
-            eth_token_id,
-            supply_type,
-        })
-        .expect("Failed to emit event");
+    /// Set rate limit of the token in the specified direction or remove it if `limit`
+    /// is `None`. Can be called only by a [State::admin] when the [State::timelock]
+    /// is disabled.
+    pub fn set_rate_limit(
+        &mut self,
+        vara_token_id: ActorId,
+        direction: Direction,
+        limit: Option<RateLimit>,
+    ) {
+        self.ensure_admin();
+        self.ensure_not_timelocked();
+
+        self.update_rate_limit(vara_token_id, direction, limit);
     }
 
-    /// Change [Config]. Can be called only by a [State::admin].
+    /// Change [Config]. Can be called only by a [State::admin] when the [State::timelock]
+    /// is disabled.
     ///
     /// For more info see [Config] docs.
     pub fn update_config(&mut self, config: Config) {
         self.ensure_admin();
+        self.ensure_not_timelocked();
 
         unsafe {
             CONFIG = Some(config);
         }
     }
 
-    /// Change [State::admin]. Can be called only by a [State::admin].
+    /// Change [State::admin]. Can be called only by a [State::admin] when the
+    /// [State::timelock] is disabled.
     pub fn set_admin(&mut self, new_admin: ActorId) {
         self.ensure_admin();
+        self.ensure_not_timelocked();
 
         self.state_mut().admin = new_admin;
     }
 
-    /// Change [State::pause_admin]. Can be called only by a [State::admin].
+    /// Change [State::pause_admin]. Can be called only by a [State::admin] when the
+    /// [State::timelock] is disabled.
     pub fn set_pause_admin(&mut self, new_pause_admin: ActorId) {
         self.ensure_admin();
+        self.ensure_not_timelocked();
 
         self.state_mut().pause_admin = new_pause_admin;
     }
 
+    /// Change delay of the [State::timelock]. Zero delay disables the timelock.
+    ///
+    /// Can be called only by a [State::admin] when the [State::timelock] is disabled.
+    pub fn update_timelock_delay(&mut self, delay: u32) {
+        self.ensure_admin();
+        self.ensure_not_timelocked();
+
+        self.state_mut().timelock.set_delay(delay);
+    }
+
+    /// Schedule admin operation to be executed after the delay of the [State::timelock].
+    ///
+    /// Can be called only by a [State::admin].
+    pub fn schedule_operation(&mut self, operation: AdminOperation) -> OperationId {
+        self.ensure_admin();
+
+        let (id, executable_at) = self
+            .state_mut()
+            .timelock
+            .schedule(operation.clone(), exec::block_height());
+
+        self.notify_on(Event::OperationScheduled {
+            id,
+            operation,
+            executable_at,
+        })
+        .expect("Failed to emit event");
+
+        id
+    }
+
+    /// Execute scheduled admin operation which delay has passed.
+    ///
+    /// Can be called only by a [State::admin].
+    pub async fn execute_operation(&mut self, id: OperationId) {
+        self.ensure_admin();
+
+        let operation = self
+            .state_mut()
+            .timelock
+            .take_executable(id, exec::block_height());
+
+        self.notify_on(Event::OperationExecuted { id })
+            .expect("Failed to emit event");
+
+        self.apply_operation(operation).await;
+    }
+
+    /// Cancel scheduled admin operation.
+    ///
+    /// Can be called only by a [State::admin] or [State::pause_admin].
+    pub fn cancel_operation(&mut self, id: OperationId) {
+        self.ensure_admin_or_pause_admin();
+
+        self.state_mut().timelock.cancel(id);
+
+        self.notify_on(Event::OperationCancelled { id })
+            .expect("Failed to emit event");
+    }
+
+    /// Ensure that operation can be applied without scheduling it in the [State::timelock].
+    fn ensure_not_timelocked(&self) {
+        if self.state().timelock.delay() > 0 {
+            panic!("Operation is timelocked");
+        }
+    }
+
     /// Ensure that message sender is a [State::admin].
     fn ensure_admin(&self) {
         if self.state().admin != self.exec_context.actor_id() {
@@ -338,6 +561,64 @@ where
             .expect("Failed to deposit event");
     }
 
+    /// Pause bridging of the token pair in the specified direction.
+    ///
+    /// Unlike [VftManager::pause] it affects only requests to `submit_receipt` (for
+    /// [Direction::EthToVara]) or `request_bridging` (for [Direction::VaraToEth]) with
+    /// the specified token.
+    ///
+    /// Can be called only by a [State::admin] or [State::pause_admin].
+    pub fn pause_token(&mut self, vara_token_id: ActorId, direction: Direction) {
+        self.ensure_admin_or_pause_admin();
+
+        if !self
+            .state_mut()
+            .token_map
+            .set_paused(vara_token_id, direction, true)
+        {
+            panic!("Already paused");
+        }
+
+        self.notify_on(Event::TokenPaused {
+            vara_token_id,
+            direction,
+        })
+        .expect("Failed to emit event");
+    }
+
+    /// Unpause bridging of the token pair in the specified direction.
+    ///
+    /// It will effectively cancel effect of the [VftManager::pause_token].
+    ///
+    /// Can be called only by a [State::admin] or [State::pause_admin].
+    pub fn unpause_token(&mut self, vara_token_id: ActorId, direction: Direction) {
+        self.ensure_admin_or_pause_admin();
+
+        if !self
+            .state_mut()
+            .token_map
+            .set_paused(vara_token_id, direction, false)
+        {
+            panic!("Already unpaused");
+        }
+
+        self.notify_on(Event::TokenUnpaused {
+            vara_token_id,
+            direction,
+        })
+        .expect("Failed to emit event");
+    }
+
+    /// Ensure that message sender is a [State::admin] or [State::pause_admin].
+    fn ensure_admin_or_pause_admin(&self) {
+        let sender = self.exec_context.actor_id();
+        let state = self.state();
+
+        if sender != state.admin && sender != state.pause_admin {
+            panic!("Access rejected");
+        }
+    }
+
     fn ensure_running(&self) -> Result<(), Error> {
         if self.state().is_paused {
             Err(Error::Paused)
@@ -346,6 +627,55 @@ where
         }
     }
 
+    /// Check that transfer fits into the [rate limits](State::rate_limits) of the token
+    /// and account it. Emits [Event::RateLimitExceeded] if it doesn't.
+    ///
+    /// Accounted amount should be [released](VftManager::release_rate_limit) if tokens
+    /// end up not being transferred.
+    fn consume_rate_limit(
+        &mut self,
+        vara_token_id: ActorId,
+        direction: Direction,
+        amount: U256,
+    ) -> Result<(), Error> {
+        let result = self.state_mut().rate_limits.consume(
+            vara_token_id,
+            direction,
+            amount,
+            exec::block_height(),
+        );
+
+        if result.is_err() {
+            self.notify_on(Event::RateLimitExceeded {
+                vara_token_id,
+                direction,
+                amount,
+            })
+            .expect("Failed to emit event");
+        }
+
+        result
+    }
+
+    /// Return `amount` tokens [consumed](VftManager::consume_rate_limit) at the block
+    /// `consumed_at` back to the [rate limits](State::rate_limits) of the token. It's called
+    /// when tokens haven't been transferred after all.
+    fn release_rate_limit(
+        &mut self,
+        vara_token_id: ActorId,
+        direction: Direction,
+        amount: U256,
+        consumed_at: u32,
+    ) {
+        self.state_mut().rate_limits.release(
+            vara_token_id,
+            direction,
+            amount,
+            consumed_at,
+            exec::block_height(),
+        );
+    }
+
     /// Submit rlp-encoded transaction receipt.
     ///
     /// This receipt is decoded under the hood and checked that it's a valid receipt from tx
@@ -383,9 +713,30 @@ where
 
         let sender = self.exec_context.actor_id();
 
+        self.state()
+            .token_map
+            .ensure_not_paused(&vara_token_id, Direction::VaraToEth)?;
+        self.consume_rate_limit(vara_token_id, Direction::VaraToEth, amount)?;
+
         request_bridging::request_bridging(self, sender, vara_token_id, amount, receiver).await
     }
 
+    /// Request refund of the tokens which have failed to be delivered to the receiver
+    /// on Gear. Tokens are returned to the original sender on Ethereum.
+    ///
+    /// Can be called by anyone since the refund receiver is fixed by the deposit.
+    pub async fn claim_refund(&mut self, slot: u64, transaction_index: u64) -> Result<U256, Error> {
+        self.ensure_running()?;
+
+        let value = msg::value();
+        let fee = self.config().fee_incoming;
+        if value != fee {
+            panic!("Please attach exactly {fee} value");
+        }
+
+        refund::claim_refund(self, slot, transaction_index).await
+    }
+
     /// Process message further if some error was encountered during the `request_bridging`.
     ///
     /// This method should be called only to recover funds that were stuck in the middle of the bridging
@@ -403,81 +754,114 @@ where
         request_bridging::handle_interrupted_transfer(self, msg_id).await
     }
 
+    /// Upgrade to the new `vft-manager` program transferring all the locked tokens to it.
+    ///
+    /// Can be called only by a [State::admin] when the [State::timelock] is disabled. If it
+    /// was interrupted, it also can be called directly to continue the same upgrade.
     pub async fn upgrade(&mut self, vft_manager_new: ActorId) {
         self.ensure_admin();
 
-        if !self.state().is_paused {
-            panic!("Not paused");
+        if self.state().vft_manager_new != Some(vft_manager_new) {
+            self.ensure_not_timelocked();
         }
 
-        if self
-            .state()
-            .vft_manager_new
-            .map(|address| address != vft_manager_new)
-            .unwrap_or(false)
-        {
-            panic!(
-                "Upgrade called with vft_manager_new = {:?}",
-                self.state().vft_manager_new
-            );
-        }
+        self.upgrade_to(vft_manager_new).await
+    }
 
-        self.state_mut().vft_manager_new = Some(vft_manager_new);
+    /// Remove up to `count` processed Ethereum transactions from the slots that can't be
+    /// proven anymore by the [State::checkpoint_light_client_address] program. Such
+    /// transactions are rejected by `submit_receipt` afterwards.
+    ///
+    /// Returns the amount of removed transactions. Can be called by anyone.
+    pub async fn prune_transactions(&mut self, count: u32) -> u32 {
+        let checkpoint_light_client = self.state().checkpoint_light_client_address;
+        if checkpoint_light_client.is_zero() {
+            panic!("Checkpoint light client address isn't set");
+        }
 
-        let vft_manager = exec::program_id();
-        let mut service = vft_client::Vft::new(GStdRemoting);
-        let mappings = self.state().token_map.read_state();
-        for (vft, _erc20, _supply) in mappings {
-            let balance = service
-                .balance_of(vft_manager)
-                .recv(vft)
+        // archived checkpoints are taken into account so the transactions proven
+        // against them aren't pruned
+        let slot_provable_first =
+            checkpoint_light_client_client::ServiceCheckpointFor::new(GStdRemoting)
+                .first_provable_slot()
+                .recv(checkpoint_light_client)
                 .await
-                .expect("Unable to get the balance of VftManager");
+                .expect("Failed to request the first provable slot");
 
-            if balance > 0.into()
-                && !service
-                    .transfer(vft_manager_new, balance)
-                    .send_recv(vft)
-                    .await
-                    .expect("Unable to request a transfer to the new VftManager")
-            {
-                panic!("Unable to transfer tokens to the new VftManager ({vft:?})");
-            }
+        submit_receipt::prune_transactions(slot_provable_first, count)
+    }
+
+    /// Insert already processed Ethereum transactions. It's intended to migrate them from
+    /// the previous `vft-manager` program, see [VftManager::transactions].
+    ///
+    /// Can be called only by a [State::admin] while the program is paused.
+    pub fn insert_transactions(&mut self, transactions: Vec<(u64, u64)>) {
+        self.ensure_admin();
+
+        if !self.state().is_paused {
+            panic!("Not paused");
         }
 
-        exec::exit(vft_manager_new);
+        submit_receipt::insert_transactions(transactions);
     }
 
+    /// Replace `VFT` programs that have exited with the new ones in the [State::token_map].
+    /// `vft_map` contains pairs of the old and the new `VFT` addresses.
+    ///
+    /// Can be called only by a [State::admin] when the [State::timelock] is disabled.
     pub async fn update_vfts(&mut self, vft_map: Vec<(ActorId, ActorId)>) {
         self.ensure_admin();
+        self.ensure_not_timelocked();
 
-        let gas_required = self.config().gas_for_swap_token_maps;
-        self.state_mut()
-            .token_map
-            .update_vfts(gas_required, vft_map)
-            .await
+        self.replace_vfts(vft_map).await
     }
 
-    /// Get state of a `request_bridging` message tracker.
-    pub fn request_briding_msg_tracker_state(
+    /// Get page of a `request_bridging` message tracker state.
+    ///
+    /// Returns up to `count` messages ordered by [MessageId] starting right after the `after`
+    /// message (or from the first one if it's `None`). The next page can be requested by
+    /// passing the last [MessageId] of the current page.
+    ///
+    /// Messages that have reached the terminal state are removed from the tracker
+    /// after the retention window.
+    pub fn request_bridging_msg_tracker_page(
         &self,
-        start: u32,
+        after: Option<MessageId>,
         count: u32,
     ) -> Vec<(MessageId, request_bridging::MsgTrackerMessageInfo)> {
+        request_bridging::msg_tracker_ref().messages(after, count)
+    }
+
+    /// Get state of the message in a `request_bridging` message tracker.
+    pub fn request_bridging_msg_tracker_message(
+        &self,
+        msg_id: MessageId,
+    ) -> Option<request_bridging::MsgTrackerMessageInfo> {
         request_bridging::msg_tracker_ref()
-            .message_info
-            .iter()
-            .skip(start as usize)
-            .take(count as usize)
-            .map(|(k, v)| (*k, v.clone()))
-            .collect()
+            .get_message_info(&msg_id)
+            .cloned()
     }
 
     /// Get current [token mapping](State::token_map).
-    pub fn vara_to_eth_addresses(&self) -> Vec<(ActorId, H160, TokenSupply)> {
+    ///
+    /// Every entry also contains the directions in which bridging of the pair is paused.
+    pub fn vara_to_eth_addresses(&self) -> Vec<(ActorId, H160, TokenSupply, PausedDirections)> {
         self.state().token_map.read_state()
     }
 
+    /// Get current [rate limits](State::rate_limits).
+    pub fn rate_limits(&self) -> Vec<(ActorId, Direction, RateLimit)> {
+        self.state().rate_limits.read_state()
+    }
+
+    /// Get amount of tokens bridged in the specified direction during the current
+    /// rolling window of the [rate limit](State::rate_limits).
+    pub fn rate_limit_usage(&self, vara_token_id: ActorId, direction: Direction) -> U256 {
+        self.state()
+            .rate_limits
+            .usage(vara_token_id, direction, exec::block_height())
+    }
+
     /// Get current [State::erc20_manager_address] address.
     pub fn erc20_manager_address(&self) -> H160 {
         self.state().erc20_manager_address
@@ -508,6 +892,36 @@ where
         self.config().clone()
     }
 
+    /// Get current delay of the [State::timelock] in blocks.
+    pub fn timelock_delay(&self) -> u32 {
+        self.state().timelock.delay()
+    }
+
+    /// Get admin operations scheduled in the [State::timelock].
+    pub fn pending_operations(&self) -> Vec<(OperationId, PendingOperation)> {
+        self.state().timelock.read_state()
+    }
+
+    /// Get current [State::checkpoint_light_client_address].
+    pub fn checkpoint_light_client_address(&self) -> ActorId {
+        self.state().checkpoint_light_client_address
+    }
+
+    /// Get the slot starting from which Ethereum transactions are accepted by `submit_receipt`.
+    pub fn transactions_slot_floor(&self) -> u64 {
+        submit_receipt::slot_floor()
+    }
+
+    /// Get Ethereum transactions which tokens have failed to be delivered.
+    pub fn failed_deliveries(&self, start: u32, count: u32) -> Vec<((u64, u64), FailedDelivery)> {
+        submit_receipt::failed_deliveries()
+            .iter()
+            .skip(start as usize)
+            .take(count as usize)
+            .map(|(key, failed_delivery)| (*key, failed_delivery.clone()))
+            .collect()
+    }
+
     /// Get current [State::historical_proxy_address].
     pub fn historical_proxy_address(&self) -> ActorId {
         self.state().historical_proxy_address
@@ -578,29 +992,21 @@ where
             use submit_receipt::token_operations;
 
             let source = self.exec_context.actor_id();
+            let deposit = submit_receipt::Deposit {
+                vara_token_id: source,
+                eth_token_id: H160::zero(),
+                sender: H160::zero(),
+                receiver: source,
+                amount: 100u32.into(),
+            };
             match _supply_type {
                 TokenSupply::Ethereum => {
-                    token_operations::mint(
-                        _slot,
-                        _transaction_index,
-                        source,
-                        source,
-                        100u32.into(),
-                        self.config(),
-                    )
-                    .await
+                    token_operations::mint(_slot, _transaction_index, deposit, self.config()).await
                 }
 
                 TokenSupply::Gear => {
-                    token_operations::unlock(
-                        _slot,
-                        _transaction_index,
-                        source,
-                        source,
-                        100u32.into(),
-                        self.config(),
-                    )
-                    .await
+                    token_operations::unlock(_slot, _transaction_index, deposit, self.config())
+                        .await
                 }
             }
         }
@@ -639,8 +1045,11 @@ where
                 pause_admin: exec_context.actor_id(),
                 erc20_manager_address: config.erc20_manager_address,
                 token_map: TokenMap::default(),
+                rate_limits: RateLimits::default(),
                 historical_proxy_address: config.historical_proxy_address,
+                checkpoint_light_client_address: ActorId::zero(),
                 is_paused: false,
+                timelock: Timelock::default(),
                 vft_manager_new: None,
             });
             CONFIG = Some(config.config);
@@ -655,6 +1064,150 @@ where
         Self { exec_context }
     }
 
+    /// Add a new token pair to a [State::token_map] and emit [Event::TokenMappingAdded].
+    fn insert_token_mapping(
+        &mut self,
+        vara_token_id: ActorId,
+        eth_token_id: H160,
+        supply_type: TokenSupply,
+    ) {
+        self.state_mut()
+            .token_map
+            .insert(vara_token_id, eth_token_id, supply_type);
+
+        self.notify_on(Event::TokenMappingAdded {
+            vara_token_id,
+            eth_token_id,
+            supply_type,
+        })
+        .expect("Failed to emit event");
+    }
+
+    /// Remove the token pair from a [State::token_map] and emit [Event::TokenMappingRemoved].
+    fn remove_token_mapping(&mut self, vara_token_id: ActorId) {
+        let (eth_token_id, supply_type) = self.state_mut().token_map.remove(vara_token_id);
+
+        self.notify_on(Event::TokenMappingRemoved {
+            vara_token_id,
+            eth_token_id,
+            supply_type,
+        })
+        .expect("Failed to emit event");
+    }
+
+    /// Set rate limit of the token in the specified direction and notify about it.
+    fn update_rate_limit(
+        &mut self,
+        vara_token_id: ActorId,
+        direction: Direction,
+        limit: Option<RateLimit>,
+    ) {
+        self.state_mut().rate_limits.set(
+            vara_token_id,
+            direction,
+            limit.clone(),
+            exec::block_height(),
+        );
+
+        self.notify_on(Event::RateLimitUpdated {
+            vara_token_id,
+            direction,
+            limit,
+        })
+        .expect("Failed to emit event");
+    }
+
+    /// Replace exited `VFT` programs in a [State::token_map].
+    async fn replace_vfts(&mut self, vft_map: Vec<(ActorId, ActorId)>) {
+        let gas_required = self.config().gas_for_swap_token_maps;
+        self.state_mut()
+            .token_map
+            .update_vfts(gas_required, vft_map)
+            .await
+    }
+
+    /// Apply admin operation scheduled in the [State::timelock].
+    async fn apply_operation(&mut self, operation: AdminOperation) {
+        match operation {
+            AdminOperation::MapVaraToEthAddress {
+                vara_token_id,
+                eth_token_id,
+                supply_type,
+            } => self.insert_token_mapping(vara_token_id, eth_token_id, supply_type),
+            AdminOperation::UpdateHistoricalProxyAddress(historical_proxy_address) => {
+                self.state_mut().historical_proxy_address = historical_proxy_address
+            }
+            AdminOperation::UpdateErc20ManagerAddress(erc20_manager_address) => {
+                self.state_mut().erc20_manager_address = erc20_manager_address
+            }
+            AdminOperation::UpdateCheckpointLightClientAddress(checkpoint_light_client_address) => {
+                self.state_mut().checkpoint_light_client_address = checkpoint_light_client_address
+            }
+            AdminOperation::Upgrade(vft_manager_new) => self.upgrade_to(vft_manager_new).await,
+            AdminOperation::UpdateTimelockDelay(delay) => {
+                self.state_mut().timelock.set_delay(delay)
+            }
+            AdminOperation::RemoveVaraToEthAddress(vara_token_id) => {
+                self.remove_token_mapping(vara_token_id)
+            }
+            AdminOperation::UpdateConfig(config) => unsafe { CONFIG = Some(config) },
+            AdminOperation::UpdateVfts(vft_map) => self.replace_vfts(vft_map).await,
+            AdminOperation::SetRateLimit {
+                vara_token_id,
+                direction,
+                limit,
+            } => self.update_rate_limit(vara_token_id, direction, limit),
+            AdminOperation::SetAdmin(admin) => self.state_mut().admin = admin,
+            AdminOperation::SetPauseAdmin(pause_admin) => {
+                self.state_mut().pause_admin = pause_admin
+            }
+        }
+    }
+
+    /// Transfer tokens locked on the current program to the new `vft-manager` and exit.
+    async fn upgrade_to(&mut self, vft_manager_new: ActorId) {
+        if !self.state().is_paused {
+            panic!("Not paused");
+        }
+
+        if self
+            .state()
+            .vft_manager_new
+            .map(|address| address != vft_manager_new)
+            .unwrap_or(false)
+        {
+            panic!(
+                "Upgrade called with vft_manager_new = {:?}",
+                self.state().vft_manager_new
+            );
+        }
+
+        self.state_mut().vft_manager_new = Some(vft_manager_new);
+
+        let vft_manager = exec::program_id();
+        let mut service = vft_client::Vft::new(GStdRemoting);
+        let mappings = self.state().token_map.read_state();
+        for (vft, _erc20, _supply, _paused) in mappings {
+            let balance = service
+                .balance_of(vft_manager)
+                .recv(vft)
+                .await
+                .expect("Unable to get the balance of VftManager");
+
+            if balance > 0.into()
+                && !service
+                    .transfer(vft_manager_new, balance)
+                    .send_recv(vft)
+                    .await
+                    .expect("Unable to request a transfer to the new VftManager")
+            {
+                panic!("Unable to transfer tokens to the new VftManager ({vft:?})");
+            }
+        }
+
+        exec::exit(vft_manager_new);
+    }
+
     /// Get a reference to the global [State].
     fn state(&self) -> &State {
         unsafe { static_ref!(STATE).as_ref() }.expect("VftManager::seed() should be called")
@@ -0,0 +1,184 @@
+//! Per-token limits of the amount of tokens bridged through the VFT Manager service.
+//!
+//! They bound the damage in case mapped token or the light client gets compromised.
+
+use collections::HashMap;
+use sails_rs::prelude::*;
+
+use super::{error::Error, Direction};
+
+/// Limits of the amount of tokens bridged in one direction.
+#[derive(Debug, Clone, Encode, Decode, TypeInfo)]
+pub struct RateLimit {
+    /// Maximum amount of tokens in a single transfer. Unlimited if `None`.
+    pub max_per_transfer: Option<U256>,
+    /// Maximum amount of tokens transferred during the last [RateLimit::window] blocks.
+    /// Unlimited if `None`.
+    pub window_cap: Option<U256>,
+    /// Length of the rolling window in blocks.
+    pub window: u32,
+}
+
+/// Amount of tokens transferred during the current and the previous fixed windows.
+///
+/// Usage in the rolling window is estimated as the amount transferred during the current
+/// fixed window plus the part of the previous one that the rolling window still overlaps.
+#[derive(Debug, Clone, Default)]
+struct WindowUsage {
+    /// Block the current fixed window has started at.
+    start: u32,
+    current: U256,
+    previous: U256,
+}
+
+impl WindowUsage {
+    /// Move to the fixed window containing the block `now`.
+    fn roll(&mut self, window: u32, now: u32) {
+        let elapsed = now.saturating_sub(self.start);
+        if window == 0 || elapsed < window {
+            return;
+        }
+
+        self.previous = if elapsed / window == 1 {
+            self.current
+        } else {
+            U256::zero()
+        };
+        self.current = U256::zero();
+        self.start = now - elapsed % window;
+    }
+
+    /// Stop accounting `amount` transferred at the block `consumed_at` if the rolling window
+    /// still overlaps it.
+    fn release(&mut self, window: u32, amount: U256, consumed_at: u32, now: u32) {
+        self.roll(window, now);
+
+        if window == 0 || consumed_at >= self.start {
+            self.current = self.current.saturating_sub(amount);
+        } else if consumed_at >= self.start.saturating_sub(window) {
+            self.previous = self.previous.saturating_sub(amount);
+        }
+    }
+
+    /// Amount transferred during the rolling window ending at the block `now`.
+    fn amount(&self, window: u32, now: u32) -> U256 {
+        let mut usage = self.clone();
+        usage.roll(window, now);
+
+        if window == 0 {
+            return usage.current;
+        }
+
+        let overlap = window - now.saturating_sub(usage.start);
+        let previous = usage.previous.saturating_mul(overlap.into()) / window;
+
+        usage.current.saturating_add(previous)
+    }
+}
+
+/// Rate limits of the mapped tokens.
+#[derive(Debug, Default)]
+pub struct RateLimits {
+    limits: HashMap<(ActorId, Direction), (RateLimit, WindowUsage)>,
+}
+
+impl RateLimits {
+    /// Set or remove (if `limit` is `None`) limit of the token in the specified direction.
+    ///
+    /// Amount transferred during the current window is preserved when the limit is changed.
+    pub fn set(
+        &mut self,
+        vara_token_id: ActorId,
+        direction: Direction,
+        limit: Option<RateLimit>,
+        now: u32,
+    ) {
+        let Some(limit) = limit else {
+            self.limits.remove(&(vara_token_id, direction));
+            return;
+        };
+
+        if limit.window_cap.is_some() && limit.window == 0 {
+            panic!("Window should be non-zero");
+        }
+
+        self.limits
+            .entry((vara_token_id, direction))
+            .and_modify(|(current, _)| *current = limit.clone())
+            .or_insert_with(|| {
+                (
+                    limit,
+                    WindowUsage {
+                        start: now,
+                        ..Default::default()
+                    },
+                )
+            });
+    }
+
+    /// Get all the limits that are set.
+    pub fn read_state(&self) -> Vec<(ActorId, Direction, RateLimit)> {
+        self.limits
+            .iter()
+            .map(|((vara_token_id, direction), (limit, _))| {
+                (*vara_token_id, *direction, limit.clone())
+            })
+            .collect()
+    }
+
+    /// Get amount of tokens transferred during the rolling window ending at the block `now`.
+    pub fn usage(&self, vara_token_id: ActorId, direction: Direction, now: u32) -> U256 {
+        self.limits
+            .get(&(vara_token_id, direction))
+            .map(|(limit, usage)| usage.amount(limit.window, now))
+            .unwrap_or_default()
+    }
+
+    /// Check that transfer of `amount` tokens fits into the limits and account it
+    /// in the current window.
+    pub fn consume(
+        &mut self,
+        vara_token_id: ActorId,
+        direction: Direction,
+        amount: U256,
+        now: u32,
+    ) -> Result<(), Error> {
+        let Some((limit, usage)) = self.limits.get_mut(&(vara_token_id, direction)) else {
+            return Ok(());
+        };
+
+        if limit
+            .max_per_transfer
+            .map(|max| amount > max)
+            .unwrap_or(false)
+        {
+            return Err(Error::TransferLimitExceeded);
+        }
+
+        if let Some(cap) = limit.window_cap {
+            if usage.amount(limit.window, now).saturating_add(amount) > cap {
+                return Err(Error::WindowCapExceeded);
+            }
+        }
+
+        usage.roll(limit.window, now);
+        usage.current = usage.current.saturating_add(amount);
+
+        Ok(())
+    }
+
+    /// Return `amount` tokens [consumed](RateLimits::consume) at the block `consumed_at`
+    /// back to the limits when the transfer has failed.
+    pub fn release(
+        &mut self,
+        vara_token_id: ActorId,
+        direction: Direction,
+        amount: U256,
+        consumed_at: u32,
+        now: u32,
+    ) {
+        if let Some((limit, usage)) = self.limits.get_mut(&(vara_token_id, direction)) {
+            usage.release(limit.window, amount, consumed_at, now);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Refund of the tokens which failed to be delivered to the receiver on Gear.
+//!
+//! Tokens of such deposits stay locked on Ethereum, so they're returned to the original
+//! sender by sending a message to the `ERC20Manager` through the `pallet-gear-eth-bridge`
+//! built-in actor, the same way as in the [request_bridging](super::request_bridging).
+
+use gstd::msg;
+use sails_rs::{gstd::ExecContext, prelude::*};
+
+use super::{
+    error::Error,
+    request_bridging::{decode_bridge_reply, Payload},
+    submit_receipt::{failed_deliveries, failed_deliveries_mut, RefundStatus},
+    Event, VftManager,
+};
+
+/// Request refund of the [failed delivery](super::submit_receipt::FailedDelivery)
+/// of the Ethereum transaction.
+///
+/// Returns nonce of the refund message queued by the `pallet-gear-eth-bridge` built-in actor.
+pub async fn claim_refund<T: ExecContext>(
+    service: &mut VftManager<T>,
+    slot: u64,
+    transaction_index: u64,
+) -> Result<U256, Error> {
+    let key = (slot, transaction_index);
+    let failed_delivery = failed_deliveries_mut()
+        .get_mut(&key)
+        .ok_or(Error::NoFailedDelivery)?;
+
+    if failed_delivery.refund != RefundStatus::NotRequested {
+        return Err(Error::RefundAlreadyRequested);
+    }
+
+    let deposit = failed_delivery.deposit.clone();
+    let state = service.state();
+    let config = service.config();
+
+    let bytes = gbuiltin_eth_bridge::Request::SendEthMessage {
+        destination: state.erc20_manager_address,
+        payload: Payload {
+            receiver: deposit.sender,
+            token_id: deposit.eth_token_id,
+            amount: deposit.amount,
+        }
+        .pack(),
+    }
+    .encode();
+
+    let future = msg::send_bytes_with_gas_for_reply(
+        state.gear_bridge_builtin,
+        bytes,
+        config.gas_to_send_request_to_builtin,
+        config.fee_bridge,
+        config.gas_for_reply_deposit,
+    )
+    .map_err(|_| Error::SendFailure)?
+    .up_to(Some(config.reply_timeout))
+    .map_err(|_| Error::ReplyTimeout)?
+    .handle_reply(move || handle_reply_hook(key))
+    .map_err(|_| Error::ReplyHook)?;
+
+    // Status is switched only when the message is sent so the refund can't be requested twice.
+    // If the reply hook won't be executed, refund stays requested since it's unknown
+    // whether the message was queued by the built-in actor.
+    failed_delivery.refund = RefundStatus::Requested;
+
+    future.await.map_err(|_| Error::ReplyFailure)?;
+
+    let RefundStatus::Completed(nonce) = failed_deliveries()
+        .get(&key)
+        .expect("Failed deliveries are never removed; qed")
+        .refund
+    else {
+        return Err(Error::MessageFailed);
+    };
+
+    service
+        .notify_on(Event::RefundRequested {
+            slot,
+            transaction_index,
+            nonce,
+            eth_token_id: deposit.eth_token_id,
+            receiver: deposit.sender,
+            amount: deposit.amount,
+        })
+        .expect("Failed to emit event");
+
+    Ok(nonce)
+}
+
+/// Handle reply received from `pallet-gear-eth-bridge` built-in actor.
+///
+/// Refund can be requested again if the built-in actor has failed to queue the message.
+fn handle_reply_hook(key: (u64, u64)) {
+    let failed_delivery = failed_deliveries_mut()
+        .get_mut(&key)
+        .expect("Unexpected: failed delivery does not exist");
+
+    let nonce = msg::reply_code()
+        .ok()
+        .filter(|code| code.is_success())
+        .and_then(|_| msg::load_bytes().ok())
+        .and_then(|bytes| decode_bridge_reply(&bytes).ok().flatten());
+
+    failed_delivery.refund = match nonce {
+        Some(nonce) => RefundStatus::Completed(nonce),
+        None => RefundStatus::NotRequested,
+    };
+}
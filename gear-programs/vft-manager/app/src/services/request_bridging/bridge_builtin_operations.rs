@@ -104,7 +104,7 @@ fn handle_reply_hook(msg_id: MessageId) {
 }
 
 /// Decode reply received from `pallet-gear-eth-bridge` built-in actor.
-fn decode_bridge_reply(mut bytes: &[u8]) -> Result<Option<U256>, Error> {
+pub fn decode_bridge_reply(mut bytes: &[u8]) -> Result<Option<U256>, Error> {
     let reply =
         gbuiltin_eth_bridge::Response::decode(&mut bytes).map_err(|_| Error::BuiltinDecode)?;
 
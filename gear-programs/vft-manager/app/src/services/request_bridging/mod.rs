@@ -1,14 +1,15 @@
 //! Gear -> ethereum bridging request entrypoint of `VFTManager` service.
 
+use gstd::exec;
 use sails_rs::{gstd::ExecContext, prelude::*};
 
-use super::{error::Error, Event, TokenSupply, VftManager};
+use super::{error::Error, Direction, Event, TokenSupply, VftManager};
 
 mod bridge_builtin_operations;
 mod msg_tracker;
 mod token_operations;
 
-use bridge_builtin_operations::Payload;
+pub(super) use bridge_builtin_operations::{decode_bridge_reply, Payload};
 
 pub use msg_tracker::{
     msg_tracker_mut, msg_tracker_ref, MessageInfo as MsgTrackerMessageInfo, MessageStatus,
@@ -23,6 +24,9 @@ pub fn seed() {
 /// Lock/burn `vft` tokens (specific operation depends on the token supply type) and send
 /// request to the bridge built-in actor. If request is failed then tokens will be refunded back
 /// to the sender.
+///
+/// Transfer should be already accounted in the rate limits at the current block. It's
+/// released from them if tokens are refused to be locked/burned or get refunded.
 pub async fn request_bridging<T: ExecContext>(
     service: &mut VftManager<T>,
     sender: ActorId,
@@ -35,6 +39,7 @@ pub async fn request_bridging<T: ExecContext>(
     let eth_token_id = service.state().token_map.get_eth_token_id(&vara_token_id)?;
     let supply_type = service.state().token_map.get_supply_type(&vara_token_id)?;
     let config = service.config();
+    let requested_at = exec::block_height();
 
     let transaction_details = TxDetails {
         vara_token_id,
@@ -42,6 +47,7 @@ pub async fn request_bridging<T: ExecContext>(
         amount,
         receiver,
         token_supply: supply_type,
+        requested_at,
     };
 
     msg_tracker_mut().insert_message_info(
@@ -50,17 +56,32 @@ pub async fn request_bridging<T: ExecContext>(
         transaction_details,
     );
 
-    match supply_type {
+    let deposit_result = match supply_type {
         TokenSupply::Ethereum => {
-            token_operations::burn(vara_token_id, sender, amount, config, msg_id)
-                .await
-                .expect("Failed to burn tokens");
+            token_operations::burn(vara_token_id, sender, amount, config, msg_id).await
         }
         TokenSupply::Gear => {
-            token_operations::lock(vara_token_id, sender, amount, config, msg_id)
-                .await
-                .expect("Failed to lock tokens");
+            token_operations::lock(vara_token_id, sender, amount, config, msg_id).await
+        }
+    };
+
+    if let Err(e) = deposit_result {
+        // State is saved before the reply is awaited, so the transfer is already accounted
+        // in the rate limits. It's released only if `VFT` program has definitely refused
+        // to take the tokens since otherwise they can be returned later with
+        // `handle_interrupted_transfer`.
+        let deposit_failed = msg_tracker_ref()
+            .get_message_info(&msg_id)
+            .map(|info| info.status == MessageStatus::TokenDepositCompleted(false))
+            .unwrap_or(false);
+
+        if !deposit_failed {
+            panic!("Failed to deposit tokens: {e:?}");
         }
+
+        service.release_rate_limit(vara_token_id, Direction::VaraToEth, amount, requested_at);
+
+        return Err(e);
     }
 
     let payload = Payload {
@@ -103,6 +124,8 @@ pub async fn request_bridging<T: ExecContext>(
                 }
             }
 
+            service.release_rate_limit(vara_token_id, Direction::VaraToEth, amount, requested_at);
+
             return Err(e);
         }
     };
@@ -149,6 +172,7 @@ pub async fn handle_interrupted_transfer<T: ExecContext>(
         amount,
         receiver: _,
         token_supply,
+        requested_at,
     } = msg_info.details;
 
     match msg_info.status {
@@ -175,6 +199,8 @@ pub async fn handle_interrupted_transfer<T: ExecContext>(
         }
     }
 
+    service.release_rate_limit(vara_token_id, Direction::VaraToEth, amount, requested_at);
+
     Ok(())
 }
 
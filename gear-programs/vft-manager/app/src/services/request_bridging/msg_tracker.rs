@@ -1,16 +1,31 @@
 use super::super::TokenSupply;
-use gstd::{prelude::collections::HashMap, static_mut, static_ref, MessageId};
+use core::ops::Bound;
+use gstd::{
+    exec,
+    prelude::collections::{BTreeMap, VecDeque},
+    static_mut, static_ref, MessageId,
+};
 use sails_rs::prelude::*;
 
 static mut MSG_TRACKER: Option<MessageTracker> = None;
 
-// TODO: Remove completed messages from tracker.
+/// Amount of blocks during which messages in the terminal state are kept in the tracker
+/// (approximately a day).
+const COMPLETED_MESSAGES_RETENTION: u32 = 28_800;
+
+/// Maximum amount of completed messages removed from the tracker at once. It bounds
+/// the extra gas that pruning adds to the `request_bridging` call.
+const MAX_PRUNED_MESSAGES: usize = 10;
+
 /// State machine which tracks state of each message that was submitted into
 /// `request_bridging` method.
 #[derive(Default, Debug)]
 pub struct MessageTracker {
     /// Message states.
-    pub message_info: HashMap<MessageId, MessageInfo>,
+    pub message_info: BTreeMap<MessageId, MessageInfo>,
+    /// Messages that have reached the terminal state along with the block it happened at.
+    /// Ordered by the block so the oldest ones are pruned first.
+    completed: VecDeque<(u32, MessageId)>,
 }
 
 /// Entry for a single message in [MessageTracker].
@@ -35,6 +50,8 @@ pub struct TxDetails {
     pub receiver: H160,
     /// [TokenSupply] type of the token being bridged.
     pub token_supply: TokenSupply,
+    /// Block the bridging was requested at. Transfer is accounted in the rate limits at it.
+    pub requested_at: u32,
 }
 
 /// State in which message processing can be.
@@ -56,6 +73,17 @@ pub enum MessageStatus {
     TokensReturnComplete(bool),
 }
 
+impl MessageStatus {
+    /// Check whether message processing is finished and can't be continued with
+    /// `handle_request_bridging_interrupted_transfer`.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::BridgeResponseReceived(Some(_)) | Self::TokensReturnComplete(true)
+        )
+    }
+}
+
 /// Initialize global state of the message tracker.
 pub fn init() {
     unsafe { MSG_TRACKER = Some(MessageTracker::default()) }
@@ -73,12 +101,17 @@ pub fn msg_tracker_mut() -> &'static mut MessageTracker {
 
 impl MessageTracker {
     /// Start tracking state of the message.
+    ///
+    /// It also removes messages that have been in the terminal state for longer
+    /// than the retention window.
     pub fn insert_message_info(
         &mut self,
         msg_id: MessageId,
         status: MessageStatus,
         details: TxDetails,
     ) {
+        self.prune_completed(exec::block_height());
+
         self.message_info
             .insert(msg_id, MessageInfo { status, details });
     }
@@ -86,10 +119,55 @@ impl MessageTracker {
     /// Drive state machine further for a given `msg_id`.
     pub fn update_message_status(&mut self, msg_id: MessageId, status: MessageStatus) {
         if let Some(info) = self.message_info.get_mut(&msg_id) {
+            if status.is_terminal() {
+                self.completed.push_back((exec::block_height(), msg_id));
+            }
+
             info.status = status;
         }
     }
 
+    /// Remove messages that have been in the terminal state for longer than
+    /// [COMPLETED_MESSAGES_RETENTION] blocks.
+    fn prune_completed(&mut self, now: u32) {
+        for _ in 0..MAX_PRUNED_MESSAGES {
+            let Some(&(block, msg_id)) = self.completed.front() else {
+                break;
+            };
+
+            if now < block.saturating_add(COMPLETED_MESSAGES_RETENTION) {
+                break;
+            }
+
+            self.completed.pop_front();
+
+            // Message could've been already removed from the tracker.
+            if self
+                .message_info
+                .get(&msg_id)
+                .map(|info| info.status.is_terminal())
+                .unwrap_or(false)
+            {
+                self.message_info.remove(&msg_id);
+            }
+        }
+    }
+
+    /// Get up to `count` tracked messages ordered by [MessageId] which go after the `after`
+    /// message or from the beginning if it's `None`.
+    pub fn messages(&self, after: Option<MessageId>, count: u32) -> Vec<(MessageId, MessageInfo)> {
+        let start = match after {
+            Some(after) => Bound::Excluded(after),
+            None => Bound::Unbounded,
+        };
+
+        self.message_info
+            .range((start, Bound::Unbounded))
+            .take(count as usize)
+            .map(|(msg_id, info)| (*msg_id, info.clone()))
+            .collect()
+    }
+
     /// Get current state of the tracked message. Will return `None` if message isn't found.
     pub fn get_message_info(&self, msg_id: &MessageId) -> Option<&MessageInfo> {
         self.message_info.get(msg_id)
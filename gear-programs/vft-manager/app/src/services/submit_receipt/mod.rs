@@ -1,8 +1,8 @@
-use collections::btree_set::BTreeSet;
+use collections::{btree_map::BTreeMap, btree_set::BTreeSet};
 use gstd::{static_mut, static_ref};
 use sails_rs::{gstd::ExecContext, prelude::*};
 
-use super::{error::Error, TokenSupply, VftManager};
+use super::{error::Error, Direction, Event, TokenSupply, VftManager};
 
 pub mod abi;
 pub mod token_operations;
@@ -15,6 +15,49 @@ static mut TRANSACTIONS: Option<BTreeSet<(u64, u64)>> = None;
 /// program can store.
 const TX_HISTORY_DEPTH: usize = 50_000_000;
 
+/// Ethereum transactions which tokens have failed to be delivered to the receiver on Gear.
+/// Such tokens can be refunded to the sender on Ethereum, see [super::refund].
+static mut FAILED_DELIVERIES: Option<BTreeMap<(u64, u64), FailedDelivery>> = None;
+
+/// Tokens deposited on Ethereum that should be delivered to the receiver on Gear.
+#[derive(Debug, Clone, Encode, Decode, TypeInfo)]
+pub struct Deposit {
+    /// `VFT` token address that should be minted/unlocked.
+    pub vara_token_id: ActorId,
+    /// `ERC20` token address that was locked/burned on Ethereum.
+    pub eth_token_id: H160,
+    /// Original token owner on the Ethereum side.
+    pub sender: H160,
+    /// Receiver of the tokens on the Gear side.
+    pub receiver: ActorId,
+    /// Deposited amount of tokens.
+    pub amount: U256,
+}
+
+/// State of the refund of a [FailedDelivery].
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum RefundStatus {
+    /// Refund can be claimed.
+    NotRequested,
+    /// Message to the `gear-eth-bridge` built-in actor is sent, waiting for the reply.
+    Requested,
+    /// Refund message is queued by the `gear-eth-bridge` built-in actor with the nonce.
+    Completed(U256),
+}
+
+/// Deposit which tokens the `VFT` program has refused to mint/unlock.
+#[derive(Debug, Clone, Encode, Decode, TypeInfo)]
+pub struct FailedDelivery {
+    /// Details of the deposit.
+    pub deposit: Deposit,
+    /// State of the refund to the sender on Ethereum.
+    pub refund: RefundStatus,
+}
+
+/// Transactions from the slots before this one are rejected as too old. It follows the first
+/// slot the light client can still prove, so transactions below it are removed from [TRANSACTIONS].
+static mut SLOT_FLOOR: u64 = 0;
+
 /// Get reference to a transactions storage.
 pub fn transactions() -> &'static BTreeSet<(u64, u64)> {
     unsafe { static_ref!(TRANSACTIONS).as_ref() }.expect("Program should be constructed")
@@ -25,10 +68,57 @@ pub fn transactions_mut() -> &'static mut BTreeSet<(u64, u64)> {
     unsafe { static_mut!(TRANSACTIONS).as_mut() }.expect("Program should be constructed")
 }
 
+/// Get reference to a failed deliveries storage.
+pub fn failed_deliveries() -> &'static BTreeMap<(u64, u64), FailedDelivery> {
+    unsafe { static_ref!(FAILED_DELIVERIES).as_ref() }.expect("Program should be constructed")
+}
+
+/// Get mutable reference to a failed deliveries storage.
+pub fn failed_deliveries_mut() -> &'static mut BTreeMap<(u64, u64), FailedDelivery> {
+    unsafe { static_mut!(FAILED_DELIVERIES).as_mut() }.expect("Program should be constructed")
+}
+
+/// Get the slot starting from which transactions are accepted.
+pub fn slot_floor() -> u64 {
+    unsafe { SLOT_FLOOR }
+}
+
+/// Raise the slot floor up to `slot_provable_first` and remove up to `count` processed
+/// transactions that are below it. Returns the amount of removed transactions.
+pub fn prune_transactions(slot_provable_first: u64, count: u32) -> u32 {
+    let floor = cmp::max(slot_floor(), slot_provable_first);
+    unsafe {
+        SLOT_FLOOR = floor;
+    }
+
+    let transactions = transactions_mut();
+    let mut pruned = 0;
+    while pruned < count
+        && transactions
+            .first()
+            .map(|(slot, _)| *slot < floor)
+            .unwrap_or(false)
+    {
+        transactions.pop_first();
+        pruned += 1;
+    }
+
+    pruned
+}
+
+/// Insert already processed transactions, e.g. when migrating state from the previous
+/// program. Transactions below the slot floor are skipped.
+pub fn insert_transactions(processed: Vec<(u64, u64)>) {
+    let floor = slot_floor();
+
+    transactions_mut().extend(processed.into_iter().filter(|(slot, _)| *slot >= floor));
+}
+
 /// Initialize state that's used by this VFT Manager method.
 pub fn seed() {
     unsafe {
         TRANSACTIONS = Some(BTreeSet::new());
+        FAILED_DELIVERIES = Some(BTreeMap::new());
     }
 }
 
@@ -38,6 +128,9 @@ pub fn seed() {
 /// sent to `ERC20Manager` contract. Also it will check that this transaction haven't been
 /// processed yet.
 ///
+/// If the `VFT` program refuses to mint/unlock tokens, transaction is considered processed
+/// and the [FailedDelivery] is recorded so the tokens can be refunded on Ethereum.
+///
 /// This method can be called only by [State::historical_proxy_address] program.
 pub async fn submit_receipt<T: ExecContext>(
     service: &mut VftManager<T>,
@@ -83,6 +176,10 @@ pub async fn submit_receipt<T: ExecContext>(
 
     let transactions = transactions_mut();
     let key = (slot, transaction_index);
+    if slot < slot_floor() {
+        return Err(Error::TransactionTooOld);
+    }
+
     if transactions.contains(&key) {
         return Err(Error::AlreadyProcessed);
     }
@@ -98,32 +195,53 @@ pub async fn submit_receipt<T: ExecContext>(
 
     let amount = U256::from_little_endian(event.amount.as_le_slice());
     let receiver = ActorId::from(event.to.0);
+    let deposit = Deposit {
+        vara_token_id,
+        eth_token_id: H160::from(event.token.0 .0),
+        sender: H160::from(event.from.0 .0),
+        receiver,
+        amount,
+    };
+
+    service
+        .state()
+        .token_map
+        .ensure_not_paused(&vara_token_id, Direction::EthToVara)?;
+    service.consume_rate_limit(vara_token_id, Direction::EthToVara, amount)?;
+    let consumed_at = gstd::exec::block_height();
 
-    match service.state().token_map.get_supply_type(&vara_token_id)? {
+    let result = match service.state().token_map.get_supply_type(&vara_token_id)? {
         TokenSupply::Ethereum => {
-            token_operations::mint(
-                slot,
-                transaction_index,
-                vara_token_id,
-                receiver,
-                amount,
-                service.config(),
-            )
-            .await
+            token_operations::mint(slot, transaction_index, deposit.clone(), service.config()).await
         }
 
         TokenSupply::Gear => {
-            token_operations::unlock(
+            token_operations::unlock(slot, transaction_index, deposit.clone(), service.config())
+                .await
+        }
+    };
+
+    // Tokens aren't delivered if the `VFT` program has refused them or the transaction
+    // isn't recorded as processed so it can be submitted again.
+    let delivered = transactions().contains(&key) && !failed_deliveries().contains_key(&key);
+    if result.is_err() && !delivered {
+        service.release_rate_limit(vara_token_id, Direction::EthToVara, amount, consumed_at);
+    }
+
+    if let Err(Error::DeliveryFailed) = result {
+        service
+            .notify_on(Event::DeliveryFailed {
                 slot,
                 transaction_index,
                 vara_token_id,
+                sender: deposit.sender,
                 receiver,
                 amount,
-                service.config(),
-            )
-            .await
-        }
+            })
+            .expect("Failed to emit event");
     }
+
+    result
 }
 
 pub fn fill_transactions() -> bool {
@@ -1,28 +1,32 @@
 use super::super::{Config, Error};
-use gstd::msg;
+use super::{Deposit, FailedDelivery, RefundStatus};
+use gstd::{
+    errors::{ErrorReplyReason, ReplyCode, SimpleExecutionError},
+    msg,
+};
 use sails_rs::{calls::ActionIo, prelude::*};
 use vft_client::{vft::io::TransferFrom, vft_admin::io::Mint};
 
 trait Reply {
-    fn check(&self);
+    fn is_success(&self) -> bool;
 }
 
 impl Reply for () {
-    fn check(&self) {}
+    fn is_success(&self) -> bool {
+        true
+    }
 }
 
 impl Reply for bool {
-    fn check(&self) {
-        if !*self {
-            panic!("Request to transfer tokens failed");
-        }
+    fn is_success(&self) -> bool {
+        *self
     }
 }
 
 async fn send<Action>(
     slot: u64,
     transaction_index: u64,
-    token_id: ActorId,
+    deposit: Deposit,
     params: &Action::Params,
     config: &Config,
 ) -> Result<(), Error>
@@ -36,32 +40,66 @@ where
     // If there is not enough gas for execution then the VFT-program will exit because of
     // the out of gas and hence its state will be reverted. That means that no tokens will be
     // minted/transferred and moreover our reply hook will not get ever executed.
-    gstd::msg::send_bytes_for_reply(token_id, payload, 0, config.gas_for_reply_deposit)
-        .map_err(|_| Error::SendFailure)?
-        .up_to(Some(config.reply_timeout))
-        .map_err(|_| Error::ReplyTimeout)?
-        .handle_reply(move || handle_reply::<Action>(slot, transaction_index))
-        .map_err(|_| Error::ReplyHook)?
-        .await
-        .map_err(|_| Error::ReplyFailure)?;
+    let token_id = deposit.vara_token_id;
+    let result =
+        gstd::msg::send_bytes_for_reply(token_id, payload, 0, config.gas_for_reply_deposit)
+            .map_err(|_| Error::SendFailure)?
+            .up_to(Some(config.reply_timeout))
+            .map_err(|_| Error::ReplyTimeout)?
+            .handle_reply(move || handle_reply::<Action>(slot, transaction_index, deposit))
+            .map_err(|_| Error::ReplyHook)?
+            .await;
+
+    // Reply hook has recorded the delivery as failed so the transaction is processed
+    // but tokens aren't delivered.
+    if super::failed_deliveries().contains_key(&(slot, transaction_index)) {
+        return Err(Error::DeliveryFailed);
+    }
+
+    result.map_err(|_| Error::ReplyFailure)?;
 
     Ok(())
 }
 
-fn handle_reply<Action>(slot: u64, transaction_index: u64)
+fn handle_reply<Action>(slot: u64, transaction_index: u64, deposit: Deposit)
 where
     Action: ActionIo,
     Action::Reply: Reply,
 {
-    let reply_bytes = msg::load_bytes()
-        .expect("May fail because of the insufficient gas only but the limit was specified by the caller; qed");
-    let reply = Action::decode_reply(&reply_bytes)
-        .expect("May fail only if there is no VFT-program at the specified address; qed");
+    let reply_code =
+        msg::reply_code().expect("Shouldn't fail since called from the reply hook; qed");
+
+    // The VFT-program has run out of gas so its state is reverted and the request
+    // can be retried later with more gas.
+    if let ReplyCode::Error(ErrorReplyReason::Execution(SimpleExecutionError::RanOutOfGas)) =
+        reply_code
+    {
+        panic!("VFT-program has run out of gas");
+    }
+
+    let delivered = reply_code.is_success()
+        && {
+            let reply_bytes = msg::load_bytes()
+            .expect("May fail because of the insufficient gas only but the limit was specified by the caller; qed");
+            let reply = Action::decode_reply(&reply_bytes)
+                .expect("May fail only if there is no VFT-program at the specified address; qed");
 
-    reply.check();
+            reply.is_success()
+        };
 
-    // To that point we have a successful response from the VFT and enough gas to save
-    // the information about processed Ethereum transaction.
+    // To that point we have a definitive response from the VFT and enough gas to save
+    // the information about processed Ethereum transaction. If the tokens weren't
+    // delivered, the transaction is still considered processed but it becomes refundable.
+
+    if !delivered {
+        super::failed_deliveries_mut().insert(
+            (slot, transaction_index),
+            FailedDelivery {
+                deposit,
+                refund: RefundStatus::NotRequested,
+            },
+        );
+    }
 
     let transactions = super::transactions_mut();
     if super::TX_HISTORY_DEPTH <= transactions.len() {
@@ -71,29 +109,22 @@ where
     transactions.insert((slot, transaction_index));
 }
 
-/// Mint `amount` tokens into the `receiver` address.
+/// Mint deposited tokens into the receiver address.
 ///
 /// It will send `Mint` call to the corresponding `VFT` program and
 /// asyncronously wait for the reply.
 pub async fn mint(
     slot: u64,
     transaction_index: u64,
-    token_id: ActorId,
-    receiver: ActorId,
-    amount: U256,
+    deposit: Deposit,
     config: &Config,
 ) -> Result<(), Error> {
-    send::<Mint>(
-        slot,
-        transaction_index,
-        token_id,
-        &(receiver, amount),
-        config,
-    )
-    .await
+    let params = (deposit.receiver, deposit.amount);
+
+    send::<Mint>(slot, transaction_index, deposit, &params, config).await
 }
 
-/// Transfer `amount` tokens from the current program address to the `receiver` address,
+/// Transfer deposited tokens from the current program address to the receiver address,
 /// effectively unlocking them.
 ///
 /// It will send `TransferFrom` call to the corresponding `VFT` program and
@@ -101,19 +132,11 @@ pub async fn mint(
 pub async fn unlock(
     slot: u64,
     transaction_index: u64,
-    token_id: ActorId,
-    receiver: ActorId,
-    amount: U256,
+    deposit: Deposit,
     config: &Config,
 ) -> Result<(), Error> {
     let sender = gstd::exec::program_id();
+    let params = (sender, deposit.receiver, deposit.amount);
 
-    send::<TransferFrom>(
-        slot,
-        transaction_index,
-        token_id,
-        &(sender, receiver, amount),
-        config,
-    )
-    .await
+    send::<TransferFrom>(slot, transaction_index, deposit, &params, config).await
 }
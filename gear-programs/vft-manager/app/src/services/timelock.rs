@@ -0,0 +1,127 @@
+//! Delayed execution of the sensitive admin operations.
+//!
+//! When the delay is non-zero, admin can't apply such operations directly. Instead they're
+//! scheduled and can be executed only after the delay passes, giving users time to react and
+//! [pause admin](super::State::pause_admin) an opportunity to cancel them.
+
+use collections::BTreeMap;
+use sails_rs::prelude::*;
+
+use super::{Config, Direction, RateLimit, TokenSupply};
+
+pub type OperationId = u64;
+
+/// Admin operation that is subject to the timelock.
+#[derive(Debug, Clone, Encode, Decode, TypeInfo)]
+pub enum AdminOperation {
+    /// See [super::VftManager::map_vara_to_eth_address].
+    MapVaraToEthAddress {
+        vara_token_id: ActorId,
+        eth_token_id: H160,
+        supply_type: TokenSupply,
+    },
+    /// See [super::VftManager::update_historical_proxy_address].
+    UpdateHistoricalProxyAddress(ActorId),
+    /// See [super::VftManager::update_erc20_manager_address].
+    UpdateErc20ManagerAddress(H160),
+    /// See [super::VftManager::update_checkpoint_light_client_address].
+    UpdateCheckpointLightClientAddress(ActorId),
+    /// See [super::VftManager::upgrade].
+    Upgrade(ActorId),
+    /// See [super::VftManager::update_timelock_delay].
+    UpdateTimelockDelay(u32),
+    /// See [super::VftManager::remove_vara_to_eth_address].
+    RemoveVaraToEthAddress(ActorId),
+    /// See [super::VftManager::update_config].
+    UpdateConfig(Config),
+    /// See [super::VftManager::update_vfts].
+    UpdateVfts(Vec<(ActorId, ActorId)>),
+    /// See [super::VftManager::set_rate_limit].
+    SetRateLimit {
+        vara_token_id: ActorId,
+        direction: Direction,
+        limit: Option<RateLimit>,
+    },
+    /// See [super::VftManager::set_admin].
+    SetAdmin(ActorId),
+    /// See [super::VftManager::set_pause_admin].
+    SetPauseAdmin(ActorId),
+}
+
+/// Operation waiting for its delay to pass.
+#[derive(Debug, Clone, Encode, Decode, TypeInfo)]
+pub struct PendingOperation {
+    /// Scheduled operation.
+    pub operation: AdminOperation,
+    /// Block starting from which the operation can be executed.
+    pub executable_at: u32,
+}
+
+/// Queue of the scheduled admin operations.
+#[derive(Debug, Default)]
+pub struct Timelock {
+    /// Delay in blocks between scheduling of an operation and its execution.
+    /// Operations are applied immediately if it's zero.
+    delay: u32,
+    next_id: OperationId,
+    pending: BTreeMap<OperationId, PendingOperation>,
+}
+
+impl Timelock {
+    pub fn delay(&self) -> u32 {
+        self.delay
+    }
+
+    pub fn set_delay(&mut self, delay: u32) {
+        self.delay = delay;
+    }
+
+    /// Add operation to the queue. Returns its id and the block starting from which
+    /// it can be executed.
+    pub fn schedule(&mut self, operation: AdminOperation, now: u32) -> (OperationId, u32) {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let executable_at = now.saturating_add(self.delay);
+        self.pending.insert(
+            id,
+            PendingOperation {
+                operation,
+                executable_at,
+            },
+        );
+
+        (id, executable_at)
+    }
+
+    /// Remove operation from the queue if its delay has passed.
+    ///
+    /// Will panic if operation isn't found or isn't executable yet.
+    pub fn take_executable(&mut self, id: OperationId, now: u32) -> AdminOperation {
+        let pending = self.pending.get(&id).expect("Operation not found");
+        if now < pending.executable_at {
+            panic!(
+                "Operation is executable since block {}",
+                pending.executable_at
+            );
+        }
+
+        self.pending
+            .remove(&id)
+            .expect("Operation is checked to exist above; qed")
+            .operation
+    }
+
+    /// Remove operation from the queue. Will panic if operation isn't found.
+    pub fn cancel(&mut self, id: OperationId) {
+        self.pending.remove(&id).expect("Operation not found");
+    }
+
+    /// Read state of the queue. Will return all the pending operations.
+    pub fn read_state(&self) -> Vec<(OperationId, PendingOperation)> {
+        self.pending
+            .iter()
+            .map(|(id, pending)| (*id, pending.clone()))
+            .collect()
+    }
+}
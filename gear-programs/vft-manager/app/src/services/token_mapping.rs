@@ -3,13 +3,39 @@ use gstd::errors::{Error as GStdError, ErrorReplyReason};
 use sails_rs::{calls::*, errors::Error as SailsError, gstd::calls::GStdRemoting, prelude::*};
 use vft_client::traits::Vft;
 
-use super::{error::Error, TokenSupply};
+use super::{error::Error, Direction, TokenSupply};
+
+/// Directions in which bridging of the token pair is paused.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct PausedDirections {
+    /// Bridging from Ethereum to Gear is paused.
+    pub eth_to_vara: bool,
+    /// Bridging from Gear to Ethereum is paused.
+    pub vara_to_eth: bool,
+}
+
+impl PausedDirections {
+    fn flag_mut(&mut self, direction: Direction) -> &mut bool {
+        match direction {
+            Direction::EthToVara => &mut self.eth_to_vara,
+            Direction::VaraToEth => &mut self.vara_to_eth,
+        }
+    }
+
+    fn is_paused(&self, direction: Direction) -> bool {
+        match direction {
+            Direction::EthToVara => self.eth_to_vara,
+            Direction::VaraToEth => self.vara_to_eth,
+        }
+    }
+}
 
 /// Mapping between `VFT` and `ERC20` tokens.
 #[derive(Debug, Default)]
 pub struct TokenMap {
-    /// Mapping from `VFT` token addresses to `ERC20` token addresses and the [TokenSupply] type.
-    vara_to_eth: HashMap<ActorId, (H160, TokenSupply)>,
+    /// Mapping from `VFT` token addresses to `ERC20` token addresses, the [TokenSupply] type
+    /// and the [PausedDirections] of the pair.
+    vara_to_eth: HashMap<ActorId, (H160, TokenSupply, PausedDirections)>,
     /// Mapping from `ERC20` token addresses to `VFT` token addresses.
     eth_to_vara: HashMap<H160, ActorId>,
 }
@@ -21,7 +47,10 @@ impl TokenMap {
     pub fn insert(&mut self, vara_token_id: ActorId, eth_token_id: H160, supply: TokenSupply) {
         if self
             .vara_to_eth
-            .insert(vara_token_id, (eth_token_id, supply))
+            .insert(
+                vara_token_id,
+                (eth_token_id, supply, PausedDirections::default()),
+            )
             .is_some()
         {
             panic!("Mapping already present");
@@ -40,7 +69,7 @@ impl TokenMap {
     ///
     /// Will return error if `vara_token_id` don't correspond to the already existing mapping.
     pub fn remove(&mut self, vara_token_id: ActorId) -> (H160, TokenSupply) {
-        let (eth_token_id, supply_type) = self
+        let (eth_token_id, supply_type, _paused) = self
             .vara_to_eth
             .remove(&vara_token_id)
             .expect("Mapping not found");
@@ -60,7 +89,7 @@ impl TokenMap {
         self.vara_to_eth
             .get(vara_token_id)
             .cloned()
-            .map(|(eth_token_id, _supply)| eth_token_id)
+            .map(|(eth_token_id, _supply, _paused)| eth_token_id)
             .ok_or(Error::NoCorrespondingEthAddress)
     }
 
@@ -81,22 +110,65 @@ impl TokenMap {
         self.vara_to_eth
             .get(vara_token_id)
             .cloned()
-            .map(|(_eth_token_id, supply)| supply)
+            .map(|(_eth_token_id, supply, _paused)| supply)
             .ok_or(Error::NoCorrespondingVaraAddress)
     }
 
+    /// Set pause flag of the token pair in the specified direction.
+    ///
+    /// Returns `false` if the flag already has the requested value. Will panic if mapping
+    /// isn't found.
+    pub fn set_paused(
+        &mut self,
+        vara_token_id: ActorId,
+        direction: Direction,
+        paused: bool,
+    ) -> bool {
+        let (_eth_token_id, _supply, paused_directions) = self
+            .vara_to_eth
+            .get_mut(&vara_token_id)
+            .expect("Mapping not found");
+
+        let flag = paused_directions.flag_mut(direction);
+        if *flag == paused {
+            return false;
+        }
+
+        *flag = paused;
+
+        true
+    }
+
+    /// Check that bridging of the token pair isn't paused in the specified direction.
+    ///
+    /// Tokens that aren't present in the mapping are considered not paused.
+    pub fn ensure_not_paused(
+        &self,
+        vara_token_id: &ActorId,
+        direction: Direction,
+    ) -> Result<(), Error> {
+        match self.vara_to_eth.get(vara_token_id) {
+            Some((_eth_token_id, _supply, paused)) if paused.is_paused(direction) => {
+                Err(Error::TokenPaused)
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Read state of the token mapping. Will return all entries present in the mapping.
-    pub fn read_state(&self) -> Vec<(ActorId, H160, TokenSupply)> {
+    pub fn read_state(&self) -> Vec<(ActorId, H160, TokenSupply, PausedDirections)> {
         self.vara_to_eth
             .clone()
             .into_iter()
-            .map(|(vara_token, (eth_token, supply))| (vara_token, eth_token, supply))
+            .map(|(vara_token, (eth_token, supply, paused))| {
+                (vara_token, eth_token, supply, paused)
+            })
             .collect()
     }
 
     fn swap_maps(
         &mut self,
-        vara_to_eth: &mut HashMap<ActorId, (H160, TokenSupply)>,
+        vara_to_eth: &mut HashMap<ActorId, (H160, TokenSupply, PausedDirections)>,
         eth_to_vara: &mut HashMap<H160, ActorId>,
     ) {
         mem::swap(&mut self.vara_to_eth, vara_to_eth);
@@ -117,7 +189,7 @@ impl TokenMap {
 
         let vft_manager = gstd::exec::program_id();
         let service = vft_client::Vft::new(GStdRemoting);
-        for (vft, (erc20, supply)) in &self.vara_to_eth {
+        for (vft, (erc20, supply, paused)) in &self.vara_to_eth {
             let vft_new = match vft_map
                 .iter()
                 .find_map(|(vft_old, vft_new)| (vft_old == vft).then_some(vft_new))
@@ -143,7 +215,7 @@ impl TokenMap {
                 },
             };
 
-            vara_to_eth.insert(*vft_new, (*erc20, *supply));
+            vara_to_eth.insert(*vft_new, (*erc20, *supply, *paused));
             eth_to_vara.insert(*erc20, *vft_new);
         }
 
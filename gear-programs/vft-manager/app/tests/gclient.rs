@@ -533,6 +533,7 @@ async fn msg_tracker_state() -> Result<()> {
                 amount: Default::default(),
                 receiver: Default::default(),
                 token_supply: vft_manager_client::TokenSupply::Ethereum,
+                requested_at: 0,
             },
         )
         .send_recv(vft_manager_id)
@@ -540,14 +541,14 @@ async fn msg_tracker_state() -> Result<()> {
         .map_err(|e| anyhow!("{e:?}"))?;
 
     let result = service
-        .request_briding_msg_tracker_state(1, 10)
+        .request_bridging_msg_tracker_page(Some(Default::default()), 10)
         .recv(vft_manager_id)
         .await
         .map_err(|e| anyhow!("{e:?}"))?;
     assert!(result.is_empty());
 
     let result = service
-        .request_briding_msg_tracker_state(0, 2)
+        .request_bridging_msg_tracker_page(None, 2)
         .recv(vft_manager_id)
         .await
         .map_err(|e| anyhow!("{e:?}"))?;
@@ -564,6 +565,7 @@ async fn msg_tracker_state() -> Result<()> {
                     amount: Default::default(),
                     receiver: Default::default(),
                     token_supply: vft_manager_client::TokenSupply::Ethereum,
+                    requested_at: 0,
                 },
             }
         )
@@ -981,7 +983,7 @@ async fn update_vfts() -> Result<()> {
         (extended_vft_id_2, ([3u8; 20].into(), TokenSupply::Ethereum)),
     ]
     .into();
-    for (vft, erc20, supply) in service
+    for (vft, erc20, supply, _paused) in service
         .vara_to_eth_addresses()
         .with_gas_limit(gas_limit)
         .recv(vft_manager_id)
@@ -4,8 +4,8 @@ use sails_rs::{calls::*, gtest::calls::*, prelude::*};
 use vft_client::{traits::*, Vft as VftC, VftAdmin as VftAdminC, VftFactory as VftFactoryC};
 use vft_manager_app::services::eth_abi::ERC20_MANAGER;
 use vft_manager_client::{
-    traits::*, Config, Error, InitConfig, TokenSupply, VftManager as VftManagerC,
-    VftManagerFactory as VftManagerFactoryC,
+    traits::*, AdminOperation, Config, Direction, Error, InitConfig, PausedDirections, RateLimit,
+    RefundStatus, TokenSupply, VftManager as VftManagerC, VftManagerFactory as VftManagerFactoryC,
 };
 
 const REMOTING_ACTOR_ID: u64 = 1_000;
@@ -436,6 +436,735 @@ async fn test_pause_works() {
     assert_paused!(false);
 }
 
+#[tokio::test]
+async fn test_pause_token_works() {
+    let Fixture {
+        remoting,
+        vft_manager_program_id,
+        gear_supply_vft,
+        eth_supply_vft,
+    } = setup_for_test().await;
+
+    let mut vft_manager = VftManagerC::new(remoting.clone());
+
+    vft_manager
+        .pause_token(eth_supply_vft, Direction::EthToVara)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    vft_manager
+        .pause_token(gear_supply_vft, Direction::VaraToEth)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+
+    let mappings = vft_manager
+        .vara_to_eth_addresses()
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    for (vft, _erc20, _supply, paused) in mappings {
+        let expected = PausedDirections {
+            eth_to_vara: vft == eth_supply_vft,
+            vara_to_eth: vft == gear_supply_vft,
+        };
+        assert_eq!(paused, expected);
+    }
+
+    let account_id: ActorId = 100_000.into();
+    let amount = U256::from(10_000_000_000_u64);
+    let mut historical_proxy =
+        VftManagerC::new(remoting.clone().with_actor_id(HISTORICAL_PROXY_ID.into()));
+
+    let receipt_rlp = create_receipt_rlp(account_id, ERC20_TOKEN_ETH_SUPPLY, amount);
+    let result = historical_proxy
+        .submit_receipt(0, 0, receipt_rlp)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(result, Err(Error::TokenPaused));
+
+    let result = vft_manager
+        .request_bridging(gear_supply_vft, amount, ETH_TOKEN_RECEIVER)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(result, Err(Error::TokenPaused));
+
+    // Other directions and token pairs keep working.
+    let receipt_rlp = create_receipt_rlp(account_id, ERC20_TOKEN_GEAR_SUPPLY, U256::zero());
+    let result = historical_proxy
+        .submit_receipt(0, 1, receipt_rlp)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_ne!(result, Err(Error::TokenPaused));
+
+    vft_manager
+        .unpause_token(eth_supply_vft, Direction::EthToVara)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+
+    let receipt_rlp = create_receipt_rlp(account_id, ERC20_TOKEN_ETH_SUPPLY, amount);
+    historical_proxy
+        .submit_receipt(0, 0, receipt_rlp)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let account_balance = balance_of(&remoting, eth_supply_vft, account_id).await;
+    assert_eq!(account_balance, amount);
+}
+
+#[tokio::test]
+async fn test_rate_limits() {
+    let Fixture {
+        remoting,
+        vft_manager_program_id,
+        gear_supply_vft,
+        eth_supply_vft,
+    } = setup_for_test().await;
+
+    let account_id: ActorId = 100_000.into();
+    remoting.system().mint_to(account_id, 100_000_000_000_000);
+
+    let mut vft_manager = VftManagerC::new(remoting.clone());
+    vft_manager
+        .set_rate_limit(
+            gear_supply_vft,
+            Direction::VaraToEth,
+            Some(RateLimit {
+                max_per_transfer: Some(100.into()),
+                window_cap: Some(150.into()),
+                window: 1_000,
+            }),
+        )
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    vft_manager
+        .set_rate_limit(
+            eth_supply_vft,
+            Direction::EthToVara,
+            Some(RateLimit {
+                max_per_transfer: Some(50.into()),
+                window_cap: None,
+                window: 0,
+            }),
+        )
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+
+    let rate_limits = vft_manager
+        .rate_limits()
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(rate_limits.len(), 2);
+
+    VftAdminC::new(remoting.clone())
+        .mint(account_id, 1_000.into())
+        .send_recv(gear_supply_vft)
+        .await
+        .unwrap();
+
+    let ok = VftC::new(remoting.clone().with_actor_id(account_id))
+        .approve(vft_manager_program_id, 1_000.into())
+        .send_recv(gear_supply_vft)
+        .await
+        .unwrap();
+    assert!(ok);
+
+    let mut user_vft_manager = VftManagerC::new(remoting.clone().with_actor_id(account_id));
+    let result = user_vft_manager
+        .request_bridging(gear_supply_vft, 101.into(), ETH_TOKEN_RECEIVER)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(result, Err(Error::TransferLimitExceeded));
+
+    let result = user_vft_manager
+        .request_bridging(gear_supply_vft, 100.into(), ETH_TOKEN_RECEIVER)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert!(result.is_ok());
+
+    let usage = vft_manager
+        .rate_limit_usage(gear_supply_vft, Direction::VaraToEth)
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(usage, 100.into());
+
+    let result = user_vft_manager
+        .request_bridging(gear_supply_vft, 60.into(), ETH_TOKEN_RECEIVER)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(result, Err(Error::WindowCapExceeded));
+
+    // Limits of the other direction aren't affected.
+    let usage = vft_manager
+        .rate_limit_usage(gear_supply_vft, Direction::EthToVara)
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert!(usage.is_zero());
+
+    let receipt_rlp = create_receipt_rlp(account_id, ERC20_TOKEN_ETH_SUPPLY, 51.into());
+    let result = VftManagerC::new(remoting.clone().with_actor_id(HISTORICAL_PROXY_ID.into()))
+        .submit_receipt(0, 0, receipt_rlp)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(result, Err(Error::TransferLimitExceeded));
+
+    let balance = balance_of(&remoting, eth_supply_vft, account_id).await;
+    assert!(balance.is_zero());
+}
+
+#[tokio::test]
+async fn test_rate_limit_released_on_failed_deposit() {
+    let Fixture {
+        remoting,
+        vft_manager_program_id,
+        eth_supply_vft,
+        ..
+    } = setup_for_test().await;
+
+    let account_id: ActorId = 100_000.into();
+    remoting.system().mint_to(account_id, 100_000_000_000_000);
+
+    let mut vft_manager = VftManagerC::new(remoting.clone());
+    vft_manager
+        .set_rate_limit(
+            eth_supply_vft,
+            Direction::VaraToEth,
+            Some(RateLimit {
+                max_per_transfer: None,
+                window_cap: Some(150.into()),
+                window: 1_000,
+            }),
+        )
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+
+    // Account has no tokens to burn.
+    let mut user_vft_manager = VftManagerC::new(remoting.clone().with_actor_id(account_id));
+    let result = user_vft_manager
+        .request_bridging(eth_supply_vft, 100.into(), ETH_TOKEN_RECEIVER)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert!(result.is_err());
+
+    let usage = vft_manager
+        .rate_limit_usage(eth_supply_vft, Direction::VaraToEth)
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert!(usage.is_zero());
+
+    VftAdminC::new(remoting.clone())
+        .mint(account_id, 150.into())
+        .send_recv(eth_supply_vft)
+        .await
+        .unwrap();
+
+    let result = user_vft_manager
+        .request_bridging(eth_supply_vft, 150.into(), ETH_TOKEN_RECEIVER)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert!(result.is_ok());
+
+    let usage = vft_manager
+        .rate_limit_usage(eth_supply_vft, Direction::VaraToEth)
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(usage, 150.into());
+}
+
+#[tokio::test]
+async fn test_rate_limit_window_rollover() {
+    let Fixture {
+        remoting,
+        vft_manager_program_id,
+        gear_supply_vft,
+        ..
+    } = setup_for_test().await;
+
+    const WINDOW: u32 = 100;
+
+    let account_id: ActorId = 100_000.into();
+    remoting.system().mint_to(account_id, 100_000_000_000_000);
+
+    VftAdminC::new(remoting.clone())
+        .mint(account_id, 1_000.into())
+        .send_recv(gear_supply_vft)
+        .await
+        .unwrap();
+
+    let ok = VftC::new(remoting.clone().with_actor_id(account_id))
+        .approve(vft_manager_program_id, 1_000.into())
+        .send_recv(gear_supply_vft)
+        .await
+        .unwrap();
+    assert!(ok);
+
+    // Fixed windows start at the block the limit is set at.
+    let mut vft_manager = VftManagerC::new(remoting.clone());
+    vft_manager
+        .set_rate_limit(
+            gear_supply_vft,
+            Direction::VaraToEth,
+            Some(RateLimit {
+                max_per_transfer: None,
+                window_cap: Some(150.into()),
+                window: WINDOW,
+            }),
+        )
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+
+    let mut user_vft_manager = VftManagerC::new(remoting.clone().with_actor_id(account_id));
+    let result = user_vft_manager
+        .request_bridging(gear_supply_vft, 100.into(), ETH_TOKEN_RECEIVER)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert!(result.is_ok());
+
+    let result = user_vft_manager
+        .request_bridging(gear_supply_vft, 100.into(), ETH_TOKEN_RECEIVER)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(result, Err(Error::WindowCapExceeded));
+
+    // The previous window is partially overlapped by the rolling one.
+    for _ in 0..WINDOW + WINDOW / 2 {
+        remoting.system().run_next_block();
+    }
+
+    let usage = vft_manager
+        .rate_limit_usage(gear_supply_vft, Direction::VaraToEth)
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert!(!usage.is_zero());
+    assert!(usage < 100.into());
+
+    // The transfer has left the rolling window completely.
+    for _ in 0..WINDOW {
+        remoting.system().run_next_block();
+    }
+
+    let usage = vft_manager
+        .rate_limit_usage(gear_supply_vft, Direction::VaraToEth)
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert!(usage.is_zero());
+
+    let result = user_vft_manager
+        .request_bridging(gear_supply_vft, 150.into(), ETH_TOKEN_RECEIVER)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_timelock() {
+    let Fixture {
+        remoting,
+        vft_manager_program_id,
+        gear_supply_vft,
+        ..
+    } = setup_for_test().await;
+
+    const DELAY: u32 = 5;
+    let erc20_manager_address_new = H160([2; 20]);
+    let pause_admin_new = 22222.into();
+
+    let mut vft_manager = VftManagerC::new(remoting.clone());
+
+    let pause_admin = 11111.into();
+    let pause_remoting = remoting.clone().with_actor_id(pause_admin);
+    pause_remoting
+        .system()
+        .mint_to(pause_admin, 100_000_000_000_000);
+    let mut pause_admin_vft_manager = VftManagerC::new(pause_remoting);
+
+    vft_manager
+        .set_pause_admin(pause_admin)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    vft_manager
+        .update_timelock_delay(DELAY)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+
+    let result = vft_manager
+        .update_erc20_manager_address(erc20_manager_address_new)
+        .send_recv(vft_manager_program_id)
+        .await;
+    assert!(result.is_err());
+
+    let result = vft_manager
+        .remove_vara_to_eth_address(gear_supply_vft)
+        .send_recv(vft_manager_program_id)
+        .await;
+    assert!(result.is_err());
+
+    let config = vft_manager
+        .get_config()
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    let result = vft_manager
+        .update_config(config)
+        .send_recv(vft_manager_program_id)
+        .await;
+    assert!(result.is_err());
+
+    let result = vft_manager
+        .update_vfts(vec![])
+        .send_recv(vft_manager_program_id)
+        .await;
+    assert!(result.is_err());
+
+    // admins and rate limits can't be changed instantly either
+    let result = vft_manager
+        .set_admin(pause_admin)
+        .send_recv(vft_manager_program_id)
+        .await;
+    assert!(result.is_err());
+
+    let result = vft_manager
+        .set_pause_admin(pause_admin_new)
+        .send_recv(vft_manager_program_id)
+        .await;
+    assert!(result.is_err());
+
+    let result = vft_manager
+        .set_rate_limit(gear_supply_vft, Direction::VaraToEth, None)
+        .send_recv(vft_manager_program_id)
+        .await;
+    assert!(result.is_err());
+
+    let id = vft_manager
+        .schedule_operation(AdminOperation::UpdateErc20ManagerAddress(
+            erc20_manager_address_new,
+        ))
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    let remove_id = vft_manager
+        .schedule_operation(AdminOperation::RemoveVaraToEthAddress(gear_supply_vft))
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    let pause_admin_id = vft_manager
+        .schedule_operation(AdminOperation::SetPauseAdmin(pause_admin_new))
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    let cancelled_id = vft_manager
+        .schedule_operation(AdminOperation::UpdateHistoricalProxyAddress(42.into()))
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+
+    let result = vft_manager
+        .execute_operation(id)
+        .send_recv(vft_manager_program_id)
+        .await;
+    assert!(result.is_err());
+
+    pause_admin_vft_manager
+        .cancel_operation(cancelled_id)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+
+    for _ in 0..DELAY {
+        remoting.system().run_next_block();
+    }
+
+    vft_manager
+        .execute_operation(id)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+
+    vft_manager
+        .execute_operation(remove_id)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+
+    vft_manager
+        .execute_operation(pause_admin_id)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+
+    let pause_admin = vft_manager
+        .pause_admin()
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(pause_admin, pause_admin_new);
+
+    let mappings = vft_manager
+        .vara_to_eth_addresses()
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert!(mappings
+        .iter()
+        .all(|(vara_token_id, ..)| *vara_token_id != gear_supply_vft));
+
+    let erc20_manager_address = vft_manager
+        .erc20_manager_address()
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(erc20_manager_address, erc20_manager_address_new);
+
+    let historical_proxy_address = vft_manager
+        .historical_proxy_address()
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(historical_proxy_address, HISTORICAL_PROXY_ID.into());
+
+    let pending_operations = vft_manager
+        .pending_operations()
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert!(pending_operations.is_empty());
+}
+
+#[tokio::test]
+async fn test_insert_transactions() {
+    let Fixture {
+        remoting,
+        vft_manager_program_id,
+        ..
+    } = setup_for_test().await;
+
+    let mut vft_manager = VftManagerC::new(remoting.clone());
+
+    let result = vft_manager
+        .insert_transactions(vec![(10, 1)])
+        .send_recv(vft_manager_program_id)
+        .await;
+    assert!(result.is_err());
+
+    vft_manager
+        .pause()
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    vft_manager
+        .insert_transactions(vec![(10, 1)])
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    vft_manager
+        .unpause()
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+
+    let account_id: ActorId = 100_000.into();
+    let receipt_rlp = create_receipt_rlp(account_id, ERC20_TOKEN_ETH_SUPPLY, U256::from(1));
+    let result = VftManagerC::new(remoting.clone().with_actor_id(HISTORICAL_PROXY_ID.into()))
+        .submit_receipt(10, 1, receipt_rlp)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(result, Err(Error::AlreadyProcessed));
+}
+
+#[tokio::test]
+async fn test_refund_failed_delivery() {
+    let Fixture {
+        remoting,
+        vft_manager_program_id,
+        gear_supply_vft,
+        ..
+    } = setup_for_test().await;
+
+    // VFT Manager has no locked tokens so unlocking them will fail.
+    let account_id: ActorId = 100_000.into();
+    let amount = U256::from(10_000_000_000_u64);
+    let receipt_rlp = create_receipt_rlp(account_id, ERC20_TOKEN_GEAR_SUPPLY, amount);
+    let mut historical_proxy =
+        VftManagerC::new(remoting.clone().with_actor_id(HISTORICAL_PROXY_ID.into()));
+    let result = historical_proxy
+        .submit_receipt(0, 0, receipt_rlp.clone())
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(result, Err(Error::DeliveryFailed));
+
+    let result = historical_proxy
+        .submit_receipt(0, 0, receipt_rlp)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(result, Err(Error::AlreadyProcessed));
+
+    let account_balance = balance_of(&remoting, gear_supply_vft, account_id).await;
+    assert!(account_balance.is_zero());
+
+    let mut vft_manager = VftManagerC::new(remoting.clone());
+    let failed_deliveries = vft_manager
+        .failed_deliveries(0, 10)
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(failed_deliveries.len(), 1);
+    let ((slot, transaction_index), failed_delivery) = &failed_deliveries[0];
+    assert_eq!((*slot, *transaction_index), (0, 0));
+    assert_eq!(failed_delivery.deposit.sender, H160([3; 20]));
+    assert_eq!(failed_delivery.deposit.amount, amount);
+    assert_eq!(failed_delivery.refund, RefundStatus::NotRequested);
+
+    let result = vft_manager
+        .claim_refund(0, 1)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(result, Err(Error::NoFailedDelivery));
+
+    let nonce = vft_manager
+        .claim_refund(0, 0)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(nonce, U256::from(1));
+
+    let result = vft_manager
+        .claim_refund(0, 0)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(result, Err(Error::RefundAlreadyRequested));
+
+    let failed_deliveries = vft_manager
+        .failed_deliveries(0, 10)
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        failed_deliveries[0].1.refund,
+        RefundStatus::Completed(nonce)
+    );
+}
+
+#[tokio::test]
+async fn test_completed_messages_pruned() {
+    // Mirror the limits of the message tracker of `request_bridging`.
+    const COMPLETED_MESSAGES_RETENTION: u32 = 28_800;
+    const MAX_PRUNED_MESSAGES: usize = 10;
+
+    let Fixture {
+        remoting,
+        vft_manager_program_id,
+        gear_supply_vft,
+        ..
+    } = setup_for_test().await;
+
+    let account_id: ActorId = 100_000.into();
+    remoting.system().mint_to(account_id, 100_000_000_000_000);
+
+    let requests = MAX_PRUNED_MESSAGES + 3;
+    let amount = U256::from(requests);
+
+    VftAdminC::new(remoting.clone())
+        .mint(account_id, amount)
+        .send_recv(gear_supply_vft)
+        .await
+        .unwrap();
+
+    let ok = VftC::new(remoting.clone().with_actor_id(account_id))
+        .approve(vft_manager_program_id, amount)
+        .send_recv(gear_supply_vft)
+        .await
+        .unwrap();
+    assert!(ok);
+
+    let mut vft_manager = VftManagerC::new(remoting.clone().with_actor_id(account_id));
+    for _ in 0..=MAX_PRUNED_MESSAGES {
+        vft_manager
+            .request_bridging(gear_supply_vft, U256::one(), ETH_TOKEN_RECEIVER)
+            .send_recv(vft_manager_program_id)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    let messages = vft_manager
+        .request_bridging_msg_tracker_page(None, requests as u32)
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), MAX_PRUNED_MESSAGES + 1);
+
+    let system = remoting.system();
+    system.run_to_block(system.block_height() + COMPLETED_MESSAGES_RETENTION);
+
+    // A single request removes at most MAX_PRUNED_MESSAGES expired messages.
+    vft_manager
+        .request_bridging(gear_supply_vft, U256::one(), ETH_TOKEN_RECEIVER)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let messages = vft_manager
+        .request_bridging_msg_tracker_page(None, requests as u32)
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 2);
+
+    // The rest of the expired messages are removed by the next request while
+    // the recent ones are kept.
+    vft_manager
+        .request_bridging(gear_supply_vft, U256::one(), ETH_TOKEN_RECEIVER)
+        .send_recv(vft_manager_program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let messages = vft_manager
+        .request_bridging_msg_tracker_page(None, requests as u32)
+        .recv(vft_manager_program_id)
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 2);
+    assert!(messages
+        .iter()
+        .all(|(_, info)| info.details.requested_at > COMPLETED_MESSAGES_RETENTION));
+}
+
 async fn balance_of(
     remoting: &GTestRemoting,
     vft_program_id: ActorId,
@@ -50,7 +50,17 @@ pub struct MerkleProof {
     pub leaf_index: u64,
 }
 
-#[derive(Clone, Debug, Default, PartialEq)]
+/// Compact proof of inclusion of several leaves into the same merkle tree.
+/// See [`crate::merkle`] for the format.
+pub struct MerkleMultiProof {
+    pub root: [u8; KECCAK_HASH_SIZE],
+    pub proof: Vec<[u8; KECCAK_HASH_SIZE]>,
+    pub num_leaves: u64,
+    /// Indices and hashes of the proven leaves sorted by index.
+    pub leaves: Vec<(u64, [u8; KECCAK_HASH_SIZE])>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Encode, Decode)]
 pub struct Message {
     pub nonce_le: [u8; 32],
     pub source: [u8; 32],
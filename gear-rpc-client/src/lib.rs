@@ -36,6 +36,7 @@ use trie_db::{node::NodeHandle, ChildReference};
 use crate::dto::StorageInclusionProof;
 
 pub mod dto;
+pub mod merkle;
 
 struct StorageTrieInclusionProof {
     branch_nodes_data: Vec<BranchNodeData>,
@@ -621,6 +622,25 @@ impl GearApi {
         })
     }
 
+    /// Fetch a compact proof of inclusion of several messages into the merkle tree of the
+    /// given block. Messages should be queued in the same block.
+    pub async fn fetch_messages_inclusion_merkle_multi_proof(
+        &self,
+        block: H256,
+        message_hashes: &[H256],
+    ) -> anyhow::Result<dto::MerkleMultiProof> {
+        let mut proofs = Vec::with_capacity(message_hashes.len());
+        for message_hash in message_hashes {
+            let proof = self
+                .fetch_message_inclusion_merkle_proof(block, *message_hash)
+                .await?;
+
+            proofs.push((merkle::leaf_hash(&message_hash.0), proof));
+        }
+
+        merkle::build_multi_proof(proofs)
+    }
+
     /// Fetch queue merkle root for the given block.
     pub async fn fetch_queue_merkle_root(&self, block: H256) -> anyhow::Result<H256> {
         let block = (*self.api).blocks().at(block).await?;
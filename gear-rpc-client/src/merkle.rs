@@ -0,0 +1,366 @@
+//! Compact multiproofs of inclusion into the binary merkle tree of queued messages.
+//!
+//! The tree is built the same way as in `binary-merkle-tree`: nodes are hashed pairwise
+//! and the last node of a level with an odd number of nodes is promoted to the upper level
+//! as is. A multiproof contains only the nodes that can't be calculated from the proven
+//! leaves, in the order they're consumed when going through the tree bottom-up and
+//! left-to-right. Verification algorithm matches `MerkleMultiProof.sol`.
+
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+use keccak_hash::keccak_256;
+
+use crate::dto::{MerkleMultiProof, MerkleProof};
+
+type Hash = [u8; 32];
+
+fn hash_pair(left: &Hash, right: &Hash) -> Hash {
+    let mut hash = [0; 32];
+    keccak_256(&[&left[..], &right[..]].concat(), &mut hash);
+
+    hash
+}
+
+/// Hash of the leaf for the message with the given hash.
+pub fn leaf_hash(message_hash: &Hash) -> Hash {
+    let mut hash = [0; 32];
+    keccak_256(message_hash, &mut hash);
+
+    hash
+}
+
+/// Calculate root of the merkle tree from the single-leaf proof. Mirrors
+/// `MessageQueue.sol::calculateMerkleRoot`.
+pub fn calculate_root(proof: &[Hash], leaf: Hash, num_leaves: u64, leaf_index: u64) -> Hash {
+    let mut width = num_leaves;
+    let mut index = leaf_index;
+
+    proof.iter().fold(leaf, |hash, node| {
+        let hash = if index % 2 == 1 || index + 1 == width {
+            hash_pair(node, &hash)
+        } else {
+            hash_pair(&hash, node)
+        };
+
+        index /= 2;
+        width = (width - 1) / 2 + 1;
+
+        hash
+    })
+}
+
+/// Calculate root of the merkle tree from the multiproof of the given leaves. Leaves should
+/// be sorted by their indices. Returns `None` if the proof is malformed.
+pub fn calculate_multi_proof_root(
+    proof: &[Hash],
+    leaves: &[(u64, Hash)],
+    num_leaves: u64,
+) -> Option<Hash> {
+    if leaves.is_empty()
+        || leaves.windows(2).any(|pair| pair[0].0 >= pair[1].0)
+        || leaves.iter().any(|(index, _)| *index >= num_leaves)
+    {
+        return None;
+    }
+
+    let mut proof = proof.iter();
+    let mut nodes = leaves.to_vec();
+    let mut width = num_leaves;
+    while width > 1 {
+        let mut nodes_next = Vec::with_capacity(nodes.len());
+        let mut i = 0;
+        while i < nodes.len() {
+            let (index, mut hash) = nodes[i];
+            if index % 2 == 1 {
+                hash = hash_pair(proof.next()?, &hash);
+            } else if index + 1 < width {
+                match nodes.get(i + 1) {
+                    Some((index_next, hash_next)) if *index_next == index + 1 => {
+                        hash = hash_pair(&hash, hash_next);
+                        i += 1;
+                    }
+                    _ => hash = hash_pair(&hash, proof.next()?),
+                }
+            }
+
+            nodes_next.push((index / 2, hash));
+            i += 1;
+        }
+
+        nodes = nodes_next;
+        width = (width - 1) / 2 + 1;
+    }
+
+    if proof.next().is_some() {
+        return None;
+    }
+
+    Some(nodes[0].1)
+}
+
+/// Combine single-leaf proofs from the same merkle tree into a multiproof.
+pub fn build_multi_proof(proofs: Vec<(Hash, MerkleProof)>) -> anyhow::Result<MerkleMultiProof> {
+    let (_, first) = proofs
+        .first()
+        .ok_or_else(|| anyhow!("No proofs to combine"))?;
+    let (root, num_leaves) = (first.root, first.num_leaves);
+
+    // Every node known from the provided proofs keyed by its level and index.
+    let mut known = BTreeMap::new();
+    let mut leaves = Vec::with_capacity(proofs.len());
+    for (leaf, proof) in proofs {
+        if proof.root != root || proof.num_leaves != num_leaves {
+            anyhow::bail!(
+                "Proof for leaf #{} belongs to a different merkle tree",
+                proof.leaf_index
+            );
+        }
+
+        let mut nodes = proof.proof.iter();
+        let (mut level, mut index, mut width, mut hash) =
+            (0u32, proof.leaf_index, num_leaves, leaf);
+        known.insert((level, index), hash);
+        while width > 1 {
+            let sibling = index ^ 1;
+            if sibling < width {
+                let node = nodes
+                    .next()
+                    .ok_or_else(|| anyhow!("Proof for leaf #{} is too short", proof.leaf_index))?;
+                known.insert((level, sibling), *node);
+
+                hash = if index % 2 == 1 {
+                    hash_pair(node, &hash)
+                } else {
+                    hash_pair(&hash, node)
+                };
+            }
+
+            level += 1;
+            index /= 2;
+            width = (width - 1) / 2 + 1;
+            known.insert((level, index), hash);
+        }
+
+        if hash != root {
+            anyhow::bail!("Proof for leaf #{} is invalid", proof.leaf_index);
+        }
+
+        leaves.push((proof.leaf_index, leaf));
+    }
+
+    leaves.sort_by_key(|(index, _)| *index);
+    if leaves.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+        anyhow::bail!("Duplicated leaves in the proofs");
+    }
+
+    // Go through the tree the same way the verifier does and collect nodes it can't calculate.
+    let mut proof = Vec::new();
+    let mut indices: Vec<_> = leaves.iter().map(|(index, _)| *index).collect();
+    let (mut level, mut width) = (0u32, num_leaves);
+    while width > 1 {
+        let mut indices_next = Vec::with_capacity(indices.len());
+        let mut i = 0;
+        while i < indices.len() {
+            let index = indices[i];
+            let sibling = if index % 2 == 1 {
+                Some(index - 1)
+            } else if index + 1 < width {
+                if indices.get(i + 1) == Some(&(index + 1)) {
+                    i += 1;
+                    None
+                } else {
+                    Some(index + 1)
+                }
+            } else {
+                None
+            };
+
+            if let Some(sibling) = sibling {
+                let node = known
+                    .get(&(level, sibling))
+                    .ok_or_else(|| anyhow!("Node #{sibling} at level {level} is unknown"))?;
+                proof.push(*node);
+            }
+
+            indices_next.push(index / 2);
+            i += 1;
+        }
+
+        indices = indices_next;
+        level += 1;
+        width = (width - 1) / 2 + 1;
+    }
+
+    Ok(MerkleMultiProof {
+        root,
+        proof,
+        num_leaves,
+        leaves,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(s: &str) -> Hash {
+        hex::decode(s).unwrap().try_into().unwrap()
+    }
+
+    fn keccak(data: &[u8]) -> Hash {
+        let mut hash = [0; 32];
+        keccak_256(data, &mut hash);
+
+        hash
+    }
+
+    // Builds the tree level by level the same way as `binary-merkle-tree`.
+    fn build_tree(leaves: &[Hash]) -> Vec<Vec<Hash>> {
+        let mut levels = vec![leaves.to_vec()];
+        while levels.last().unwrap().len() > 1 {
+            let level = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    [node] => *node,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(level);
+        }
+
+        levels
+    }
+
+    fn single_proof(tree: &[Vec<Hash>], leaf_index: u64) -> MerkleProof {
+        let mut index = leaf_index as usize;
+        let mut proof = vec![];
+        for level in &tree[..tree.len() - 1] {
+            if let Some(node) = level.get(index ^ 1) {
+                proof.push(*node);
+            }
+
+            index /= 2;
+        }
+
+        MerkleProof {
+            root: tree.last().unwrap()[0],
+            proof,
+            num_leaves: tree[0].len() as u64,
+            leaf_index,
+        }
+    }
+
+    // Vectors are taken from `MessageQueue.t.sol`.
+    #[test]
+    fn single_proof_vectors() {
+        let leaf_2 = keccak(&[[2; 64].as_slice(), &[0; 31], &[2; 3]].concat());
+        let leaf_3 = keccak(&[[3; 64].as_slice(), &[0; 31], &[3; 3]].concat());
+        let proof_tail = [
+            hash("30cdfaedf81fed4b4564ef0e8c04c56d3481e0121501c2dcc12288e01f3ceb94"),
+            hash("f87bc57ba7962a2b733f78df0e777ca31499b78c4d6f64c6d49ab0fd1dc60f44"),
+            hash("ed0dcf662c10b0827133e6e99e415b0d97da1a92ce69eb717838d55cc9067c49"),
+            hash("2387406c963403e53d56621d1cef73b80089994ee4c5866ae2d21eaa9fcdfe01"),
+            hash("08ab6b1030ad30cece656ac2638a8aed651bd759a6486241a293610f84927f52"),
+            hash("e7e9ede5fe38231d6c068bc8f5d95b76eed9b255f9b892f77c4f640cc86514ac"),
+        ];
+        let root = hash("bd18567f3cd28d09dc4f8b0f367415dc19f0e32d47424015eaf22103a4bf4cb3");
+
+        let proof_2 = [&[leaf_3][..], &proof_tail].concat();
+        assert_eq!(calculate_root(&proof_2, leaf_2, 101, 2), root);
+        assert_eq!(
+            calculate_multi_proof_root(&proof_2, &[(2, leaf_2)], 101),
+            Some(root)
+        );
+
+        let proof_3 = [&[leaf_2][..], &proof_tail].concat();
+        assert_eq!(calculate_root(&proof_3, leaf_3, 101, 3), root);
+
+        // Both leaves share the rest of the path so it's enough to prove them together.
+        assert_eq!(
+            calculate_multi_proof_root(&proof_tail, &[(2, leaf_2), (3, leaf_3)], 101),
+            Some(root)
+        );
+
+        let leaf_100 = hash("cee28748a98c81d3eb24f23af4876c8d71c75efc61416bfd2bb018390b138794");
+        let proof_100 = [
+            hash("69b655dccf32e0c3e4d4f427875a09b8cde36a2e6d1b980a8b1f8b134425652f"),
+            hash("6d6e07bcb08ba34a789918ab09f0a8aabd1c42a1e7b8625448dab3ed03a02b59"),
+            hash("bdfbb5c1b5550cf03c9819c027ee7d51d3153d372968cdfae6f01d261cb6877b"),
+        ];
+        let root_100 = hash("8db8d383e63f1ff7bbd1b35d7d1f240f6fce68aa12e60cd3a446021f8cd04226");
+        assert_eq!(calculate_root(&proof_100, leaf_100, 101, 100), root_100);
+        assert_eq!(
+            calculate_multi_proof_root(&proof_100, &[(100, leaf_100)], 101),
+            Some(root_100)
+        );
+    }
+
+    #[test]
+    fn multi_proofs() {
+        for num_leaves in 1..40u64 {
+            let leaves: Vec<_> = (0..num_leaves)
+                .map(|i| keccak(&[i as u8, num_leaves as u8]))
+                .collect();
+            let tree = build_tree(&leaves);
+            let root = tree.last().unwrap()[0];
+
+            for i in 0..num_leaves {
+                let proof = single_proof(&tree, i);
+                assert_eq!(
+                    calculate_root(&proof.proof, leaves[i as usize], num_leaves, i),
+                    root
+                );
+            }
+
+            let subsets: [Vec<u64>; 4] = [
+                (0..num_leaves).collect(),
+                (0..num_leaves).step_by(3).collect(),
+                (0..num_leaves).rev().step_by(2).collect(),
+                vec![num_leaves - 1, 0],
+            ];
+            for subset in subsets {
+                let mut subset = subset;
+                subset.dedup();
+
+                let proofs = subset
+                    .iter()
+                    .map(|i| (leaves[*i as usize], single_proof(&tree, *i)))
+                    .collect();
+                let multi_proof = build_multi_proof(proofs).unwrap();
+
+                let mut proven: Vec<_> = subset.iter().map(|i| (*i, leaves[*i as usize])).collect();
+                proven.sort_by_key(|(i, _)| *i);
+                assert_eq!(multi_proof.leaves, proven);
+                assert_eq!(
+                    calculate_multi_proof_root(&multi_proof.proof, &proven, num_leaves),
+                    Some(root),
+                    "num_leaves = {num_leaves}, subset = {subset:?}"
+                );
+
+                // Proof with a missing node or an extra one is rejected.
+                if let Some((_, proof)) = multi_proof.proof.split_last() {
+                    assert_ne!(
+                        calculate_multi_proof_root(proof, &proven, num_leaves),
+                        Some(root)
+                    );
+                }
+                let proof_extended = [&multi_proof.proof[..], &[root]].concat();
+                assert!(calculate_multi_proof_root(&proof_extended, &proven, num_leaves).is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_leaves() {
+        let leaf = [1; 32];
+
+        assert!(calculate_multi_proof_root(&[], &[], 2).is_none());
+        assert!(calculate_multi_proof_root(&[], &[(2, leaf)], 2).is_none());
+        assert!(calculate_multi_proof_root(&[], &[(1, leaf), (0, leaf)], 2).is_none());
+        assert!(calculate_multi_proof_root(&[], &[(1, leaf), (1, leaf)], 2).is_none());
+    }
+}
@@ -0,0 +1,185 @@
+//! Job API of the standalone prover service.
+//!
+//! Proving takes minutes, so jobs are processed asynchronously: client submits a [`Job`] to
+//! `POST /jobs` and receives its id, then polls `GET /jobs/{id}` until the [`JobStatus`] is
+//! final. Request and response bodies are SCALE encoded.
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use parity_scale_codec::{Decode, Encode};
+use prover::proving::{
+    self, BlockFinality, ExportedProofWithCircuitData, GenesisConfig, ProofWithCircuitData,
+    StorageInclusion,
+};
+use reqwest::header::CONTENT_TYPE;
+
+pub type JobId = u64;
+
+/// Timeout of a single request to the prover service.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Arguments of the corresponding functions from `prover::proving`.
+#[derive(Clone, Encode, Decode)]
+pub enum Job {
+    Genesis {
+        current_epoch_block_finality: BlockFinality,
+        genesis_config: GenesisConfig,
+        next_validator_set_inclusion_proof: StorageInclusion,
+        next_validator_set_data: Vec<u8>,
+    },
+    ValidatorSetChange {
+        previous_proof: ProofWithCircuitData,
+        current_epoch_block_finality: BlockFinality,
+        next_validator_set_inclusion_proof: StorageInclusion,
+        next_validator_set_data: Vec<u8>,
+    },
+    MessageSent {
+        previous_proof: ProofWithCircuitData,
+        block_finality_proof: BlockFinality,
+        genesis_config: GenesisConfig,
+        message_inclusion_proof: StorageInclusion,
+        message_contents: Vec<u8>,
+    },
+}
+
+impl Job {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Genesis { .. } => "genesis",
+            Self::ValidatorSetChange { .. } => "validator_set_change",
+            Self::MessageSent { .. } => "message_sent",
+        }
+    }
+
+    /// Build the proof. It's CPU-heavy, so it shouldn't be called from async context directly.
+    pub fn prove(self) -> JobOutput {
+        match self {
+            Self::Genesis {
+                current_epoch_block_finality,
+                genesis_config,
+                next_validator_set_inclusion_proof,
+                next_validator_set_data,
+            } => JobOutput::ProofWithCircuitData(proving::prove_genesis(
+                current_epoch_block_finality,
+                genesis_config,
+                next_validator_set_inclusion_proof,
+                next_validator_set_data,
+            )),
+            Self::ValidatorSetChange {
+                previous_proof,
+                current_epoch_block_finality,
+                next_validator_set_inclusion_proof,
+                next_validator_set_data,
+            } => JobOutput::ProofWithCircuitData(proving::prove_validator_set_change(
+                previous_proof,
+                current_epoch_block_finality,
+                next_validator_set_inclusion_proof,
+                next_validator_set_data,
+            )),
+            Self::MessageSent {
+                previous_proof,
+                block_finality_proof,
+                genesis_config,
+                message_inclusion_proof,
+                message_contents,
+            } => JobOutput::ExportedProofWithCircuitData(proving::prove_message_sent(
+                previous_proof,
+                block_finality_proof,
+                genesis_config,
+                message_inclusion_proof,
+                message_contents,
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Encode, Decode)]
+pub enum JobOutput {
+    /// Output of `Genesis` and `ValidatorSetChange` jobs.
+    ProofWithCircuitData(ProofWithCircuitData),
+    /// Output of `MessageSent` job.
+    ExportedProofWithCircuitData(ExportedProofWithCircuitData),
+}
+
+#[derive(Clone, Encode, Decode)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done(JobOutput),
+    Failed(String),
+    /// Job is finished but its result is already evicted by the newer ones.
+    Expired,
+}
+
+/// Client of the prover service.
+#[derive(Clone)]
+pub struct Client {
+    client: reqwest::Client,
+    url: String,
+    poll_interval: Duration,
+}
+
+impl Client {
+    pub fn new(url: &str, poll_interval: Duration) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("Failed to build HTTP client"),
+            url: url.trim_end_matches('/').to_string(),
+            poll_interval,
+        }
+    }
+
+    pub async fn submit(&self, job: &Job) -> anyhow::Result<JobId> {
+        let response = self
+            .client
+            .post(format!("{}/jobs", self.url))
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .body(job.encode())
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+
+        Ok(JobId::decode(&mut &response[..])?)
+    }
+
+    pub async fn status(&self, id: JobId) -> anyhow::Result<JobStatus> {
+        let response = self
+            .client
+            .get(format!("{}/jobs/{id}", self.url))
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+
+        Ok(JobStatus::decode(&mut &response[..])?)
+    }
+
+    /// Submit the job and wait until it's processed.
+    pub async fn prove(&self, job: &Job) -> anyhow::Result<JobOutput> {
+        let id = self.submit(job).await?;
+        log::debug!("Submitted {} job #{id} to the prover service", job.name());
+
+        loop {
+            match self.status(id).await? {
+                JobStatus::Queued | JobStatus::Running => {
+                    tokio::time::sleep(self.poll_interval).await;
+                }
+                JobStatus::Done(output) => return Ok(output),
+                JobStatus::Failed(error) => {
+                    return Err(anyhow!("Prover service failed job #{id}: {error}"));
+                }
+                JobStatus::Expired => {
+                    return Err(anyhow!(
+                        "Result of job #{id} expired before it was fetched from the prover service"
+                    ));
+                }
+            }
+        }
+    }
+}
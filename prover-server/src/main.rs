@@ -0,0 +1,254 @@
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Router,
+};
+use clap::Parser;
+use parity_scale_codec::{Decode, Encode};
+use prover_server::{Job, JobId, JobStatus};
+use tokio::{net::TcpListener, sync::mpsc};
+
+/// Amount of finished jobs which results are kept for the clients.
+const MAX_FINISHED_JOBS: usize = 64;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// Address to serve the job API on
+    #[arg(
+        long = "endpoint",
+        default_value = "0.0.0.0:3030",
+        env = "PROVER_SERVER_ENDPOINT"
+    )]
+    endpoint: String,
+    /// Directory to cache built circuits in
+    #[arg(long = "circuit-cache-dir", env = "CIRCUIT_CACHE_DIR")]
+    circuit_cache_dir: Option<PathBuf>,
+}
+
+#[derive(Default)]
+struct Jobs {
+    next_id: JobId,
+    statuses: BTreeMap<JobId, JobStatus>,
+}
+
+impl Jobs {
+    fn push(&mut self) -> JobId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.statuses.insert(id, JobStatus::Queued);
+
+        id
+    }
+
+    /// Status of the job or `None` if the job with such id was never submitted.
+    fn status(&self, id: JobId) -> Option<JobStatus> {
+        match self.statuses.get(&id) {
+            Some(status) => Some(status.clone()),
+            // Jobs are removed only when their results are evicted.
+            None if id < self.next_id => Some(JobStatus::Expired),
+            None => None,
+        }
+    }
+
+    fn set_status(&mut self, id: JobId, status: JobStatus) {
+        self.statuses.insert(id, status);
+
+        let finished: Vec<_> = self
+            .statuses
+            .iter()
+            .filter(|(_, status)| matches!(status, JobStatus::Done(_) | JobStatus::Failed(_)))
+            .map(|(id, _)| *id)
+            .collect();
+
+        for id in finished
+            .iter()
+            .take(finished.len().saturating_sub(MAX_FINISHED_JOBS))
+        {
+            self.statuses.remove(id);
+        }
+    }
+}
+
+#[derive(Clone)]
+struct AppState {
+    jobs: Arc<Mutex<Jobs>>,
+    queue: mpsc::UnboundedSender<(JobId, Job)>,
+}
+
+#[tokio::main]
+async fn main() {
+    let _ = dotenv::dotenv();
+
+    pretty_env_logger::formatted_timed_builder()
+        .filter_level(log::LevelFilter::Off)
+        .format_target(false)
+        .filter(Some("prover"), log::LevelFilter::Info)
+        .filter(Some("prover_server"), log::LevelFilter::Info)
+        .format_timestamp_secs()
+        .parse_default_env()
+        .init();
+
+    let cli = Cli::parse();
+
+    if let Some(dir) = cli.circuit_cache_dir {
+        prover::proving::set_circuit_cache_dir(dir);
+    }
+
+    let jobs = Arc::new(Mutex::new(Jobs::default()));
+    let (queue, receiver) = mpsc::unbounded_channel();
+
+    tokio::spawn(process_jobs(jobs.clone(), receiver));
+
+    let app = Router::new()
+        .route("/jobs", post(submit_job))
+        .route("/jobs/:id", get(job_status))
+        .with_state(AppState { jobs, queue });
+
+    let listener = TcpListener::bind(&cli.endpoint)
+        .await
+        .expect("Failed to bind the job API endpoint");
+
+    log::info!("Serving job API on {}", cli.endpoint);
+
+    axum::serve(listener, app)
+        .await
+        .expect("Job API server failed");
+}
+
+/// Jobs are proven one by one as every proof already utilizes all the available cores.
+async fn process_jobs(jobs: Arc<Mutex<Jobs>>, mut receiver: mpsc::UnboundedReceiver<(JobId, Job)>) {
+    while let Some((id, job)) = receiver.recv().await {
+        let name = job.name();
+        log::info!("Proving {name} job #{id}");
+
+        jobs.lock()
+            .expect("Jobs mutex poisoned")
+            .set_status(id, JobStatus::Running);
+
+        let now = Instant::now();
+        let status = match tokio::task::spawn_blocking(move || job.prove()).await {
+            Ok(output) => {
+                log::info!("Proven {name} job #{id} in {}ms", now.elapsed().as_millis());
+
+                JobStatus::Done(output)
+            }
+            Err(e) => {
+                let error = match e.try_into_panic() {
+                    Ok(panic) => panic
+                        .downcast_ref::<&str>()
+                        .map(|s| s.to_string())
+                        .or_else(|| panic.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| String::from("Prover panicked")),
+                    Err(e) => e.to_string(),
+                };
+
+                log::error!("Failed to prove {name} job #{id}: {error}");
+
+                JobStatus::Failed(error)
+            }
+        };
+
+        jobs.lock()
+            .expect("Jobs mutex poisoned")
+            .set_status(id, status);
+    }
+}
+
+async fn submit_job(
+    State(state): State<AppState>,
+    body: Bytes,
+) -> Result<Vec<u8>, (StatusCode, String)> {
+    let job = Job::decode(&mut &body[..]).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("Failed to decode job: {e}"),
+        )
+    })?;
+
+    let id = state.jobs.lock().expect("Jobs mutex poisoned").push();
+
+    log::info!("Received {} job #{id}", job.name());
+
+    state.queue.send((id, job)).map_err(|_| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            String::from("Job queue is closed"),
+        )
+    })?;
+
+    Ok(id.encode())
+}
+
+async fn job_status(
+    State(state): State<AppState>,
+    Path(id): Path<JobId>,
+) -> Result<Vec<u8>, StatusCode> {
+    state
+        .jobs
+        .lock()
+        .expect("Jobs mutex poisoned")
+        .status(id)
+        .map(|status| status.encode())
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_failed(status: &Option<JobStatus>) -> bool {
+        matches!(status, Some(JobStatus::Failed(_)))
+    }
+
+    #[test]
+    fn job_lifecycle() {
+        let mut jobs = Jobs::default();
+
+        let id = jobs.push();
+        assert!(matches!(jobs.status(id), Some(JobStatus::Queued)));
+        assert!(jobs.status(id + 1).is_none());
+
+        jobs.set_status(id, JobStatus::Running);
+        assert!(matches!(jobs.status(id), Some(JobStatus::Running)));
+
+        jobs.set_status(id, JobStatus::Failed(String::from("error")));
+        assert!(is_failed(&jobs.status(id)));
+    }
+
+    #[test]
+    fn finished_jobs_expire() {
+        let mut jobs = Jobs::default();
+
+        let running = jobs.push();
+        jobs.set_status(running, JobStatus::Running);
+
+        let finished: Vec<_> = (0..MAX_FINISHED_JOBS + 2)
+            .map(|_| {
+                let id = jobs.push();
+                jobs.set_status(id, JobStatus::Failed(String::from("error")));
+
+                id
+            })
+            .collect();
+
+        // The oldest results are evicted but the job isn't confused with an unknown one.
+        assert!(matches!(jobs.status(finished[0]), Some(JobStatus::Expired)));
+        assert!(matches!(jobs.status(finished[1]), Some(JobStatus::Expired)));
+        assert!(finished[2..].iter().all(|id| is_failed(&jobs.status(*id))));
+
+        // Unfinished jobs are never evicted.
+        assert!(matches!(jobs.status(running), Some(JobStatus::Running)));
+
+        assert!(jobs.status(jobs.next_id).is_none());
+    }
+}
@@ -6,6 +6,7 @@
 //! NOTE: This circuit decides that block is finalized when more than 2/3 of validator set have
 //! signed it.
 
+use parity_scale_codec::{Decode, Encode};
 use plonky2::{
     iop::{target::Target, witness::PartialWitness},
     plonk::{circuit_builder::CircuitBuilder, circuit_data::CircuitConfig},
@@ -61,7 +62,7 @@ impl_parsable_target_set! {
 }
 
 /// Pre-commit data that's used to prove validator signs.
-#[derive(Clone)]
+#[derive(Clone, Encode, Decode)]
 pub struct PreCommit {
     /// Public key of validator this pre-commit belongs to.
     pub public_key: [u8; consts::ED25519_PUBLIC_KEY_SIZE],
@@ -76,7 +77,7 @@ struct ProcessedPreCommit {
     signature: [u8; consts::ED25519_SIGNATURE_SIZE],
 }
 
-#[derive(Clone)]
+#[derive(Clone, Encode, Decode)]
 pub struct BlockFinality {
     /// Actual validator set for current authority set id.
     pub validator_set: Vec<[u8; consts::ED25519_PUBLIC_KEY_SIZE]>,
@@ -0,0 +1,374 @@
+//! ### Cache of built circuits.
+//!
+//! Circuits that verify other proofs embed verifier data of the inner circuits as constants,
+//! so they're keyed by digests of the inner circuits and by `GenesisConfig` if it's embedded
+//! into the circuit too. Built circuits are kept in memory and, if cache directory is set,
+//! persisted to disk to be loaded on subsequent runs instead of being rebuilt.
+
+use std::{
+    collections::HashMap,
+    fs,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use keccak_hash::keccak;
+use parity_scale_codec::{Decode, Encode};
+use plonky2::{
+    field::types::PrimeField64,
+    hash::hash_types::HashOut,
+    plonk::circuit_data::{CircuitData, VerifierCircuitData},
+    util::serialization::{Buffer, DefaultGateSerializer, DefaultGeneratorSerializer, IoResult},
+};
+
+use crate::{prelude::*, proving::GenesisConfig};
+
+/// Version of the cache file format. Bump it when circuits or the format change.
+const CACHE_VERSION: u32 = 1;
+
+static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Set directory to persist built circuits to. Can be set only once.
+pub fn set_cache_dir(dir: PathBuf) -> Result<(), PathBuf> {
+    CACHE_DIR.set(dir)
+}
+
+/// Targets required to set witness for the built circuit.
+pub trait CircuitTargets: Sized {
+    fn write(&self, buffer: &mut Vec<u8>) -> IoResult<()>;
+    fn read(buffer: &mut Buffer) -> IoResult<Self>;
+}
+
+pub struct CachedCircuit<T> {
+    pub circuit_data: CircuitData<F, C, D>,
+    pub targets: T,
+}
+
+#[derive(Encode, Decode)]
+struct CacheFile {
+    version: u32,
+    inner_circuits: [u8; 32],
+    genesis_config: Option<GenesisConfig>,
+    circuit_digest: [u64; 4],
+    /// Keccak256 of `circuit_data` and `targets`.
+    checksum: [u8; 32],
+    circuit_data: Vec<u8>,
+    targets: Vec<u8>,
+}
+
+pub struct CircuitCache<T> {
+    name: &'static str,
+    persistent: bool,
+    /// Circuits are built outside of the mutex, so the cache itself is locked only to get the
+    /// entry for the key. Concurrent requests for the same key wait for a single build.
+    entries: Mutex<HashMap<[u8; 32], Arc<OnceLock<Arc<CachedCircuit<T>>>>>>,
+}
+
+impl<T: CircuitTargets> CircuitCache<T> {
+    /// Cache that persists circuits to the cache directory.
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            persistent: true,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Cache that keeps circuits only in memory. Used for circuits containing gates that
+    /// don't support serialization.
+    pub fn in_memory(name: &'static str) -> Self {
+        Self {
+            name,
+            persistent: false,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn get_or_build(
+        &self,
+        inner_circuits: &[&VerifierCircuitData<F, C, D>],
+        genesis_config: Option<GenesisConfig>,
+        build: impl FnOnce() -> CachedCircuit<T>,
+    ) -> Arc<CachedCircuit<T>> {
+        let inner_circuits: [u8; 32] = keccak(
+            inner_circuits
+                .iter()
+                .flat_map(|circuit| digest_to_u64s(circuit.verifier_only.circuit_digest))
+                .flat_map(u64::to_le_bytes)
+                .collect::<Vec<_>>(),
+        )
+        .0;
+        let key = keccak((inner_circuits, genesis_config).encode()).0;
+
+        let entry = self
+            .entries
+            .lock()
+            .expect("Circuit cache mutex poisoned")
+            .entry(key)
+            .or_default()
+            .clone();
+
+        entry
+            .get_or_init(|| {
+                Arc::new(self.load_or_build(key, inner_circuits, genesis_config, build))
+            })
+            .clone()
+    }
+
+    fn load_or_build(
+        &self,
+        key: [u8; 32],
+        inner_circuits: [u8; 32],
+        genesis_config: Option<GenesisConfig>,
+        build: impl FnOnce() -> CachedCircuit<T>,
+    ) -> CachedCircuit<T> {
+        let path = CACHE_DIR
+            .get()
+            .filter(|_| self.persistent)
+            .map(|dir| dir.join(format!("{}-{}.bin", self.name, hex(&key))));
+
+        let loaded = path.as_deref().and_then(|path| {
+            self.load(path, inner_circuits, genesis_config)
+                .map_err(|e| log::warn!("Rejected cached {} circuit: {e}", self.name))
+                .ok()
+                .flatten()
+        });
+
+        match loaded {
+            Some(cached) => {
+                log::debug!("Loaded {} circuit from cache", self.name);
+                cached
+            }
+            None => {
+                log::debug!("Building {} circuit...", self.name);
+                let cached = build();
+
+                if let Some(path) = path {
+                    if let Err(e) = Self::store(&path, inner_circuits, genesis_config, &cached) {
+                        log::warn!("Failed to cache {} circuit: {e}", self.name);
+                    }
+                }
+
+                cached
+            }
+        }
+    }
+
+    /// Load circuit from file. Returns `None` if there's no cached circuit.
+    fn load(
+        &self,
+        path: &Path,
+        inner_circuits: [u8; 32],
+        genesis_config: Option<GenesisConfig>,
+    ) -> anyhow::Result<Option<CachedCircuit<T>>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let file = fs::read(path)?;
+        let file = CacheFile::decode(&mut &file[..])?;
+
+        anyhow::ensure!(
+            file.version == CACHE_VERSION,
+            "cache version {} differs from the current one {CACHE_VERSION}",
+            file.version
+        );
+        anyhow::ensure!(
+            file.inner_circuits == inner_circuits,
+            "it's built for different inner circuits"
+        );
+        anyhow::ensure!(
+            file.genesis_config == genesis_config,
+            "it's built for different genesis config"
+        );
+        anyhow::ensure!(
+            keccak([&file.circuit_data[..], &file.targets[..]].concat()).0 == file.checksum,
+            "checksum mismatch"
+        );
+
+        let circuit_data = CircuitData::from_bytes(
+            &file.circuit_data,
+            &DefaultGateSerializer,
+            &DefaultGeneratorSerializer::<C, D> {
+                _phantom: PhantomData,
+            },
+        )
+        .map_err(|e| anyhow::anyhow!("failed to deserialize circuit data: {e:?}"))?;
+        anyhow::ensure!(
+            digest_to_u64s(circuit_data.verifier_only.circuit_digest) == file.circuit_digest,
+            "circuit digest mismatch"
+        );
+
+        let targets = T::read(&mut Buffer::new(&file.targets))
+            .map_err(|e| anyhow::anyhow!("failed to deserialize targets: {e:?}"))?;
+
+        Ok(Some(CachedCircuit {
+            circuit_data,
+            targets,
+        }))
+    }
+
+    fn store(
+        path: &Path,
+        inner_circuits: [u8; 32],
+        genesis_config: Option<GenesisConfig>,
+        cached: &CachedCircuit<T>,
+    ) -> anyhow::Result<()> {
+        let circuit_data = cached
+            .circuit_data
+            .to_bytes(
+                &DefaultGateSerializer,
+                &DefaultGeneratorSerializer::<C, D> {
+                    _phantom: PhantomData,
+                },
+            )
+            .map_err(|e| anyhow::anyhow!("failed to serialize circuit data: {e:?}"))?;
+
+        let mut targets = Vec::new();
+        cached
+            .targets
+            .write(&mut targets)
+            .map_err(|e| anyhow::anyhow!("failed to serialize targets: {e:?}"))?;
+
+        let file = CacheFile {
+            version: CACHE_VERSION,
+            inner_circuits,
+            genesis_config,
+            circuit_digest: digest_to_u64s(cached.circuit_data.verifier_only.circuit_digest),
+            checksum: keccak([&circuit_data[..], &targets[..]].concat()).0,
+            circuit_data,
+            targets,
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        // Write to the temporary file first so the partially written cache is never loaded.
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, file.encode())?;
+        fs::rename(tmp_path, path)?;
+
+        Ok(())
+    }
+}
+
+fn digest_to_u64s(digest: HashOut<F>) -> [u64; 4] {
+    digest.elements.map(|el| el.to_canonical_u64())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use plonky2::{
+        iop::target::Target,
+        plonk::{circuit_builder::CircuitBuilder, circuit_data::CircuitConfig},
+        util::serialization::{Read, Write},
+    };
+
+    struct TestTargets {
+        input: Target,
+    }
+
+    impl CircuitTargets for TestTargets {
+        fn write(&self, buffer: &mut Vec<u8>) -> IoResult<()> {
+            buffer.write_target(self.input)
+        }
+
+        fn read(buffer: &mut Buffer) -> IoResult<Self> {
+            Ok(Self {
+                input: buffer.read_target()?,
+            })
+        }
+    }
+
+    fn build() -> CachedCircuit<TestTargets> {
+        let mut builder = CircuitBuilder::<F, D>::new(CircuitConfig::standard_recursion_config());
+        let input = builder.add_virtual_public_input();
+        let square = builder.square(input);
+        builder.register_public_input(square);
+
+        CachedCircuit {
+            circuit_data: builder.build::<C>(),
+            targets: TestTargets { input },
+        }
+    }
+
+    #[test]
+    fn test_circuit_cache_integrity() {
+        let path = std::env::temp_dir()
+            .join(format!("prover-circuit-cache-{}", std::process::id()))
+            .join("test.bin");
+        let genesis_config = GenesisConfig {
+            authority_set_id: 1,
+            authority_set_hash: [1; 32],
+        };
+        let cache = CircuitCache::<TestTargets>::new("test");
+
+        let cached = build();
+        CircuitCache::store(&path, [0; 32], Some(genesis_config), &cached).unwrap();
+
+        let loaded = cache
+            .load(&path, [0; 32], Some(genesis_config))
+            .unwrap()
+            .expect("Circuit is cached");
+        assert_eq!(
+            loaded.circuit_data.verifier_only.circuit_digest,
+            cached.circuit_data.verifier_only.circuit_digest
+        );
+        assert_eq!(loaded.targets.input, cached.targets.input);
+
+        let other_genesis_config = GenesisConfig {
+            authority_set_id: 2,
+            ..genesis_config
+        };
+        assert!(cache
+            .load(&path, [0; 32], Some(other_genesis_config))
+            .is_err());
+        assert!(cache.load(&path, [1; 32], Some(genesis_config)).is_err());
+
+        let mut file = fs::read(&path).unwrap();
+        let last = file.len() - 1;
+        file[last] ^= 1;
+        fs::write(&path, file).unwrap();
+        assert!(cache.load(&path, [0; 32], Some(genesis_config)).is_err());
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_circuit_cache_builds_once_per_key() {
+        let inner = build().circuit_data.verifier_data();
+        let genesis_config = GenesisConfig {
+            authority_set_id: 1,
+            authority_set_hash: [1; 32],
+        };
+        let cache = CircuitCache::<TestTargets>::in_memory("test");
+        let builds = AtomicUsize::new(0);
+        let build = || {
+            builds.fetch_add(1, Ordering::SeqCst);
+            build()
+        };
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| cache.get_or_build(&[&inner], Some(genesis_config), build));
+            }
+        });
+        assert_eq!(builds.load(Ordering::SeqCst), 1);
+
+        let other_genesis_config = GenesisConfig {
+            authority_set_id: 2,
+            ..genesis_config
+        };
+        cache.get_or_build(&[&inner], Some(other_genesis_config), build);
+        assert_eq!(builds.load(Ordering::SeqCst), 2);
+    }
+}
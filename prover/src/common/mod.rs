@@ -13,7 +13,7 @@ use plonky2::{
             CircuitConfig, CircuitData, CommonCircuitData, VerifierCircuitData,
             VerifierCircuitTarget,
         },
-        proof::{Proof, ProofWithPublicInputs},
+        proof::{Proof, ProofWithPublicInputs, ProofWithPublicInputsTarget},
     },
 };
 use std::{marker::PhantomData, sync::Arc};
@@ -22,6 +22,7 @@ use crate::{prelude::*, proving::ExportedProofWithCircuitData};
 
 #[macro_use]
 pub mod targets;
+pub mod circuit_cache;
 pub mod generic_blake2;
 pub mod poseidon_bn128;
 
@@ -220,6 +221,13 @@ pub trait BuilderExt {
         witness: &mut PartialWitness<F>,
     ) -> T;
 
+    /// Declare verifier data of the circuit as a constant and recursively verify its proof.
+    /// The proof should be set to the returned target.
+    fn verify_constant_circuit_proof<T: TargetSet>(
+        &mut self,
+        circuit_data: &VerifierCircuitData<F, C, D>,
+    ) -> (ProofWithPublicInputsTarget<D>, T);
+
     /// Select if `condition` { `a` } else { `b` }
     fn select_target_set<T: TargetSet>(&mut self, condition: BoolTarget, a: &T, b: &T) -> T;
 
@@ -233,18 +241,31 @@ impl BuilderExt for CircuitBuilder<F, D> {
         proof: &ProofWithCircuitData<T>,
         witness: &mut PartialWitness<F>,
     ) -> T {
-        let proof_with_pis_target = self.add_virtual_proof_with_pis(&proof.circuit_data.common);
-        let verifier_data_target = self.constant_verifier_data(&proof.circuit_data.verifier_only);
+        let (proof_with_pis_target, public_inputs) =
+            self.verify_constant_circuit_proof(&proof.circuit_data);
 
         witness.set_proof_with_pis_target(&proof_with_pis_target, &proof.proof());
 
+        public_inputs
+    }
+
+    fn verify_constant_circuit_proof<T: TargetSet>(
+        &mut self,
+        circuit_data: &VerifierCircuitData<F, C, D>,
+    ) -> (ProofWithPublicInputsTarget<D>, T) {
+        let proof_with_pis_target = self.add_virtual_proof_with_pis(&circuit_data.common);
+        let verifier_data_target = self.constant_verifier_data(&circuit_data.verifier_only);
+
         self.verify_proof::<C>(
             &proof_with_pis_target,
             &verifier_data_target,
-            &proof.circuit_data.common,
+            &circuit_data.common,
         );
 
-        T::parse_exact(&mut proof_with_pis_target.public_inputs.into_iter())
+        let public_inputs =
+            T::parse_exact(&mut proof_with_pis_target.public_inputs.iter().copied());
+
+        (proof_with_pis_target, public_inputs)
     }
 
     fn select_target_set<T: TargetSet>(&mut self, condition: BoolTarget, a: &T, b: &T) -> T {
@@ -1,17 +1,24 @@
 //! ### Circuit that's used to prove that message was queued for relaying.
 
+use lazy_static::lazy_static;
 use plonky2::{
     iop::{
         target::{BoolTarget, Target},
         witness::{PartialWitness, WitnessWrite},
     },
-    plonk::{circuit_builder::CircuitBuilder, circuit_data::CircuitConfig},
+    plonk::{
+        circuit_builder::CircuitBuilder,
+        circuit_data::{CircuitConfig, VerifierCircuitData},
+        proof::ProofWithPublicInputsTarget,
+    },
+    util::serialization::{Buffer, IoResult, Read, Write},
 };
 
 use crate::{
-    block_finality::BlockFinality,
+    block_finality::{BlockFinality, BlockFinalityTarget},
     common::{
         array_to_bits,
+        circuit_cache::{CachedCircuit, CircuitCache, CircuitTargets},
         targets::{
             impl_target_set, ArrayTarget, Blake2Target, Blake2TargetGoldilocks,
             MessageTargetGoldilocks, TargetBitOperations, TargetSet,
@@ -20,7 +27,7 @@ use crate::{
     },
     consts::MESSAGE_SIZE_IN_BITS,
     prelude::*,
-    storage_inclusion::StorageInclusion,
+    storage_inclusion::{StorageInclusion, StorageInclusionTarget},
 };
 
 impl_target_set! {
@@ -68,6 +75,35 @@ pub struct MessageSent {
     pub message_storage_data: Vec<u8>,
 }
 
+lazy_static! {
+    // `blake2` circuit uses gates that don't support serialization, so the circuit is cached
+    // only in memory.
+    static ref CACHE: CircuitCache<WitnessTargets> = CircuitCache::in_memory("message_sent");
+}
+
+/// Targets that are set to prove the built circuit.
+struct WitnessTargets {
+    inclusion_proof: ProofWithPublicInputsTarget<D>,
+    finality_proof: ProofWithPublicInputsTarget<D>,
+    storage_data: Vec<BoolTarget>,
+}
+
+impl CircuitTargets for WitnessTargets {
+    fn write(&self, buffer: &mut Vec<u8>) -> IoResult<()> {
+        buffer.write_target_proof_with_public_inputs(&self.inclusion_proof)?;
+        buffer.write_target_proof_with_public_inputs(&self.finality_proof)?;
+        buffer.write_target_bool_vec(&self.storage_data)
+    }
+
+    fn read(buffer: &mut Buffer) -> IoResult<Self> {
+        Ok(Self {
+            inclusion_proof: buffer.read_target_proof_with_public_inputs()?,
+            finality_proof: buffer.read_target_proof_with_public_inputs()?,
+            storage_data: buffer.read_target_bool_vec()?,
+        })
+    }
+}
+
 impl MessageSent {
     pub fn prove(self) -> ProofWithCircuitData<MessageSentTarget> {
         log::debug!("Proving message presence in finalized block...");
@@ -77,13 +113,48 @@ impl MessageSent {
 
         log::debug!("Composing inclusion and finality proofs...");
 
-        let mut builder = CircuitBuilder::new(CircuitConfig::standard_recursion_config());
+        let cached = CACHE.get_or_build(
+            &[
+                inclusion_proof.circuit_data(),
+                finality_proof.circuit_data(),
+            ],
+            None,
+            || {
+                Self::build(
+                    inclusion_proof.circuit_data(),
+                    finality_proof.circuit_data(),
+                )
+            },
+        );
+
         let mut witness = PartialWitness::new();
+        witness
+            .set_proof_with_pis_target(&cached.targets.inclusion_proof, &inclusion_proof.proof());
+        witness.set_proof_with_pis_target(&cached.targets.finality_proof, &finality_proof.proof());
+
+        let storage_data_bits = array_to_bits(&self.message_storage_data);
+        assert_eq!(
+            storage_data_bits.len(),
+            cached.targets.storage_data.len(),
+            "Unexpected message storage data length"
+        );
+        for (target, bit) in cached.targets.storage_data.iter().zip(storage_data_bits) {
+            witness.set_bool_target(*target, bit);
+        }
+
+        ProofWithCircuitData::prove_from_circuit_data(&cached.circuit_data, witness)
+    }
+
+    fn build(
+        inclusion_circuit: &VerifierCircuitData<F, C, D>,
+        finality_circuit: &VerifierCircuitData<F, C, D>,
+    ) -> CachedCircuit<WitnessTargets> {
+        let mut builder = CircuitBuilder::new(CircuitConfig::standard_recursion_config());
 
-        let inclusion_proof_target =
-            builder.recursively_verify_constant_proof(&inclusion_proof, &mut witness);
-        let finality_proof_target =
-            builder.recursively_verify_constant_proof(&finality_proof, &mut witness);
+        let (inclusion_proof, inclusion_proof_target) =
+            builder.verify_constant_circuit_proof::<StorageInclusionTarget>(inclusion_circuit);
+        let (finality_proof, finality_proof_target) =
+            builder.verify_constant_circuit_proof::<BlockFinalityTarget>(finality_circuit);
 
         let block_number =
             Target::from_bool_targets_le(finality_proof_target.message.block_number, &mut builder);
@@ -92,14 +163,12 @@ impl MessageSent {
             .block_hash
             .connect(&finality_proof_target.message.block_hash, &mut builder);
 
-        let storage_data_bits = array_to_bits(&self.message_storage_data);
-        let mut storage_data_bit_targets = storage_data_bits.into_iter().map(|bit| {
-            let target = builder.add_virtual_bool_target_safe();
-            witness.set_bool_target(target, bit);
-            target.target
-        });
-        let storage_data_target =
-            MessageInStorageTarget::parse_exact(&mut storage_data_bit_targets);
+        let storage_data: Vec<_> = (0..MESSAGE_SIZE_IN_BITS)
+            .map(|_| builder.add_virtual_bool_target_safe())
+            .collect();
+        let storage_data_target = MessageInStorageTarget::parse_exact(
+            &mut storage_data.iter().map(|target| target.target),
+        );
 
         storage_data_target
             .hash(&mut builder)
@@ -122,6 +191,13 @@ impl MessageSent {
         }
         .register_as_public_inputs(&mut builder);
 
-        ProofWithCircuitData::prove_from_builder(builder, witness)
+        CachedCircuit {
+            circuit_data: builder.build::<C>(),
+            targets: WitnessTargets {
+                inclusion_proof,
+                finality_proof,
+                storage_data,
+            },
+        }
     }
 }
@@ -1,5 +1,6 @@
 //! ### Circuit that's used to ceate proof that will be submitted to ethereum.
 
+use lazy_static::lazy_static;
 use plonky2::{
     iop::{
         target::Target,
@@ -8,13 +9,15 @@ use plonky2::{
     plonk::{
         circuit_builder::CircuitBuilder,
         circuit_data::{CircuitConfig, VerifierCircuitData},
-        proof::ProofWithPublicInputs,
+        proof::{ProofWithPublicInputs, ProofWithPublicInputsTarget},
     },
+    util::serialization::{Buffer, IoResult, Read, Write},
 };
 use plonky2_field::types::Field;
 
 use crate::{
     common::{
+        circuit_cache::{CachedCircuit, CircuitCache, CircuitTargets},
         targets::{impl_target_set, Blake2TargetGoldilocks, MessageTargetGoldilocks, TargetSet},
         BuilderExt, ProofWithCircuitData,
     },
@@ -25,7 +28,7 @@ use crate::{
 
 pub mod message_sent;
 
-use message_sent::MessageSent;
+use message_sent::{MessageSent, MessageSentTarget};
 
 impl_target_set! {
     /// Public inputs for `FinalProof`.
@@ -46,41 +49,80 @@ pub struct FinalProof {
     pub message_sent: MessageSent,
 }
 
+lazy_static! {
+    static ref CACHE: CircuitCache<WitnessTargets> = CircuitCache::new("final_proof");
+}
+
+/// Targets that are set to prove the built circuit.
+struct WitnessTargets {
+    message_sent_proof: ProofWithPublicInputsTarget<D>,
+    current_validator_set_proof: ProofWithPublicInputsTarget<D>,
+}
+
+impl CircuitTargets for WitnessTargets {
+    fn write(&self, buffer: &mut Vec<u8>) -> IoResult<()> {
+        buffer.write_target_proof_with_public_inputs(&self.message_sent_proof)?;
+        buffer.write_target_proof_with_public_inputs(&self.current_validator_set_proof)
+    }
+
+    fn read(buffer: &mut Buffer) -> IoResult<Self> {
+        Ok(Self {
+            message_sent_proof: buffer.read_target_proof_with_public_inputs()?,
+            current_validator_set_proof: buffer.read_target_proof_with_public_inputs()?,
+        })
+    }
+}
+
 impl FinalProof {
     pub fn prove(self, genesis_config: GenesisConfig) -> ProofWithCircuitData<FinalProofTarget> {
         let message_sent_proof = self.message_sent.prove();
 
         log::debug!("Composing message sent and latest validator set proofs...");
 
-        let mut config = CircuitConfig::standard_recursion_config();
-        config.fri_config.cap_height = 0;
-        let mut builder = CircuitBuilder::new(config);
-        let mut witness = PartialWitness::new();
+        let cached = CACHE.get_or_build(
+            &[
+                message_sent_proof.circuit_data(),
+                &self.current_validator_set_verifier_data,
+            ],
+            Some(genesis_config),
+            || {
+                Self::build(
+                    message_sent_proof.circuit_data(),
+                    &self.current_validator_set_verifier_data,
+                    genesis_config,
+                )
+            },
+        );
 
-        let message_sent_target =
-            builder.recursively_verify_constant_proof(&message_sent_proof, &mut witness);
+        let mut witness = PartialWitness::new();
+        witness.set_proof_with_pis_target(
+            &cached.targets.message_sent_proof,
+            &message_sent_proof.proof(),
+        );
+        witness.set_proof_with_pis_target(
+            &cached.targets.current_validator_set_proof,
+            &self.current_validator_set_proof,
+        );
 
-        let latest_validator_set_target = {
-            let proof_with_pis_target = builder
-                .add_virtual_proof_with_pis(&self.current_validator_set_verifier_data.common);
-            let verifier_data_target = builder
-                .constant_verifier_data(&self.current_validator_set_verifier_data.verifier_only);
+        ProofWithCircuitData::prove_from_circuit_data(&cached.circuit_data, witness)
+    }
 
-            witness.set_proof_with_pis_target(
-                &proof_with_pis_target,
-                &self.current_validator_set_proof,
-            );
+    fn build(
+        message_sent_circuit: &VerifierCircuitData<F, C, D>,
+        current_validator_set_circuit: &VerifierCircuitData<F, C, D>,
+        genesis_config: GenesisConfig,
+    ) -> CachedCircuit<WitnessTargets> {
+        let mut config = CircuitConfig::standard_recursion_config();
+        config.fri_config.cap_height = 0;
+        let mut builder = CircuitBuilder::new(config);
 
-            builder.verify_proof::<C>(
-                &proof_with_pis_target,
-                &verifier_data_target,
-                &self.current_validator_set_verifier_data.common,
-            );
+        let (message_sent_proof, message_sent_target) =
+            builder.verify_constant_circuit_proof::<MessageSentTarget>(message_sent_circuit);
 
-            LatestValidatorSetTarget::parse_exact(
-                &mut proof_with_pis_target.public_inputs.into_iter(),
-            )
-        };
+        let (current_validator_set_proof, latest_validator_set_target) = builder
+            .verify_constant_circuit_proof::<LatestValidatorSetTarget>(
+            current_validator_set_circuit,
+        );
 
         message_sent_target
             .validator_set_hash
@@ -111,6 +153,12 @@ impl FinalProof {
         }
         .register_as_public_inputs(&mut builder);
 
-        ProofWithCircuitData::prove_from_builder(builder, witness)
+        CachedCircuit {
+            circuit_data: builder.build::<C>(),
+            targets: WitnessTargets {
+                message_sent_proof,
+                current_validator_set_proof,
+            },
+        }
     }
 }
@@ -1,6 +1,7 @@
 //! ### Circuit that's used to prove correct transition from genesis to current validator set.
 
 use itertools::Itertools;
+use lazy_static::lazy_static;
 use plonky2::{
     field::types::Field,
     iop::{
@@ -9,14 +10,17 @@ use plonky2::{
     },
     plonk::{
         circuit_builder::CircuitBuilder,
-        circuit_data::{CircuitConfig, CircuitData, CommonCircuitData},
+        circuit_data::{CircuitConfig, VerifierCircuitData, VerifierCircuitTarget},
         proof::{ProofWithPublicInputs, ProofWithPublicInputsTarget},
     },
     recursion::dummy_circuit::cyclic_base_proof,
+    util::serialization::{Buffer, IoResult, Read, Write},
 };
+use std::sync::Arc;
 
 use crate::{
     common::{
+        circuit_cache::{CachedCircuit, CircuitCache, CircuitTargets},
         common_data_for_recursion,
         targets::{impl_target_set, Blake2TargetGoldilocks, TargetSet, VerifierDataTarget},
         BuilderExt, ProofWithCircuitData,
@@ -27,7 +31,7 @@ use crate::{
 
 pub mod next_validator_set;
 
-use next_validator_set::NextValidatorSet;
+use next_validator_set::{NextValidatorSet, NextValidatorSetTarget};
 
 // Depends on the `CircuitConfig` used to generate this proof.
 // `CircuitConfig::dtandard_recurion_config()` sets 16 merkle cap elements.
@@ -55,15 +59,39 @@ pub struct LatestValidatorSet {
     pub change_proof: NextValidatorSet,
 }
 
-/// Intermediate data that's used in the process of building circuit.
-struct Circuit {
-    cyclic_circuit_data: CircuitData<F, C, D>,
-
-    common_data: CommonCircuitData<F, D>,
+lazy_static! {
+    static ref CACHE: CircuitCache<WitnessTargets> = CircuitCache::new("latest_validator_set");
+}
 
+/// Targets that are set to prove the built circuit.
+struct WitnessTargets {
+    next_validator_set_proof: ProofWithPublicInputsTarget<D>,
+    verifier_data: VerifierCircuitTarget,
     condition: BoolTarget,
     inner_cyclic_proof_with_pis: ProofWithPublicInputsTarget<D>,
+}
 
+impl CircuitTargets for WitnessTargets {
+    fn write(&self, buffer: &mut Vec<u8>) -> IoResult<()> {
+        buffer.write_target_proof_with_public_inputs(&self.next_validator_set_proof)?;
+        buffer.write_target_verifier_circuit(&self.verifier_data)?;
+        buffer.write_target_bool(self.condition)?;
+        buffer.write_target_proof_with_public_inputs(&self.inner_cyclic_proof_with_pis)
+    }
+
+    fn read(buffer: &mut Buffer) -> IoResult<Self> {
+        Ok(Self {
+            next_validator_set_proof: buffer.read_target_proof_with_public_inputs()?,
+            verifier_data: buffer.read_target_verifier_circuit()?,
+            condition: buffer.read_target_bool()?,
+            inner_cyclic_proof_with_pis: buffer.read_target_proof_with_public_inputs()?,
+        })
+    }
+}
+
+/// Built circuit along with partially set witness.
+struct Circuit {
+    cached: Arc<CachedCircuit<WitnessTargets>>,
     witness: PartialWitness<F>,
 }
 
@@ -79,28 +107,35 @@ impl Circuit {
             .enumerate()
             .collect();
 
-        self.witness.set_bool_target(self.condition, false);
+        let cyclic_circuit_data = &self.cached.circuit_data;
+        let targets = &self.cached.targets;
+
+        // Common data of the cyclic circuit is checked to be equal to the one used
+        // for recursion when the circuit is built.
+        self.witness.set_bool_target(targets.condition, false);
         self.witness.set_proof_with_pis_target::<C, D>(
-            &self.inner_cyclic_proof_with_pis,
+            &targets.inner_cyclic_proof_with_pis,
             &cyclic_base_proof(
-                &self.common_data,
-                &self.cyclic_circuit_data.verifier_only,
+                &cyclic_circuit_data.common,
+                &cyclic_circuit_data.verifier_only,
                 genesis_data_pis,
             ),
         );
 
-        ProofWithCircuitData::prove_from_circuit_data(&self.cyclic_circuit_data, self.witness)
+        ProofWithCircuitData::prove_from_circuit_data(cyclic_circuit_data, self.witness)
     }
 
     fn prove_recursive(
         mut self,
         composed_proof: ProofWithPublicInputs<F, C, D>,
     ) -> ProofWithCircuitData<LatestValidatorSetTarget> {
-        self.witness.set_bool_target(self.condition, true);
+        let targets = &self.cached.targets;
+
+        self.witness.set_bool_target(targets.condition, true);
         self.witness
-            .set_proof_with_pis_target(&self.inner_cyclic_proof_with_pis, &composed_proof);
+            .set_proof_with_pis_target(&targets.inner_cyclic_proof_with_pis, &composed_proof);
 
-        ProofWithCircuitData::prove_from_circuit_data(&self.cyclic_circuit_data, self.witness)
+        ProofWithCircuitData::prove_from_circuit_data(&self.cached.circuit_data, self.witness)
     }
 }
 
@@ -126,6 +161,26 @@ impl LatestValidatorSet {
     fn build_circuit(self) -> Circuit {
         let next_validator_set_proof = self.change_proof.prove();
 
+        let cached = CACHE.get_or_build(&[next_validator_set_proof.circuit_data()], None, || {
+            Self::build(next_validator_set_proof.circuit_data())
+        });
+
+        let mut witness = PartialWitness::new();
+        witness.set_proof_with_pis_target(
+            &cached.targets.next_validator_set_proof,
+            &next_validator_set_proof.proof(),
+        );
+        witness.set_verifier_data_target(
+            &cached.targets.verifier_data,
+            &cached.circuit_data.verifier_only,
+        );
+
+        Circuit { cached, witness }
+    }
+
+    fn build(
+        next_validator_set_circuit: &VerifierCircuitData<F, C, D>,
+    ) -> CachedCircuit<WitnessTargets> {
         let mut builder = CircuitBuilder::new(CircuitConfig::standard_recursion_config());
         let one = builder.one();
 
@@ -136,10 +191,10 @@ impl LatestValidatorSet {
         genesis_authority_set_hash.register_as_public_inputs(&mut builder);
 
         // Verify validator set change
-        let mut witness = PartialWitness::new();
-
-        let next_authority_set_public_inputs =
-            builder.recursively_verify_constant_proof(&next_validator_set_proof, &mut witness);
+        let (next_validator_set_proof, next_authority_set_public_inputs) = builder
+            .verify_constant_circuit_proof::<NextValidatorSetTarget>(
+            next_validator_set_circuit,
+        );
 
         let current_set_id = next_authority_set_public_inputs.current_authority_set_id;
         let next_set_id = builder.add(current_set_id, one);
@@ -152,7 +207,7 @@ impl LatestValidatorSet {
             .register_as_public_inputs(&mut builder);
 
         // Recursion
-        let verifier_data_target = builder.add_verifier_data_public_inputs();
+        let verifier_data = builder.add_verifier_data_public_inputs();
         let common_data = common_data_for_recursion(
             CircuitConfig::standard_recursion_config(),
             builder.num_public_inputs(),
@@ -198,19 +253,14 @@ impl LatestValidatorSet {
             )
             .unwrap();
 
-        let cyclic_circuit_data = builder.build::<C>();
-
-        witness.set_verifier_data_target(&verifier_data_target, &cyclic_circuit_data.verifier_only);
-
-        Circuit {
-            cyclic_circuit_data,
-
-            common_data,
-
-            condition,
-            inner_cyclic_proof_with_pis,
-
-            witness,
+        CachedCircuit {
+            circuit_data: builder.build::<C>(),
+            targets: WitnessTargets {
+                next_validator_set_proof,
+                verifier_data,
+                condition,
+                inner_cyclic_proof_with_pis,
+            },
         }
     }
 }
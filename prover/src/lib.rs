@@ -59,6 +59,7 @@ pub mod proving {
         },
     };
     use consts::BLAKE2_DIGEST_SIZE;
+    use parity_scale_codec::{Decode, Encode};
     use plonky2::{
         plonk::{
             circuit_data::{CommonCircuitData, VerifierCircuitData},
@@ -67,6 +68,7 @@ pub mod proving {
         util::serialization::DefaultGateSerializer,
     };
     use serde::{Deserialize, Serialize};
+    use std::path::PathBuf;
 
     pub use crate::block_finality::{BlockFinality, PreCommit};
     pub use crate::storage_inclusion::{BranchNodeData, StorageInclusion};
@@ -74,7 +76,7 @@ pub mod proving {
     use self::consts::BLAKE2_DIGEST_SIZE_IN_GOLDILOCKS_FIELD_ELEMENTS;
 
     /// Represents proof along with public inputs. Can be verified using `CircuitData`.
-    #[derive(Clone)]
+    #[derive(Clone, Encode, Decode)]
     pub struct Proof(pub Vec<u8>);
 
     impl Proof {
@@ -100,7 +102,7 @@ pub mod proving {
     }
 
     /// Represents circuit data that's sufficient to verify a `Proof`.
-    #[derive(Clone)]
+    #[derive(Clone, Encode, Decode)]
     pub struct CircuitData(pub Vec<u8>);
 
     impl CircuitData {
@@ -126,7 +128,7 @@ pub mod proving {
         }
     }
 
-    #[derive(Clone)]
+    #[derive(Clone, Encode, Decode)]
     pub struct ProofWithCircuitData {
         pub proof: Proof,
         pub circuit_data: CircuitData,
@@ -155,7 +157,7 @@ pub mod proving {
     }
 
     /// All the data that's exported to `gnark-wrapper` as `JSON` strings.
-    #[derive(Clone, Serialize, Deserialize)]
+    #[derive(Clone, Serialize, Deserialize, Encode, Decode)]
     pub struct ExportedProofWithCircuitData {
         pub proof_with_public_inputs: String,
         pub common_circuit_data: String,
@@ -164,7 +166,7 @@ pub mod proving {
 
     /// Genesis config of a bridge. Note that any change in genesis config renders all the subsequent
     /// proofs invalid(they will NOT get verified on ethereum) as circuit digest will change.
-    #[derive(Clone, Copy)]
+    #[derive(Clone, Copy, PartialEq, Eq, Encode, Decode)]
     pub struct GenesisConfig {
         pub authority_set_id: u64,
         pub authority_set_hash: [u8; BLAKE2_DIGEST_SIZE],
@@ -186,6 +188,15 @@ pub mod proving {
         }
     }
 
+    /// Persist built circuits to `dir` so they're loaded instead of being rebuilt on the
+    /// subsequent runs. Cached circuits are checked to be built for the same inner circuits and
+    /// `GenesisConfig` before being used. Should be called before any proving.
+    pub fn set_circuit_cache_dir(dir: PathBuf) {
+        if common::circuit_cache::set_cache_dir(dir).is_err() {
+            log::warn!("Circuit cache directory is already set");
+        }
+    }
+
     /// Prove very first transition from genesis authority set to the subsequent.
     /// # Arguments
     ///
@@ -10,6 +10,7 @@
 //! All the above means that any data that's stored in `Leaf` or `HashedValueLeaf` will be parsed,
 //! except ones that have length < 32 bytes.
 
+use parity_scale_codec::{Decode, Encode};
 use plonky2::{
     iop::witness::PartialWitness,
     plonk::{circuit_builder::CircuitBuilder, circuit_data::CircuitConfig},
@@ -42,7 +43,7 @@ impl_parsable_target_set! {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Encode, Decode)]
 pub struct BranchNodeData {
     /// Encoded data for branch node.
     pub data: Vec<u8>,
@@ -50,7 +51,7 @@ pub struct BranchNodeData {
     pub child_nibble: u8,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Encode, Decode)]
 pub struct StorageInclusion {
     /// Encoded block header.
     pub block_header_data: Vec<u8>,
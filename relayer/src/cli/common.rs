@@ -1,4 +1,6 @@
-use clap::Args;
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
 
 #[derive(Args)]
 pub struct ProofStorageArgs {
@@ -17,6 +19,23 @@ pub struct GenesisConfigArgs {
     pub authority_set_id: u64,
 }
 
+#[derive(Args)]
+pub struct ProverArgs {
+    /// URL of the prover service. If not set, proofs are built by the relayer itself
+    #[arg(long = "prover-url", env = "PROVER_URL")]
+    pub url: Option<String>,
+    /// Interval in seconds between polls of the prover service for the job status
+    #[arg(
+        long = "prover-poll-interval",
+        default_value = "5",
+        env = "PROVER_POLL_INTERVAL"
+    )]
+    pub poll_interval: u64,
+    /// Directory to cache built circuits in. Used only when proofs are built by the relayer
+    #[arg(long = "circuit-cache-dir", env = "CIRCUIT_CACHE_DIR")]
+    pub circuit_cache_dir: Option<PathBuf>,
+}
+
 #[derive(Args)]
 pub struct GearSignerArgs {
     #[clap(flatten)]
@@ -26,8 +45,28 @@ pub struct GearSignerArgs {
     /// provides default users from the keyring (e.g., "//Alice", "//Bob",
     /// etc.). The password for URI should be specified in the same `suri`,
     /// separated by the ':' char
-    #[arg(long = "gear-suri", env = "GEAR_SURI")]
-    pub suri: String,
+    #[arg(
+        long = "gear-suri",
+        env = "GEAR_SURI",
+        required_unless_present = "remote_signer_url"
+    )]
+    pub suri: Option<String>,
+
+    /// URL of the remote sr25519 signer used instead of `--gear-suri`
+    #[arg(
+        long = "gear-remote-signer-url",
+        env = "GEAR_REMOTE_SIGNER_URL",
+        conflicts_with = "suri",
+        requires = "remote_signer_public_key"
+    )]
+    pub remote_signer_url: Option<String>,
+
+    /// Public key of the remote signer key in hex
+    #[arg(
+        long = "gear-remote-signer-public-key",
+        env = "GEAR_REMOTE_SIGNER_PUBLIC_KEY"
+    )]
+    pub remote_signer_public_key: Option<String>,
 }
 
 #[derive(Args)]
@@ -59,8 +98,83 @@ pub struct EthereumSignerArgs {
     pub ethereum_args: EthereumArgs,
 
     /// Private key for fee payer
-    #[arg(long = "eth-fee-payer", env = "ETH_FEE_PAYER")]
-    pub fee_payer: String,
+    #[arg(
+        long = "eth-fee-payer",
+        env = "ETH_FEE_PAYER",
+        required_unless_present = "web3signer_url"
+    )]
+    pub fee_payer: Option<String>,
+
+    /// URL of the Web3Signer holding fee payer key. Used instead of `--eth-fee-payer`
+    #[arg(
+        long = "eth-web3signer-url",
+        env = "ETH_WEB3SIGNER_URL",
+        conflicts_with = "fee_payer",
+        requires = "web3signer_public_key"
+    )]
+    pub web3signer_url: Option<String>,
+
+    /// Uncompressed secp256k1 public key of the fee payer in Web3Signer
+    #[arg(long = "eth-web3signer-public-key", env = "ETH_WEB3SIGNER_PUBLIC_KEY")]
+    pub web3signer_public_key: Option<String>,
+
+    #[clap(flatten)]
+    pub gas_args: GasArgs,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum GasStrategyKind {
+    /// Fees are estimated by the ethereum node
+    Provider,
+    /// Fees are estimated by the ethereum node but never exceed `--max-fee-per-gas`
+    FixedCap,
+    /// Priority fee is a percentile of the priority fees paid in the recent blocks
+    Percentile,
+}
+
+#[derive(Args)]
+pub struct GasArgs {
+    /// Strategy used to price transactions sent to ethereum
+    #[arg(
+        long = "gas-strategy",
+        env = "ETH_GAS_STRATEGY",
+        value_enum,
+        default_value = "provider"
+    )]
+    pub gas_strategy: GasStrategyKind,
+
+    /// Upper limit of the max fee per gas in gwei. Required by the fixed-cap strategy
+    #[arg(long = "max-fee-per-gas", env = "ETH_MAX_FEE_PER_GAS")]
+    pub max_fee_per_gas: Option<u64>,
+
+    /// Percentile of the priority fees used by the percentile strategy
+    #[arg(
+        long = "priority-fee-percentile",
+        env = "ETH_PRIORITY_FEE_PERCENTILE",
+        default_value = "50"
+    )]
+    pub priority_fee_percentile: f64,
+
+    /// Count of the recent blocks considered by the percentile strategy
+    #[arg(
+        long = "fee-history-blocks",
+        env = "ETH_FEE_HISTORY_BLOCKS",
+        default_value = "10"
+    )]
+    pub fee_history_blocks: u64,
+
+    /// Time in seconds after which a transaction that isn't included yet is replaced
+    /// with the same one but with higher fees. If not specified transactions aren't replaced
+    #[arg(long = "replace-after", env = "ETH_REPLACE_AFTER")]
+    pub replace_after: Option<u64>,
+
+    /// Fee increase of the replacement transaction in percents
+    #[arg(
+        long = "fee-bump-percent",
+        env = "ETH_FEE_BUMP_PERCENT",
+        default_value = "15"
+    )]
+    pub fee_bump_percent: u32,
 }
 
 #[derive(Args)]
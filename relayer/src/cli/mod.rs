@@ -3,8 +3,8 @@ use clap::{Args, Parser, Subcommand};
 mod common;
 
 pub use common::{
-    BeaconRpcArgs, EthereumArgs, EthereumSignerArgs, GearArgs, GearSignerArgs, GenesisConfigArgs,
-    PrometheusArgs, ProofStorageArgs,
+    BeaconRpcArgs, EthereumArgs, EthereumSignerArgs, GasArgs, GasStrategyKind, GearArgs,
+    GearSignerArgs, GenesisConfigArgs, PrometheusArgs, ProofStorageArgs, ProverArgs,
 };
 
 #[derive(Parser)]
@@ -27,6 +27,10 @@ pub enum CliCommands {
     GearEthTokens(GearEthTokensArgs),
     /// Relay tokens from ethereum to gear
     EthGearTokens(EthGearTokensArgs),
+    /// Relay messages sent through VaraMessenger from ethereum to gear
+    EthGearMessages(EthGearMessagesArgs),
+    /// Prove delivery of the paid messages to bridging-payment
+    EthGearDeliveryProofs(EthGearDeliveryProofsArgs),
 
     /// Manually relay message from gear to ethereum
     GearEthManual(GearEthManualArgs),
@@ -36,6 +40,9 @@ pub enum CliCommands {
     /// Start kill switch relayer
     KillSwitch(KillSwitchArgs),
 
+    /// Report ethereum gas price to the fee oracle of bridging-payment
+    GasPriceOracle(GasPriceOracleArgs),
+
     /// Fetch relayed merkle roots to Ethereum
     FetchMerkleRoots(FetchMerkleRootsArgs),
 }
@@ -52,6 +59,8 @@ pub struct GearEthCoreArgs {
     pub prometheus_args: PrometheusArgs,
     #[clap(flatten)]
     pub proof_storage_args: ProofStorageArgs,
+    #[clap(flatten)]
+    pub prover_args: ProverArgs,
 
     /// Authority set id to start relaying from. If not specified equals to one from the latest finalized block
     #[arg(long, env = "START_AUTHORITY_SET_ID")]
@@ -82,10 +91,36 @@ pub struct GearEthTokensArgs {
     #[clap(subcommand)]
     pub command: GearEthTokensCommands,
 
-    /// Block number to start relaying from. If not specified equals to the latest finalized block
+    /// Block number to start relaying from. If not specified equals to the block following
+    /// the last one recorded in the journal or to the latest finalized block
     #[arg(long = "from-block")]
     pub from_block: Option<u32>,
 
+    /// Path to the journal used to resume relaying after restart
+    #[arg(
+        long = "journal-path",
+        env = "GEAR_ETH_JOURNAL_PATH",
+        default_value = "./gear_eth_journal"
+    )]
+    pub journal_path: String,
+
+    /// Maximum amount of messages from the same merkle tree submitted to Ethereum
+    /// in a single transaction
+    #[arg(
+        long = "max-batch-size",
+        env = "GEAR_ETH_MAX_BATCH_SIZE",
+        default_value = "1"
+    )]
+    pub max_batch_size: usize,
+
+    /// Time in seconds to wait for a batch to fill up before submitting it
+    #[arg(
+        long = "batch-flush-timeout",
+        env = "GEAR_ETH_BATCH_FLUSH_TIMEOUT",
+        default_value = "30"
+    )]
+    pub batch_flush_timeout: u64,
+
     #[clap(flatten)]
     pub gear_args: GearArgs,
     #[clap(flatten)]
@@ -103,6 +138,11 @@ pub enum GearEthTokensCommands {
         /// Address of the bridging-payment program
         #[arg(long = "bridging-payment-address", env = "BRIDGING_PAYMENT_ADDRESS")]
         bridging_payment_address: String,
+
+        /// Address of the vft-manager program. If specified, refunds of the failed
+        /// Ethereum deposits requested from it are relayed as well
+        #[arg(long = "vft-manager-address", env = "VFT_MANAGER_ADDRESS")]
+        vft_manager_address: Option<String>,
     },
 }
 
@@ -124,6 +164,14 @@ pub struct EthGearTokensArgs {
     #[arg(long = "vft-manager-address", env = "VFT_MANAGER_ADDRESS")]
     pub vft_manager_address: String,
 
+    /// Path to the journal used to resume relaying after restart
+    #[arg(
+        long = "journal-path",
+        env = "ETH_GEAR_JOURNAL_PATH",
+        default_value = "./eth_gear_journal"
+    )]
+    pub journal_path: String,
+
     #[clap(flatten)]
     pub gear_args: GearSignerArgs,
     #[clap(flatten)]
@@ -150,6 +198,110 @@ pub enum EthGearTokensCommands {
     },
 }
 
+#[derive(Args)]
+pub struct GasPriceOracleArgs {
+    /// Address of the bridging-payment program
+    #[arg(long = "bridging-payment-address", env = "BRIDGING_PAYMENT_ADDRESS")]
+    pub bridging_payment_address: String,
+
+    /// Amount of VARA that 1 ETH is worth, used to convert the gas price
+    #[arg(long = "vara-per-eth", env = "GAS_PRICE_ORACLE_VARA_PER_ETH")]
+    pub vara_per_eth: f64,
+
+    /// Time in seconds between the gas price reports. Should be less than the
+    /// `max_gas_price_age` of the fee formula so the reported price doesn't become stale
+    #[arg(
+        long = "report-interval",
+        env = "GAS_PRICE_ORACLE_REPORT_INTERVAL",
+        default_value = "60"
+    )]
+    pub report_interval: u64,
+
+    #[clap(flatten)]
+    pub gear_args: GearSignerArgs,
+    #[clap(flatten)]
+    pub ethereum_args: EthereumArgs,
+    #[clap(flatten)]
+    pub prometheus_args: PrometheusArgs,
+}
+
+#[derive(Args)]
+pub struct EthGearMessagesArgs {
+    /// Address of the VaraMessenger contract on ethereum
+    #[arg(long = "vara-messenger-address", env = "VARA_MESSENGER_ADDRESS")]
+    pub vara_messenger_address: String,
+
+    /// Address of the checkpoint-light-client program on gear
+    #[arg(
+        long = "checkpoint-light-client-address",
+        env = "CHECKPOINT_LIGHT_CLIENT_ADDRESS"
+    )]
+    pub checkpoint_light_client_address: String,
+
+    #[arg(long = "historical-proxy-address", env = "HISTORICAL_PROXY_ADDRESS")]
+    pub historical_proxy_address: String,
+
+    /// ProgramId of the program that will receive messages. Only messages destined
+    /// to it are relayed
+    #[arg(long = "receiver-address", env = "RECEIVER_ADDRESS")]
+    pub receiver_address: String,
+
+    /// Route of the function that will be called on receiver program
+    #[arg(long = "receiver-route", env = "RECEIVER_ROUTE")]
+    pub receiver_route: String,
+
+    /// Path to the journal used to resume relaying after restart
+    #[arg(
+        long = "journal-path",
+        env = "ETH_GEAR_MESSAGES_JOURNAL_PATH",
+        default_value = "./eth_gear_messages_journal"
+    )]
+    pub journal_path: String,
+
+    #[clap(flatten)]
+    pub gear_args: GearSignerArgs,
+    #[clap(flatten)]
+    pub ethereum_args: EthereumArgs,
+    #[clap(flatten)]
+    pub beacon_rpc: BeaconRpcArgs,
+    #[clap(flatten)]
+    pub prometheus_args: PrometheusArgs,
+}
+
+#[derive(Args)]
+pub struct EthGearDeliveryProofsArgs {
+    /// Address of the bridging-payment program on gear
+    #[arg(long = "bridging-payment-address", env = "BRIDGING_PAYMENT_ADDRESS")]
+    pub bridging_payment_address: String,
+
+    /// Address of the checkpoint-light-client program on gear
+    #[arg(
+        long = "checkpoint-light-client-address",
+        env = "CHECKPOINT_LIGHT_CLIENT_ADDRESS"
+    )]
+    pub checkpoint_light_client_address: String,
+
+    #[arg(long = "historical-proxy-address", env = "HISTORICAL_PROXY_ADDRESS")]
+    pub historical_proxy_address: String,
+
+    /// Path to the journal used to resume relaying after restart
+    #[arg(
+        long = "journal-path",
+        env = "ETH_GEAR_DELIVERY_PROOFS_JOURNAL_PATH",
+        default_value = "./eth_gear_delivery_proofs_journal"
+    )]
+    pub journal_path: String,
+
+    #[clap(flatten)]
+    pub gear_args: GearSignerArgs,
+    #[clap(flatten)]
+    pub ethereum_args: EthereumArgs,
+    #[clap(flatten)]
+    pub beacon_rpc: BeaconRpcArgs,
+    #[clap(flatten)]
+    pub prometheus_args: PrometheusArgs,
+}
+
 #[derive(Args)]
 pub struct GearEthManualArgs {
     /// Nonce of the target message
@@ -220,6 +372,8 @@ pub struct KillSwitchArgs {
     pub prometheus_args: PrometheusArgs,
     #[clap(flatten)]
     pub proof_storage_args: ProofStorageArgs,
+    #[clap(flatten)]
+    pub prover_args: ProverArgs,
 }
 
 #[derive(Args)]
@@ -1,11 +1,11 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use alloy::transports::{RpcError, TransportErrorKind};
 use prover::proving::GenesisConfig;
 
 use crate::{
     proof_storage::ProofStorage,
-    prover_interface::{self, FinalProof},
+    prover_interface::{self, FinalProof, Prover},
 };
 
 use ethereum_client::{EthApi, TxHash};
@@ -13,12 +13,16 @@ use gear_rpc_client::GearApi;
 
 pub(crate) type SyncStepCount = usize;
 
+/// Prove authority set changes up to `latest_authority_set_id` but no more than
+/// `max_steps` of them in a single call.
 pub(crate) async fn sync_authority_set_id(
     gear_api: &GearApi,
+    prover: &Prover,
     proof_storage: &mut dyn ProofStorage,
     genesis_config: GenesisConfig,
     latest_authority_set_id: u64,
     latest_proven_authority_set_id: Option<u64>,
+    max_steps: u64,
 ) -> anyhow::Result<SyncStepCount> {
     let Some(latest_proven) = latest_proven_authority_set_id else {
         if latest_authority_set_id <= genesis_config.authority_set_id {
@@ -31,7 +35,7 @@ pub(crate) async fn sync_authority_set_id(
             return Ok(0);
         }
 
-        let proof = prover_interface::prove_genesis(gear_api, genesis_config).await?;
+        let proof = prover_interface::prove_genesis(gear_api, prover, genesis_config).await?;
         proof_storage
             .init(proof, genesis_config.authority_set_id)
             .unwrap();
@@ -50,14 +54,23 @@ pub(crate) async fn sync_authority_set_id(
     }
 
     if latest_proven < latest_authority_set_id {
-        let mut proof = proof_storage.get_proof_for_authority_set_id(latest_proven)?;
-
-        for set_id in latest_proven..latest_authority_set_id {
-            proof = prover_interface::prove_validator_set_change(gear_api, proof, set_id).await?;
-            proof_storage.update(proof.proof.clone(), set_id + 1)?;
-        }
+        let proof = proof_storage.get_proof_for_authority_set_id(latest_proven)?;
+        let sync_up_to = latest_authority_set_id.min(latest_proven.saturating_add(max_steps));
+
+        prover_interface::prove_validator_set_changes(
+            gear_api,
+            prover,
+            proof,
+            latest_proven..sync_up_to,
+            |authority_set_id, proof| {
+                proof_storage.update(proof.proof.clone(), authority_set_id)?;
+
+                Ok(())
+            },
+        )
+        .await?;
 
-        let step_count = latest_authority_set_id - latest_proven;
+        let step_count = sync_up_to - latest_proven;
         return Ok(step_count as usize);
     }
 
@@ -91,6 +104,32 @@ pub(crate) async fn submit_merkle_root_to_ethereum(
     Ok(tx_hash)
 }
 
+/// Replace the transaction with the one paying higher fees if it isn't included
+/// for longer than the gas strategy allows.
+pub(crate) async fn replace_stuck_transaction(
+    eth_api: &EthApi,
+    tx_hash: &mut TxHash,
+    submitted_at: &mut Instant,
+) -> anyhow::Result<()> {
+    let Some(timeout) = eth_api.replacement_timeout() else {
+        return Ok(());
+    };
+
+    if submitted_at.elapsed() < timeout {
+        return Ok(());
+    }
+
+    if let Some(tx_hash_new) = eth_api.replace_transaction(*tx_hash).await? {
+        log::warn!("Transaction {tx_hash} is stuck. Replaced with {tx_hash_new}");
+
+        *tx_hash = tx_hash_new;
+    }
+
+    *submitted_at = Instant::now();
+
+    Ok(())
+}
+
 pub(crate) fn is_rpc_transport_error_recoverable(err: &RpcError<TransportErrorKind>) -> bool {
     match err {
         RpcError::Transport(transport) => match transport {
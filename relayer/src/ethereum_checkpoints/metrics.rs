@@ -23,5 +23,25 @@ impl_metered_service! {
             "checkpoints_relayer_account_total_balance",
             "The total balance of the account used to send messages",
         ),
+        pub sync_committee_participation: IntGauge = IntGauge::new(
+            "checkpoints_relayer_sync_committee_participation",
+            "Amount of the sync committee members that signed the latest accepted update",
+        ),
+        pub sync_committee_participation_min: IntGauge = IntGauge::new(
+            "checkpoints_relayer_sync_committee_participation_min",
+            "Minimal sync committee participation among the recently accepted updates",
+        ),
+        pub participation_signature_slot: IntGauge = IntGauge::new(
+            "checkpoints_relayer_participation_signature_slot",
+            "The signature slot of the latest accepted update",
+        ),
+        pub participation_attested_slot: IntGauge = IntGauge::new(
+            "checkpoints_relayer_participation_attested_slot",
+            "The attested slot of the latest accepted update",
+        ),
+        pub unarchived_checkpoints: IntGauge = IntGauge::new(
+            "checkpoints_relayer_unarchived_checkpoints",
+            "Amount of the evicted checkpoints that aren't exported to the archive yet",
+        ),
     }
 }
@@ -12,11 +12,15 @@ use tokio::{
     time::{self, Duration},
 };
 
+use checkpoint_light_client_client::{
+    traits::{ServiceArchive as _, ServiceState as _},
+    Order, ServiceArchive, ServiceState,
+};
 use checkpoint_light_client_io::{
     Error, Hash256, ReplayBack, Slot, Update as SyncCommitteeUpdate, G2,
 };
 use ethereum_beacon_client::{slots_batch::Iter as SlotsBatchIter, BeaconClient};
-use ethereum_common::{utils as eth_utils, MAX_REQUEST_LIGHT_CLIENT_UPDATES};
+use ethereum_common::{network::Network, MAX_REQUEST_LIGHT_CLIENT_UPDATES};
 use sails_rs::{calls::*, gclient::calls::*};
 use utils_prometheus::MeteredService;
 
@@ -27,6 +31,10 @@ mod sync_update;
 const SIZE_CHANNEL: usize = 100_000;
 const COUNT_FAILURE: usize = 3;
 const DELAY_SECS_UPDATE_REQUEST: u64 = 30;
+// Amount of the latest accepted updates the minimal participation is calculated over.
+const PARTICIPATION_WINDOW: u32 = 32;
+// Maximum amount of the evicted checkpoints exported to the archive at once.
+const SIZE_EXPORT_BATCH: u32 = 256;
 // The constant is intentionally duplicated since vara-runtime is too heavy dependency.
 const UNITS: u128 = 1_000_000_000_000;
 
@@ -43,7 +51,8 @@ pub struct Relayer {
 
     metrics: metrics::Updates,
 
-    size_batch: u64,
+    /// Amount of epochs in the batch of the replayed back headers.
+    size_batch_multiplier: u64,
 }
 
 impl MeteredService for Relayer {
@@ -57,14 +66,14 @@ impl Relayer {
         program_id: H256,
         beacon_client: BeaconClient,
         gear_api: GearApi,
-        size_batch: u64,
+        size_batch_multiplier: u64,
     ) -> Self {
         Self {
             program_id,
             beacon_client,
             gear_api,
             metrics: metrics::Updates::new(),
-            size_batch,
+            size_batch_multiplier,
         }
     }
 
@@ -74,9 +83,22 @@ impl Relayer {
         let mut signal_interrupt =
             unix::signal(SignalKind::interrupt()).expect("Set SIGINT handler");
 
+        let remoting = GClientRemoting::new(self.gear_api.clone());
+        let network = match ServiceState::new(remoting.clone())
+            .network()
+            .recv(self.program_id.0.into())
+            .await
+        {
+            Ok(network) => network,
+            Err(e) => {
+                log::error!("Unable to get network of the program: {e:?}");
+                return;
+            }
+        };
+
         let (sender, mut receiver) = mpsc::channel(SIZE_CHANNEL);
 
-        sync_update::spawn_receiver(self.beacon_client.clone(), sender);
+        sync_update::spawn_receiver(self.beacon_client.clone(), network.clone(), sender);
 
         let gas_limit_block = self
             .gear_api
@@ -96,7 +118,7 @@ impl Relayer {
             .expect("Updates receiver should be open before the loop");
 
         let mut slot_last = sync_update.finalized_header.slot;
-        let remoting = GClientRemoting::new(self.gear_api.clone());
+        let mut attested_slot_last = sync_update.attested_header.slot;
 
         match sync_update::try_to_apply(
             &remoting,
@@ -117,13 +139,16 @@ impl Relayer {
             })) => {
                 if let Err(e) = replay_back::execute(replay_back::Args {
                     beacon_client: &self.beacon_client,
+                    network: &network,
                     remoting: &remoting,
                     program_id: self.program_id.0,
                     gas_limit,
                     replay_back,
                     checkpoint,
                     sync_update,
-                    size_batch: self.size_batch,
+                    size_batch: self
+                        .size_batch_multiplier
+                        .saturating_mul(network.slots_per_epoch()),
                     sync_aggregate_encoded,
                 })
                 .await
@@ -139,6 +164,8 @@ impl Relayer {
         }
 
         update_total_balance(&self.gear_api, &self.metrics).await;
+        update_participation(&remoting, self.program_id.0, &self.metrics).await;
+        export_checkpoints(&remoting, self.program_id.0, gas_limit, &self.metrics).await;
 
         loop {
             let future_interrupt = signal_interrupt.recv();
@@ -163,6 +190,7 @@ impl Relayer {
                 }
             };
             let slot = sync_update.finalized_header.slot;
+            let attested_slot = sync_update.attested_header.slot;
 
             self.metrics
                 .fetched_sync_update_slot
@@ -173,7 +201,11 @@ impl Relayer {
                 self.metrics.total_fetched_finality_updates.inc();
             }
 
-            if slot == slot_last {
+            // non-finalized updates are accepted by the program only in the forced update mode
+            if slot == slot_last
+                && (attested_slot <= attested_slot_last
+                    || !sync_update::force_update_enabled(&remoting, self.program_id.0).await)
+            {
                 continue;
             }
 
@@ -188,6 +220,7 @@ impl Relayer {
             {
                 Ok(Ok(_)) => {
                     slot_last = slot;
+                    attested_slot_last = attested_slot;
 
                     if committee_update {
                         self.metrics.processed_committee_updates.inc();
@@ -203,6 +236,7 @@ impl Relayer {
                     log::error!("The program failed with: {e:?}. Skipping");
                     if let Error::NotActual = e {
                         slot_last = slot;
+                        attested_slot_last = attested_slot;
                     }
                 }
                 Err(e) => {
@@ -212,6 +246,8 @@ impl Relayer {
             }
 
             update_total_balance(&self.gear_api, &self.metrics).await;
+            update_participation(&remoting, self.program_id.0, &self.metrics).await;
+            export_checkpoints(&remoting, self.program_id.0, gas_limit, &self.metrics).await;
         }
     }
 }
@@ -227,3 +263,81 @@ async fn update_total_balance(client: &GearApi, update_metrics: &metrics::Update
         Err(e) => log::error!("Unable to get total balance: {e:?}"),
     }
 }
+
+/// Export the checkpoints evicted from the window of the program to the archive. The program
+/// keeps a bounded amount of them so they should be exported regularly.
+async fn export_checkpoints(
+    remoting: &GClientRemoting,
+    program_id: [u8; 32],
+    gas_limit: u64,
+    update_metrics: &metrics::Updates,
+) {
+    let mut service = ServiceArchive::new(remoting.clone());
+    let count = match service.unarchived_count().recv(program_id.into()).await {
+        Ok(count) => count,
+        Err(e) => {
+            log::error!("Unable to get amount of the unarchived checkpoints: {e:?}");
+            return;
+        }
+    };
+
+    update_metrics.unarchived_checkpoints.set(count.into());
+    if count == 0 {
+        return;
+    }
+
+    match service
+        .export(SIZE_EXPORT_BATCH)
+        .with_gas_limit(gas_limit)
+        .send_recv(program_id.into())
+        .await
+    {
+        Ok(exported) => {
+            log::info!("Exported {exported} checkpoint(s) to the archive");
+
+            update_metrics
+                .unarchived_checkpoints
+                .set(count.saturating_sub(exported).into());
+        }
+        Err(e) => log::error!("Unable to export checkpoints to the archive: {e:?}"),
+    }
+}
+
+async fn update_participation(
+    remoting: &GClientRemoting,
+    program_id: [u8; 32],
+    update_metrics: &metrics::Updates,
+) {
+    let service = ServiceState::new(remoting.clone());
+    let participation = match service
+        .participation(Order::Reverse, 0, PARTICIPATION_WINDOW)
+        .recv(program_id.into())
+        .await
+    {
+        Ok(participation) => participation,
+        Err(e) => {
+            log::error!("Unable to get sync committee participation: {e:?}");
+            return;
+        }
+    };
+
+    let Some(latest) = participation.first() else {
+        return;
+    };
+
+    update_metrics
+        .sync_committee_participation
+        .set(latest.bit_count.into());
+    update_metrics
+        .participation_signature_slot
+        .set(i64::from_le_bytes(latest.signature_slot.to_le_bytes()));
+    update_metrics
+        .participation_attested_slot
+        .set(i64::from_le_bytes(latest.attested_slot.to_le_bytes()));
+
+    if let Some(min) = participation.iter().map(|p| p.bit_count).min() {
+        update_metrics
+            .sync_committee_participation_min
+            .set(min.into());
+    }
+}
@@ -4,6 +4,7 @@ use ethereum_beacon_client::{self, BeaconClient};
 
 pub struct Args<'a> {
     pub beacon_client: &'a BeaconClient,
+    pub network: &'a Network,
     pub remoting: &'a GClientRemoting,
     pub program_id: [u8; 32],
     pub gas_limit: u64,
@@ -17,6 +18,7 @@ pub struct Args<'a> {
 pub async fn execute(args: Args<'_>) -> AnyResult<()> {
     let Args {
         beacon_client,
+        network,
         remoting,
         program_id,
         gas_limit,
@@ -50,7 +52,7 @@ pub async fn execute(args: Args<'_>) -> AnyResult<()> {
         slot_start = finalized_header;
     }
 
-    let period_start = 1 + eth_utils::calculate_period(slot_start);
+    let period_start = 1 + network.calculate_period(slot_start);
     let updates = beacon_client
         .get_updates(period_start, MAX_REQUEST_LIGHT_CLIENT_UPDATES)
         .await
@@ -1,15 +1,18 @@
 use super::*;
-use checkpoint_light_client_client::{traits::ServiceSyncUpdate as _, ServiceSyncUpdate};
+use checkpoint_light_client_client::{
+    traits::{ServiceForceUpdate as _, ServiceSyncUpdate as _},
+    ServiceForceUpdate, ServiceSyncUpdate,
+};
 use ethereum_beacon_client::{utils, BeaconClient};
 use std::ops::ControlFlow::{self, *};
 
-pub fn spawn_receiver(beacon_client: BeaconClient, sender: Sender<SyncUpdate>) {
+pub fn spawn_receiver(beacon_client: BeaconClient, network: Network, sender: Sender<SyncUpdate>) {
     tokio::spawn(async move {
         log::info!("Update receiver spawned");
 
         let mut failures = 0;
         loop {
-            match receive(&beacon_client, &sender).await {
+            match receive(&beacon_client, &network, &sender).await {
                 Ok(Break(_)) => break,
                 Ok(Continue(_)) => (),
                 Err(e) => {
@@ -29,6 +32,7 @@ pub fn spawn_receiver(beacon_client: BeaconClient, sender: Sender<SyncUpdate>) {
 
 async fn receive(
     beacon_client: &BeaconClient,
+    network: &Network,
     sender: &Sender<SyncUpdate>,
 ) -> AnyResult<ControlFlow<()>> {
     let finality_update = beacon_client
@@ -36,7 +40,7 @@ async fn receive(
         .await
         .map_err(|e| anyhow!("Unable to fetch FinalityUpdate: {e:?}"))?;
 
-    let period = eth_utils::calculate_period(finality_update.finalized_header.slot);
+    let period = network.calculate_period(finality_update.finalized_header.slot);
     let mut updates = beacon_client
         .get_updates(period, 1)
         .await
@@ -101,3 +105,17 @@ pub async fn try_to_apply(
         .await
         .map_err(|e| anyhow!("Failed to apply sync committee: {e:?}"))
 }
+
+pub async fn force_update_enabled(remoting: &GClientRemoting, program_id: [u8; 32]) -> bool {
+    match ServiceForceUpdate::new(remoting.clone())
+        .config()
+        .recv(program_id.into())
+        .await
+    {
+        Ok(config) => config.is_some(),
+        Err(e) => {
+            log::error!("Unable to get the forced update config: {e:?}");
+            false
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use anyhow::anyhow;
+use bridging_payment_client::{
+    bridging_payment::io::UpdateGasPrice, traits::BridgingPayment as _, BridgingPayment, Direction,
+};
+use ethereum_client::EthApi;
+use gclient::GearApi;
+use primitive_types::H256;
+use prometheus::{IntCounter, IntGauge};
+use sails_rs::{calls::*, gclient::calls::*};
+use utils_prometheus::{impl_metered_service, MeteredService};
+
+use crate::message_relayer::{
+    common::gear::signer::GearSigner, eth_to_gear::api_provider::ApiProviderConnection,
+};
+
+// The constant is intentionally duplicated since vara-runtime is too heavy dependency.
+const UNITS: u128 = 1_000_000_000_000;
+const WEI_PER_ETH: u128 = 1_000_000_000_000_000_000;
+
+impl_metered_service! {
+    struct Metrics {
+        ethereum_gas_price: IntGauge = IntGauge::new(
+            "gas_price_oracle_ethereum_gas_price",
+            "Latest observed Ethereum gas price in wei",
+        ),
+        reported_gas_price: IntGauge = IntGauge::new(
+            "gas_price_oracle_reported_gas_price",
+            "Latest gas price reported to bridging-payment in the smallest units of VARA",
+        ),
+        failed_reports_cnt: IntCounter = IntCounter::new(
+            "gas_price_oracle_failed_reports_cnt",
+            "Amount of failed gas price reports",
+        ),
+    }
+}
+
+/// Periodically reports Ethereum gas price to the fee oracle of the `bridging-payment`
+/// program so the fee for the messages sent to Ethereum follows the gas price.
+///
+/// The account of the relayer should be allowed to report gas prices by the admin
+/// of the program with `set_gas_price_oracle`.
+pub struct GasPriceOracle {
+    eth_api: EthApi,
+    api_provider: ApiProviderConnection,
+    signer: GearSigner,
+    bridging_payment_address: H256,
+    vara_per_eth: f64,
+    interval: Duration,
+
+    metrics: Metrics,
+}
+
+impl MeteredService for GasPriceOracle {
+    fn get_sources(&self) -> impl IntoIterator<Item = Box<dyn prometheus::core::Collector>> {
+        self.metrics.get_sources()
+    }
+}
+
+impl GasPriceOracle {
+    pub fn new(
+        eth_api: EthApi,
+        api_provider: ApiProviderConnection,
+        signer: GearSigner,
+        bridging_payment_address: H256,
+        vara_per_eth: f64,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            eth_api,
+            api_provider,
+            signer,
+            bridging_payment_address,
+            vara_per_eth,
+            interval,
+
+            metrics: Metrics::new(),
+        }
+    }
+
+    pub async fn run(mut self) {
+        log::info!("Starting gas price oracle");
+
+        loop {
+            if let Err(e) = self.report().await {
+                log::error!("Failed to report gas price: {e:?}");
+                self.metrics.failed_reports_cnt.inc();
+
+                if let Err(e) = self.api_provider.reconnect().await {
+                    log::error!("Gas price oracle unable to reconnect: {e}");
+                }
+            }
+
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    async fn report(&mut self) -> anyhow::Result<()> {
+        let gas_price_wei = self.eth_api.gas_price().await?;
+        self.metrics
+            .ethereum_gas_price
+            .set(i64::try_from(gas_price_wei).unwrap_or(i64::MAX));
+
+        let gas_price = to_vara_units(gas_price_wei, self.vara_per_eth);
+        if gas_price == 0 {
+            return Err(anyhow!(
+                "Gas price of {gas_price_wei} wei is less than the smallest unit of VARA"
+            ));
+        }
+
+        match &self.signer {
+            GearSigner::Suri(suri) => {
+                let gear_api = self.api_provider.gclient_client(suri)?;
+
+                BridgingPayment::new(GClientRemoting::new(gear_api))
+                    .update_gas_price(Direction::VaraToEth, gas_price)
+                    .send_recv(self.bridging_payment_address.0.into())
+                    .await
+                    .map_err(|e| anyhow!("Failed to send gas price to bridging-payment: {e:?}"))?;
+            }
+
+            GearSigner::Remote(signer) => {
+                let api = self.api_provider.api();
+                // Use 95% of block gas limit for all extrinsics.
+                let gas_limit = GearApi::from(api.clone()).block_gas_limit()? / 100 * 95;
+
+                let call = UpdateGasPrice::encode_call(&(Direction::VaraToEth, gas_price));
+                let reply = signer
+                    .send_message(&api, self.bridging_payment_address, call, gas_limit)
+                    .await
+                    .map_err(|e| anyhow!("Failed to send gas price to bridging-payment: {e:?}"))?;
+
+                UpdateGasPrice::decode_reply(&reply)
+                    .map_err(|e| anyhow!("Failed to decode bridging-payment reply: {e:?}"))?;
+            }
+        }
+
+        log::info!("Reported gas price of {gas_price_wei} wei as {gas_price} VARA units");
+        self.metrics
+            .reported_gas_price
+            .set(i64::try_from(gas_price).unwrap_or(i64::MAX));
+
+        Ok(())
+    }
+}
+
+/// Convert the price in wei to the smallest units of VARA with the exchange rate.
+fn to_vara_units(price_wei: u128, vara_per_eth: f64) -> u128 {
+    let units_per_eth = (vara_per_eth * UNITS as f64) as u128;
+
+    price_wei.saturating_mul(units_per_eth) / WEI_PER_ETH
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gas_price_conversion() {
+        // 30 gwei with 1 ETH = 200 000 VARA
+        assert_eq!(to_vara_units(30_000_000_000, 200_000.0), 6_000_000_000);
+        assert_eq!(to_vara_units(1, 1.0), 0);
+        assert_eq!(to_vara_units(WEI_PER_ETH, 0.5), UNITS / 2);
+    }
+}
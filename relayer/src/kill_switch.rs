@@ -17,7 +17,7 @@ use crate::{
     },
     message_relayer::eth_to_gear::api_provider::ApiProviderConnection,
     proof_storage::ProofStorage,
-    prover_interface::{self, FinalProof},
+    prover_interface::{self, FinalProof, Prover},
 };
 use block_finality_archiver::Metrics as BlockFinalityArchiverMetrics;
 
@@ -67,6 +67,7 @@ enum State {
 pub struct KillSwitchRelayer {
     api_provider: ApiProviderConnection,
     eth_api: EthApi,
+    prover: Prover,
     genesis_config: GenesisConfig,
     proof_storage: Box<dyn ProofStorage>,
 
@@ -92,6 +93,7 @@ impl KillSwitchRelayer {
     pub async fn new(
         api_provider: ApiProviderConnection,
         eth_api: EthApi,
+        prover: Prover,
         genesis_config: GenesisConfig,
         proof_storage: Box<dyn ProofStorage>,
         from_eth_block: Option<u64>,
@@ -100,6 +102,7 @@ impl KillSwitchRelayer {
         Self {
             api_provider,
             eth_api,
+            prover,
             genesis_config,
             proof_storage,
             start_from_eth_block: from_eth_block,
@@ -298,10 +301,12 @@ impl KillSwitchRelayer {
 
         sync_authority_set_id(
             &gear_api,
+            &self.prover,
             self.proof_storage.as_mut(),
             self.genesis_config,
             latest_authority_set_id,
             latest_proven_authority_set_id,
+            u64::MAX,
         )
         .await
     }
@@ -404,6 +409,7 @@ impl KillSwitchRelayer {
 
         prover_interface::prove_final_with_block_finality(
             &gear_api,
+            &self.prover,
             inner_proof,
             self.genesis_config,
             (block_hash, block_finality),
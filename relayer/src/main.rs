@@ -3,22 +3,31 @@ use std::time::Duration;
 use clap::Parser;
 
 use ethereum_beacon_client::BeaconClient;
-use ethereum_client::EthApi;
-use ethereum_common::SLOTS_PER_EPOCH;
+use ethereum_client::{
+    gas::{GasPricing, GasStrategy, Replacement},
+    EthApi, Web3Signer,
+};
+use gas_price_oracle::GasPriceOracle;
 use kill_switch::KillSwitchRelayer;
 use message_relayer::{
+    common::{
+        ethereum::batcher::BatchConfig,
+        gear::signer::{GearSigner, RemoteSigner},
+    },
     eth_to_gear::{self, api_provider::ApiProvider},
-    gear_to_eth,
+    gear_to_eth::{self, journal::Journal},
 };
 use primitive_types::U256;
 use proof_storage::{FileSystemProofStorage, GearProofStorage, ProofStorage};
 use prover::proving::GenesisConfig;
+use prover_interface::Prover;
 use relay_merkle_roots::MerkleRootRelayer;
 use utils_prometheus::MetricsBuilder;
 
 mod cli;
 mod common;
 mod ethereum_checkpoints;
+mod gas_price_oracle;
 mod hex_utils;
 mod kill_switch;
 mod message_relayer;
@@ -27,11 +36,15 @@ mod prover_interface;
 mod relay_merkle_roots;
 
 use cli::{
-    BeaconRpcArgs, Cli, CliCommands, EthGearManualArgs, EthGearTokensArgs, EthGearTokensCommands,
-    EthereumArgs, EthereumSignerArgs, FetchMerkleRootsArgs, GearArgs, GearEthTokensCommands,
-    GearSignerArgs, GenesisConfigArgs, ProofStorageArgs,
+    BeaconRpcArgs, Cli, CliCommands, EthGearDeliveryProofsArgs, EthGearManualArgs,
+    EthGearMessagesArgs, EthGearTokensArgs, EthGearTokensCommands, EthereumArgs,
+    EthereumSignerArgs, FetchMerkleRootsArgs, GasArgs, GasStrategyKind, GearArgs,
+    GearEthTokensCommands, GearSignerArgs, GenesisConfigArgs, ProofStorageArgs, ProverArgs,
 };
 
+/// Timeout of requests to the remote signers.
+const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[tokio::main]
 async fn main() {
     let _ = dotenv::dotenv();
@@ -60,7 +73,7 @@ async fn main() {
             .expect("Failed to connect to Gear API");
             let eth_api = create_eth_signer_client(&args.ethereum_args);
 
-            let metrics = MetricsBuilder::new();
+            let metrics = MetricsBuilder::new().register_service(eth_api.nonce_manager());
 
             let (proof_storage, metrics) =
                 create_proof_storage(&args.proof_storage_args, &args.gear_args, metrics).await;
@@ -70,6 +83,7 @@ async fn main() {
             let relayer = MerkleRootRelayer::new(
                 api_provider.connection(),
                 eth_api,
+                create_prover(&args.prover_args),
                 genesis_config,
                 proof_storage,
                 args.start_authority_set_id,
@@ -95,7 +109,7 @@ async fn main() {
 
             let eth_api = create_eth_signer_client(&args.ethereum_args);
 
-            let metrics = MetricsBuilder::new();
+            let metrics = MetricsBuilder::new().register_service(eth_api.nonce_manager());
 
             let (proof_storage, metrics) =
                 create_proof_storage(&args.proof_storage_args, &args.gear_args, metrics).await;
@@ -108,6 +122,7 @@ async fn main() {
             let mut kill_switch = KillSwitchRelayer::new(
                 api_provider.connection(),
                 eth_api,
+                create_prover(&args.prover_args),
                 genesis_config,
                 proof_storage,
                 args.from_eth_block,
@@ -123,6 +138,39 @@ async fn main() {
             api_provider.spawn();
             kill_switch.run().await.expect("Kill switch relayer failed");
         }
+        CliCommands::GasPriceOracle(args) => {
+            let eth_api = create_eth_client(&args.ethereum_args);
+            let gear_signer = create_gear_signer(&args.gear_args);
+
+            let api_provider = ApiProvider::new(
+                args.gear_args.common.domain.clone(),
+                args.gear_args.common.port,
+                args.gear_args.common.retries,
+            )
+            .await
+            .expect("Failed to create API provider");
+
+            let bridging_payment_address = hex_utils::decode_h256(&args.bridging_payment_address)
+                .expect("Failed to parse address");
+
+            let oracle = GasPriceOracle::new(
+                eth_api,
+                api_provider.connection(),
+                gear_signer,
+                bridging_payment_address,
+                args.vara_per_eth,
+                Duration::from_secs(args.report_interval),
+            );
+
+            MetricsBuilder::new()
+                .register_service(&oracle)
+                .build()
+                .run(args.prometheus_args.endpoint)
+                .await;
+
+            api_provider.spawn();
+            oracle.run().await;
+        }
         CliCommands::GearEthTokens(args) => {
             let eth_api = create_eth_signer_client(&args.ethereum_args);
 
@@ -132,7 +180,8 @@ async fn main() {
                 vara_rpc_retries: args.gear_args.retries,
             };
 
-            let mut metrics_builder = MetricsBuilder::new();
+            let mut metrics_builder =
+                MetricsBuilder::new().register_service(eth_api.nonce_manager());
 
             let provider = ApiProvider::new(
                 gsdk_args.vara_domain.clone(),
@@ -142,12 +191,20 @@ async fn main() {
             .await
             .expect("Failed to create API provider");
 
+            let journal = Journal::open(&args.journal_path).expect("Failed to open relay journal");
+            let batch_config = BatchConfig {
+                max_size: args.max_batch_size,
+                flush_timeout: Duration::from_secs(args.batch_flush_timeout),
+            };
+
             match args.command {
                 GearEthTokensCommands::AllTokenTransfers => {
                     let relayer = gear_to_eth::all_token_transfers::Relayer::new(
                         eth_api,
                         args.from_block,
                         provider.connection(),
+                        journal,
+                        batch_config,
                     )
                     .await
                     .unwrap();
@@ -159,16 +216,23 @@ async fn main() {
                 }
                 GearEthTokensCommands::PaidTokenTransfers {
                     bridging_payment_address,
+                    vft_manager_address,
                 } => {
                     let bridging_payment_address =
                         hex_utils::decode_h256(&bridging_payment_address)
                             .expect("Failed to parse address");
+                    let vft_manager_address = vft_manager_address.map(|address| {
+                        hex_utils::decode_h256(&address).expect("Failed to parse address")
+                    });
 
                     let relayer = gear_to_eth::paid_token_transfers::Relayer::new(
                         eth_api,
                         args.from_block,
                         bridging_payment_address,
+                        vft_manager_address,
                         provider.connection(),
+                        journal,
+                        batch_config,
                     )
                     .await
                     .unwrap();
@@ -201,12 +265,8 @@ async fn main() {
             } else {
                 1
             };
-            let relayer = ethereum_checkpoints::Relayer::new(
-                program_id,
-                beacon_client,
-                gear_api,
-                multiplier.saturating_mul(SLOTS_PER_EPOCH),
-            );
+            let relayer =
+                ethereum_checkpoints::Relayer::new(program_id, beacon_client, gear_api, multiplier);
 
             MetricsBuilder::new()
                 .register_service(&relayer)
@@ -221,6 +281,7 @@ async fn main() {
             checkpoint_light_client_address,
             historical_proxy_address,
             vft_manager_address,
+            journal_path,
             gear_args,
             ethereum_args,
             beacon_rpc,
@@ -228,6 +289,7 @@ async fn main() {
         }) => {
             let eth_api = create_eth_client(&ethereum_args);
             let beacon_client = create_beacon_client(&beacon_rpc).await;
+            let gear_signer = create_gear_signer(&gear_args);
 
             let gsdk_args = message_relayer::common::GSdkArgs {
                 vara_domain: gear_args.common.domain,
@@ -249,6 +311,9 @@ async fn main() {
             let vft_manager_address =
                 hex_utils::decode_h256(&vft_manager_address).expect("Failed to parse address");
 
+            let journal = eth_to_gear::journal::Journal::open(&journal_path)
+                .expect("Failed to open relay journal");
+
             match command {
                 EthGearTokensCommands::AllTokenTransfers {
                     erc20_manager_address,
@@ -257,7 +322,7 @@ async fn main() {
                         .expect("Failed to parse address");
 
                     let relayer = eth_to_gear::all_token_transfers::Relayer::new(
-                        gear_args.suri,
+                        gear_signer,
                         eth_api,
                         beacon_client,
                         erc20_manager_address,
@@ -265,6 +330,7 @@ async fn main() {
                         historical_proxy_address,
                         vft_manager_address,
                         provider.connection(),
+                        journal,
                     )
                     .await
                     .expect("Failed to create relayer");
@@ -286,7 +352,7 @@ async fn main() {
                             .expect("Failed to parse address");
 
                     let relayer = eth_to_gear::paid_token_transfers::Relayer::new(
-                        gear_args.suri,
+                        gear_signer,
                         eth_api,
                         beacon_client,
                         bridging_payment_address,
@@ -294,6 +360,7 @@ async fn main() {
                         historical_proxy_address,
                         vft_manager_address,
                         provider.connection(),
+                        journal,
                     )
                     .await
                     .expect("Failed to create relayer");
@@ -314,6 +381,134 @@ async fn main() {
                 tokio::time::sleep(Duration::from_millis(100)).await;
             }
         }
+        CliCommands::EthGearMessages(EthGearMessagesArgs {
+            vara_messenger_address,
+            checkpoint_light_client_address,
+            historical_proxy_address,
+            receiver_address,
+            receiver_route,
+            journal_path,
+            gear_args,
+            ethereum_args,
+            beacon_rpc,
+            prometheus_args,
+        }) => {
+            let eth_api = create_eth_client(&ethereum_args);
+            let beacon_client = create_beacon_client(&beacon_rpc).await;
+            let gear_signer = create_gear_signer(&gear_args);
+
+            let provider = ApiProvider::new(
+                gear_args.common.domain.clone(),
+                gear_args.common.port,
+                gear_args.common.retries,
+            )
+            .await
+            .expect("Failed to create API provider");
+
+            let vara_messenger_address =
+                hex_utils::decode_h160(&vara_messenger_address).expect("Failed to parse address");
+            let checkpoint_light_client_address =
+                hex_utils::decode_h256(&checkpoint_light_client_address)
+                    .expect("Failed to parse address");
+            let historical_proxy_address =
+                hex_utils::decode_h256(&historical_proxy_address).expect("Failed to parse address");
+            let receiver_address =
+                hex_utils::decode_h256(&receiver_address).expect("Failed to parse address");
+            let receiver_route = hex_utils::decode_byte_vec(&receiver_route)
+                .expect("Failed to decode receiver route");
+
+            let journal = eth_to_gear::journal::Journal::open_messages(&journal_path)
+                .expect("Failed to open relay journal");
+
+            let relayer = eth_to_gear::messages::Relayer::new(
+                gear_signer,
+                eth_api,
+                beacon_client,
+                vara_messenger_address,
+                checkpoint_light_client_address,
+                historical_proxy_address,
+                receiver_address,
+                receiver_route,
+                provider.connection(),
+                journal,
+            )
+            .await
+            .expect("Failed to create relayer");
+
+            MetricsBuilder::new()
+                .register_service(&relayer)
+                .build()
+                .run(prometheus_args.endpoint)
+                .await;
+
+            provider.spawn();
+            relayer.run().await;
+
+            loop {
+                // relayer.run() spawns thread and exits, so we need to add this loop after calling run.
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+        CliCommands::EthGearDeliveryProofs(EthGearDeliveryProofsArgs {
+            bridging_payment_address,
+            checkpoint_light_client_address,
+            historical_proxy_address,
+            journal_path,
+            gear_args,
+            ethereum_args,
+            beacon_rpc,
+            prometheus_args,
+        }) => {
+            let eth_api = create_eth_client(&ethereum_args);
+            let beacon_client = create_beacon_client(&beacon_rpc).await;
+            let gear_signer = create_gear_signer(&gear_args);
+
+            let provider = ApiProvider::new(
+                gear_args.common.domain.clone(),
+                gear_args.common.port,
+                gear_args.common.retries,
+            )
+            .await
+            .expect("Failed to create API provider");
+
+            let bridging_payment_address =
+                hex_utils::decode_h256(&bridging_payment_address).expect("Failed to parse address");
+            let checkpoint_light_client_address =
+                hex_utils::decode_h256(&checkpoint_light_client_address)
+                    .expect("Failed to parse address");
+            let historical_proxy_address =
+                hex_utils::decode_h256(&historical_proxy_address).expect("Failed to parse address");
+
+            let journal = eth_to_gear::journal::Journal::open_delivery_proofs(&journal_path)
+                .expect("Failed to open relay journal");
+
+            let relayer = eth_to_gear::delivery_proofs::Relayer::new(
+                gear_signer,
+                eth_api,
+                beacon_client,
+                bridging_payment_address,
+                checkpoint_light_client_address,
+                historical_proxy_address,
+                provider.connection(),
+                journal,
+            )
+            .await
+            .expect("Failed to create relayer");
+
+            MetricsBuilder::new()
+                .register_service(&relayer)
+                .build()
+                .run(prometheus_args.endpoint)
+                .await;
+
+            provider.spawn();
+            relayer.run().await;
+
+            loop {
+                // relayer.run() spawns thread and exits, so we need to add this loop after calling run.
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
         CliCommands::GearEthManual(args) => {
             let nonce =
                 hex_utils::decode_byte_vec(&args.nonce).expect("Failed to parse message nonce");
@@ -334,7 +529,8 @@ async fn main() {
                 args.block,
                 args.from_eth_block,
             )
-            .await;
+            .await
+            .expect("Failed to start relaying the message");
             api_provider.spawn();
             loop {
                 // relay() spawns thread and exits, so we need to add this loop after calling run.
@@ -354,6 +550,7 @@ async fn main() {
         }) => {
             use sails_rs::calls::ActionIo;
 
+            let gear_signer = create_gear_signer(&gear_args);
             let gear_client_args = message_relayer::common::GSdkArgs {
                 vara_domain: gear_args.common.domain,
                 vara_port: gear_args.common.port,
@@ -388,7 +585,7 @@ async fn main() {
 
             eth_to_gear::manual::relay(
                 provider.connection(),
-                gear_args.suri,
+                gear_signer,
                 eth_api,
                 beacon_client,
                 checkpoint_light_client_address,
@@ -398,7 +595,8 @@ async fn main() {
                 tx_hash,
                 slot,
             )
-            .await;
+            .await
+            .expect("Failed to start relaying the transaction");
             provider.spawn();
             loop {
                 // relay() spawns thread and exits, so we need to add this loop after calling run.
@@ -417,7 +615,11 @@ async fn main() {
 async fn create_gclient_client(args: &GearSignerArgs) -> gclient::GearApi {
     gclient::GearApi::builder()
         .retries(args.common.retries)
-        .suri(&args.suri)
+        .suri(
+            args.suri
+                .as_ref()
+                .expect("Remote signer isn't supported by this command, use --gear-suri"),
+        )
         .build(gclient::WSAddress::new(
             &args.common.domain,
             args.common.port,
@@ -436,14 +638,68 @@ fn create_eth_signer_client(args: &EthereumSignerArgs) -> EthApi {
     } = &args.ethereum_args;
     let timeout = Duration::from_secs(eth_timeout.unwrap_or(0).into());
 
-    EthApi::new(
-        eth_endpoint,
-        mq_address,
-        relayer_address,
-        Some(&args.fee_payer),
-        timeout,
-    )
-    .expect("Error while creating ethereum client")
+    let eth_api = match (&args.web3signer_url, &args.web3signer_public_key) {
+        (Some(url), Some(public_key)) => {
+            let signer = Web3Signer::new(url, public_key, REMOTE_SIGNER_TIMEOUT)
+                .expect("Failed to create Web3Signer client");
+
+            EthApi::with_signer(eth_endpoint, mq_address, relayer_address, signer, timeout)
+        }
+        _ => EthApi::new(
+            eth_endpoint,
+            mq_address,
+            relayer_address,
+            args.fee_payer.as_deref(),
+            timeout,
+        ),
+    };
+
+    eth_api
+        .expect("Error while creating ethereum client")
+        .with_gas_strategy(gas_strategy(&args.gas_args))
+}
+
+fn create_gear_signer(args: &GearSignerArgs) -> GearSigner {
+    match (&args.remote_signer_url, &args.remote_signer_public_key) {
+        (Some(url), Some(public_key)) => {
+            let public_key =
+                hex_utils::decode_h256(public_key).expect("Failed to parse remote signer key");
+            let signer = RemoteSigner::new(url.clone(), public_key.0, REMOTE_SIGNER_TIMEOUT)
+                .expect("Failed to create remote signer client");
+
+            GearSigner::Remote(signer)
+        }
+        _ => GearSigner::Suri(args.suri.clone().expect("--gear-suri is required")),
+    }
+}
+
+fn gas_strategy(args: &GasArgs) -> GasStrategy {
+    const WEI_IN_GWEI: u128 = 1_000_000_000;
+
+    let max_fee_per_gas = args
+        .max_fee_per_gas
+        .map(|max_fee_per_gas| max_fee_per_gas as u128 * WEI_IN_GWEI);
+
+    let pricing = match args.gas_strategy {
+        GasStrategyKind::Provider => GasPricing::Provider,
+        GasStrategyKind::FixedCap => GasPricing::FixedCap {
+            max_fee_per_gas: max_fee_per_gas
+                .expect("--max-fee-per-gas is required by the fixed-cap gas strategy"),
+        },
+        GasStrategyKind::Percentile => GasPricing::Percentile {
+            percentile: args.priority_fee_percentile,
+            blocks: args.fee_history_blocks,
+            max_fee_per_gas,
+        },
+    };
+
+    GasStrategy {
+        pricing,
+        replacement: args.replace_after.map(|timeout| Replacement {
+            timeout: Duration::from_secs(timeout),
+            bump_percent: args.fee_bump_percent,
+        }),
+    }
 }
 
 fn create_eth_client(args: &EthereumArgs) -> EthApi {
@@ -509,6 +765,22 @@ fn create_genesis_config(genesis_config_args: &GenesisConfigArgs) -> GenesisConf
     }
 }
 
+fn create_prover(args: &ProverArgs) -> Prover {
+    match &args.url {
+        Some(url) => Prover::Remote(prover_server::Client::new(
+            url,
+            Duration::from_secs(args.poll_interval),
+        )),
+        None => {
+            if let Some(dir) = &args.circuit_cache_dir {
+                prover::proving::set_circuit_cache_dir(dir.clone());
+            }
+
+            Prover::Local
+        }
+    }
+}
+
 async fn fetch_merkle_roots(args: FetchMerkleRootsArgs) -> anyhow::Result<()> {
     let eth_api = create_eth_client(&args.ethereum_args);
     let block_finalized = eth_api.finalized_block_number().await?;
@@ -0,0 +1,281 @@
+use super::*;
+use crate::{common::BASE_RETRY_DELAY, message_relayer::common::MessageInBlock};
+use prometheus::IntGauge;
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    time::{self, Instant},
+};
+use utils_prometheus::{impl_metered_service, MeteredService};
+
+pub type MessageBatch = (Vec<MessageInBlock>, RelayedMerkleRoot);
+
+#[derive(Clone, Copy, Debug)]
+pub struct BatchConfig {
+    /// Maximum amount of messages submitted in a single transaction.
+    pub max_size: usize,
+    /// How long to wait for other messages from the same merkle tree before
+    /// submitting an incomplete batch.
+    pub flush_timeout: Duration,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 1,
+            flush_timeout: Duration::ZERO,
+        }
+    }
+}
+
+struct Batch {
+    merkle_root: RelayedMerkleRoot,
+    messages: Vec<MessageInBlock>,
+    deadline: Instant,
+}
+
+struct Batches {
+    config: BatchConfig,
+    inner: Vec<Batch>,
+}
+
+impl Batches {
+    fn new(config: BatchConfig) -> Self {
+        Self {
+            config,
+            inner: Vec::new(),
+        }
+    }
+
+    fn message_count(&self) -> usize {
+        self.inner.iter().map(|batch| batch.messages.len()).sum()
+    }
+
+    fn next_deadline(&self) -> Option<Instant> {
+        self.inner.iter().map(|batch| batch.deadline).min()
+    }
+
+    // Some(_) -> the batch the message is added to is full and should be submitted
+    fn add(
+        &mut self,
+        message: MessageInBlock,
+        merkle_root: RelayedMerkleRoot,
+        now: Instant,
+    ) -> Option<MessageBatch> {
+        let i = match self.inner.iter().position(|batch| {
+            batch.merkle_root.authority_set_id == merkle_root.authority_set_id
+                && batch.merkle_root.block == merkle_root.block
+        }) {
+            Some(i) => i,
+            None => {
+                self.inner.push(Batch {
+                    merkle_root,
+                    messages: Vec::with_capacity(self.config.max_size),
+                    deadline: now + self.config.flush_timeout,
+                });
+
+                self.inner.len() - 1
+            }
+        };
+
+        self.inner[i].messages.push(message);
+        if self.inner[i].messages.len() < self.config.max_size.max(1) {
+            return None;
+        }
+
+        let batch = self.inner.swap_remove(i);
+
+        Some((batch.messages, batch.merkle_root))
+    }
+
+    fn drain_expired(&mut self, now: Instant) -> Vec<MessageBatch> {
+        let (expired, pending) = self
+            .inner
+            .drain(..)
+            .partition::<Vec<_>, _>(|batch| batch.deadline <= now);
+        self.inner = pending;
+
+        expired
+            .into_iter()
+            .map(|batch| (batch.messages, batch.merkle_root))
+            .collect()
+    }
+
+    fn drain_all(&mut self) -> Vec<MessageBatch> {
+        self.inner
+            .drain(..)
+            .map(|batch| (batch.messages, batch.merkle_root))
+            .collect()
+    }
+}
+
+/// Groups messages that have a relayed merkle root by the merkle tree they belong to
+/// so they can be submitted to Ethereum in a single transaction.
+pub struct Batcher {
+    metrics: Metrics,
+    batches: Batches,
+}
+
+impl MeteredService for Batcher {
+    fn get_sources(&self) -> impl IntoIterator<Item = Box<dyn prometheus::core::Collector>> {
+        self.metrics.get_sources()
+    }
+}
+
+impl_metered_service! {
+    struct Metrics {
+        message_count: IntGauge = IntGauge::new(
+            "ethereum_batcher_message_count",
+            "Count of messages waiting for their batch to be submitted",
+        ),
+    }
+}
+
+impl Batcher {
+    pub fn new(config: BatchConfig) -> Self {
+        Self {
+            metrics: Metrics::new(),
+            batches: Batches::new(config),
+        }
+    }
+
+    pub async fn run(
+        mut self,
+        mut messages: UnboundedReceiver<(MessageInBlock, RelayedMerkleRoot)>,
+    ) -> UnboundedReceiver<MessageBatch> {
+        let (mut batches_out, receiver) = mpsc::unbounded_channel();
+        tokio::task::spawn(async move {
+            loop {
+                match run_inner(&mut self, &mut messages, &mut batches_out).await {
+                    Ok(_) => break,
+                    Err(e) => {
+                        log::error!("{e:?}");
+
+                        if batches_out.is_closed() {
+                            log::info!("Channel with batches closed. Exiting");
+                            break;
+                        }
+
+                        tokio::time::sleep(BASE_RETRY_DELAY).await;
+                    }
+                }
+            }
+        });
+
+        receiver
+    }
+}
+
+async fn run_inner(
+    this: &mut Batcher,
+    messages: &mut UnboundedReceiver<(MessageInBlock, RelayedMerkleRoot)>,
+    batches_out: &mut UnboundedSender<MessageBatch>,
+) -> anyhow::Result<()> {
+    loop {
+        let received = match this.batches.next_deadline() {
+            Some(deadline) => time::timeout_at(deadline, messages.recv()).await.ok(),
+            None => Some(messages.recv().await),
+        };
+
+        match received {
+            None => {
+                for batch in this.batches.drain_expired(Instant::now()) {
+                    batches_out.send(batch)?;
+                }
+            }
+
+            Some(None) => {
+                log::info!("Channel with messages closed. Exiting");
+
+                for batch in this.batches.drain_all() {
+                    batches_out.send(batch)?;
+                }
+
+                return Ok(());
+            }
+
+            Some(Some((message, merkle_root))) => {
+                if let Some(batch) = this.batches.add(message, merkle_root, Instant::now()) {
+                    batches_out.send(batch)?;
+                }
+            }
+        }
+
+        this.metrics
+            .message_count
+            .set(this.batches.message_count() as _);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message_relayer::common::AuthoritySetId;
+
+    fn merkle_root(block: u32) -> RelayedMerkleRoot {
+        RelayedMerkleRoot {
+            block: GearBlockNumber(block),
+            block_hash: Default::default(),
+            authority_set_id: AuthoritySetId(1),
+            merkle_root: Default::default(),
+        }
+    }
+
+    fn message(block: u32) -> MessageInBlock {
+        MessageInBlock {
+            message: Default::default(),
+            block: GearBlockNumber(block),
+            block_hash: Default::default(),
+            authority_set_id: AuthoritySetId(1),
+        }
+    }
+
+    #[test]
+    fn batches() {
+        let now = Instant::now();
+        let mut batches = Batches::new(BatchConfig {
+            max_size: 2,
+            flush_timeout: Duration::from_secs(10),
+        });
+
+        assert!(batches.add(message(1), merkle_root(5), now).is_none());
+        assert!(batches.add(message(2), merkle_root(6), now).is_none());
+        assert_eq!(batches.message_count(), 2);
+
+        let (messages, root) = batches.add(message(3), merkle_root(5), now).unwrap();
+        assert_eq!(messages, vec![message(1), message(3)]);
+        assert_eq!(root, merkle_root(5));
+        assert_eq!(batches.message_count(), 1);
+
+        assert!(batches.drain_expired(now).is_empty());
+        assert_eq!(batches.next_deadline(), Some(now + Duration::from_secs(10)));
+
+        let expired = batches.drain_expired(now + Duration::from_secs(10));
+        assert_eq!(expired, vec![(vec![message(2)], merkle_root(6))]);
+        assert!(batches.next_deadline().is_none());
+    }
+
+    #[test]
+    fn default_config_disables_batching() {
+        let mut batches = Batches::new(BatchConfig::default());
+
+        let (messages, _) = batches
+            .add(message(1), merkle_root(5), Instant::now())
+            .unwrap();
+        assert_eq!(messages, vec![message(1)]);
+        assert_eq!(batches.message_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn stops_when_batches_receiver_is_dropped() {
+        let (messages_sender, messages) = mpsc::unbounded_channel();
+        let batches = Batcher::new(BatchConfig::default()).run(messages).await;
+        drop(batches);
+
+        messages_sender.send((message(1), merkle_root(5))).unwrap();
+
+        // the batcher drops its receiver of the messages once it exits
+        time::timeout(Duration::from_secs(5), messages_sender.closed())
+            .await
+            .expect("Batcher should exit when the batches channel is closed");
+    }
+}
@@ -9,12 +9,14 @@ use crate::{
     message_relayer::{
         common::{AuthoritySetId, EthereumBlockNumber, GearBlockNumber, RelayedMerkleRoot},
         eth_to_gear::api_provider::ApiProviderConnection,
+        gear_to_eth::journal::Journal,
     },
 };
 
 pub struct MerkleRootExtractor {
     eth_api: EthApi,
     api_provider: ApiProviderConnection,
+    journal: Journal,
 
     metrics: Metrics,
 }
@@ -35,10 +37,11 @@ impl_metered_service! {
 }
 
 impl MerkleRootExtractor {
-    pub fn new(eth_api: EthApi, api_provider: ApiProviderConnection) -> Self {
+    pub fn new(eth_api: EthApi, api_provider: ApiProviderConnection, journal: Journal) -> Self {
         Self {
             eth_api,
             api_provider,
+            journal,
 
             metrics: Metrics::new(),
         }
@@ -51,6 +54,11 @@ impl MerkleRootExtractor {
         let (sender, receiver) = unbounded_channel();
 
         tokio::task::spawn(async move {
+            if let Err(err) = self.replay_journal(&sender) {
+                log::error!("Failed to replay merkle roots from the journal: {err}");
+                return;
+            }
+
             let mut attempts = 0;
 
             loop {
@@ -101,6 +109,14 @@ impl MerkleRootExtractor {
         receiver
     }
 
+    fn replay_journal(&self, sender: &UnboundedSender<RelayedMerkleRoot>) -> anyhow::Result<()> {
+        for merkle_root in self.journal.merkle_roots()? {
+            sender.send(merkle_root)?;
+        }
+
+        Ok(())
+    }
+
     async fn run_inner(
         &self,
         blocks: &mut UnboundedReceiver<EthereumBlockNumber>,
@@ -122,6 +138,7 @@ impl MerkleRootExtractor {
                     );
                 }
 
+                let mut relayed_merkle_roots = Vec::with_capacity(merkle_roots.len());
                 for (merkle_root, _block_number_eth) in merkle_roots {
                     self.metrics
                         .latest_merkle_root_for_block
@@ -140,12 +157,20 @@ impl MerkleRootExtractor {
                         authority_set_id
                     );
 
-                    sender.send(RelayedMerkleRoot {
+                    relayed_merkle_roots.push(RelayedMerkleRoot {
                         block: GearBlockNumber(merkle_root.block_number as u32),
                         block_hash,
                         authority_set_id,
                         merkle_root: merkle_root.merkle_root,
-                    })?;
+                    });
+                }
+
+                self.journal
+                    .add_merkle_roots(block, &relayed_merkle_roots)
+                    .await?;
+
+                for merkle_root in relayed_merkle_roots {
+                    sender.send(merkle_root)?;
                 }
             }
         }
@@ -8,7 +8,10 @@ use utils_prometheus::{impl_metered_service, MeteredService};
 
 use crate::{
     common::{self, BASE_RETRY_DELAY, MAX_RETRIES},
-    message_relayer::common::{EthereumBlockNumber, TxHashWithSlot},
+    message_relayer::{
+        common::{EthereumBlockNumber, TxHashWithSlot},
+        eth_to_gear::journal::Journal,
+    },
 };
 
 use super::find_slot_by_block_number;
@@ -16,6 +19,7 @@ use super::find_slot_by_block_number;
 pub struct MessagePaidEventExtractor {
     eth_api: EthApi,
     beacon_client: BeaconClient,
+    journal: Journal,
 
     bridging_payment_address: H160,
 
@@ -42,10 +46,12 @@ impl MessagePaidEventExtractor {
         eth_api: EthApi,
         beacon_client: BeaconClient,
         bridging_payment_address: H160,
+        journal: Journal,
     ) -> Self {
         Self {
             eth_api,
             beacon_client,
+            journal,
 
             bridging_payment_address,
 
@@ -60,6 +66,11 @@ impl MessagePaidEventExtractor {
         let (sender, receiver) = unbounded_channel();
 
         tokio::task::spawn(async move {
+            if let Err(err) = self.replay_journal(&sender) {
+                log::error!("Failed to replay paid messages from the journal: {err}");
+                return;
+            }
+
             let mut attempts = 0;
 
             loop {
@@ -96,6 +107,22 @@ impl MessagePaidEventExtractor {
         receiver
     }
 
+    fn replay_journal(&self, sender: &UnboundedSender<TxHashWithSlot>) -> anyhow::Result<()> {
+        let deposits = self.journal.deposits_to_relay()?;
+        if !deposits.is_empty() {
+            log::info!(
+                "Replaying {} paid messages from the journal",
+                deposits.len()
+            );
+        }
+
+        for deposit in deposits {
+            sender.send(deposit)?;
+        }
+
+        Ok(())
+    }
+
     async fn run_inner(
         &self,
         sender: &UnboundedSender<TxHashWithSlot>,
@@ -119,6 +146,8 @@ impl MessagePaidEventExtractor {
             .await?;
 
         if events.is_empty() {
+            self.journal.add_deposits(block, vec![]).await?;
+
             return Ok(());
         }
 
@@ -137,11 +166,16 @@ impl MessagePaidEventExtractor {
             );
         }
 
-        for FeePaidEntry { tx_hash } in events {
-            sender.send(TxHashWithSlot {
+        let deposits = events
+            .into_iter()
+            .map(|FeePaidEntry { tx_hash }| TxHashWithSlot {
                 slot_number,
                 tx_hash,
-            })?;
+            })
+            .collect();
+
+        for deposit in self.journal.add_deposits(block, deposits).await? {
+            sender.send(deposit)?;
         }
 
         Ok(())
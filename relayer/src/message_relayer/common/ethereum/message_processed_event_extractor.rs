@@ -0,0 +1,258 @@
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+use bridging_payment_client::bridging_payment::io;
+use gear_core::message::ReplyCode;
+use parity_scale_codec::Decode;
+use primitive_types::{H256, U256};
+use prometheus::IntCounter;
+use sails_rs::calls::ActionIo;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+use ethereum_beacon_client::BeaconClient;
+use ethereum_client::{EthApi, MessageProcessedEntry, TxHash};
+use utils_prometheus::{impl_metered_service, MeteredService};
+
+use crate::{
+    common::{self, BASE_RETRY_DELAY, MAX_RETRIES},
+    message_relayer::{
+        common::{EthereumBlockNumber, TxHashWithSlot},
+        eth_to_gear::{api_provider::ApiProviderConnection, journal::Journal},
+    },
+};
+
+use super::find_slot_by_block_number;
+
+/// Discovers `MessageQueue` transactions that delivered messages paid through
+/// the `bridging-payment` program so their receipts can be submitted as delivery proofs.
+///
+/// Transactions without messages that have a pending payment are skipped.
+pub struct MessageProcessedEventExtractor {
+    eth_api: EthApi,
+    beacon_client: BeaconClient,
+    api_provider: ApiProviderConnection,
+    journal: Journal,
+
+    bridging_payment_address: H256,
+
+    metrics: Metrics,
+}
+
+impl MeteredService for MessageProcessedEventExtractor {
+    fn get_sources(&self) -> impl IntoIterator<Item = Box<dyn prometheus::core::Collector>> {
+        self.metrics.get_sources()
+    }
+}
+
+impl_metered_service! {
+    struct Metrics {
+        total_delivered_messages_found: IntCounter = IntCounter::new(
+            "message_processed_event_extractor_total_delivered_messages_found",
+            "Total amount of messages delivered to ethereum discovered",
+        ),
+        total_delivery_proofs_found: IntCounter = IntCounter::new(
+            "message_processed_event_extractor_total_delivery_proofs_found",
+            "Total amount of transactions delivering paid messages discovered",
+        ),
+    }
+}
+
+impl MessageProcessedEventExtractor {
+    pub fn new(
+        eth_api: EthApi,
+        beacon_client: BeaconClient,
+        api_provider: ApiProviderConnection,
+        bridging_payment_address: H256,
+        journal: Journal,
+    ) -> Self {
+        Self {
+            eth_api,
+            beacon_client,
+            api_provider,
+            journal,
+
+            bridging_payment_address,
+
+            metrics: Metrics::new(),
+        }
+    }
+
+    pub async fn run(
+        mut self,
+        mut blocks: UnboundedReceiver<EthereumBlockNumber>,
+    ) -> UnboundedReceiver<TxHashWithSlot> {
+        let (sender, receiver) = unbounded_channel();
+
+        tokio::task::spawn(async move {
+            if let Err(err) = self.replay_journal(&sender) {
+                log::error!("Failed to replay delivery proofs from the journal: {err}");
+                return;
+            }
+
+            let mut attempts = 0;
+
+            loop {
+                let res = self.run_inner(&sender, &mut blocks).await;
+                if let Err(err) = res {
+                    attempts += 1;
+                    let delay = BASE_RETRY_DELAY * 2u32.pow(attempts - 1);
+
+                    log::error!(
+                        "Message processed event extractor failed (attempt {}/{}): {}. Retrying in {:?}",
+                        attempts,
+                        MAX_RETRIES,
+                        err,
+                        delay
+                    );
+                    if attempts >= MAX_RETRIES {
+                        log::error!("Maximum attempts reached, exiting...");
+                        break;
+                    }
+                    tokio::time::sleep(delay).await;
+                    if common::is_transport_error_recoverable(&err) {
+                        self.eth_api = match self.eth_api.reconnect() {
+                            Ok(api) => api,
+                            Err(err) => {
+                                log::error!("Failed to reconnect to Ethereum: {}", err);
+                                break;
+                            }
+                        }
+                    }
+
+                    if let Err(err) = self.api_provider.reconnect().await {
+                        log::error!("Failed to reconnect to Gear: {err}");
+                        break;
+                    }
+                }
+            }
+        });
+
+        receiver
+    }
+
+    fn replay_journal(&self, sender: &UnboundedSender<TxHashWithSlot>) -> anyhow::Result<()> {
+        let proofs = self.journal.deposits_to_relay()?;
+        if !proofs.is_empty() {
+            log::info!(
+                "Replaying {} delivery proofs from the journal",
+                proofs.len()
+            );
+        }
+
+        for proof in proofs {
+            sender.send(proof)?;
+        }
+
+        Ok(())
+    }
+
+    async fn run_inner(
+        &self,
+        sender: &UnboundedSender<TxHashWithSlot>,
+        blocks: &mut UnboundedReceiver<EthereumBlockNumber>,
+    ) -> anyhow::Result<()> {
+        loop {
+            while let Some(block) = blocks.recv().await {
+                self.process_block_events(block, sender).await?;
+            }
+        }
+    }
+
+    async fn process_block_events(
+        &self,
+        block: EthereumBlockNumber,
+        sender: &UnboundedSender<TxHashWithSlot>,
+    ) -> anyhow::Result<()> {
+        let events = self.eth_api.fetch_message_processed_events(block.0).await?;
+
+        self.metrics
+            .total_delivered_messages_found
+            .inc_by(events.len() as u64);
+
+        // The whole receipt is submitted so fees of all the messages delivered
+        // by the transaction are claimed at once.
+        let mut transactions: BTreeMap<TxHash, Vec<[u8; 32]>> = BTreeMap::new();
+        for MessageProcessedEntry { nonce_le, tx_hash } in events {
+            transactions.entry(tx_hash).or_default().push(nonce_le);
+        }
+
+        let mut tx_hashes = vec![];
+        for (tx_hash, nonces) in transactions {
+            if self.has_pending_payment(&nonces).await? {
+                tx_hashes.push(tx_hash);
+            }
+        }
+
+        if tx_hashes.is_empty() {
+            self.journal.add_deposits(block, vec![]).await?;
+
+            return Ok(());
+        }
+
+        let slot_number =
+            find_slot_by_block_number(&self.eth_api, &self.beacon_client, block).await?;
+
+        self.metrics
+            .total_delivery_proofs_found
+            .inc_by(tx_hashes.len() as u64);
+
+        let proofs = tx_hashes
+            .into_iter()
+            .map(|tx_hash| {
+                log::info!(
+                    "Found delivery of the paid messages: tx_hash={}, slot_number={}",
+                    hex::encode(tx_hash.0),
+                    slot_number.0,
+                );
+
+                TxHashWithSlot {
+                    slot_number,
+                    tx_hash,
+                }
+            })
+            .collect();
+
+        for proof in self.journal.add_deposits(block, proofs).await? {
+            sender.send(proof)?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether the fee of any of the messages is held by the bridging-payment program.
+    async fn has_pending_payment(&self, nonces: &[[u8; 32]]) -> anyhow::Result<bool> {
+        let gear_api = self.api_provider.client();
+        let api = gclient::GearApi::from(gear_api.api.clone());
+        let origin = H256::from_slice(api.account_id().as_ref());
+        let gas_limit = api.block_gas_limit()?;
+
+        for nonce_le in nonces {
+            let payload = io::PaidMessage::encode_call(U256::from_little_endian(nonce_le));
+            let reply_info = api
+                .calculate_reply_for_handle(
+                    Some(origin),
+                    self.bridging_payment_address.into(),
+                    payload,
+                    gas_limit,
+                    0,
+                )
+                .await?;
+
+            let paid_message: <io::PaidMessage as ActionIo>::Reply = match reply_info.code {
+                ReplyCode::Success(_) => {
+                    Decode::decode(&mut &reply_info.payload[io::PaidMessage::ROUTE.len()..])?
+                }
+                ReplyCode::Error(reason) => {
+                    Err(anyhow!("Failed to query paid message, reason: {reason:?}"))?
+                }
+                ReplyCode::Unsupported => Err(anyhow!("Failed to query paid message"))?,
+            };
+
+            if paid_message.is_some() {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
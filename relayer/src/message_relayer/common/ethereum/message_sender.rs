@@ -1,17 +1,21 @@
-use crate::message_relayer::common::RelayedMerkleRoot;
+use std::collections::HashMap;
+
+use crate::message_relayer::common::{ethereum::batcher::MessageBatch, RelayedMerkleRoot};
 use crate::{
     common::{self, BASE_RETRY_DELAY, MAX_RETRIES},
     message_relayer::{
         common::{GearBlockNumber, MessageInBlock},
         eth_to_gear::api_provider::ApiProviderConnection,
+        gear_to_eth::journal::{Journal, MAX_SUBMISSION_FAILURES},
     },
 };
-use ethereum_client::{Error, EthApi, TxHash, TxStatus};
+use anyhow::anyhow;
+use ethereum_client::{ContentMessage, Error, EthApi, TxHash, TxStatus};
 use futures::{
     future::{self, Either},
     pin_mut,
 };
-use gear_rpc_client::{dto::Message, GearApi};
+use gear_rpc_client::{dto::Message, merkle, GearApi};
 use keccak_hash::keccak_256;
 use primitive_types::H256;
 use prometheus::{Gauge, IntCounter, IntGauge};
@@ -21,11 +25,38 @@ use tokio::{
 };
 use utils_prometheus::{impl_metered_service, MeteredService};
 
-type Status = (TxHash, Result<TxStatus, Error>);
+/// Amount of the consecutive failures to get status of the submission after which its
+/// transactions are considered dropped.
+const MAX_STATUS_ERRORS: u32 = 3;
+
+/// Status of the submission and the transaction it relates to.
+type Status = (Submission, TxHash, Result<TxStatus, Error>);
+
+/// Batch of messages submitted to Ethereum.
+#[derive(Debug)]
+struct Submission {
+    batch: MessageBatch,
+    /// The submitted transaction and the ones that replaced it. All of them have the same
+    /// nonce so at most one gets included.
+    tx_hashes: Vec<TxHash>,
+    /// Amount of the consecutive failures to get status of the transactions.
+    status_errors: u32,
+}
+
+impl Submission {
+    fn new(batch: MessageBatch, tx_hashes: Vec<TxHash>) -> Self {
+        Self {
+            batch,
+            tx_hashes,
+            status_errors: 0,
+        }
+    }
+}
 
 pub struct MessageSender {
     eth_api: EthApi,
     api_provider: ApiProviderConnection,
+    journal: Journal,
 
     metrics: Metrics,
 }
@@ -50,27 +81,34 @@ impl_metered_service! {
             "ethereum_message_sender_total_failed_txs",
             "Total amount of txs sent to ethereum and failed",
         ),
+        total_replaced_txs: IntCounter = IntCounter::new(
+            "ethereum_message_sender_total_replaced_txs",
+            "Total amount of stuck txs replaced with the ones paying higher fees",
+        ),
     }
 }
 
 impl MessageSender {
-    pub fn new(eth_api: EthApi, api_provider: ApiProviderConnection) -> Self {
+    pub fn new(eth_api: EthApi, api_provider: ApiProviderConnection, journal: Journal) -> Self {
         Self {
             eth_api,
             api_provider,
+            journal,
 
             metrics: Metrics::new(),
         }
     }
 
-    pub async fn run(
-        mut self,
-        mut messages: UnboundedReceiver<(MessageInBlock, RelayedMerkleRoot)>,
-    ) {
+    pub async fn run(mut self, mut messages: UnboundedReceiver<MessageBatch>) {
         tokio::task::spawn(async move {
             let mut attempts = 0;
 
             let (tx_sender, mut tx_receiver) = mpsc::unbounded_channel();
+            if let Err(e) = resume_pending_submissions(&self, &tx_sender) {
+                log::error!("Failed to resume pending transactions from the journal: {e}");
+                return;
+            }
+
             loop {
                 match run_inner(&mut self, &mut messages, &mut tx_receiver, &tx_sender).await {
                     Ok(_) => break,
@@ -115,9 +153,44 @@ impl MessageSender {
     }
 }
 
+fn resume_pending_submissions(
+    this: &MessageSender,
+    tx_sender: &UnboundedSender<Status>,
+) -> anyhow::Result<()> {
+    let mut submissions: HashMap<Vec<TxHash>, MessageBatch> = HashMap::new();
+    for (message, submission) in this.journal.pending_submissions()? {
+        submissions
+            .entry(submission.tx_hashes())
+            .or_insert_with(|| (vec![], submission.merkle_root))
+            .0
+            .push(message);
+    }
+
+    for (tx_hashes, batch) in submissions {
+        log::info!(
+            "Resuming tracking of transactions {tx_hashes:?} relaying messages with nonces {:?}",
+            batch
+                .0
+                .iter()
+                .map(|message| hex::encode(message.message.nonce_le))
+                .collect::<Vec<_>>()
+        );
+
+        this.metrics.pending_tx_count.inc();
+
+        tokio::spawn(get_tx_status(
+            this.eth_api.clone(),
+            Submission::new(batch, tx_hashes),
+            tx_sender.clone(),
+        ));
+    }
+
+    Ok(())
+}
+
 async fn run_inner(
     this: &mut MessageSender,
-    messages: &mut UnboundedReceiver<(MessageInBlock, RelayedMerkleRoot)>,
+    messages: &mut UnboundedReceiver<MessageBatch>,
     tx_receiver: &mut UnboundedReceiver<Status>,
     tx_sender: &UnboundedSender<Status>,
 ) -> anyhow::Result<()> {
@@ -143,68 +216,297 @@ async fn run_inner(
                 return Ok(());
             }
 
-            Either::Left((Some((message, merkle_root)), _)) => {
-                let tx_hash = submit_message(
-                    &gear_api,
-                    &this.eth_api,
-                    &message.message,
-                    merkle_root.block,
-                    merkle_root.block_hash,
-                )
-                .await?;
-                this.metrics.pending_tx_count.inc();
-
-                tokio::spawn(get_tx_status(
-                    this.eth_api.clone(),
-                    tx_hash,
-                    tx_sender.clone(),
-                ));
+            Either::Left((Some(batch), _)) => {
+                submit_batch(this, &gear_api, batch, tx_sender).await?;
             }
 
             Either::Right((Some(status), _)) => {
-                check_tx_status(this, status);
+                check_tx_status(this, &gear_api, status, tx_sender).await?;
             }
         }
     }
 }
 
-fn check_tx_status(this: &mut MessageSender, status: Status) {
-    let (tx_hash, status) = status;
+async fn submit_batch(
+    this: &mut MessageSender,
+    gear_api: &GearApi,
+    batch: MessageBatch,
+    tx_sender: &UnboundedSender<Status>,
+) -> anyhow::Result<()> {
+    let (messages, merkle_root) = batch;
+
+    // `processMessages` reverts if any message of the batch is already processed
+    let mut messages_new = Vec::with_capacity(messages.len());
+    for message in messages {
+        let nonce = message.message.nonce_le;
+        if this.eth_api.is_message_processed(nonce).await? {
+            log::info!(
+                "Message with nonce {} is already processed. Skipping",
+                hex::encode(nonce)
+            );
+
+            this.journal.remove_message(&nonce).await?;
+
+            continue;
+        }
+
+        messages_new.push(message);
+    }
+
+    if messages_new.is_empty() {
+        return Ok(());
+    }
+
+    let error = match submit_messages(gear_api, &this.eth_api, &messages_new, &merkle_root).await {
+        Ok(tx_hash) => {
+            return track_submission(this, (messages_new, merkle_root), tx_hash, tx_sender).await
+        }
+
+        Err(e) if messages_new.len() == 1 || common::is_transport_error_recoverable(&e) => {
+            return Err(e)
+        }
+
+        Err(e) => e,
+    };
+
+    // An invalid message fails the whole batch so the messages are submitted one by one.
+    log::warn!(
+        "Failed to submit batch of {} messages: {error}. Submitting them one by one",
+        messages_new.len()
+    );
+
+    for message in messages_new {
+        let nonce = message.message.nonce_le;
+        let batch = (vec![message], merkle_root);
+        match submit_messages(gear_api, &this.eth_api, &batch.0, &merkle_root).await {
+            Ok(tx_hash) => track_submission(this, batch, tx_hash, tx_sender).await?,
+
+            Err(e) if common::is_transport_error_recoverable(&e) => return Err(e),
+
+            // the message isn't marked as submitted so it's relayed again after restart
+            Err(e) => log::error!(
+                "Failed to submit message with nonce {}: {e}",
+                hex::encode(nonce)
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+async fn track_submission(
+    this: &mut MessageSender,
+    batch: MessageBatch,
+    tx_hash: TxHash,
+    tx_sender: &UnboundedSender<Status>,
+) -> anyhow::Result<()> {
+    this.metrics.pending_tx_count.inc();
+
+    let (messages, merkle_root) = &batch;
+    for message in messages {
+        this.journal
+            .set_submission(&message.message.nonce_le, tx_hash, merkle_root)
+            .await?;
+    }
+
+    tokio::spawn(get_tx_status(
+        this.eth_api.clone(),
+        Submission::new(batch, vec![tx_hash]),
+        tx_sender.clone(),
+    ));
+
+    Ok(())
+}
+
+async fn check_tx_status(
+    this: &mut MessageSender,
+    gear_api: &GearApi,
+    status: Status,
+    tx_sender: &UnboundedSender<Status>,
+) -> anyhow::Result<()> {
+    let (mut submission, tx_hash, status) = status;
+    if status.is_ok() {
+        submission.status_errors = 0;
+    }
+
     match status {
+        Ok(TxStatus::Pending) if this.eth_api.replacement_timeout().is_some() => {
+            match this.eth_api.replace_transaction(tx_hash).await {
+                Ok(Some(tx_hash_new)) => {
+                    this.metrics.total_replaced_txs.inc();
+
+                    log::warn!("Transaction {tx_hash} is stuck. Replaced with {tx_hash_new}");
+
+                    for message in &submission.batch.0 {
+                        this.journal
+                            .add_replacement(&message.message.nonce_le, tx_hash_new)
+                            .await?;
+                    }
+
+                    submission.tx_hashes.push(tx_hash_new);
+                }
+
+                Ok(None) => {}
+
+                Err(e) => log::warn!("Failed to replace transaction {tx_hash}: {e}"),
+            }
+
+            tokio::spawn(get_tx_status(
+                this.eth_api.clone(),
+                submission,
+                tx_sender.clone(),
+            ));
+        }
+
         Ok(TxStatus::Pending) => {
-            log::error!("Transaction {tx_hash} is still pending");
+            log::warn!("Transaction {tx_hash} is still pending");
+
+            tokio::spawn(get_tx_status(
+                this.eth_api.clone(),
+                submission,
+                tx_sender.clone(),
+            ));
         }
 
         Ok(TxStatus::Finalized) => {
             this.metrics.pending_tx_count.dec();
 
             log::info!("Transaction {tx_hash} has been finalized");
+
+            for message in &submission.batch.0 {
+                this.journal
+                    .remove_message(&message.message.nonce_le)
+                    .await?;
+            }
         }
 
         Ok(TxStatus::Failed) => {
-            this.metrics.total_failed_txs.inc();
-
             log::error!("Failed to finalize transaction {tx_hash}");
+
+            retry_failed(this, gear_api, submission, tx_sender).await?;
+        }
+
+        Err(e) if submission.status_errors + 1 < MAX_STATUS_ERRORS => {
+            submission.status_errors += 1;
+
+            log::warn!(
+                "Unable to get status of the transactions {:?} (attempt: {}/{MAX_STATUS_ERRORS}): {e:?}",
+                submission.tx_hashes,
+                submission.status_errors
+            );
+
+            tokio::spawn(get_tx_status(
+                this.eth_api.clone(),
+                submission,
+                tx_sender.clone(),
+            ));
         }
 
         Err(e) => {
-            log::warn!("Unable to get status of the transaction {tx_hash:?}: {e:?}")
+            log::error!(
+                "Unable to get status of the transactions {:?}: {e:?}. Considering them dropped",
+                submission.tx_hashes
+            );
+
+            retry_failed(this, gear_api, submission, tx_sender).await?;
         }
     }
+
+    Ok(())
+}
+
+async fn retry_failed(
+    this: &mut MessageSender,
+    gear_api: &GearApi,
+    submission: Submission,
+    tx_sender: &UnboundedSender<Status>,
+) -> anyhow::Result<()> {
+    this.metrics.pending_tx_count.dec();
+    this.metrics.total_failed_txs.inc();
+
+    let (messages, merkle_root) = submission.batch;
+    let mut messages_retry = Vec::with_capacity(messages.len());
+    for message in messages {
+        let nonce = message.message.nonce_le;
+        match this.journal.fail_submission(&nonce).await? {
+            true => messages_retry.push(message),
+            false => log::error!(
+                "Message with nonce {} failed to be relayed {MAX_SUBMISSION_FAILURES} times. Giving up",
+                hex::encode(nonce)
+            ),
+        }
+    }
+
+    if messages_retry.is_empty() {
+        return Ok(());
+    }
+
+    if let Err(e) = submit_batch(this, gear_api, (messages_retry, merkle_root), tx_sender).await {
+        // the messages are marked as failed so they're relayed again after restart
+        log::error!(
+            "Failed to resubmit messages of the transactions {:?}: {e}",
+            submission.tx_hashes
+        );
+    }
+
+    Ok(())
+}
+
+/// Status of the submission. Only one of its transactions can be included, the others
+/// are dropped by the node. Returns the status of the included transaction if any,
+/// otherwise the status of the latest known one.
+async fn submission_status(
+    eth_api: &EthApi,
+    tx_hashes: &[TxHash],
+) -> (TxHash, Result<TxStatus, Error>) {
+    let mut statuses = Vec::with_capacity(tx_hashes.len());
+    for tx_hash in tx_hashes {
+        statuses.push((*tx_hash, eth_api.get_tx_status(*tx_hash).await));
+    }
+
+    settle(statuses)
 }
 
-async fn get_tx_status(eth_api: EthApi, tx_hash: TxHash, tx_sender: UnboundedSender<Status>) {
-    // wait for 18 minutes for the first time and for 5 minutes in the next three attempts
-    let mut iter = [18, 5, 5, 5].iter().peekable();
-    while let Some(minutes) = iter.next() {
-        time::sleep(Duration::from_secs(minutes * 60)).await;
+fn settle(statuses: Vec<(TxHash, Result<TxStatus, Error>)>) -> (TxHash, Result<TxStatus, Error>) {
+    let rank = |status: &Result<TxStatus, Error>| match status {
+        Ok(TxStatus::Finalized | TxStatus::Failed) => 2,
+        Ok(TxStatus::Pending) => 1,
+        Err(_) => 0,
+    };
+
+    // the last one of the equally ranked statuses is taken
+    statuses
+        .into_iter()
+        .max_by_key(|(_tx_hash, status)| rank(status))
+        .expect("Submission has at least one transaction")
+}
 
-        let status = eth_api.get_tx_status(tx_hash).await;
+async fn get_tx_status(
+    eth_api: EthApi,
+    submission: Submission,
+    tx_sender: UnboundedSender<Status>,
+) {
+    // With replacement enabled the sender decides whether to replace the transaction
+    // each time the timeout passes.
+    let delays = match eth_api.replacement_timeout() {
+        Some(timeout) => vec![timeout],
+        // wait for 18 minutes for the first time and for 5 minutes in the next three attempts
+        None => [18, 5, 5, 5]
+            .into_iter()
+            .map(|minutes| Duration::from_secs(minutes * 60))
+            .collect(),
+    };
+
+    let mut iter = delays.into_iter().peekable();
+    while let Some(delay) = iter.next() {
+        time::sleep(delay).await;
+
+        let (tx_hash, status) = submission_status(&eth_api, &submission.tx_hashes).await;
         match status {
             Ok(TxStatus::Pending) if iter.peek().is_some() => {}
 
             status => {
-                let result = tx_sender.send((tx_hash, status));
+                let result = tx_sender.send((submission, tx_hash, status));
                 if result.is_err() {
                     log::error!("Failed to notify about transaction status: tx_hash = {tx_hash}, error = {result:?}");
                 }
@@ -215,6 +517,74 @@ async fn get_tx_status(eth_api: EthApi, tx_hash: TxHash, tx_sender: UnboundedSen
     }
 }
 
+async fn submit_messages(
+    gear_api: &GearApi,
+    eth_api: &EthApi,
+    messages: &[MessageInBlock],
+    merkle_root: &RelayedMerkleRoot,
+) -> anyhow::Result<TxHash> {
+    if let [message] = messages {
+        return submit_message(
+            gear_api,
+            eth_api,
+            &message.message,
+            merkle_root.block,
+            merkle_root.block_hash,
+        )
+        .await;
+    }
+
+    let messages: Vec<_> = messages
+        .iter()
+        .map(|message| (message_hash(&message.message), &message.message))
+        .collect();
+    for (message_hash, message) in &messages {
+        log::info!(
+            "Relaying message with hash {} and nonce {} as a part of batch",
+            hex::encode(message_hash),
+            hex::encode(message.nonce_le)
+        );
+    }
+
+    let message_hashes: Vec<_> = messages.iter().map(|(hash, _)| H256::from(*hash)).collect();
+    let proof = gear_api
+        .fetch_messages_inclusion_merkle_multi_proof(merkle_root.block_hash, &message_hashes)
+        .await?;
+
+    // Contract expects messages in the same order as leaves of the multiproof.
+    let mut content_messages = Vec::with_capacity(messages.len());
+    for (leaf_index, leaf_hash) in &proof.leaves {
+        let (_, message) = messages
+            .iter()
+            .find(|(message_hash, _)| merkle::leaf_hash(message_hash) == *leaf_hash)
+            .ok_or_else(|| anyhow!("Leaf #{leaf_index} doesn't match any message of the batch"))?;
+
+        content_messages.push(ContentMessage {
+            leaf_index: *leaf_index as u32,
+            nonce: message.nonce_le,
+            sender: message.source,
+            receiver: message.destination,
+            payload: message.payload.to_vec(),
+        });
+    }
+
+    let tx_hash = eth_api
+        .provide_content_messages(
+            merkle_root.block.0,
+            proof.num_leaves as u32,
+            content_messages,
+            proof.proof,
+        )
+        .await?;
+
+    log::info!(
+        "Batch of {} messages relaying started: tx_hash = {tx_hash}",
+        messages.len()
+    );
+
+    Ok(tx_hash)
+}
+
 async fn submit_message(
     gear_api: &GearApi,
     eth_api: &EthApi,
@@ -269,3 +639,44 @@ fn message_hash(message: &Message) -> [u8; 32] {
 
     hash
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settle_on_included_transaction() {
+        let original = TxHash::from([1; 32]);
+        let replacement = TxHash::from([2; 32]);
+
+        // the original transaction is included so the replacement is dropped by the node
+        let (tx_hash, status) = settle(vec![
+            (original, Ok(TxStatus::Finalized)),
+            (replacement, Err(Error::ErrorFetchingTransaction)),
+        ]);
+        assert_eq!(tx_hash, original);
+        assert!(matches!(status, Ok(TxStatus::Finalized)));
+
+        let (tx_hash, status) = settle(vec![
+            (original, Ok(TxStatus::Failed)),
+            (replacement, Err(Error::ErrorFetchingTransaction)),
+        ]);
+        assert_eq!(tx_hash, original);
+        assert!(matches!(status, Ok(TxStatus::Failed)));
+
+        // both are in the mempool so the latest replacement is tracked
+        let (tx_hash, status) = settle(vec![
+            (original, Ok(TxStatus::Pending)),
+            (replacement, Ok(TxStatus::Pending)),
+        ]);
+        assert_eq!(tx_hash, replacement);
+        assert!(matches!(status, Ok(TxStatus::Pending)));
+
+        let (tx_hash, status) = settle(vec![
+            (original, Err(Error::ErrorFetchingTransaction)),
+            (replacement, Err(Error::ErrorFetchingTransaction)),
+        ]);
+        assert_eq!(tx_hash, replacement);
+        assert!(status.is_err());
+    }
+}
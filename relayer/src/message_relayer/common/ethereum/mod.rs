@@ -11,11 +11,14 @@ use ethereum_common::beacon::electra::Block;
 use super::{EthereumBlockNumber, EthereumSlotNumber, GearBlockNumber, RelayedMerkleRoot};
 
 pub mod accumulator;
+pub mod batcher;
 pub mod block_listener;
 pub mod deposit_event_extractor;
 pub mod merkle_root_extractor;
 pub mod message_paid_event_extractor;
+pub mod message_processed_event_extractor;
 pub mod message_sender;
+pub mod vara_message_event_extractor;
 
 async fn find_slot_by_block_number(
     eth_api: &EthApi,
@@ -0,0 +1,196 @@
+use prometheus::IntCounter;
+use sails_rs::{H160, H256};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+use ethereum_beacon_client::BeaconClient;
+use ethereum_client::{EthApi, VaraMessageEventEntry};
+use utils_prometheus::{impl_metered_service, MeteredService};
+
+use crate::{
+    common::{self, BASE_RETRY_DELAY, MAX_RETRIES},
+    message_relayer::{
+        common::{EthereumBlockNumber, TxHashWithSlot},
+        eth_to_gear::journal::Journal,
+    },
+};
+
+use super::find_slot_by_block_number;
+
+pub struct VaraMessageEventExtractor {
+    eth_api: EthApi,
+    beacon_client: BeaconClient,
+    journal: Journal,
+
+    vara_messenger_address: H160,
+    /// Only messages to this program are relayed if specified.
+    destination: Option<H256>,
+
+    metrics: Metrics,
+}
+
+impl MeteredService for VaraMessageEventExtractor {
+    fn get_sources(&self) -> impl IntoIterator<Item = Box<dyn prometheus::core::Collector>> {
+        self.metrics.get_sources()
+    }
+}
+
+impl_metered_service! {
+    struct Metrics {
+        total_messages_found: IntCounter = IntCounter::new(
+            "vara_message_event_extractor_total_messages_found",
+            "Total amount of messages to gear discovered",
+        ),
+    }
+}
+
+impl VaraMessageEventExtractor {
+    pub fn new(
+        eth_api: EthApi,
+        beacon_client: BeaconClient,
+        vara_messenger_address: H160,
+        destination: Option<H256>,
+        journal: Journal,
+    ) -> Self {
+        Self {
+            eth_api,
+            beacon_client,
+            journal,
+
+            vara_messenger_address,
+            destination,
+
+            metrics: Metrics::new(),
+        }
+    }
+
+    pub async fn run(
+        mut self,
+        mut blocks: UnboundedReceiver<EthereumBlockNumber>,
+    ) -> UnboundedReceiver<TxHashWithSlot> {
+        let (sender, receiver) = unbounded_channel();
+
+        tokio::task::spawn(async move {
+            if let Err(err) = self.replay_journal(&sender) {
+                log::error!("Failed to replay messages from the journal: {err}");
+                return;
+            }
+
+            let mut attempts = 0;
+
+            loop {
+                let res = self.run_inner(&sender, &mut blocks).await;
+                if let Err(err) = res {
+                    attempts += 1;
+                    let delay = BASE_RETRY_DELAY * 2u32.pow(attempts - 1);
+
+                    log::error!(
+                        "Vara message event extractor failed (attempt {}/{}): {}. Retrying in {:?}",
+                        attempts,
+                        MAX_RETRIES,
+                        err,
+                        delay
+                    );
+                    if attempts >= MAX_RETRIES {
+                        log::error!("Maximum attempts reached, exiting...");
+                        break;
+                    }
+                    tokio::time::sleep(delay).await;
+                    if common::is_transport_error_recoverable(&err) {
+                        self.eth_api = match self.eth_api.reconnect() {
+                            Ok(api) => api,
+                            Err(err) => {
+                                log::error!("Failed to reconnect to Ethereum: {}", err);
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        receiver
+    }
+
+    fn replay_journal(&self, sender: &UnboundedSender<TxHashWithSlot>) -> anyhow::Result<()> {
+        let messages = self.journal.deposits_to_relay()?;
+        if !messages.is_empty() {
+            log::info!("Replaying {} messages from the journal", messages.len());
+        }
+
+        for message in messages {
+            sender.send(message)?;
+        }
+
+        Ok(())
+    }
+
+    async fn run_inner(
+        &self,
+        sender: &UnboundedSender<TxHashWithSlot>,
+        blocks: &mut UnboundedReceiver<EthereumBlockNumber>,
+    ) -> anyhow::Result<()> {
+        loop {
+            while let Some(block) = blocks.recv().await {
+                self.process_block_events(block, sender).await?;
+            }
+        }
+    }
+
+    async fn process_block_events(
+        &self,
+        block: EthereumBlockNumber,
+        sender: &UnboundedSender<TxHashWithSlot>,
+    ) -> anyhow::Result<()> {
+        let events = self
+            .eth_api
+            .fetch_vara_message_events(self.vara_messenger_address, self.destination, block.0)
+            .await?;
+
+        if events.is_empty() {
+            self.journal.add_deposits(block, vec![]).await?;
+
+            return Ok(());
+        }
+
+        let slot_number =
+            find_slot_by_block_number(&self.eth_api, &self.beacon_client, block).await?;
+
+        self.metrics
+            .total_messages_found
+            .inc_by(events.len() as u64);
+
+        for ev in &events {
+            log::info!(
+                "Found message to gear: tx_hash={}, from={}, destination={}, nonce={}, payload_len={}, slot_number={}",
+                hex::encode(ev.tx_hash.0),
+                hex::encode(ev.from.0),
+                hex::encode(ev.destination.0),
+                ev.nonce,
+                ev.payload.len(),
+                slot_number.0,
+            );
+        }
+
+        // Receiver gets the whole receipt so all the messages of the transaction
+        // are delivered at once.
+        let mut tx_hashes: Vec<_> = events
+            .into_iter()
+            .map(|VaraMessageEventEntry { tx_hash, .. }| tx_hash)
+            .collect();
+        tx_hashes.dedup();
+
+        let messages = tx_hashes
+            .into_iter()
+            .map(|tx_hash| TxHashWithSlot {
+                slot_number,
+                tx_hash,
+            })
+            .collect();
+
+        for message in self.journal.add_deposits(block, messages).await? {
+            sender.send(message)?;
+        }
+
+        Ok(())
+    }
+}
@@ -5,16 +5,21 @@ use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 use utils_prometheus::{impl_metered_service, MeteredService};
 
 use bridging_payment_client::bridging_payment::events::BridgingPaymentEvents;
+use vft_manager_client::vft_manager::events::VftManagerEvents;
 
 use crate::message_relayer::{
     common::{GearBlockNumber, PaidMessage},
     eth_to_gear::api_provider::ApiProviderConnection,
+    gear_to_eth::journal::Journal,
 };
 
 pub struct MessagePaidEventExtractor {
     bridging_payment_address: H256,
+    /// Refunds requested from vft-manager are paid by the users as well.
+    vft_manager_address: Option<H256>,
 
     api_provider: ApiProviderConnection,
+    journal: Journal,
 
     metrics: Metrics,
 }
@@ -35,10 +40,17 @@ impl_metered_service! {
 }
 
 impl MessagePaidEventExtractor {
-    pub fn new(api_provider: ApiProviderConnection, bridging_payment_address: H256) -> Self {
+    pub fn new(
+        api_provider: ApiProviderConnection,
+        bridging_payment_address: H256,
+        vft_manager_address: Option<H256>,
+        journal: Journal,
+    ) -> Self {
         Self {
             bridging_payment_address,
+            vft_manager_address,
             api_provider,
+            journal,
             metrics: Metrics::new(),
         }
     }
@@ -50,6 +62,11 @@ impl MessagePaidEventExtractor {
         let (sender, receiver) = unbounded_channel();
 
         tokio::task::spawn(async move {
+            if let Err(err) = self.replay_journal(&sender) {
+                log::error!("Failed to replay paid messages from the journal: {err}");
+                return;
+            }
+
             loop {
                 let res = self.run_inner(&sender, &mut blocks).await;
                 if let Err(err) = res {
@@ -72,6 +89,14 @@ impl MessagePaidEventExtractor {
         receiver
     }
 
+    fn replay_journal(&self, sender: &UnboundedSender<PaidMessage>) -> anyhow::Result<()> {
+        for paid_message in self.journal.paid_messages()? {
+            sender.send(paid_message)?;
+        }
+
+        Ok(())
+    }
+
     async fn run_inner(
         &self,
         sender: &UnboundedSender<PaidMessage>,
@@ -79,18 +104,18 @@ impl MessagePaidEventExtractor {
     ) -> anyhow::Result<()> {
         loop {
             while let Some(block) = blocks.recv().await {
-                self.process_block_events(block.0, sender).await?;
+                self.process_block_events(block, sender).await?;
             }
         }
     }
 
     async fn process_block_events(
         &self,
-        block: u32,
+        block: GearBlockNumber,
         sender: &UnboundedSender<PaidMessage>,
     ) -> anyhow::Result<()> {
         let gear_api = self.api_provider.client();
-        let block_hash = gear_api.block_number_to_hash(block).await?;
+        let block_hash = gear_api.block_number_to_hash(block.0).await?;
 
         // As bridging-payment uses sails to send events, destnation will be zeroed.
         let destination = H256::zero();
@@ -99,26 +124,70 @@ impl MessagePaidEventExtractor {
             .user_message_sent_events(self.bridging_payment_address, destination, block_hash)
             .await?;
 
-        if messages.is_empty() {
-            return Ok(());
-        }
-
-        log::info!("Found {} paid messages at block #{}", messages.len(), block);
-
-        self.metrics
-            .total_messages_found
-            .inc_by(messages.len() as u64);
-
+        let mut paid_messages = Vec::with_capacity(messages.len());
         for message in messages {
             let user_reply = BridgingPaymentEvents::decode_event(message.payload)
                 .map_err(|_| anyhow::anyhow!("Failed to decode bridging payment event"))?;
 
-            let BridgingPaymentEvents::BridgingPaid { nonce } = user_reply;
+            // bridging-payment also reports gas price updates and fee settlements.
+            let BridgingPaymentEvents::BridgingPaid { nonce, deadline } = user_reply else {
+                continue;
+            };
 
             let mut nonce_le = [0; 32];
             nonce.to_little_endian(&mut nonce_le);
 
-            sender.send(PaidMessage { nonce: nonce_le })?;
+            paid_messages.push(PaidMessage {
+                nonce: nonce_le,
+                deadline: Some(GearBlockNumber(deadline)),
+            });
+        }
+
+        if !paid_messages.is_empty() {
+            log::info!(
+                "Found {} paid messages at block #{}",
+                paid_messages.len(),
+                block
+            );
+
+            self.metrics
+                .total_messages_found
+                .inc_by(paid_messages.len() as u64);
+        }
+
+        if let Some(vft_manager_address) = self.vft_manager_address {
+            let messages = gear_api
+                .user_message_sent_events(vft_manager_address, destination, block_hash)
+                .await?;
+
+            let mut refunds = 0;
+            for message in messages {
+                // vft-manager emits a lot of other events that we're not interested in.
+                let Ok(VftManagerEvents::RefundRequested { nonce, .. }) =
+                    VftManagerEvents::decode_event(message.payload)
+                else {
+                    continue;
+                };
+
+                let mut nonce_le = [0; 32];
+                nonce.to_little_endian(&mut nonce_le);
+
+                paid_messages.push(PaidMessage {
+                    nonce: nonce_le,
+                    deadline: None,
+                });
+                refunds += 1;
+            }
+
+            if refunds > 0 {
+                log::info!("Found {} refund messages at block #{}", refunds, block);
+
+                self.metrics.total_messages_found.inc_by(refunds);
+            }
+        }
+
+        for paid_message in self.journal.add_paid_messages(block, paid_messages).await? {
+            sender.send(paid_message)?;
         }
 
         Ok(())
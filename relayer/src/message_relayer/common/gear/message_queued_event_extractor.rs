@@ -1,6 +1,7 @@
 use crate::message_relayer::{
     common::{AuthoritySetId, GearBlockNumber, MessageInBlock, H256},
     eth_to_gear::api_provider::ApiProviderConnection,
+    gear_to_eth::journal::Journal,
 };
 use gear_rpc_client::GearApi;
 use prometheus::IntCounter;
@@ -9,6 +10,7 @@ use utils_prometheus::{impl_metered_service, MeteredService};
 
 pub struct MessageQueuedEventExtractor {
     api_provider: ApiProviderConnection,
+    journal: Journal,
 
     metrics: Metrics,
 }
@@ -29,9 +31,10 @@ impl_metered_service! {
 }
 
 impl MessageQueuedEventExtractor {
-    pub fn new(api_provider: ApiProviderConnection) -> Self {
+    pub fn new(api_provider: ApiProviderConnection, journal: Journal) -> Self {
         Self {
             api_provider,
+            journal,
             metrics: Metrics::new(),
         }
     }
@@ -43,6 +46,11 @@ impl MessageQueuedEventExtractor {
         let (sender, receiver) = unbounded_channel();
 
         tokio::task::spawn(async move {
+            if let Err(err) = self.replay_journal(&sender) {
+                log::error!("Failed to replay queued messages from the journal: {err}");
+                return;
+            }
+
             loop {
                 let res = self.run_inner(&sender, &mut blocks).await;
                 if let Err(err) = res {
@@ -64,6 +72,22 @@ impl MessageQueuedEventExtractor {
         receiver
     }
 
+    fn replay_journal(&self, sender: &UnboundedSender<MessageInBlock>) -> anyhow::Result<()> {
+        let messages = self.journal.messages_to_relay()?;
+        if !messages.is_empty() {
+            log::info!(
+                "Replaying {} queued messages from the journal",
+                messages.len()
+            );
+        }
+
+        for message in messages {
+            sender.send(message)?;
+        }
+
+        Ok(())
+    }
+
     async fn run_inner(
         &self,
         sender: &UnboundedSender<MessageInBlock>,
@@ -95,15 +119,20 @@ impl MessageQueuedEventExtractor {
             self.metrics
                 .total_messages_found
                 .inc_by(messages.len() as u64);
+        }
 
-            for message in messages {
-                sender.send(MessageInBlock {
-                    message,
-                    block,
-                    block_hash,
-                    authority_set_id: AuthoritySetId(authority_set_id),
-                })?;
-            }
+        let messages = messages
+            .into_iter()
+            .map(|message| MessageInBlock {
+                message,
+                block,
+                block_hash,
+                authority_set_id: AuthoritySetId(authority_set_id),
+            })
+            .collect();
+
+        for message in self.journal.add_queued_messages(block, messages).await? {
+            sender.send(message)?;
         }
 
         Ok(())
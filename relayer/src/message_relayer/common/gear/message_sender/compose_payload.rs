@@ -1,6 +1,10 @@
 use std::ops::ControlFlow;
 
-use checkpoint_light_client_client::{traits::ServiceCheckpointFor as _, ServiceCheckpointFor};
+use checkpoint_archive_client::{traits::CheckpointArchive as _, CheckpointArchive};
+use checkpoint_light_client_client::{
+    traits::{ServiceArchive as _, ServiceCheckpointFor as _},
+    ServiceArchive, ServiceCheckpointFor,
+};
 use ethereum_beacon_client::BeaconClient;
 
 use alloy::{network::primitives::BlockTransactionsKind, primitives::TxHash, providers::Provider};
@@ -106,7 +110,7 @@ async fn build_inclusion_proof(
 
     let historical_proxy = HistoricalProxy::new(remoting.clone());
     let eth_events = eth_events_electra_client::EthereumEventClient::new(remoting.clone());
-    let service_checkpoint = ServiceCheckpointFor::new(remoting);
+    let service_checkpoint = ServiceCheckpointFor::new(remoting.clone());
 
     let beacon_block_parent = beacon_client
         .get_block_by_hash::<beacon::electra::Block>(beacon_root_parent)
@@ -134,12 +138,30 @@ async fn build_inclusion_proof(
         .await
         .map_err(|e| anyhow::anyhow!(e))?;
 
-    let (checkpoint_slot, checkpoint) = service_checkpoint
-        .get(slot)
-        .recv(checkpoint_endpoint)
+    let finalized_only = eth_events
+        .finalized_only()
+        .recv(endpoint)
         .await
-        .map_err(|e| anyhow::anyhow!(e))?
-        .map_err(|e| anyhow::anyhow!("Checkpoint error: {:?}", e))?;
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    // the proof should be built against the same checkpoint the endpoint requests
+    let checkpoint = match finalized_only {
+        true => {
+            service_checkpoint
+                .get_finalized(slot)
+                .recv(checkpoint_endpoint)
+                .await
+        }
+        false => service_checkpoint.get(slot).recv(checkpoint_endpoint).await,
+    };
+    let (checkpoint_slot, checkpoint) = match checkpoint {
+        Ok(Ok(checkpoint)) => checkpoint,
+        // the checkpoint might be evicted to the archive which can't be requested
+        // by the light client in a query so request the archive directly
+        result => request_archived_checkpoint(remoting, checkpoint_endpoint, slot)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Checkpoint error: {result:?}"))?,
+    };
 
     let block = BlockGenericForBlockBody {
         slot,
@@ -181,3 +203,155 @@ async fn build_inclusion_proof(
 
     Ok(BlockInclusionProof { block, headers })
 }
+
+async fn request_archived_checkpoint(
+    remoting: GClientRemoting,
+    checkpoint_light_client_id: ActorId,
+    slot: u64,
+) -> AnyResult<Option<(u64, H256)>> {
+    let archive = ServiceArchive::new(remoting.clone())
+        .address()
+        .recv(checkpoint_light_client_id)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    let Some(archive) = archive else {
+        return Ok(None);
+    };
+
+    CheckpointArchive::new(remoting)
+        .get(slot)
+        .recv(archive)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use checkpoint_archive_client::{
+        traits::CheckpointArchiveFactory as _, CheckpointArchiveFactory,
+    };
+    use checkpoint_light_client_client::{
+        traits::CheckpointLightClientFactory as _, CheckpointLightClientFactory,
+    };
+    use checkpoint_light_client_io::{Init, G2};
+    use ethereum_beacon_client::utils;
+    use ethereum_common::{
+        base_types::BytesFixed,
+        network::Network,
+        utils::{BootstrapResponse, UpdateData},
+    };
+    use gclient::GearApi;
+    use gear_core::ids::prelude::*;
+    use sails_rs::calls::*;
+
+    // chain data of the checkpoint-light-client tests
+    const SEPOLIA_UPDATE_640: &[u8] = include_bytes!(
+        "../../../../../../gear-programs/checkpoint-light-client/app/tests/chain-data/sepolia-update-640.json"
+    );
+    const SEPOLIA_BOOTSTRAP_640: &[u8] = include_bytes!(
+        "../../../../../../gear-programs/checkpoint-light-client/app/tests/chain-data/sepolia-bootstrap-640.json"
+    );
+
+    fn construct_init() -> Init {
+        let BootstrapResponse { data: bootstrap } =
+            serde_json::from_slice(SEPOLIA_BOOTSTRAP_640).unwrap();
+        let mut updates: Vec<UpdateData> = serde_json::from_slice(SEPOLIA_UPDATE_640).unwrap();
+        let update = updates.pop().unwrap().data;
+
+        let signature = <G2 as ark_serialize::CanonicalDeserialize>::deserialize_compressed(
+            &update.sync_aggregate.sync_committee_signature.0 .0[..],
+        )
+        .unwrap();
+        let sync_aggregate_encoded = update.sync_aggregate.encode();
+
+        Init {
+            network: Network::Sepolia,
+            sync_committee_current_pub_keys: utils::map_public_keys(
+                &bootstrap.current_sync_committee.pubkeys,
+            ),
+            sync_committee_current_aggregate_pubkey: bootstrap
+                .current_sync_committee
+                .aggregate_pubkey,
+            sync_committee_current_branch: bootstrap
+                .current_sync_committee_branch
+                .into_iter()
+                .map(|BytesFixed(bytes)| bytes.0)
+                .collect(),
+            update: utils::sync_update_from_update(signature, update),
+            sync_aggregate_encoded,
+            checkpoints_window: None,
+        }
+    }
+
+    async fn upload_code(api: &GearApi, wasm_binary: &[u8]) -> CodeId {
+        api.upload_code(wasm_binary)
+            .await
+            .map(|(code_id, ..)| code_id)
+            .unwrap_or_else(|_| CodeId::generate(wasm_binary))
+    }
+
+    #[tokio::test]
+    async fn requests_archived_checkpoint() {
+        let api = GearApi::dev().await.unwrap();
+        let remoting = GClientRemoting::new(api.clone());
+        let gas_limit = api.block_gas_limit().unwrap() / 100 * 95;
+        let salt: [u8; 32] = rand::random();
+        let account = ActorId::from(<[u8; 32]>::from(api.account_id().clone()));
+
+        let code_id = upload_code(&api, checkpoint_light_client::WASM_BINARY).await;
+        let checkpoint_light_client_id = CheckpointLightClientFactory::new(remoting.clone())
+            .init(construct_init())
+            .with_gas_limit(gas_limit)
+            .send_recv(code_id, salt)
+            .await
+            .unwrap();
+
+        // the test account appends the checkpoints instead of the light client
+        let code_id = upload_code(&api, checkpoint_archive::WASM_BINARY).await;
+        let archive_id = CheckpointArchiveFactory::new(remoting.clone())
+            .new(account)
+            .send_recv(code_id, salt)
+            .await
+            .unwrap();
+
+        let checkpoints = vec![
+            (32, H256::from_low_u64_be(32)),
+            (64, H256::from_low_u64_be(64)),
+        ];
+        CheckpointArchive::new(remoting.clone())
+            .append(checkpoints.clone())
+            .send_recv(archive_id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // the light client has no archive
+        let result = request_archived_checkpoint(remoting.clone(), checkpoint_light_client_id, 33)
+            .await
+            .unwrap();
+        assert_eq!(result, None);
+
+        ServiceArchive::new(remoting.clone())
+            .set_address(Some(archive_id))
+            .send_recv(checkpoint_light_client_id)
+            .await
+            .unwrap();
+
+        for (slot, expected) in [
+            (32, Some(checkpoints[0])),
+            (33, Some(checkpoints[1])),
+            (64, Some(checkpoints[1])),
+            // the checkpoint preceding the oldest archived one is unknown
+            (31, None),
+            (65, None),
+        ] {
+            let result =
+                request_archived_checkpoint(remoting.clone(), checkpoint_light_client_id, slot)
+                    .await
+                    .unwrap();
+            assert_eq!(result, expected, "slot = {slot}");
+        }
+    }
+}
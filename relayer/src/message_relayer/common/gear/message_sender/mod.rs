@@ -1,7 +1,10 @@
 use crate::{
     common::{self, BASE_RETRY_DELAY, MAX_RETRIES},
-    eth_to_gear::api_provider::ApiProviderConnection,
-    message_relayer::common::{EthereumSlotNumber, TxHashWithSlot},
+    eth_to_gear::{
+        api_provider::ApiProviderConnection,
+        journal::{DepositStatus, Journal},
+    },
+    message_relayer::common::{gear::signer::GearSigner, EthereumSlotNumber, TxHashWithSlot},
 };
 use ethereum_beacon_client::BeaconClient;
 use ethereum_client::EthApi;
@@ -11,7 +14,10 @@ use futures::{
     pin_mut,
 };
 use gclient::GearApi;
-use historical_proxy_client::{traits::HistoricalProxy as _, HistoricalProxy};
+use gsdk::ext::sp_runtime::AccountId32;
+use historical_proxy_client::{
+    historical_proxy::io::Redirect, traits::HistoricalProxy as _, HistoricalProxy,
+};
 use primitive_types::H256;
 use prometheus::IntGauge;
 use sails_rs::{
@@ -20,23 +26,33 @@ use sails_rs::{
     Encode,
 };
 
-use tokio::{sync::mpsc::UnboundedReceiver, time::Duration};
+use tokio::{
+    sync::mpsc::UnboundedReceiver,
+    time::{Duration, Instant},
+};
 use utils_prometheus::{impl_metered_service, MeteredService};
 use vft_manager_client::vft_manager::io::SubmitReceipt;
 
 mod compose_payload;
 
+/// Delay before submitting again the deposit that the receiver program can't process
+/// at the moment.
+const DEFERRED_RETRY_DELAY: Duration = Duration::from_secs(10 * 60);
+
 pub struct MessageSender {
     api_provider: ApiProviderConnection,
-    suri: String,
+    signer: GearSigner,
     eth_api: EthApi,
     beacon_client: BeaconClient,
     historical_proxy_address: H256,
     receiver_address: H256,
     receiver_route: Vec<u8>,
     decode_reply: bool,
+    journal: Journal,
 
     waiting_checkpoint: Vec<TxHashWithSlot>,
+    /// Deferred deposits along with the time they should be submitted again at.
+    deferred: Vec<(Instant, TxHashWithSlot)>,
 
     metrics: Metrics,
 }
@@ -57,6 +73,10 @@ impl_metered_service! {
             "gear_message_sender_messages_waiting_finality",
             "Amount of messages waiting for finality on gear",
         ),
+        messages_deferred: IntGauge = IntGauge::new(
+            "gear_message_sender_messages_deferred",
+            "Amount of messages the receiver program can't process at the moment",
+        ),
         fee_payer_balance: IntGauge = IntGauge::new(
             "gear_message_sender_fee_payer_balance",
             "Transaction fee payer balance",
@@ -68,25 +88,28 @@ impl MessageSender {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         api_provider: ApiProviderConnection,
-        suri: String,
+        signer: GearSigner,
         eth_api: EthApi,
         beacon_client: BeaconClient,
         historical_proxy_address: H256,
         receiver_address: H256,
         receiver_route: Vec<u8>,
         decode_reply: bool,
+        journal: Journal,
     ) -> Self {
         Self {
             api_provider,
-            suri,
+            signer,
             eth_api,
             beacon_client,
             historical_proxy_address,
             receiver_address,
             receiver_route,
             decode_reply,
+            journal,
 
             waiting_checkpoint: vec![],
+            deferred: vec![],
 
             metrics: Metrics::new(),
         }
@@ -154,7 +177,7 @@ impl MessageSender {
         &self,
         message: &TxHashWithSlot,
         gear_api: &GearApi,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<DepositStatus> {
         let payload = compose_payload::compose(
             &self.beacon_client,
             gear_api,
@@ -174,27 +197,65 @@ impl MessageSender {
         // Use 95% of block gas limit for all extrinsics.
         let gas_limit = gas_limit_block / 100 * 95;
 
-        let remoting = GClientRemoting::new(gear_api.clone());
-
-        let mut proxy_service = HistoricalProxy::new(remoting.clone());
+        let receiver_reply = match &self.signer {
+            GearSigner::Suri(_) => {
+                let remoting = GClientRemoting::new(gear_api.clone());
+
+                let mut proxy_service = HistoricalProxy::new(remoting.clone());
+
+                let (_, receiver_reply) = proxy_service
+                    .redirect(
+                        payload.proof_block.block.slot,
+                        payload.encode(),
+                        self.receiver_address.into(),
+                        self.receiver_route.clone(),
+                    )
+                    .with_gas_limit(gas_limit)
+                    .send_recv(self.historical_proxy_address.into())
+                    .await
+                    .map_err(|e| {
+                        anyhow::anyhow!(
+                            "Failed to send message to historical proxy address: {:?}",
+                            e
+                        )
+                    })?
+                    .map_err(|e| anyhow::anyhow!("Internal historical proxy error: {:?}", e))?;
+
+                receiver_reply
+            }
 
-        let (_, receiver_reply) = proxy_service
-            .redirect(
-                payload.proof_block.block.slot,
-                payload.encode(),
-                self.receiver_address.into(),
-                self.receiver_route.clone(),
-            )
-            .with_gas_limit(gas_limit)
-            .send_recv(self.historical_proxy_address.into())
-            .await
-            .map_err(|e| {
-                anyhow::anyhow!(
-                    "Failed to send message to historical proxy address: {:?}",
-                    e
-                )
-            })?
-            .map_err(|e| anyhow::anyhow!("Internal historical proxy error: {:?}", e))?;
+            GearSigner::Remote(signer) => {
+                let call = Redirect::encode_call(&(
+                    payload.proof_block.block.slot,
+                    payload.encode(),
+                    self.receiver_address.into(),
+                    self.receiver_route.clone(),
+                ));
+
+                let reply = signer
+                    .send_message(
+                        &self.api_provider.api(),
+                        self.historical_proxy_address,
+                        call,
+                        gas_limit,
+                    )
+                    .await
+                    .map_err(|e| {
+                        anyhow::anyhow!(
+                            "Failed to send message to historical proxy address: {:?}",
+                            e
+                        )
+                    })?;
+
+                let (_, receiver_reply) = Redirect::decode_reply(&reply)
+                    .map_err(|e| {
+                        anyhow::anyhow!("Failed to decode historical proxy reply: {:?}", e)
+                    })?
+                    .map_err(|e| anyhow::anyhow!("Internal historical proxy error: {:?}", e))?;
+
+                receiver_reply
+            }
+        };
 
         // TODO: Refactor this approach. #255
         log::debug!("Received reply: {}", hex::encode(&receiver_reply));
@@ -206,6 +267,46 @@ impl MessageSender {
                 Ok(_) => {}
                 Err(vft_manager_client::Error::NotSupportedEvent) => {
                     log::warn!("Dropping message for {} as it's considered invalid by vft-manager(probably unsupported ERC20 token)", message.tx_hash);
+
+                    return Ok(DepositStatus::Rejected);
+                }
+                Err(vft_manager_client::Error::DeliveryFailed) => {
+                    log::warn!(
+                        "Tokens from {} weren't delivered by vft-manager and can be refunded",
+                        message.tx_hash
+                    );
+                }
+                Err(vft_manager_client::Error::AlreadyProcessed) => {
+                    // The message might have been relayed before restart while its
+                    // submission result wasn't saved to the journal.
+                    log::info!(
+                        "Message for {} has already been processed by vft-manager",
+                        message.tx_hash
+                    );
+                }
+                Err(vft_manager_client::Error::TransactionTooOld) => {
+                    log::warn!(
+                        "Dropping message for {} as it's too old to be processed by vft-manager",
+                        message.tx_hash
+                    );
+
+                    return Ok(DepositStatus::Rejected);
+                }
+                Err(
+                    e @ (vft_manager_client::Error::Paused
+                    | vft_manager_client::Error::TokenPaused
+                    | vft_manager_client::Error::TransferLimitExceeded
+                    | vft_manager_client::Error::WindowCapExceeded),
+                ) => {
+                    log::warn!(
+                        "vft-manager can't process message for {} at the moment: {:?}. \
+                        Retrying in {:?}",
+                        message.tx_hash,
+                        e,
+                        DEFERRED_RETRY_DELAY
+                    );
+
+                    return Ok(DepositStatus::Deferred);
                 }
                 Err(e) => {
                     anyhow::bail!("Internal vft-manager error: {:?}", e);
@@ -213,12 +314,17 @@ impl MessageSender {
             }
         }
 
-        Ok(())
+        Ok(DepositStatus::Relayed)
     }
 
     async fn update_balance_metric(&self, gear_api: &GearApi) -> anyhow::Result<()> {
+        let account_id = match &self.signer {
+            GearSigner::Suri(_) => gear_api.account_id().clone(),
+            GearSigner::Remote(signer) => AccountId32::from(signer.account_id()),
+        };
+
         let balance = gear_api
-            .total_balance(gear_api.account_id())
+            .total_balance(&account_id)
             .await
             .map_err(|e| anyhow::anyhow!("Unable to get total balance: {e:?}"))?;
 
@@ -239,7 +345,11 @@ async fn run_inner(
     let mut latest_checkpoint_slot = None;
 
     loop {
-        let gear_api = self_.api_provider.gclient_client(&self_.suri)?;
+        let gear_api = match &self_.signer {
+            GearSigner::Suri(suri) => self_.api_provider.gclient_client(suri)?,
+            // Extrinsics are signed by the remote signer so the client is used only for queries.
+            GearSigner::Remote(_) => GearApi::from(self_.api_provider.api()),
+        };
         self_.update_balance_metric(&gear_api).await?;
 
         let recv_messages = messages.recv();
@@ -275,6 +385,15 @@ async fn run_inner(
             }
         }
 
+        let now = Instant::now();
+        let (ready, deferred): (Vec<_>, Vec<_>) = std::mem::take(&mut self_.deferred)
+            .into_iter()
+            .partition(|(retry_at, _)| *retry_at <= now);
+        self_.deferred = deferred;
+        self_
+            .waiting_checkpoint
+            .extend(ready.into_iter().map(|(_, message)| message));
+
         if self_.waiting_checkpoint.is_empty() {
             log::info!("There are no waiting checkpoints.");
             tokio::time::sleep(Duration::from_millis(300)).await;
@@ -285,10 +404,21 @@ async fn run_inner(
         for i in (0..self_.waiting_checkpoint.len()).rev() {
             if self_.waiting_checkpoint[i].slot_number <= latest_checkpoint_slot.unwrap_or_default()
             {
+                let message = &self_.waiting_checkpoint[i];
                 self_
-                    .submit_message(&self_.waiting_checkpoint[i], &gear_api)
+                    .journal
+                    .set_status(message, DepositStatus::InFlight)
                     .await?;
-                let _ = self_.waiting_checkpoint.remove(i);
+
+                let status = self_.submit_message(message, &gear_api).await?;
+                self_.journal.set_status(message, status).await?;
+
+                let message = self_.waiting_checkpoint.remove(i);
+                if status == DepositStatus::Deferred {
+                    self_
+                        .deferred
+                        .push((Instant::now() + DEFERRED_RETRY_DELAY, message));
+                }
             }
         }
 
@@ -298,5 +428,9 @@ async fn run_inner(
             .metrics
             .messages_waiting_checkpoint
             .set(self_.waiting_checkpoint.len() as i64);
+        self_
+            .metrics
+            .messages_deferred
+            .set(self_.deferred.len() as i64);
     }
 }
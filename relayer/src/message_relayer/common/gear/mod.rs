@@ -3,3 +3,4 @@ pub mod checkpoints_extractor;
 pub mod message_paid_event_extractor;
 pub mod message_queued_event_extractor;
 pub mod message_sender;
+pub mod signer;
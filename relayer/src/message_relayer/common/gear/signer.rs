@@ -0,0 +1,240 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use gear_core::ids::MessageId;
+use gsdk::Api;
+use primitive_types::H256;
+use reqwest::{header::CONTENT_TYPE, Client};
+use serde::{Deserialize, Serialize};
+use subxt::{
+    dynamic::Value,
+    utils::{AccountId32, MultiAddress, MultiSignature},
+};
+
+/// Key used to sign extrinsics sent to Gear.
+#[derive(Clone)]
+pub enum GearSigner {
+    /// Substrate URI of the key held by the relayer.
+    Suri(String),
+    /// Key held by the remote signer.
+    Remote(RemoteSigner),
+}
+
+#[derive(Serialize)]
+struct Request {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'static str,
+    params: SignParams,
+}
+
+#[derive(Serialize)]
+struct SignParams {
+    public_key: String,
+    payload: String,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    result: Option<String>,
+    error: Option<ResponseError>,
+}
+
+#[derive(Deserialize)]
+struct ResponseError {
+    code: i64,
+    message: String,
+}
+
+/// Client of the remote sr25519 signer.
+///
+/// The signer is expected to serve the JSON-RPC method `sr25519_sign` with params
+/// `{ "public_key": "0x..", "payload": "0x.." }` that returns hex encoded signature
+/// of the payload.
+#[derive(Clone)]
+pub struct RemoteSigner {
+    client: Client,
+    url: String,
+    public_key: [u8; 32],
+}
+
+impl RemoteSigner {
+    pub fn new(url: String, public_key: [u8; 32], timeout: Duration) -> anyhow::Result<Self> {
+        let client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .context("Failed to build HTTP client for the remote signer")?;
+
+        Ok(Self {
+            client,
+            url,
+            public_key,
+        })
+    }
+
+    pub fn account_id(&self) -> [u8; 32] {
+        self.public_key
+    }
+
+    pub async fn sign(&self, payload: &[u8]) -> anyhow::Result<[u8; 64]> {
+        let request = serde_json::to_vec(&Request {
+            jsonrpc: "2.0",
+            id: 1,
+            method: "sr25519_sign",
+            params: SignParams {
+                public_key: format!("0x{}", hex::encode(self.public_key)),
+                payload: format!("0x{}", hex::encode(payload)),
+            },
+        })?;
+
+        let response = self
+            .client
+            .post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(request)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        let response: Response = serde_json::from_slice(&response)?;
+
+        if let Some(ResponseError { code, message }) = response.error {
+            return Err(anyhow!("Remote signer returned error {code}: {message}"));
+        }
+
+        let signature = response
+            .result
+            .ok_or_else(|| anyhow!("Remote signer returned neither result nor error"))?;
+        let signature = hex::decode(signature.trim_start_matches("0x"))?;
+
+        <[u8; 64]>::try_from(&signature[..])
+            .map_err(|_| anyhow!("Remote signer returned signature of wrong length"))
+    }
+
+    /// Send message signed by the remote signer and wait for the reply to it.
+    pub async fn send_message(
+        &self,
+        api: &Api,
+        destination: H256,
+        payload: Vec<u8>,
+        gas_limit: u64,
+    ) -> anyhow::Result<Vec<u8>> {
+        let gear_api = gclient::GearApi::from(api.clone());
+        let mut listener = gear_api.subscribe().await?;
+
+        let call = subxt::dynamic::tx(
+            "Gear",
+            "send_message",
+            vec![
+                Value::from_bytes(destination.0),
+                Value::from_bytes(payload),
+                Value::u128(gas_limit.into()),
+                Value::u128(0),
+                Value::bool(false),
+            ],
+        );
+
+        let account_id = AccountId32::from(self.public_key);
+        let partial = api
+            .tx()
+            .create_partial_signed(&call, &account_id, Default::default())
+            .await?;
+
+        let signature = self.sign(&partial.signer_payload()).await?;
+        let extrinsic = partial.sign_with_address_and_signature(
+            &MultiAddress::Id(account_id),
+            &MultiSignature::Sr25519(signature),
+        );
+
+        let events = extrinsic
+            .submit_and_watch()
+            .await?
+            .wait_for_finalized_success()
+            .await?;
+
+        let mut message_id = None;
+        for event in events.iter() {
+            let event = event?;
+            if event.pallet_name() == "Gear" && event.variant_name() == "MessageQueued" {
+                // The first field of the event is the id of the queued message.
+                let id = event
+                    .field_bytes()
+                    .get(..32)
+                    .ok_or_else(|| anyhow!("Malformed MessageQueued event"))?;
+                message_id = Some(MessageId::from(<[u8; 32]>::try_from(id)?));
+
+                break;
+            }
+        }
+
+        let message_id =
+            message_id.ok_or_else(|| anyhow!("Message isn't queued by the extrinsic"))?;
+
+        let (_, reply, _value) = listener.reply_bytes_on(message_id).await?;
+
+        reply.map_err(|e| anyhow!("Message {message_id} is replied with error: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, routing::post, Json, Router};
+    use serde_json::{json, Value as JsonValue};
+    use sp_core::{sr25519, Pair};
+    use tokio::net::TcpListener;
+
+    async fn mock_sign(
+        State(pair): State<sr25519::Pair>,
+        Json(request): Json<JsonValue>,
+    ) -> Json<JsonValue> {
+        let public_key = request["params"]["public_key"].as_str().unwrap();
+        if public_key != format!("0x{}", hex::encode(pair.public().0)) {
+            return Json(json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": { "code": -32602, "message": "Unknown key" },
+            }));
+        }
+
+        let payload = request["params"]["payload"].as_str().unwrap();
+        let payload = hex::decode(payload.trim_start_matches("0x")).unwrap();
+        let signature = pair.sign(&payload);
+
+        Json(json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": format!("0x{}", hex::encode(signature.0)),
+        }))
+    }
+
+    /// Start a mock signer holding the key and return its URL.
+    async fn spawn_mock_signer(pair: sr25519::Pair) -> String {
+        let app = Router::new().route("/", post(mock_sign)).with_state(pair);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        format!("http://{address}")
+    }
+
+    #[tokio::test]
+    async fn signs_with_remote_key() {
+        let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+        let url = spawn_mock_signer(pair.clone()).await;
+
+        let signer =
+            RemoteSigner::new(url.clone(), pair.public().0, Duration::from_secs(5)).unwrap();
+        let payload = b"payload to sign";
+        let signature = signer.sign(payload).await.unwrap();
+        assert!(sr25519::Pair::verify(
+            &sr25519::Signature::from_raw(signature),
+            payload,
+            &pair.public()
+        ));
+
+        let signer = RemoteSigner::new(url, [1; 32], Duration::from_secs(5)).unwrap();
+        assert!(signer.sign(payload).await.is_err());
+    }
+}
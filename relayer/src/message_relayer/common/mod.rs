@@ -1,26 +1,44 @@
 use ethereum_client::TxHash;
 use gear_rpc_client::dto::Message;
+use parity_scale_codec::{Decode, Encode};
 use primitive_types::H256;
 
 pub mod ethereum;
 pub mod gear;
 pub mod paid_messages_filter;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, derive_more::Display)]
+#[derive(
+    Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, derive_more::Display, Encode, Decode,
+)]
 pub struct AuthoritySetId(pub u64);
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, derive_more::Display)]
+#[derive(
+    Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, derive_more::Display, Encode, Decode,
+)]
 pub struct GearBlockNumber(pub u32);
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, derive_more::Display)]
+#[derive(
+    Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, derive_more::Display, Encode, Decode,
+)]
 pub struct EthereumBlockNumber(pub u64);
 
 #[derive(
-    Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Default, derive_more::Display,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Debug,
+    Default,
+    derive_more::Display,
+    Encode,
+    Decode,
 )]
 pub struct EthereumSlotNumber(pub u64);
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
 pub struct MessageInBlock {
     pub message: Message,
     pub block: GearBlockNumber,
@@ -31,9 +49,12 @@ pub struct MessageInBlock {
 #[derive(Clone, Copy, Debug)]
 pub struct PaidMessage {
     pub nonce: [u8; 32],
+    /// Block after which the fee can be refunded to the payer. `None` if the fee
+    /// can't be refunded.
+    pub deadline: Option<GearBlockNumber>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Encode, Decode)]
 pub struct RelayedMerkleRoot {
     pub block: GearBlockNumber,
     pub block_hash: H256,
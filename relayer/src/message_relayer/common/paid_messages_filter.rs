@@ -1,18 +1,28 @@
-use futures::{
-    future::{self, Either},
-    pin_mut,
-};
 use std::collections::HashMap;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
-use prometheus::IntGauge;
+use prometheus::{IntCounter, IntGauge};
 use utils_prometheus::{impl_metered_service, MeteredService};
 
-use super::{MessageInBlock, PaidMessage};
+use super::{GearBlockNumber, MessageInBlock, PaidMessage};
+use crate::message_relayer::gear_to_eth::journal::Journal;
 
+/// Amount of Gear blocks a discovered message waits for its payment. After that the message
+/// is dropped and removed from the journal.
+const UNPAID_MESSAGE_RETENTION: u32 = 28_800;
+
+/// Matches discovered messages with their payments.
+///
+/// Payment can be refunded to the payer after its deadline so such payments are
+/// dropped together with the messages paid with them, even if both were discovered.
+/// Both are removed from the journal as well.
+/// Messages that aren't paid within `UNPAID_MESSAGE_RETENTION` blocks are dropped as well
+/// so they don't pile up in the journal.
 pub struct PaidMessagesFilter {
     pending_messages: HashMap<[u8; 32], MessageInBlock>,
-    pending_nonces: Vec<[u8; 32]>,
+    pending_payments: Vec<PaidMessage>,
+    latest_block: Option<GearBlockNumber>,
+    journal: Journal,
 
     metrics: Metrics,
 }
@@ -28,15 +38,25 @@ impl_metered_service! {
         pending_messages_count: IntGauge = IntGauge::new(
             "paid_messages_filter_pending_messages_count",
             "Amount of discovered but not paid messages",
-        )
+        ),
+        expired_payments_count: IntCounter = IntCounter::new(
+            "paid_messages_filter_expired_payments_count",
+            "Amount of payments dropped because their deadline has passed",
+        ),
+        unpaid_messages_count: IntCounter = IntCounter::new(
+            "paid_messages_filter_unpaid_messages_count",
+            "Amount of messages dropped because they weren't paid in time",
+        ),
     }
 }
 
 impl PaidMessagesFilter {
-    pub fn new() -> Self {
+    pub fn new(journal: Journal) -> Self {
         Self {
             pending_messages: HashMap::default(),
-            pending_nonces: vec![],
+            pending_payments: vec![],
+            latest_block: None,
+            journal,
 
             metrics: Metrics::new(),
         }
@@ -46,12 +66,21 @@ impl PaidMessagesFilter {
         mut self,
         mut messages: UnboundedReceiver<MessageInBlock>,
         mut paid_messages: UnboundedReceiver<PaidMessage>,
+        mut blocks: UnboundedReceiver<GearBlockNumber>,
     ) -> UnboundedReceiver<MessageInBlock> {
         let (sender, receiver) = unbounded_channel();
 
         tokio::spawn(async move {
             loop {
-                match run_inner(&mut self, &sender, &mut messages, &mut paid_messages).await {
+                let res = run_inner(
+                    &mut self,
+                    &sender,
+                    &mut messages,
+                    &mut paid_messages,
+                    &mut blocks,
+                )
+                .await;
+                match res {
                     Ok(_) => break,
                     Err(e) => log::error!("Paid messages filter failed: {e}"),
                 }
@@ -60,6 +89,35 @@ impl PaidMessagesFilter {
 
         receiver
     }
+
+    /// Drop the messages that haven't been paid within `UNPAID_MESSAGE_RETENTION` blocks.
+    async fn prune_unpaid_messages(&mut self) -> anyhow::Result<()> {
+        let Some(latest_block) = self.latest_block else {
+            return Ok(());
+        };
+
+        let unpaid: Vec<_> = self
+            .pending_messages
+            .iter()
+            .filter(|(_, message)| {
+                message.block.0.saturating_add(UNPAID_MESSAGE_RETENTION) < latest_block.0
+            })
+            .map(|(nonce, _)| *nonce)
+            .collect();
+
+        for nonce in unpaid {
+            log::info!(
+                "Message with nonce {} isn't paid in time, skipping it",
+                hex::encode(nonce)
+            );
+
+            self.journal.remove_message(&nonce).await?;
+            self.pending_messages.remove(&nonce);
+            self.metrics.unpaid_messages_count.inc();
+        }
+
+        Ok(())
+    }
 }
 
 async fn run_inner(
@@ -67,26 +125,16 @@ async fn run_inner(
     sender: &UnboundedSender<MessageInBlock>,
     messages: &mut UnboundedReceiver<MessageInBlock>,
     paid_messages: &mut UnboundedReceiver<PaidMessage>,
+    blocks: &mut UnboundedReceiver<GearBlockNumber>,
 ) -> anyhow::Result<()> {
     loop {
-        let recv_messages = messages.recv();
-        pin_mut!(recv_messages);
-
-        let recv_paid_messages = paid_messages.recv();
-        pin_mut!(recv_paid_messages);
+        tokio::select! {
+            message = messages.recv() => {
+                let Some(message) = message else {
+                    log::info!("Channel with messages closed. Exiting");
+                    return Ok(());
+                };
 
-        match future::select(recv_messages, recv_paid_messages).await {
-            Either::Left((None, _)) => {
-                log::info!("Channel with messages closed. Exiting");
-                return Ok(());
-            }
-
-            Either::Right((None, _)) => {
-                log::info!("Channel with paid messages closed. Exiting");
-                return Ok(());
-            }
-
-            Either::Left((Some(message), _)) => {
                 if let Some(msg) = self_
                     .pending_messages
                     .insert(message.message.nonce_le, message)
@@ -98,19 +146,152 @@ async fn run_inner(
                 }
             }
 
-            Either::Right((Some(PaidMessage { nonce }), _)) => self_.pending_nonces.push(nonce),
+            paid_message = paid_messages.recv() => {
+                let Some(paid_message) = paid_message else {
+                    log::info!("Channel with paid messages closed. Exiting");
+                    return Ok(());
+                };
+
+                self_.pending_payments.push(paid_message);
+            }
+
+            block = blocks.recv() => {
+                let Some(block) = block else {
+                    log::info!("Channel with gear blocks closed. Exiting");
+                    return Ok(());
+                };
+
+                self_.latest_block = self_.latest_block.max(Some(block));
+            }
         }
 
-        for i in (0..self_.pending_nonces.len()).rev() {
-            if let Some(message) = self_.pending_messages.remove(&self_.pending_nonces[i]) {
+        for i in (0..self_.pending_payments.len()).rev() {
+            let payment = self_.pending_payments[i];
+            if is_expired(&payment, self_.latest_block) {
+                log::warn!(
+                    "Payment for message with nonce {} has expired, skipping the message",
+                    hex::encode(payment.nonce),
+                );
+
+                // the payment and the message shouldn't be replayed after restart
+                self_.journal.remove_message(&payment.nonce).await?;
+                self_.pending_messages.remove(&payment.nonce);
+                self_.metrics.expired_payments_count.inc();
+                self_.pending_payments.remove(i);
+                continue;
+            }
+
+            if let Some(message) = self_.pending_messages.remove(&payment.nonce) {
                 sender.send(message)?;
-                self_.pending_nonces.remove(i);
+                self_.pending_payments.remove(i);
             }
         }
 
+        self_.prune_unpaid_messages().await?;
+
         self_
             .metrics
             .pending_messages_count
             .set(self_.pending_messages.len() as i64);
     }
 }
+
+/// Fee of the payment can be refunded at the blocks after its deadline.
+fn is_expired(payment: &PaidMessage, latest_block: Option<GearBlockNumber>) -> bool {
+    match (payment.deadline, latest_block) {
+        (Some(deadline), Some(latest_block)) => latest_block > deadline,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message_relayer::common::AuthoritySetId;
+    use gear_rpc_client::dto::Message;
+    use primitive_types::H256;
+
+    fn message(nonce: u8, block: u32) -> MessageInBlock {
+        MessageInBlock {
+            message: Message {
+                nonce_le: [nonce; 32],
+                ..Default::default()
+            },
+            block: GearBlockNumber(block),
+            block_hash: H256::repeat_byte(nonce),
+            authority_set_id: AuthoritySetId(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn unpaid_messages_are_pruned() {
+        let journal = Journal::temporary().unwrap();
+        journal
+            .add_queued_messages(GearBlockNumber(10), vec![message(1, 10), message(2, 10)])
+            .await
+            .unwrap();
+
+        let (messages_sender, messages) = unbounded_channel();
+        let (paid_messages_sender, paid_messages) = unbounded_channel();
+        let (blocks_sender, blocks) = unbounded_channel();
+        let mut filtered_messages = PaidMessagesFilter::new(journal.clone())
+            .run(messages, paid_messages, blocks)
+            .await;
+
+        for message in journal.messages_to_relay().unwrap() {
+            messages_sender.send(message).unwrap();
+        }
+        paid_messages_sender
+            .send(PaidMessage {
+                nonce: [1; 32],
+                deadline: None,
+            })
+            .unwrap();
+
+        let filtered = filtered_messages.recv().await.unwrap();
+        assert_eq!(filtered.message.nonce_le, [1; 32]);
+
+        // the paid message is kept until it's relayed
+        blocks_sender
+            .send(GearBlockNumber(10 + UNPAID_MESSAGE_RETENTION + 1))
+            .unwrap();
+        while journal.messages().unwrap().len() > 1 {
+            tokio::task::yield_now().await;
+        }
+
+        let messages = journal.messages().unwrap();
+        assert_eq!(messages[0].message.message.nonce_le, [1; 32]);
+    }
+
+    #[tokio::test]
+    async fn expired_payments_are_removed() {
+        let journal = Journal::temporary().unwrap();
+        journal
+            .add_queued_messages(GearBlockNumber(10), vec![message(1, 10)])
+            .await
+            .unwrap();
+        let payment = PaidMessage {
+            nonce: [1; 32],
+            deadline: Some(GearBlockNumber(20)),
+        };
+        journal
+            .add_paid_messages(GearBlockNumber(10), vec![payment])
+            .await
+            .unwrap();
+
+        let (_messages_sender, messages) = unbounded_channel();
+        let (paid_messages_sender, paid_messages) = unbounded_channel();
+        let (blocks_sender, blocks) = unbounded_channel();
+        let _filtered_messages = PaidMessagesFilter::new(journal.clone())
+            .run(messages, paid_messages, blocks)
+            .await;
+
+        blocks_sender.send(GearBlockNumber(21)).unwrap();
+        paid_messages_sender.send(payment).unwrap();
+        while !journal.paid_messages().unwrap().is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        assert!(journal.messages().unwrap().is_empty());
+    }
+}
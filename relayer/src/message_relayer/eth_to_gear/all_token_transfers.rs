@@ -14,10 +14,11 @@ use crate::message_relayer::common::{
     gear::{
         block_listener::BlockListener as GearBlockListener,
         checkpoints_extractor::CheckpointsExtractor, message_sender::MessageSender,
+        signer::GearSigner,
     },
 };
 
-use super::api_provider::ApiProviderConnection;
+use super::{api_provider::ApiProviderConnection, journal::Journal};
 
 pub struct Relayer {
     gear_block_listener: GearBlockListener,
@@ -43,7 +44,7 @@ impl MeteredService for Relayer {
 impl Relayer {
     #[allow(clippy::too_many_arguments)]
     pub async fn new(
-        suri: String,
+        signer: GearSigner,
         eth_api: EthApi,
         beacon_client: BeaconClient,
         erc20_manager_address: H160,
@@ -51,6 +52,7 @@ impl Relayer {
         historical_proxy_address: H256,
         vft_manager_address: H256,
         api_provider: ApiProviderConnection,
+        journal: Journal,
     ) -> anyhow::Result<Self> {
         let from_gear_block = {
             let gear_api = api_provider.client();
@@ -60,13 +62,17 @@ impl Relayer {
         };
         let gear_block_listener = GearBlockListener::new(api_provider.clone(), from_gear_block);
 
-        let from_eth_block = eth_api.finalized_block_number().await?;
+        let from_eth_block = match journal.last_processed_ethereum_block()? {
+            Some(block) => block.0 + 1,
+            None => eth_api.finalized_block_number().await?,
+        };
         let ethereum_block_listener = EthereumBlockListener::new(eth_api.clone(), from_eth_block);
 
         let deposit_event_extractor = DepositEventExtractor::new(
             eth_api.clone(),
             beacon_client.clone(),
             erc20_manager_address,
+            journal.clone(),
         );
 
         let checkpoints_extractor =
@@ -77,13 +83,14 @@ impl Relayer {
 
         let gear_message_sender = MessageSender::new(
             api_provider.clone(),
-            suri,
+            signer,
             eth_api,
             beacon_client,
             historical_proxy_address,
             vft_manager_address,
             route,
             true,
+            journal,
         );
 
         Ok(Self {
@@ -64,6 +64,10 @@ impl ApiProviderConnection {
             .map_err(|e| anyhow::anyhow!("failed to set suri: {}", e))
     }
 
+    pub fn api(&self) -> Api {
+        self.api.clone()
+    }
+
     pub fn client(&self) -> GearApi {
         GearApi::from(self.api.clone())
     }
@@ -0,0 +1,119 @@
+use primitive_types::H256;
+use sails_rs::calls::ActionIo;
+use std::iter;
+
+use ethereum_beacon_client::BeaconClient;
+use ethereum_client::EthApi;
+use utils_prometheus::MeteredService;
+
+use crate::message_relayer::common::{
+    ethereum::{
+        block_listener::BlockListener as EthereumBlockListener,
+        message_processed_event_extractor::MessageProcessedEventExtractor,
+    },
+    gear::{
+        block_listener::BlockListener as GearBlockListener,
+        checkpoints_extractor::CheckpointsExtractor, message_sender::MessageSender,
+        signer::GearSigner,
+    },
+};
+
+use super::{api_provider::ApiProviderConnection, journal::Journal};
+
+/// Relayer of the `MessageQueue` transactions that delivered paid messages to Ethereum.
+/// Their receipts are submitted to the `bridging-payment` program so the fees can be claimed.
+pub struct Relayer {
+    gear_block_listener: GearBlockListener,
+    ethereum_block_listener: EthereumBlockListener,
+
+    message_processed_event_extractor: MessageProcessedEventExtractor,
+    checkpoints_extractor: CheckpointsExtractor,
+
+    gear_message_sender: MessageSender,
+}
+
+impl MeteredService for Relayer {
+    fn get_sources(&self) -> impl IntoIterator<Item = Box<dyn prometheus::core::Collector>> {
+        iter::empty()
+            .chain(self.gear_block_listener.get_sources())
+            .chain(self.ethereum_block_listener.get_sources())
+            .chain(self.message_processed_event_extractor.get_sources())
+            .chain(self.checkpoints_extractor.get_sources())
+            .chain(self.gear_message_sender.get_sources())
+    }
+}
+
+impl Relayer {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new(
+        signer: GearSigner,
+        eth_api: EthApi,
+        beacon_client: BeaconClient,
+        bridging_payment_address: H256,
+        checkpoint_light_client_address: H256,
+        historical_proxy_address: H256,
+        api_provider: ApiProviderConnection,
+        journal: Journal,
+    ) -> anyhow::Result<Self> {
+        let from_gear_block = {
+            let gear_api = api_provider.client();
+            let from_gear_block = gear_api.latest_finalized_block().await?;
+
+            gear_api.block_hash_to_number(from_gear_block).await?
+        };
+        let gear_block_listener = GearBlockListener::new(api_provider.clone(), from_gear_block);
+
+        let from_eth_block = match journal.last_processed_ethereum_block()? {
+            Some(block) => block.0 + 1,
+            None => eth_api.finalized_block_number().await?,
+        };
+        let ethereum_block_listener = EthereumBlockListener::new(eth_api.clone(), from_eth_block);
+
+        let message_processed_event_extractor = MessageProcessedEventExtractor::new(
+            eth_api.clone(),
+            beacon_client.clone(),
+            api_provider.clone(),
+            bridging_payment_address,
+            journal.clone(),
+        );
+
+        let checkpoints_extractor =
+            CheckpointsExtractor::new(api_provider.clone(), checkpoint_light_client_address);
+
+        let route = bridging_payment_client::bridging_payment::io::SubmitDeliveryProof::ROUTE;
+        let gear_message_sender = MessageSender::new(
+            api_provider.clone(),
+            signer,
+            eth_api,
+            beacon_client,
+            historical_proxy_address,
+            bridging_payment_address,
+            route.to_vec(),
+            false,
+            journal,
+        );
+
+        Ok(Self {
+            gear_block_listener,
+            ethereum_block_listener,
+
+            message_processed_event_extractor,
+            checkpoints_extractor,
+
+            gear_message_sender,
+        })
+    }
+
+    pub async fn run(self) {
+        let [gear_blocks] = self.gear_block_listener.run().await;
+        let ethereum_blocks = self.ethereum_block_listener.run().await;
+
+        let proofs = self
+            .message_processed_event_extractor
+            .run(ethereum_blocks)
+            .await;
+        let checkpoints = self.checkpoints_extractor.run(gear_blocks).await;
+
+        self.gear_message_sender.run(proofs, checkpoints).await;
+    }
+}
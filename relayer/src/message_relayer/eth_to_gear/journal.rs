@@ -0,0 +1,452 @@
+use std::path::Path;
+
+use ethereum_client::TxHash;
+use parity_scale_codec::{Decode, Encode};
+
+use crate::message_relayer::common::{EthereumBlockNumber, EthereumSlotNumber, TxHashWithSlot};
+
+/// Amount of the latest relayed or rejected deposits kept in the journal.
+const MAX_FINISHED_DEPOSITS: usize = 10_000;
+
+/// Tree of the arbitrary messages sent through the `VaraMessenger` contract.
+const TREE_MESSAGES: &[u8] = b"messages";
+const TREE_DELIVERY_PROOFS: &[u8] = b"delivery_proofs";
+
+const KEY_LAST_ETHEREUM_BLOCK: &[u8] = b"meta/last_ethereum_block";
+const KEY_INDEXED: &[u8] = b"meta/indexed";
+
+const PREFIX_DEPOSIT: &[u8] = b"deposit/";
+/// Index of the deposits that aren't relayed yet.
+const PREFIX_PENDING: &[u8] = b"pending/";
+/// Index of the relayed and rejected deposits ordered by slot.
+const PREFIX_FINISHED: &[u8] = b"finished/";
+
+#[derive(Clone, Copy, Debug, PartialEq, Encode, Decode)]
+pub enum DepositStatus {
+    /// Deposit is discovered but there's no checkpoint for its slot yet.
+    WaitingCheckpoint,
+    /// Deposit is being submitted to historical-proxy. It's not known whether the submission
+    /// has succeeded so it's submitted again after restart.
+    InFlight,
+    /// Deposit is successfully processed by the receiver program.
+    Relayed,
+    /// Deposit is considered invalid by the receiver program and dropped.
+    Rejected,
+    /// Deposit can't be processed by the receiver program at the moment (e.g. because of
+    /// the rate limits or pause) so it's submitted again later.
+    Deferred,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Encode, Decode)]
+pub struct DepositRecord {
+    pub slot_number: EthereumSlotNumber,
+    pub status: DepositStatus,
+}
+
+impl DepositRecord {
+    fn is_pending(&self) -> bool {
+        matches!(
+            self.status,
+            DepositStatus::WaitingCheckpoint | DepositStatus::InFlight | DepositStatus::Deferred
+        )
+    }
+}
+
+/// Durable state of the eth->gear token relayers.
+///
+/// It records the last processed Ethereum block, discovered deposit events and
+/// results of their submission to historical-proxy so deposits that weren't relayed
+/// before restart are replayed automatically. Only the latest `MAX_FINISHED_DEPOSITS`
+/// relayed or rejected deposits are kept.
+///
+/// Token deposits are stored in the default tree of the database while the arbitrary
+/// messages have their own tree, see [Journal::open_messages].
+#[derive(Clone)]
+pub struct Journal {
+    db: sled::Db,
+    tree: sled::Tree,
+}
+
+impl Journal {
+    pub fn open<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let db = sled::open(path)?;
+        let tree = sled::Tree::clone(&db);
+
+        Self::new(db, tree)
+    }
+
+    /// Journal of the messages sent through the `VaraMessenger` contract. They're tracked
+    /// apart from the token deposits so the journals don't interfere even if they share the path.
+    pub fn open_messages<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let db = sled::open(path)?;
+        let tree = db.open_tree(TREE_MESSAGES)?;
+
+        Self::new(db, tree)
+    }
+
+    /// Journal of the `MessageQueue` transactions which receipts prove delivery
+    /// of the paid messages to the `bridging-payment` program.
+    pub fn open_delivery_proofs<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let db = sled::open(path)?;
+        let tree = db.open_tree(TREE_DELIVERY_PROOFS)?;
+
+        Self::new(db, tree)
+    }
+
+    /// Journal that lives only in memory. Used when resuming after restart isn't required.
+    pub fn temporary() -> anyhow::Result<Self> {
+        let db = sled::Config::new().temporary(true).open()?;
+        let tree = sled::Tree::clone(&db);
+
+        Self::new(db, tree)
+    }
+
+    fn new(db: sled::Db, tree: sled::Tree) -> anyhow::Result<Self> {
+        let journal = Self { db, tree };
+        journal.build_index()?;
+
+        Ok(journal)
+    }
+
+    pub fn last_processed_ethereum_block(&self) -> anyhow::Result<Option<EthereumBlockNumber>> {
+        self.tree
+            .get(KEY_LAST_ETHEREUM_BLOCK)?
+            .map(|value| {
+                let bytes = <[u8; 8]>::try_from(&value[..])
+                    .map_err(|_| anyhow::anyhow!("Malformed block number in the journal"))?;
+
+                Ok(EthereumBlockNumber(u64::from_be_bytes(bytes)))
+            })
+            .transpose()
+    }
+
+    /// Atomically stores deposits found in the Ethereum block and marks the block as processed.
+    /// Returns the deposits that weren't present in the journal before.
+    ///
+    /// The oldest finished deposits are pruned afterwards.
+    pub async fn add_deposits(
+        &self,
+        block: EthereumBlockNumber,
+        deposits: Vec<TxHashWithSlot>,
+    ) -> anyhow::Result<Vec<TxHashWithSlot>> {
+        let mut batch = sled::Batch::default();
+        let mut deposits_new = Vec::with_capacity(deposits.len());
+        for deposit in deposits {
+            let key = deposit_key(&deposit.tx_hash);
+            if self.tree.contains_key(&key)? {
+                log::warn!("Deposit {} is already in the journal", deposit.tx_hash);
+
+                continue;
+            }
+
+            let record = DepositRecord {
+                slot_number: deposit.slot_number,
+                status: DepositStatus::WaitingCheckpoint,
+            };
+            batch.insert(key, record.encode());
+            batch.insert(pending_key(&deposit.tx_hash), &[][..]);
+            deposits_new.push(deposit);
+        }
+
+        batch.insert(KEY_LAST_ETHEREUM_BLOCK, &block.0.to_be_bytes()[..]);
+
+        self.tree.apply_batch(batch)?;
+        self.prune_finished()?;
+        self.db.flush_async().await?;
+
+        Ok(deposits_new)
+    }
+
+    pub fn deposit(&self, tx_hash: &TxHash) -> anyhow::Result<Option<DepositRecord>> {
+        Ok(self
+            .tree
+            .get(deposit_key(tx_hash))?
+            .map(|value| DepositRecord::decode(&mut &value[..]))
+            .transpose()?)
+    }
+
+    pub fn deposits(&self) -> anyhow::Result<Vec<(TxHash, DepositRecord)>> {
+        self.tree
+            .scan_prefix(PREFIX_DEPOSIT)
+            .map(|entry| {
+                let (key, value) = entry?;
+                let tx_hash = TxHash::try_from(&key[PREFIX_DEPOSIT.len()..])
+                    .map_err(|_| anyhow::anyhow!("Malformed deposit key in the journal"))?;
+
+                Ok((tx_hash, DepositRecord::decode(&mut &value[..])?))
+            })
+            .collect()
+    }
+
+    /// Deposits that should be fed into the pipeline again since they weren't relayed
+    /// before restart.
+    pub fn deposits_to_relay(&self) -> anyhow::Result<Vec<TxHashWithSlot>> {
+        self.tree
+            .scan_prefix(PREFIX_PENDING)
+            .map(|entry| {
+                let (key, _) = entry?;
+                let tx_hash = TxHash::try_from(&key[PREFIX_PENDING.len()..])
+                    .map_err(|_| anyhow::anyhow!("Malformed pending deposit key in the journal"))?;
+                let record = self
+                    .deposit(&tx_hash)?
+                    .ok_or_else(|| anyhow::anyhow!("Pending deposit {tx_hash} is missing"))?;
+
+                Ok(TxHashWithSlot {
+                    slot_number: record.slot_number,
+                    tx_hash,
+                })
+            })
+            .collect()
+    }
+
+    pub async fn set_status(
+        &self,
+        deposit: &TxHashWithSlot,
+        status: DepositStatus,
+    ) -> anyhow::Result<()> {
+        let record = DepositRecord {
+            slot_number: deposit.slot_number,
+            status,
+        };
+
+        let mut batch = sled::Batch::default();
+        batch.insert(deposit_key(&deposit.tx_hash), record.encode());
+        index(&mut batch, &deposit.tx_hash, &record);
+
+        self.tree.apply_batch(batch)?;
+        self.db.flush_async().await?;
+
+        Ok(())
+    }
+
+    /// Removes the oldest finished deposits so only `MAX_FINISHED_DEPOSITS` are kept.
+    fn prune_finished(&self) -> anyhow::Result<()> {
+        // Keys are ordered by slot so the oldest deposits go first.
+        let finished = self.tree.scan_prefix(PREFIX_FINISHED).count();
+        for entry in self
+            .tree
+            .scan_prefix(PREFIX_FINISHED)
+            .take(finished.saturating_sub(MAX_FINISHED_DEPOSITS))
+        {
+            let (key, _) = entry?;
+            let tx_hash = TxHash::try_from(&key[PREFIX_FINISHED.len() + 8..])
+                .map_err(|_| anyhow::anyhow!("Malformed finished deposit key in the journal"))?;
+
+            let mut batch = sled::Batch::default();
+            batch.remove(key);
+            batch.remove(deposit_key(&tx_hash));
+            self.tree.apply_batch(batch)?;
+        }
+
+        Ok(())
+    }
+
+    /// Builds the deposit indices for the journals written before they were introduced.
+    fn build_index(&self) -> anyhow::Result<()> {
+        if self.tree.contains_key(KEY_INDEXED)? {
+            return Ok(());
+        }
+
+        let mut batch = sled::Batch::default();
+        for (tx_hash, record) in self.deposits()? {
+            index(&mut batch, &tx_hash, &record);
+        }
+        batch.insert(KEY_INDEXED, &[][..]);
+
+        self.tree.apply_batch(batch)?;
+        self.prune_finished()?;
+        self.db.flush()?;
+
+        Ok(())
+    }
+}
+
+fn index(batch: &mut sled::Batch, tx_hash: &TxHash, record: &DepositRecord) {
+    let key_finished = finished_key(record.slot_number, tx_hash);
+    match record.is_pending() {
+        true => {
+            batch.insert(pending_key(tx_hash), &[][..]);
+            batch.remove(key_finished);
+        }
+        false => {
+            batch.remove(pending_key(tx_hash));
+            batch.insert(key_finished, &[][..]);
+        }
+    }
+}
+
+fn deposit_key(tx_hash: &TxHash) -> Vec<u8> {
+    [PREFIX_DEPOSIT, &tx_hash.0[..]].concat()
+}
+
+fn pending_key(tx_hash: &TxHash) -> Vec<u8> {
+    [PREFIX_PENDING, &tx_hash.0[..]].concat()
+}
+
+fn finished_key(slot_number: EthereumSlotNumber, tx_hash: &TxHash) -> Vec<u8> {
+    [
+        PREFIX_FINISHED,
+        &slot_number.0.to_be_bytes()[..],
+        &tx_hash.0[..],
+    ]
+    .concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deposit(byte: u8, slot: u64) -> TxHashWithSlot {
+        TxHashWithSlot {
+            slot_number: EthereumSlotNumber(slot),
+            tx_hash: TxHash::from([byte; 32]),
+        }
+    }
+
+    #[tokio::test]
+    async fn deposits_lifecycle() {
+        let journal = Journal::temporary().unwrap();
+        assert!(journal.last_processed_ethereum_block().unwrap().is_none());
+
+        let added = journal
+            .add_deposits(
+                EthereumBlockNumber(100),
+                vec![deposit(1, 10), deposit(2, 10)],
+            )
+            .await
+            .unwrap();
+        assert_eq!(added.len(), 2);
+
+        let added = journal
+            .add_deposits(
+                EthereumBlockNumber(101),
+                vec![deposit(2, 10), deposit(3, 11)],
+            )
+            .await
+            .unwrap();
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].tx_hash, deposit(3, 11).tx_hash);
+        assert_eq!(
+            journal.last_processed_ethereum_block().unwrap(),
+            Some(EthereumBlockNumber(101))
+        );
+
+        journal
+            .set_status(&deposit(1, 10), DepositStatus::Relayed)
+            .await
+            .unwrap();
+        journal
+            .set_status(&deposit(2, 10), DepositStatus::InFlight)
+            .await
+            .unwrap();
+        journal
+            .set_status(&deposit(3, 11), DepositStatus::Rejected)
+            .await
+            .unwrap();
+
+        let to_relay = journal.deposits_to_relay().unwrap();
+        assert_eq!(to_relay.len(), 1);
+        assert_eq!(to_relay[0].tx_hash, deposit(2, 10).tx_hash);
+        assert_eq!(to_relay[0].slot_number, EthereumSlotNumber(10));
+
+        assert_eq!(
+            journal.deposit(&deposit(1, 10).tx_hash).unwrap(),
+            Some(DepositRecord {
+                slot_number: EthereumSlotNumber(10),
+                status: DepositStatus::Relayed,
+            })
+        );
+
+        // deferred deposit is submitted again
+        journal
+            .set_status(&deposit(2, 10), DepositStatus::Deferred)
+            .await
+            .unwrap();
+        assert_eq!(journal.deposits_to_relay().unwrap().len(), 1);
+
+        // deposit relayed again after restart
+        journal
+            .set_status(&deposit(2, 10), DepositStatus::Relayed)
+            .await
+            .unwrap();
+        assert!(journal.deposits_to_relay().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn finished_deposits_are_pruned() {
+        let journal = Journal::temporary().unwrap();
+
+        let count = MAX_FINISHED_DEPOSITS as u64 + 2;
+        let deposits = (0..count)
+            .map(|slot| TxHashWithSlot {
+                slot_number: EthereumSlotNumber(slot),
+                tx_hash: TxHash::left_padding_from(&slot.to_be_bytes()),
+            })
+            .collect::<Vec<_>>();
+        journal
+            .add_deposits(EthereumBlockNumber(1), deposits.clone())
+            .await
+            .unwrap();
+        assert_eq!(journal.deposits_to_relay().unwrap().len(), count as usize);
+
+        // relay the deposits starting from the most recent ones
+        for deposit in deposits.iter().rev().take(count as usize - 1) {
+            journal
+                .set_status(deposit, DepositStatus::Relayed)
+                .await
+                .unwrap();
+        }
+
+        // finished deposits are pruned when the next block is processed
+        journal
+            .add_deposits(EthereumBlockNumber(2), vec![])
+            .await
+            .unwrap();
+
+        // the oldest pending deposit is kept while the oldest finished one is pruned
+        assert_eq!(journal.deposits_to_relay().unwrap().len(), 1);
+        assert_eq!(journal.deposits().unwrap().len(), MAX_FINISHED_DEPOSITS + 1);
+        assert!(journal.deposit(&deposits[0].tx_hash).unwrap().is_some());
+        assert!(journal.deposit(&deposits[1].tx_hash).unwrap().is_none());
+        assert!(journal.deposit(&deposits[2].tx_hash).unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn messages_are_tracked_apart_from_deposits() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let deposits = Journal::new(db.clone(), sled::Tree::clone(&db)).unwrap();
+        let messages = Journal::new(db.clone(), db.open_tree(TREE_MESSAGES).unwrap()).unwrap();
+
+        deposits
+            .add_deposits(EthereumBlockNumber(100), vec![deposit(1, 10)])
+            .await
+            .unwrap();
+        assert!(messages.last_processed_ethereum_block().unwrap().is_none());
+        assert!(messages.deposits_to_relay().unwrap().is_empty());
+
+        let added = messages
+            .add_deposits(EthereumBlockNumber(90), vec![deposit(1, 9)])
+            .await
+            .unwrap();
+        assert_eq!(added.len(), 1);
+
+        assert_eq!(
+            deposits.last_processed_ethereum_block().unwrap(),
+            Some(EthereumBlockNumber(100))
+        );
+        assert_eq!(
+            messages.deposit(&deposit(1, 9).tx_hash).unwrap(),
+            Some(DepositRecord {
+                slot_number: EthereumSlotNumber(9),
+                status: DepositStatus::WaitingCheckpoint,
+            })
+        );
+        assert_eq!(
+            deposits.deposit(&deposit(1, 10).tx_hash).unwrap(),
+            Some(DepositRecord {
+                slot_number: EthereumSlotNumber(10),
+                status: DepositStatus::WaitingCheckpoint,
+            })
+        );
+    }
+}
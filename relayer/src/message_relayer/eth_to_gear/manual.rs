@@ -8,16 +8,17 @@ use crate::message_relayer::common::{
     gear::{
         block_listener::BlockListener as GearBlockListener,
         checkpoints_extractor::CheckpointsExtractor, message_sender::MessageSender,
+        signer::GearSigner,
     },
     EthereumSlotNumber, TxHashWithSlot,
 };
 
-use super::api_provider::ApiProviderConnection;
+use super::{api_provider::ApiProviderConnection, journal::Journal};
 
 #[allow(clippy::too_many_arguments)]
 pub async fn relay(
     api_provider: ApiProviderConnection,
-    gear_suri: String,
+    gear_signer: GearSigner,
 
     eth_api: EthApi,
     beacon_client: BeaconClient,
@@ -30,7 +31,7 @@ pub async fn relay(
 
     tx_hash: TxHash,
     slot: u64,
-) {
+) -> anyhow::Result<()> {
     let gear_api = api_provider.client();
 
     let from_gear_block = gear_api
@@ -48,29 +49,32 @@ pub async fn relay(
     let checkpoints_extractor =
         CheckpointsExtractor::new(api_provider.clone(), checkpoint_light_client_address);
 
+    let journal = Journal::temporary()?;
+
     let gear_message_sender = MessageSender::new(
         api_provider,
-        gear_suri,
+        gear_signer,
         eth_api,
         beacon_client,
         historical_proxy_address,
         receiver_address,
         receiver_route,
         true,
+        journal,
     );
 
     let [gear_blocks] = gear_block_listener.run().await;
     let (deposit_events_sender, deposit_events_receiver) = unbounded_channel();
 
-    deposit_events_sender
-        .send(TxHashWithSlot {
-            tx_hash,
-            slot_number: EthereumSlotNumber(slot),
-        })
-        .expect("Failed to send message to channel");
+    deposit_events_sender.send(TxHashWithSlot {
+        tx_hash,
+        slot_number: EthereumSlotNumber(slot),
+    })?;
 
     let checkpoints = checkpoints_extractor.run(gear_blocks).await;
     gear_message_sender
         .run(deposit_events_receiver, checkpoints)
         .await;
+
+    Ok(())
 }
@@ -0,0 +1,117 @@
+use primitive_types::{H160, H256};
+use std::iter;
+
+use ethereum_beacon_client::BeaconClient;
+use ethereum_client::EthApi;
+use utils_prometheus::MeteredService;
+
+use crate::message_relayer::common::{
+    ethereum::{
+        block_listener::BlockListener as EthereumBlockListener,
+        vara_message_event_extractor::VaraMessageEventExtractor,
+    },
+    gear::{
+        block_listener::BlockListener as GearBlockListener,
+        checkpoints_extractor::CheckpointsExtractor, message_sender::MessageSender,
+        signer::GearSigner,
+    },
+};
+
+use super::{api_provider::ApiProviderConnection, journal::Journal};
+
+/// Relayer of the arbitrary messages sent through the `VaraMessenger` contract
+/// to the specified program on gear.
+pub struct Relayer {
+    gear_block_listener: GearBlockListener,
+    ethereum_block_listener: EthereumBlockListener,
+
+    message_event_extractor: VaraMessageEventExtractor,
+    checkpoints_extractor: CheckpointsExtractor,
+
+    gear_message_sender: MessageSender,
+}
+
+impl MeteredService for Relayer {
+    fn get_sources(&self) -> impl IntoIterator<Item = Box<dyn prometheus::core::Collector>> {
+        iter::empty()
+            .chain(self.gear_block_listener.get_sources())
+            .chain(self.ethereum_block_listener.get_sources())
+            .chain(self.message_event_extractor.get_sources())
+            .chain(self.checkpoints_extractor.get_sources())
+            .chain(self.gear_message_sender.get_sources())
+    }
+}
+
+impl Relayer {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new(
+        signer: GearSigner,
+        eth_api: EthApi,
+        beacon_client: BeaconClient,
+        vara_messenger_address: H160,
+        checkpoint_light_client_address: H256,
+        historical_proxy_address: H256,
+        receiver_address: H256,
+        receiver_route: Vec<u8>,
+        api_provider: ApiProviderConnection,
+        journal: Journal,
+    ) -> anyhow::Result<Self> {
+        let from_gear_block = {
+            let gear_api = api_provider.client();
+            let from_gear_block = gear_api.latest_finalized_block().await?;
+
+            gear_api.block_hash_to_number(from_gear_block).await?
+        };
+        let gear_block_listener = GearBlockListener::new(api_provider.clone(), from_gear_block);
+
+        let from_eth_block = match journal.last_processed_ethereum_block()? {
+            Some(block) => block.0 + 1,
+            None => eth_api.finalized_block_number().await?,
+        };
+        let ethereum_block_listener = EthereumBlockListener::new(eth_api.clone(), from_eth_block);
+
+        let message_event_extractor = VaraMessageEventExtractor::new(
+            eth_api.clone(),
+            beacon_client.clone(),
+            vara_messenger_address,
+            Some(receiver_address),
+            journal.clone(),
+        );
+
+        let checkpoints_extractor =
+            CheckpointsExtractor::new(api_provider.clone(), checkpoint_light_client_address);
+
+        // Reply format of the receiver is unknown so it isn't decoded.
+        let gear_message_sender = MessageSender::new(
+            api_provider.clone(),
+            signer,
+            eth_api,
+            beacon_client,
+            historical_proxy_address,
+            receiver_address,
+            receiver_route,
+            false,
+            journal,
+        );
+
+        Ok(Self {
+            gear_block_listener,
+            ethereum_block_listener,
+
+            message_event_extractor,
+            checkpoints_extractor,
+
+            gear_message_sender,
+        })
+    }
+
+    pub async fn run(self) {
+        let [gear_blocks] = self.gear_block_listener.run().await;
+        let ethereum_blocks = self.ethereum_block_listener.run().await;
+
+        let messages = self.message_event_extractor.run(ethereum_blocks).await;
+        let checkpoints = self.checkpoints_extractor.run(gear_blocks).await;
+
+        self.gear_message_sender.run(messages, checkpoints).await;
+    }
+}
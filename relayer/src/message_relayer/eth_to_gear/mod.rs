@@ -1,4 +1,7 @@
 pub mod all_token_transfers;
 pub mod api_provider;
+pub mod delivery_proofs;
+pub mod journal;
 pub mod manual;
+pub mod messages;
 pub mod paid_token_transfers;
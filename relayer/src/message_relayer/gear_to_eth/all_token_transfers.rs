@@ -6,8 +6,11 @@ use utils_prometheus::MeteredService;
 use crate::message_relayer::{
     common::{
         ethereum::{
-            accumulator::Accumulator, block_listener::BlockListener as EthereumBlockListener,
-            merkle_root_extractor::MerkleRootExtractor, message_sender::MessageSender,
+            accumulator::Accumulator,
+            batcher::{BatchConfig, Batcher},
+            block_listener::BlockListener as EthereumBlockListener,
+            merkle_root_extractor::MerkleRootExtractor,
+            message_sender::MessageSender,
         },
         gear::{
             block_listener::BlockListener as GearBlockListener,
@@ -15,6 +18,7 @@ use crate::message_relayer::{
         },
     },
     eth_to_gear::api_provider::ApiProviderConnection,
+    gear_to_eth::journal::Journal,
 };
 
 pub struct Relayer {
@@ -24,6 +28,7 @@ pub struct Relayer {
     message_sent_listener: MessageQueuedEventExtractor,
 
     merkle_root_extractor: MerkleRootExtractor,
+    batcher: Batcher,
     message_sender: MessageSender,
 }
 
@@ -34,6 +39,7 @@ impl MeteredService for Relayer {
             .chain(self.ethereum_block_listener.get_sources())
             .chain(self.message_sent_listener.get_sources())
             .chain(self.merkle_root_extractor.get_sources())
+            .chain(self.batcher.get_sources())
             .chain(self.message_sender.get_sources())
     }
 }
@@ -43,26 +49,37 @@ impl Relayer {
         eth_api: EthApi,
         from_block: Option<u32>,
         api_provider: ApiProviderConnection,
+        journal: Journal,
+        batch_config: BatchConfig,
     ) -> anyhow::Result<Self> {
         let from_gear_block = if let Some(block) = from_block {
             block
+        } else if let Some(block) = journal.last_processed_gear_block()? {
+            block.0 + 1
         } else {
             let gear_api = api_provider.client();
             let block = gear_api.latest_finalized_block().await?;
             gear_api.block_hash_to_number(block).await?
         };
 
-        let from_eth_block = eth_api.finalized_block_number().await?;
+        let from_eth_block = match journal.last_processed_ethereum_block()? {
+            Some(block) => block.0 + 1,
+            None => eth_api.finalized_block_number().await?,
+        };
 
         let gear_block_listener = GearBlockListener::new(api_provider.clone(), from_gear_block);
 
         let ethereum_block_listener = EthereumBlockListener::new(eth_api.clone(), from_eth_block);
 
-        let message_sent_listener = MessageQueuedEventExtractor::new(api_provider.clone());
+        let message_sent_listener =
+            MessageQueuedEventExtractor::new(api_provider.clone(), journal.clone());
+
+        let merkle_root_listener =
+            MerkleRootExtractor::new(eth_api.clone(), api_provider.clone(), journal.clone());
 
-        let merkle_root_listener = MerkleRootExtractor::new(eth_api.clone(), api_provider.clone());
+        let batcher = Batcher::new(batch_config);
 
-        let message_sender = MessageSender::new(eth_api, api_provider);
+        let message_sender = MessageSender::new(eth_api, api_provider, journal);
 
         Ok(Self {
             gear_block_listener,
@@ -71,6 +88,7 @@ impl Relayer {
             message_sent_listener,
 
             merkle_root_extractor: merkle_root_listener,
+            batcher,
             message_sender,
         })
     }
@@ -85,6 +103,8 @@ impl Relayer {
         let accumulator = Accumulator::new();
         let channel_messages = accumulator.run(messages, merkle_roots).await;
 
-        self.message_sender.run(channel_messages).await;
+        let batches = self.batcher.run(channel_messages).await;
+
+        self.message_sender.run(batches).await;
     }
 }
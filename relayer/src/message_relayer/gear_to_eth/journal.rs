@@ -0,0 +1,585 @@
+use std::path::Path;
+
+use ethereum_client::TxHash;
+use parity_scale_codec::{Decode, Encode};
+
+use crate::message_relayer::common::{
+    EthereumBlockNumber, GearBlockNumber, MessageInBlock, PaidMessage, RelayedMerkleRoot,
+};
+
+/// Amount of the latest relayed merkle roots kept in the journal. Matches the capacity
+/// of the merkle roots storage in `Accumulator`.
+const MAX_STORED_MERKLE_ROOTS: usize = 100;
+
+/// Amount of the failed submissions after which the message is removed from the journal.
+pub const MAX_SUBMISSION_FAILURES: u32 = 3;
+
+const KEY_LAST_QUEUED_MESSAGES_BLOCK: &[u8] = b"meta/last_queued_messages_block";
+const KEY_LAST_PAID_MESSAGES_BLOCK: &[u8] = b"meta/last_paid_messages_block";
+const KEY_LAST_ETHEREUM_BLOCK: &[u8] = b"meta/last_ethereum_block";
+
+const PREFIX_MESSAGE: &[u8] = b"message/";
+const PREFIX_PAID_NONCE: &[u8] = b"paid/";
+const PREFIX_MERKLE_ROOT: &[u8] = b"root/";
+
+#[derive(Clone, Copy, Debug, PartialEq, Encode, Decode)]
+pub enum SubmissionStatus {
+    Pending,
+    Failed,
+}
+
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
+pub struct Submission {
+    /// Transaction relaying the message and the ones that replaced it. All of them have
+    /// the same nonce so at most one gets included.
+    pub tx_hashes: Vec<[u8; 32]>,
+    pub status: SubmissionStatus,
+    /// Merkle root the message is proven against.
+    pub merkle_root: RelayedMerkleRoot,
+    /// Amount of the failed submissions of the message.
+    pub failures: u32,
+}
+
+impl Submission {
+    pub fn tx_hashes(&self) -> Vec<TxHash> {
+        self.tx_hashes.iter().copied().map(TxHash::from).collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
+pub struct MessageRecord {
+    pub message: MessageInBlock,
+    pub submission: Option<Submission>,
+}
+
+/// Durable state of the gear->eth token relayers.
+///
+/// It records the last processed Gear and Ethereum blocks, messages that are not relayed yet,
+/// the latest relayed merkle roots and the transactions submitted to Ethereum so the pipeline
+/// is able to resume exactly where it stopped. A message is removed from the journal when
+/// the transaction relaying it gets finalized or after `MAX_SUBMISSION_FAILURES` failed
+/// submissions. The paid relayer also removes the messages that aren't paid in time.
+#[derive(Clone)]
+pub struct Journal {
+    db: sled::Db,
+}
+
+impl Journal {
+    pub fn open<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        Ok(Self {
+            db: sled::open(path)?,
+        })
+    }
+
+    /// Journal that lives only in memory. Used when resuming after restart isn't required.
+    pub fn temporary() -> anyhow::Result<Self> {
+        Ok(Self {
+            db: sled::Config::new().temporary(true).open()?,
+        })
+    }
+
+    /// The latest Gear block that was completely processed by all the event extractors.
+    pub fn last_processed_gear_block(&self) -> anyhow::Result<Option<GearBlockNumber>> {
+        let queued = self.get_u32(KEY_LAST_QUEUED_MESSAGES_BLOCK)?;
+        let paid = self.get_u32(KEY_LAST_PAID_MESSAGES_BLOCK)?;
+
+        let block = match (queued, paid) {
+            (Some(queued), Some(paid)) => Some(queued.min(paid)),
+            (block, None) | (None, block) => block,
+        };
+
+        Ok(block.map(GearBlockNumber))
+    }
+
+    pub fn last_processed_ethereum_block(&self) -> anyhow::Result<Option<EthereumBlockNumber>> {
+        Ok(self
+            .db
+            .get(KEY_LAST_ETHEREUM_BLOCK)?
+            .map(|value| decode_u64(&value))
+            .transpose()?
+            .map(EthereumBlockNumber))
+    }
+
+    /// Atomically stores queued messages found in the block and marks the block as processed.
+    /// Returns the messages that weren't present in the journal before.
+    pub async fn add_queued_messages(
+        &self,
+        block: GearBlockNumber,
+        messages: Vec<MessageInBlock>,
+    ) -> anyhow::Result<Vec<MessageInBlock>> {
+        let mut batch = sled::Batch::default();
+        let mut messages_new = Vec::with_capacity(messages.len());
+        for message in messages {
+            let key = message_key(&message.message.nonce_le);
+            if self.db.contains_key(&key)? {
+                log::warn!(
+                    "Message with nonce {} is already in the journal",
+                    hex::encode(message.message.nonce_le)
+                );
+
+                continue;
+            }
+
+            let record = MessageRecord {
+                message: message.clone(),
+                submission: None,
+            };
+            batch.insert(key, record.encode());
+            messages_new.push(message);
+        }
+
+        batch.insert(KEY_LAST_QUEUED_MESSAGES_BLOCK, &block.0.to_be_bytes()[..]);
+
+        self.db.apply_batch(batch)?;
+        self.db.flush_async().await?;
+
+        Ok(messages_new)
+    }
+
+    /// Atomically stores paid nonces found in the block and marks the block as processed.
+    /// Returns the paid messages that weren't present in the journal before.
+    pub async fn add_paid_messages(
+        &self,
+        block: GearBlockNumber,
+        paid_messages: Vec<PaidMessage>,
+    ) -> anyhow::Result<Vec<PaidMessage>> {
+        let mut batch = sled::Batch::default();
+        let mut paid_messages_new = Vec::with_capacity(paid_messages.len());
+        for paid_message in paid_messages {
+            let key = paid_nonce_key(&paid_message.nonce);
+            if self.db.contains_key(&key)? {
+                continue;
+            }
+
+            batch.insert(key, paid_message.deadline.encode());
+            paid_messages_new.push(paid_message);
+        }
+
+        batch.insert(KEY_LAST_PAID_MESSAGES_BLOCK, &block.0.to_be_bytes()[..]);
+
+        self.db.apply_batch(batch)?;
+        self.db.flush_async().await?;
+
+        Ok(paid_messages_new)
+    }
+
+    /// Atomically stores merkle roots found in the Ethereum block and marks the block
+    /// as processed. Only the latest `MAX_STORED_MERKLE_ROOTS` roots are retained.
+    pub async fn add_merkle_roots(
+        &self,
+        block: EthereumBlockNumber,
+        merkle_roots: &[RelayedMerkleRoot],
+    ) -> anyhow::Result<()> {
+        let mut batch = sled::Batch::default();
+        for merkle_root in merkle_roots {
+            batch.insert(merkle_root_key(merkle_root), merkle_root.encode());
+        }
+
+        batch.insert(KEY_LAST_ETHEREUM_BLOCK, &block.0.to_be_bytes()[..]);
+
+        self.db.apply_batch(batch)?;
+
+        // Keys are ordered by (authority set id, block) so the oldest roots go first.
+        let stored = self.db.scan_prefix(PREFIX_MERKLE_ROOT).count();
+        for entry in self
+            .db
+            .scan_prefix(PREFIX_MERKLE_ROOT)
+            .take(stored.saturating_sub(MAX_STORED_MERKLE_ROOTS))
+        {
+            let (key, _) = entry?;
+            self.db.remove(key)?;
+        }
+
+        self.db.flush_async().await?;
+
+        Ok(())
+    }
+
+    pub fn merkle_roots(&self) -> anyhow::Result<Vec<RelayedMerkleRoot>> {
+        self.db
+            .scan_prefix(PREFIX_MERKLE_ROOT)
+            .map(|entry| {
+                let (_, value) = entry?;
+
+                Ok(RelayedMerkleRoot::decode(&mut &value[..])?)
+            })
+            .collect()
+    }
+
+    pub fn messages(&self) -> anyhow::Result<Vec<MessageRecord>> {
+        self.db
+            .scan_prefix(PREFIX_MESSAGE)
+            .map(|entry| {
+                let (_, value) = entry?;
+
+                Ok(MessageRecord::decode(&mut &value[..])?)
+            })
+            .collect()
+    }
+
+    /// Messages that should be fed into the pipeline again: they either haven't been submitted
+    /// to Ethereum or their submission has failed.
+    pub fn messages_to_relay(&self) -> anyhow::Result<Vec<MessageInBlock>> {
+        Ok(self
+            .messages()?
+            .into_iter()
+            .filter(|record| {
+                record
+                    .submission
+                    .as_ref()
+                    .map(|submission| submission.status == SubmissionStatus::Failed)
+                    .unwrap_or(true)
+            })
+            .map(|record| record.message)
+            .collect())
+    }
+
+    pub fn paid_messages(&self) -> anyhow::Result<Vec<PaidMessage>> {
+        self.db
+            .scan_prefix(PREFIX_PAID_NONCE)
+            .map(|entry| {
+                let (key, value) = entry?;
+                let nonce = key[PREFIX_PAID_NONCE.len()..]
+                    .try_into()
+                    .map_err(|_| anyhow::anyhow!("Malformed paid nonce key in the journal"))?;
+                // Journals written before payment deadlines were introduced have empty values.
+                let deadline = if value.is_empty() {
+                    None
+                } else {
+                    Option::<GearBlockNumber>::decode(&mut &value[..])?
+                };
+
+                Ok(PaidMessage { nonce, deadline })
+            })
+            .collect()
+    }
+
+    /// Messages submitted to Ethereum in transactions that aren't finalized yet.
+    pub fn pending_submissions(&self) -> anyhow::Result<Vec<(MessageInBlock, Submission)>> {
+        Ok(self
+            .messages()?
+            .into_iter()
+            .filter_map(|record| {
+                let submission = record.submission?;
+
+                (submission.status == SubmissionStatus::Pending)
+                    .then_some((record.message, submission))
+            })
+            .collect())
+    }
+
+    /// Records the transaction relaying the message against the `merkle_root`.
+    pub async fn set_submission(
+        &self,
+        nonce: &[u8; 32],
+        tx_hash: TxHash,
+        merkle_root: &RelayedMerkleRoot,
+    ) -> anyhow::Result<()> {
+        let key = message_key(nonce);
+        let Some(mut record) = self.record(&key)? else {
+            return Ok(());
+        };
+
+        record.submission = Some(Submission {
+            tx_hashes: vec![tx_hash.0],
+            status: SubmissionStatus::Pending,
+            merkle_root: *merkle_root,
+            failures: record
+                .submission
+                .map(|submission| submission.failures)
+                .unwrap_or(0),
+        });
+
+        self.db.insert(key, record.encode())?;
+        self.db.flush_async().await?;
+
+        Ok(())
+    }
+
+    /// Records the transaction replacing the pending one that relays the message.
+    pub async fn add_replacement(&self, nonce: &[u8; 32], tx_hash: TxHash) -> anyhow::Result<()> {
+        let key = message_key(nonce);
+        let Some(mut record) = self.record(&key)? else {
+            return Ok(());
+        };
+
+        let Some(submission) = record.submission.as_mut() else {
+            return Err(anyhow::anyhow!(
+                "Message with nonce {} isn't submitted",
+                hex::encode(nonce)
+            ));
+        };
+
+        submission.tx_hashes.push(tx_hash.0);
+
+        self.db.insert(key, record.encode())?;
+        self.db.flush_async().await?;
+
+        Ok(())
+    }
+
+    /// Marks the submission of the message as failed. The message is removed from the journal
+    /// after `MAX_SUBMISSION_FAILURES` failures. Returns whether the message should be
+    /// submitted again.
+    pub async fn fail_submission(&self, nonce: &[u8; 32]) -> anyhow::Result<bool> {
+        let key = message_key(nonce);
+        let Some(mut record) = self.record(&key)? else {
+            return Ok(false);
+        };
+
+        let Some(submission) = record.submission.as_mut() else {
+            return Ok(true);
+        };
+
+        submission.status = SubmissionStatus::Failed;
+        submission.failures += 1;
+        if submission.failures >= MAX_SUBMISSION_FAILURES {
+            self.remove_message(nonce).await?;
+
+            return Ok(false);
+        }
+
+        self.db.insert(key, record.encode())?;
+        self.db.flush_async().await?;
+
+        Ok(true)
+    }
+
+    /// Removes the message and its payment from the journal once it's relayed.
+    pub async fn remove_message(&self, nonce: &[u8; 32]) -> anyhow::Result<()> {
+        let mut batch = sled::Batch::default();
+        batch.remove(message_key(nonce));
+        batch.remove(paid_nonce_key(nonce));
+
+        self.db.apply_batch(batch)?;
+        self.db.flush_async().await?;
+
+        Ok(())
+    }
+
+    fn record(&self, key: &[u8]) -> anyhow::Result<Option<MessageRecord>> {
+        let Some(value) = self.db.get(key)? else {
+            log::warn!(
+                "Message with nonce {} is missing in the journal",
+                hex::encode(&key[PREFIX_MESSAGE.len()..])
+            );
+
+            return Ok(None);
+        };
+
+        Ok(Some(MessageRecord::decode(&mut &value[..])?))
+    }
+
+    fn get_u32(&self, key: &[u8]) -> anyhow::Result<Option<u32>> {
+        self.db
+            .get(key)?
+            .map(|value| {
+                let bytes = <[u8; 4]>::try_from(&value[..])
+                    .map_err(|_| anyhow::anyhow!("Malformed block number in the journal"))?;
+
+                Ok(u32::from_be_bytes(bytes))
+            })
+            .transpose()
+    }
+}
+
+fn decode_u64(value: &[u8]) -> anyhow::Result<u64> {
+    let bytes = value
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Malformed block number in the journal"))?;
+
+    Ok(u64::from_be_bytes(bytes))
+}
+
+fn message_key(nonce: &[u8; 32]) -> Vec<u8> {
+    [PREFIX_MESSAGE, &nonce[..]].concat()
+}
+
+fn paid_nonce_key(nonce: &[u8; 32]) -> Vec<u8> {
+    [PREFIX_PAID_NONCE, &nonce[..]].concat()
+}
+
+fn merkle_root_key(merkle_root: &RelayedMerkleRoot) -> Vec<u8> {
+    [
+        PREFIX_MERKLE_ROOT,
+        &merkle_root.authority_set_id.0.to_be_bytes()[..],
+        &merkle_root.block.0.to_be_bytes()[..],
+    ]
+    .concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message_relayer::common::AuthoritySetId;
+    use gear_rpc_client::dto::Message;
+    use primitive_types::H256;
+
+    fn message(nonce: u8, block: u32) -> MessageInBlock {
+        MessageInBlock {
+            message: Message {
+                nonce_le: [nonce; 32],
+                ..Default::default()
+            },
+            block: GearBlockNumber(block),
+            block_hash: H256::repeat_byte(nonce),
+            authority_set_id: AuthoritySetId(1),
+        }
+    }
+
+    fn merkle_root(authority_set_id: u64, block: u32) -> RelayedMerkleRoot {
+        RelayedMerkleRoot {
+            block: GearBlockNumber(block),
+            block_hash: H256::zero(),
+            authority_set_id: AuthoritySetId(authority_set_id),
+            merkle_root: H256::zero(),
+        }
+    }
+
+    #[tokio::test]
+    async fn messages_lifecycle() {
+        let journal = Journal::temporary().unwrap();
+        assert!(journal.last_processed_gear_block().unwrap().is_none());
+
+        let added = journal
+            .add_queued_messages(GearBlockNumber(10), vec![message(1, 10), message(2, 10)])
+            .await
+            .unwrap();
+        assert_eq!(added.len(), 2);
+
+        // the same message shouldn't be added twice
+        let added = journal
+            .add_queued_messages(GearBlockNumber(11), vec![message(1, 10)])
+            .await
+            .unwrap();
+        assert!(added.is_empty());
+        assert_eq!(
+            journal.last_processed_gear_block().unwrap(),
+            Some(GearBlockNumber(11))
+        );
+
+        journal
+            .add_paid_messages(
+                GearBlockNumber(9),
+                vec![
+                    PaidMessage {
+                        nonce: [1; 32],
+                        deadline: None,
+                    },
+                    PaidMessage {
+                        nonce: [2; 32],
+                        deadline: Some(GearBlockNumber(100)),
+                    },
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            journal.last_processed_gear_block().unwrap(),
+            Some(GearBlockNumber(9))
+        );
+
+        let tx_hash = TxHash::from([7; 32]);
+        journal
+            .set_submission(&[1; 32], tx_hash, &merkle_root(1, 12))
+            .await
+            .unwrap();
+
+        let to_relay = journal.messages_to_relay().unwrap();
+        assert_eq!(to_relay, vec![message(2, 10)]);
+
+        let tx_hash_replacement = TxHash::from([8; 32]);
+        journal
+            .add_replacement(&[1; 32], tx_hash_replacement)
+            .await
+            .unwrap();
+
+        let pending = journal.pending_submissions().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].0, message(1, 10));
+        assert_eq!(pending[0].1.tx_hashes(), vec![tx_hash, tx_hash_replacement]);
+        assert_eq!(pending[0].1.merkle_root, merkle_root(1, 12));
+
+        journal.remove_message(&[1; 32]).await.unwrap();
+        assert!(journal.pending_submissions().unwrap().is_empty());
+        let paid_messages = journal.paid_messages().unwrap();
+        assert_eq!(paid_messages.len(), 1);
+        assert_eq!(paid_messages[0].nonce, [2; 32]);
+        assert_eq!(paid_messages[0].deadline, Some(GearBlockNumber(100)));
+        assert_eq!(journal.messages().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_submissions_expire() {
+        let journal = Journal::temporary().unwrap();
+        journal
+            .add_queued_messages(GearBlockNumber(10), vec![message(1, 10)])
+            .await
+            .unwrap();
+        journal
+            .add_paid_messages(
+                GearBlockNumber(10),
+                vec![PaidMessage {
+                    nonce: [1; 32],
+                    deadline: None,
+                }],
+            )
+            .await
+            .unwrap();
+
+        for failure in 1..MAX_SUBMISSION_FAILURES {
+            journal
+                .set_submission(
+                    &[1; 32],
+                    TxHash::from([failure as u8; 32]),
+                    &merkle_root(1, 12),
+                )
+                .await
+                .unwrap();
+            assert!(journal.messages_to_relay().unwrap().is_empty());
+
+            assert!(journal.fail_submission(&[1; 32]).await.unwrap());
+
+            // failed message is relayed again after restart
+            assert_eq!(journal.messages_to_relay().unwrap(), vec![message(1, 10)]);
+            assert!(journal.pending_submissions().unwrap().is_empty());
+            let records = journal.messages().unwrap();
+            assert_eq!(records[0].submission.as_ref().unwrap().failures, failure);
+        }
+
+        journal
+            .set_submission(&[1; 32], TxHash::from([0; 32]), &merkle_root(1, 12))
+            .await
+            .unwrap();
+        assert!(!journal.fail_submission(&[1; 32]).await.unwrap());
+
+        assert!(journal.messages().unwrap().is_empty());
+        assert!(journal.paid_messages().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn merkle_roots_are_bounded() {
+        let journal = Journal::temporary().unwrap();
+
+        let roots = (0..MAX_STORED_MERKLE_ROOTS as u32 + 5)
+            .map(|block| merkle_root(2, 1_000 + block))
+            .chain([merkle_root(1, 5_000)])
+            .collect::<Vec<_>>();
+        journal
+            .add_merkle_roots(EthereumBlockNumber(42), &roots)
+            .await
+            .unwrap();
+
+        let stored = journal.merkle_roots().unwrap();
+        assert_eq!(stored.len(), MAX_STORED_MERKLE_ROOTS);
+        assert_eq!(stored.first(), Some(&merkle_root(2, 1_005)));
+        assert_eq!(
+            stored.last(),
+            Some(&merkle_root(2, 1_000 + MAX_STORED_MERKLE_ROOTS as u32 + 4))
+        );
+        assert_eq!(
+            journal.last_processed_ethereum_block().unwrap(),
+            Some(EthereumBlockNumber(42))
+        );
+    }
+}
@@ -6,12 +6,16 @@ use tokio::sync::mpsc::{self, UnboundedSender};
 use crate::message_relayer::{
     common::{
         ethereum::{
-            accumulator::Accumulator, block_listener::BlockListener as EthereumBlockListener,
-            merkle_root_extractor::MerkleRootExtractor, message_sender::MessageSender,
+            accumulator::Accumulator,
+            batcher::{BatchConfig, Batcher},
+            block_listener::BlockListener as EthereumBlockListener,
+            merkle_root_extractor::MerkleRootExtractor,
+            message_sender::MessageSender,
         },
         AuthoritySetId, GearBlockNumber, MessageInBlock,
     },
     eth_to_gear::api_provider::ApiProviderConnection,
+    gear_to_eth::journal::Journal,
 };
 
 pub async fn relay(
@@ -20,7 +24,7 @@ pub async fn relay(
     message_nonce: U256,
     gear_block: u32,
     from_eth_block: Option<u64>,
-) -> UnboundedSender<MessageInBlock> {
+) -> anyhow::Result<UnboundedSender<MessageInBlock>> {
     let from_eth_block = if let Some(block) = from_eth_block {
         block
     } else {
@@ -66,9 +70,15 @@ pub async fn relay(
 
     let (queued_messages_sender, queued_messages_receiver) = mpsc::unbounded_channel();
 
+    let journal = Journal::temporary()?;
+    journal
+        .add_queued_messages(message_in_block.block, vec![message_in_block.clone()])
+        .await?;
+
     let ethereum_block_listener = EthereumBlockListener::new(eth_api.clone(), from_eth_block);
-    let merkle_root_extractor = MerkleRootExtractor::new(eth_api.clone(), api_provider.clone());
-    let message_sender = MessageSender::new(eth_api, api_provider);
+    let merkle_root_extractor =
+        MerkleRootExtractor::new(eth_api.clone(), api_provider.clone(), journal.clone());
+    let message_sender = MessageSender::new(eth_api, api_provider, journal);
 
     let ethereum_blocks = ethereum_block_listener.run().await;
     let merkle_roots = merkle_root_extractor.run(ethereum_blocks).await;
@@ -76,11 +86,11 @@ pub async fn relay(
     let channel_messages = accumulator
         .run(queued_messages_receiver, merkle_roots)
         .await;
-    message_sender.run(channel_messages).await;
+    let batcher = Batcher::new(BatchConfig::default());
+    let batches = batcher.run(channel_messages).await;
+    message_sender.run(batches).await;
 
-    queued_messages_sender
-        .send(message_in_block)
-        .expect("Failed to send message to channel");
+    queued_messages_sender.send(message_in_block)?;
 
-    queued_messages_sender
+    Ok(queued_messages_sender)
 }
@@ -1,3 +1,4 @@
 pub mod all_token_transfers;
+pub mod journal;
 pub mod manual;
 pub mod paid_token_transfers;
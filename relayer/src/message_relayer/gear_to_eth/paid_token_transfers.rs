@@ -7,8 +7,11 @@ use utils_prometheus::MeteredService;
 use crate::message_relayer::{
     common::{
         ethereum::{
-            accumulator::Accumulator, block_listener::BlockListener as EthereumBlockListener,
-            merkle_root_extractor::MerkleRootExtractor, message_sender::MessageSender,
+            accumulator::Accumulator,
+            batcher::{BatchConfig, Batcher},
+            block_listener::BlockListener as EthereumBlockListener,
+            merkle_root_extractor::MerkleRootExtractor,
+            message_sender::MessageSender,
         },
         gear::{
             block_listener::BlockListener as GearBlockListener,
@@ -18,6 +21,7 @@ use crate::message_relayer::{
         paid_messages_filter::PaidMessagesFilter,
     },
     eth_to_gear::api_provider::ApiProviderConnection,
+    gear_to_eth::journal::Journal,
 };
 
 pub struct Relayer {
@@ -30,6 +34,7 @@ pub struct Relayer {
     paid_messages_filter: PaidMessagesFilter,
 
     merkle_root_extractor: MerkleRootExtractor,
+    batcher: Batcher,
     message_sender: MessageSender,
 }
 
@@ -42,6 +47,7 @@ impl MeteredService for Relayer {
             .chain(self.message_paid_listener.get_sources())
             .chain(self.paid_messages_filter.get_sources())
             .chain(self.merkle_root_extractor.get_sources())
+            .chain(self.batcher.get_sources())
             .chain(self.message_sender.get_sources())
     }
 }
@@ -51,17 +57,25 @@ impl Relayer {
         eth_api: EthApi,
         from_block: Option<u32>,
         bridging_payment_address: H256,
+        vft_manager_address: Option<H256>,
         api_provider: ApiProviderConnection,
+        journal: Journal,
+        batch_config: BatchConfig,
     ) -> anyhow::Result<Self> {
         let from_gear_block = if let Some(block) = from_block {
             block
+        } else if let Some(block) = journal.last_processed_gear_block()? {
+            block.0 + 1
         } else {
             let gear_api = api_provider.client();
             let block = gear_api.latest_finalized_block().await?;
             gear_api.block_hash_to_number(block).await?
         };
 
-        let from_eth_block = eth_api.finalized_block_number().await?;
+        let from_eth_block = match journal.last_processed_ethereum_block()? {
+            Some(block) => block.0 + 1,
+            None => eth_api.finalized_block_number().await?,
+        };
 
         log::info!(
             "Starting gear event processing from block #{}",
@@ -73,16 +87,24 @@ impl Relayer {
 
         let ethereum_block_listener = EthereumBlockListener::new(eth_api.clone(), from_eth_block);
 
-        let message_sent_listener = MessageQueuedEventExtractor::new(api_provider.clone());
+        let message_sent_listener =
+            MessageQueuedEventExtractor::new(api_provider.clone(), journal.clone());
+
+        let message_paid_listener = MessagePaidEventExtractor::new(
+            api_provider.clone(),
+            bridging_payment_address,
+            vft_manager_address,
+            journal.clone(),
+        );
 
-        let message_paid_listener =
-            MessagePaidEventExtractor::new(api_provider.clone(), bridging_payment_address);
+        let paid_messages_filter = PaidMessagesFilter::new(journal.clone());
 
-        let paid_messages_filter = PaidMessagesFilter::new();
+        let merkle_root_listener =
+            MerkleRootExtractor::new(eth_api.clone(), api_provider.clone(), journal.clone());
 
-        let merkle_root_listener = MerkleRootExtractor::new(eth_api.clone(), api_provider.clone());
+        let batcher = Batcher::new(batch_config);
 
-        let message_sender = MessageSender::new(eth_api, api_provider);
+        let message_sender = MessageSender::new(eth_api, api_provider, journal);
 
         Ok(Self {
             gear_block_listener,
@@ -94,24 +116,30 @@ impl Relayer {
             paid_messages_filter,
 
             merkle_root_extractor: merkle_root_listener,
+            batcher,
             message_sender,
         })
     }
 
     pub async fn run(self) {
-        let [gear_blocks_0, gear_blocks_1] = self.gear_block_listener.run().await;
+        let [gear_blocks_0, gear_blocks_1, gear_blocks_2] = self.gear_block_listener.run().await;
         let ethereum_blocks = self.ethereum_block_listener.run().await;
 
         let messages = self.message_sent_listener.run(gear_blocks_0).await;
         let paid_messages = self.message_paid_listener.run(gear_blocks_1).await;
 
-        let filtered_messages = self.paid_messages_filter.run(messages, paid_messages).await;
+        let filtered_messages = self
+            .paid_messages_filter
+            .run(messages, paid_messages, gear_blocks_2)
+            .await;
 
         let merkle_roots = self.merkle_root_extractor.run(ethereum_blocks).await;
 
         let accumulator = Accumulator::new();
         let channel_messages = accumulator.run(filtered_messages, merkle_roots).await;
 
-        self.message_sender.run(channel_messages).await;
+        let batches = self.batcher.run(channel_messages).await;
+
+        self.message_sender.run(batches).await;
     }
 }
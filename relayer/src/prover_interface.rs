@@ -1,22 +1,30 @@
-use std::{str::FromStr, time::Instant};
+use std::{future::Future, ops::Range, str::FromStr, time::Instant};
 
 use utils_prometheus::MeteredService;
 
 use gear_rpc_client::{dto, GearApi};
 use num::BigUint;
 use primitive_types::H256;
-use prometheus::{core::Collector, HistogramOpts, HistogramVec};
+use prometheus::{core::Collector, Histogram, HistogramOpts, HistogramVec, IntGauge};
 use prover::proving::{
-    self, BlockFinality, BranchNodeData, GenesisConfig, PreCommit, ProofWithCircuitData,
-    StorageInclusion,
+    BlockFinality, BranchNodeData, ExportedProofWithCircuitData, GenesisConfig, PreCommit,
+    ProofWithCircuitData, StorageInclusion,
 };
+use prover_server::{Job, JobOutput};
+use tokio::sync::mpsc;
+
+/// How many authority set changes can be fetched ahead of the one being proven.
+const PROVING_QUEUE_CAPACITY: usize = 4;
 
 pub struct Metrics;
 
 impl MeteredService for Metrics {
     fn get_sources(&self) -> impl IntoIterator<Item = Box<dyn prometheus::core::Collector>> {
         let proving_time: Box<dyn Collector> = Box::from(PROVING_TIME.clone());
-        [proving_time]
+        let queue_depth: Box<dyn Collector> = Box::from(PROVING_QUEUE_DEPTH.clone());
+        let queue_wait_time: Box<dyn Collector> = Box::from(PROVING_QUEUE_WAIT_TIME.clone());
+        let queue_fetch_time: Box<dyn Collector> = Box::from(PROVING_QUEUE_FETCH_TIME.clone());
+        [proving_time, queue_depth, queue_wait_time, queue_fetch_time]
     }
 }
 
@@ -25,10 +33,63 @@ lazy_static::lazy_static!(
             HistogramOpts::new("proving_time", "ZK circuits proving time"),
             &["circuit"],
         ).unwrap();
+    static ref PROVING_QUEUE_DEPTH: IntGauge = IntGauge::new(
+            "proving_queue_depth",
+            "Authority set changes fetched and waiting to be proven",
+        ).unwrap();
+    static ref PROVING_QUEUE_WAIT_TIME: Histogram = Histogram::with_opts(
+            HistogramOpts::new(
+                "proving_queue_wait_time",
+                "Time authority set change waits in the queue before being proven",
+            ),
+        ).unwrap();
+    static ref PROVING_QUEUE_FETCH_TIME: Histogram = Histogram::with_opts(
+            HistogramOpts::new(
+                "proving_queue_fetch_time",
+                "Time to fetch the data required to prove authority set change",
+            ),
+        ).unwrap();
 );
 
+/// Where the ZK proofs are built.
+#[derive(Clone)]
+pub enum Prover {
+    /// Proofs are built by the relayer itself.
+    Local,
+    /// Proofs are built by the standalone prover service.
+    Remote(prover_server::Client),
+}
+
+impl Prover {
+    async fn prove(&self, job: Job) -> anyhow::Result<JobOutput> {
+        match self {
+            Self::Local => Ok(tokio::task::spawn_blocking(move || job.prove()).await?),
+            Self::Remote(client) => client.prove(&job).await,
+        }
+    }
+
+    async fn prove_recursive(&self, job: Job) -> anyhow::Result<ProofWithCircuitData> {
+        match self.prove(job).await? {
+            JobOutput::ProofWithCircuitData(proof) => Ok(proof),
+            JobOutput::ExportedProofWithCircuitData(_) => Err(anyhow::anyhow!(
+                "Prover returned final proof instead of recursive one"
+            )),
+        }
+    }
+
+    async fn prove_final(&self, job: Job) -> anyhow::Result<ExportedProofWithCircuitData> {
+        match self.prove(job).await? {
+            JobOutput::ExportedProofWithCircuitData(proof) => Ok(proof),
+            JobOutput::ProofWithCircuitData(_) => Err(anyhow::anyhow!(
+                "Prover returned recursive proof instead of final one"
+            )),
+        }
+    }
+}
+
 pub async fn prove_genesis(
     gear_api: &GearApi,
+    prover: &Prover,
     genesis_config: GenesisConfig,
 ) -> anyhow::Result<ProofWithCircuitData> {
     log::info!(
@@ -52,12 +113,16 @@ pub async fn prove_genesis(
 
     let timer = PROVING_TIME.with_label_values(&["genesis"]).start_timer();
 
-    let proof = prover::proving::prove_genesis(
-        parse_rpc_block_finality_proof(current_epoch_block_finality),
-        genesis_config,
-        next_validator_set_inclusion_proof,
-        next_validator_set_storage_data,
-    );
+    let proof = prover
+        .prove_recursive(Job::Genesis {
+            current_epoch_block_finality: parse_rpc_block_finality_proof(
+                current_epoch_block_finality,
+            ),
+            genesis_config,
+            next_validator_set_inclusion_proof,
+            next_validator_set_data: next_validator_set_storage_data,
+        })
+        .await?;
 
     timer.stop_and_record();
     log::info!("Genesis prove time: {}ms", now.elapsed().as_millis());
@@ -65,16 +130,19 @@ pub async fn prove_genesis(
     Ok(proof)
 }
 
-pub async fn prove_validator_set_change(
+/// Data required to prove the change of authority set besides the previous proof.
+pub struct ValidatorSetChangeInputs {
+    previous_authority_set_id: u64,
+    block_finality: BlockFinality,
+    next_validator_set_inclusion_proof: StorageInclusion,
+    next_validator_set_storage_data: Vec<u8>,
+}
+
+pub async fn fetch_validator_set_change_inputs(
     gear_api: &GearApi,
-    previous_proof: ProofWithCircuitData,
     previous_authority_set_id: u64,
-) -> anyhow::Result<ProofWithCircuitData> {
-    log::info!(
-        "Proving authority set change {} -> {}",
-        previous_authority_set_id,
-        previous_authority_set_id + 1
-    );
+) -> anyhow::Result<ValidatorSetChangeInputs> {
+    let timer = PROVING_QUEUE_FETCH_TIME.start_timer();
 
     let (block, current_epoch_block_finality) = gear_api
         .fetch_finality_proof_for_session(previous_authority_set_id)
@@ -84,8 +152,29 @@ pub async fn prove_validator_set_change(
         .fetch_next_session_keys_inclusion_proof(block)
         .await?;
     let next_validator_set_storage_data = next_validator_set_inclusion_proof.stored_data.clone();
-    let next_validator_set_inclusion_proof =
-        parse_rpc_inclusion_proof(next_validator_set_inclusion_proof);
+
+    timer.stop_and_record();
+
+    Ok(ValidatorSetChangeInputs {
+        previous_authority_set_id,
+        block_finality: parse_rpc_block_finality_proof(current_epoch_block_finality),
+        next_validator_set_inclusion_proof: parse_rpc_inclusion_proof(
+            next_validator_set_inclusion_proof,
+        ),
+        next_validator_set_storage_data,
+    })
+}
+
+pub async fn prove_validator_set_change(
+    prover: &Prover,
+    previous_proof: ProofWithCircuitData,
+    inputs: ValidatorSetChangeInputs,
+) -> anyhow::Result<ProofWithCircuitData> {
+    log::info!(
+        "Proving authority set change {} -> {}",
+        inputs.previous_authority_set_id,
+        inputs.previous_authority_set_id + 1
+    );
 
     let now = Instant::now();
 
@@ -93,12 +182,14 @@ pub async fn prove_validator_set_change(
         .with_label_values(&["validator_set_change"])
         .start_timer();
 
-    let proof = proving::prove_validator_set_change(
-        previous_proof,
-        parse_rpc_block_finality_proof(current_epoch_block_finality),
-        next_validator_set_inclusion_proof,
-        next_validator_set_storage_data,
-    );
+    let proof = prover
+        .prove_recursive(Job::ValidatorSetChange {
+            previous_proof,
+            current_epoch_block_finality: inputs.block_finality,
+            next_validator_set_inclusion_proof: inputs.next_validator_set_inclusion_proof,
+            next_validator_set_data: inputs.next_validator_set_storage_data,
+        })
+        .await?;
 
     timer.stop_and_record();
     log::info!("Recursive prove time: {}ms", now.elapsed().as_millis());
@@ -106,6 +197,108 @@ pub async fn prove_validator_set_change(
     Ok(proof)
 }
 
+struct ProvingJob<I> {
+    inputs: I,
+    queued_at: Instant,
+}
+
+/// Prove authority set changes for all the `previous_authority_set_ids` one by one.
+///
+/// Recursive proofs depend on each other so they're built sequentially but the data
+/// for the upcoming changes is fetched from the node while the previous change is being
+/// proven. `on_proven` is called with the id of the newly proven authority set and its proof.
+pub async fn prove_validator_set_changes(
+    gear_api: &GearApi,
+    prover: &Prover,
+    proof: ProofWithCircuitData,
+    previous_authority_set_ids: Range<u64>,
+    on_proven: impl FnMut(u64, &ProofWithCircuitData) -> anyhow::Result<()>,
+) -> anyhow::Result<ProofWithCircuitData> {
+    let gear_api = gear_api.clone();
+
+    prove_pipelined(
+        proof,
+        previous_authority_set_ids,
+        move |previous_authority_set_id| {
+            let gear_api = gear_api.clone();
+            async move {
+                fetch_validator_set_change_inputs(&gear_api, previous_authority_set_id).await
+            }
+        },
+        |proof, inputs| prove_validator_set_change(prover, proof, inputs),
+        on_proven,
+    )
+    .await
+}
+
+async fn prove_pipelined<P, I, FetchFut, ProveFut>(
+    mut proof: P,
+    previous_authority_set_ids: Range<u64>,
+    fetch: impl Fn(u64) -> FetchFut + Send + 'static,
+    mut prove: impl FnMut(P, I) -> ProveFut,
+    mut on_proven: impl FnMut(u64, &P) -> anyhow::Result<()>,
+) -> anyhow::Result<P>
+where
+    I: Send + 'static,
+    FetchFut: Future<Output = anyhow::Result<I>> + Send,
+    ProveFut: Future<Output = anyhow::Result<P>>,
+{
+    let (jobs_sender, mut jobs) = mpsc::channel(PROVING_QUEUE_CAPACITY);
+
+    let fetcher = {
+        let previous_authority_set_ids = previous_authority_set_ids.clone();
+
+        tokio::spawn(async move {
+            for previous_authority_set_id in previous_authority_set_ids {
+                let job = fetch(previous_authority_set_id)
+                    .await
+                    .map(|inputs| ProvingJob {
+                        inputs,
+                        queued_at: Instant::now(),
+                    });
+                let failed = job.is_err();
+
+                // Count the job only once there's room for it in the queue so the
+                // depth doesn't include the job blocked on a full queue.
+                let Ok(permit) = jobs_sender.reserve().await else {
+                    break;
+                };
+                PROVING_QUEUE_DEPTH.inc();
+                permit.send(job);
+
+                if failed {
+                    break;
+                }
+            }
+        })
+    };
+
+    let result = async {
+        for previous_authority_set_id in previous_authority_set_ids {
+            let job = jobs
+                .recv()
+                .await
+                .ok_or_else(|| anyhow::anyhow!("Proving queue closed unexpectedly"))?;
+            PROVING_QUEUE_DEPTH.dec();
+
+            let job = job?;
+            PROVING_QUEUE_WAIT_TIME.observe(job.queued_at.elapsed().as_secs_f64());
+
+            proof = prove(proof, job.inputs).await?;
+
+            on_proven(previous_authority_set_id + 1, &proof)?;
+        }
+
+        Ok::<_, anyhow::Error>(proof)
+    }
+    .await;
+
+    fetcher.abort();
+    PROVING_QUEUE_DEPTH.set(0);
+
+    result
+}
+
 #[derive(Clone)]
 pub struct FinalProof {
     pub proof: Vec<u8>,
@@ -156,6 +349,7 @@ impl FinalProof {
 
 pub async fn prove_final(
     gear_api: &GearApi,
+    prover: &Prover,
     previous_proof: ProofWithCircuitData,
     genesis_config: GenesisConfig,
     at_block: H256,
@@ -163,6 +357,7 @@ pub async fn prove_final(
     let (block, block_finality) = gear_api.fetch_finality_proof(at_block).await?;
     prove_final_with_block_finality(
         gear_api,
+        prover,
         previous_proof,
         genesis_config,
         (block, block_finality),
@@ -172,6 +367,7 @@ pub async fn prove_final(
 
 pub async fn prove_final_with_block_finality(
     gear_api: &GearApi,
+    prover: &Prover,
     previous_proof: ProofWithCircuitData,
     genesis_config: GenesisConfig,
     (block, block_finality): (H256, dto::BlockFinalityProof),
@@ -183,13 +379,15 @@ pub async fn prove_final_with_block_finality(
 
     let timer = PROVING_TIME.with_label_values(&["final"]).start_timer();
 
-    let proof = proving::prove_message_sent(
-        previous_proof,
-        parse_rpc_block_finality_proof(block_finality),
-        genesis_config,
-        sent_message_inclusion_proof,
-        message_contents,
-    );
+    let proof = prover
+        .prove_final(Job::MessageSent {
+            previous_proof,
+            block_finality_proof: parse_rpc_block_finality_proof(block_finality),
+            genesis_config,
+            message_inclusion_proof: sent_message_inclusion_proof,
+            message_contents,
+        })
+        .await?;
 
     let proof = gnark::prove_circuit(&proof);
 
@@ -286,3 +484,83 @@ pub mod gnark {
         serde_json::from_str(&result).expect("Got wrong output from gnark prover")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn pipelined_proving_is_sequential_and_bounded() {
+        let fetched = Arc::new(AtomicU64::new(0));
+
+        let mut proven = vec![];
+        let proof = prove_pipelined(
+            vec![],
+            10..30,
+            {
+                let fetched = fetched.clone();
+                move |previous_authority_set_id| {
+                    fetched.fetch_add(1, Ordering::SeqCst);
+                    async move { Ok(previous_authority_set_id) }
+                }
+            },
+            |mut proof: Vec<u64>, previous_authority_set_id| {
+                let fetched = fetched.load(Ordering::SeqCst);
+                let in_flight = fetched - proof.len() as u64;
+                async move {
+                    // Job being proven, full queue and the job waiting for a free slot.
+                    assert!(in_flight <= PROVING_QUEUE_CAPACITY as u64 + 2);
+
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    proof.push(previous_authority_set_id);
+
+                    Ok(proof)
+                }
+            },
+            |authority_set_id, _| {
+                proven.push(authority_set_id);
+
+                Ok(())
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(proof, (10..30).collect::<Vec<_>>());
+        assert_eq!(proven, (11..31).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn pipelined_proving_stops_on_fetch_error() {
+        let mut proven = vec![];
+        let result = prove_pipelined(
+            0u64,
+            0..10,
+            |previous_authority_set_id| async move {
+                if previous_authority_set_id == 3 {
+                    anyhow::bail!("Failed to fetch");
+                }
+
+                Ok(())
+            },
+            |proof, ()| async move { Ok(proof + 1) },
+            |authority_set_id, _| {
+                proven.push(authority_set_id);
+
+                Ok(())
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(proven, vec![1, 2, 3]);
+    }
+}
@@ -8,15 +8,18 @@ use utils_prometheus::{impl_metered_service, MeteredService};
 
 use crate::{
     common::{
-        self, submit_merkle_root_to_ethereum, sync_authority_set_id, SyncStepCount,
-        BASE_RETRY_DELAY, MAX_RETRIES,
+        self, replace_stuck_transaction, submit_merkle_root_to_ethereum, sync_authority_set_id,
+        SyncStepCount, BASE_RETRY_DELAY, MAX_RETRIES,
     },
     message_relayer::eth_to_gear::api_provider::ApiProviderConnection,
     proof_storage::ProofStorage,
-    prover_interface::{self, FinalProof},
+    prover_interface::{self, FinalProof, Prover},
 };
 
 const MIN_MAIN_LOOP_DURATION: Duration = Duration::from_secs(5);
+/// Maximum number of authority set changes proven in one main loop iteration so
+/// that already submitted transactions keep being tracked while relayer catches up.
+const MAX_SYNC_STEPS_PER_LOOP: u64 = 4;
 
 impl_metered_service! {
     struct Metrics {
@@ -38,6 +41,7 @@ impl_metered_service! {
 pub struct MerkleRootRelayer {
     api_provider: ApiProviderConnection,
     eth_api: EthApi,
+    prover: Prover,
 
     proof_storage: Box<dyn ProofStorage>,
     eras: Eras,
@@ -51,6 +55,7 @@ pub struct MerkleRootRelayer {
 
 struct SubmittedMerkleRoot {
     tx_hash: TxHash,
+    submitted_at: Instant,
     proof: FinalProof,
     finalized: bool,
 }
@@ -69,6 +74,7 @@ impl MerkleRootRelayer {
     pub async fn new(
         api_provider: ApiProviderConnection,
         eth_api: EthApi,
+        prover: Prover,
         genesis_config: GenesisConfig,
         proof_storage: Box<dyn ProofStorage>,
         last_sealed: Option<u64>,
@@ -77,6 +83,7 @@ impl MerkleRootRelayer {
             last_sealed,
             api_provider.clone(),
             eth_api.clone(),
+            prover.clone(),
             genesis_config,
         )
         .await
@@ -87,6 +94,7 @@ impl MerkleRootRelayer {
         MerkleRootRelayer {
             api_provider,
             eth_api,
+            prover,
             genesis_config,
             proof_storage,
             latest_submitted_merkle_root: None,
@@ -154,7 +162,11 @@ impl MerkleRootRelayer {
         let balance = self.eth_api.get_approx_balance().await?;
         self.metrics.fee_payer_balance.set(balance);
 
-        self.sync_authority_set_completely().await?;
+        log::info!("Syncing authority set");
+        let sync_steps = self.sync_authority_set().await?;
+        if sync_steps > 0 {
+            log::info!("Synced {} authority sets", sync_steps);
+        }
 
         self.eras.process(self.proof_storage.as_mut()).await?;
 
@@ -163,23 +175,6 @@ impl MerkleRootRelayer {
         self.try_finalize_submitted_merkle_root().await
     }
 
-    async fn sync_authority_set_completely(&mut self) -> anyhow::Result<()> {
-        log::info!("Syncing authority set");
-
-        loop {
-            let sync_steps = self.sync_authority_set().await?;
-            if sync_steps == 0 {
-                break;
-            } else {
-                log::info!("Synced {} authority sets", sync_steps);
-            }
-        }
-
-        log::info!("Authority set is in sync");
-
-        Ok(())
-    }
-
     async fn sync_authority_set(&mut self) -> anyhow::Result<SyncStepCount> {
         let gear_api = self.api_provider.client();
         let finalized_head = gear_api
@@ -203,10 +198,12 @@ impl MerkleRootRelayer {
 
         sync_authority_set_id(
             &gear_api,
+            &self.prover,
             self.proof_storage.as_mut(),
             self.genesis_config,
             latest_authority_set_id,
             latest_proven_authority_set_id,
+            MAX_SYNC_STEPS_PER_LOOP,
         )
         .await
     }
@@ -246,12 +243,21 @@ impl MerkleRootRelayer {
         );
 
         let authority_set_id = gear_api.signed_by_authority_set_id(finalized_head).await?;
+        if self.proof_storage.get_latest_authority_set_id() < Some(authority_set_id) {
+            log::info!(
+                "Authority set #{} is not proven yet. Skipping",
+                authority_set_id
+            );
+            return Ok(());
+        }
+
         let inner_proof = self
             .proof_storage
             .get_proof_for_authority_set_id(authority_set_id)?;
 
         let proof = prover_interface::prove_final(
             &gear_api,
+            &self.prover,
             inner_proof,
             self.genesis_config,
             finalized_head,
@@ -264,6 +270,7 @@ impl MerkleRootRelayer {
 
         self.latest_submitted_merkle_root = Some(SubmittedMerkleRoot {
             tx_hash,
+            submitted_at: Instant::now(),
             proof,
             finalized: false,
         });
@@ -301,7 +308,14 @@ impl MerkleRootRelayer {
 
                 Ok(())
             }
-            TxStatus::Pending => Ok(()),
+            TxStatus::Pending => {
+                replace_stuck_transaction(
+                    &self.eth_api,
+                    &mut submitted_merkle_root.tx_hash,
+                    &mut submitted_merkle_root.submitted_at,
+                )
+                .await
+            }
             TxStatus::Failed => {
                 let root_exists = self
                     .eth_api
@@ -330,6 +344,7 @@ impl MerkleRootRelayer {
                     submitted_merkle_root.proof.clone(),
                 )
                 .await?;
+                submitted_merkle_root.submitted_at = Instant::now();
 
                 Ok(())
             }
@@ -343,6 +358,7 @@ struct Eras {
 
     api_provider: ApiProviderConnection,
     eth_api: EthApi,
+    prover: Prover,
 
     genesis_config: GenesisConfig,
 
@@ -353,6 +369,7 @@ struct SealedNotFinalizedEra {
     era: u64,
     merkle_root_block: u32,
     tx_hash: TxHash,
+    submitted_at: Instant,
     proof: FinalProof,
 }
 
@@ -377,6 +394,7 @@ impl Eras {
         last_sealed: Option<u64>,
         api_provider: ApiProviderConnection,
         eth_api: EthApi,
+        prover: Prover,
         genesis_config: GenesisConfig,
     ) -> anyhow::Result<Self> {
         let last_sealed = if let Some(l) = last_sealed {
@@ -397,6 +415,7 @@ impl Eras {
             sealed_not_finalized: vec![],
             api_provider,
             eth_api,
+            prover,
 
             genesis_config,
 
@@ -423,8 +442,10 @@ impl Eras {
         let gear_api = self.api_provider.client();
         let latest = gear_api.latest_finalized_block().await?;
         let current_era = gear_api.signed_by_authority_set_id(latest).await?;
+        // Eras are sealed only after the authority set signing them is proven.
+        let latest_proven = proof_storage.get_latest_authority_set_id().unwrap_or(0);
 
-        while self.last_sealed + 2 <= current_era {
+        while self.last_sealed + 2 <= current_era && self.last_sealed < latest_proven {
             log::info!("Sealing era #{}", self.last_sealed + 1);
             self.seal_era(self.last_sealed + 1, proof_storage).await?;
             log::info!("Sealed era #{}", self.last_sealed + 1);
@@ -466,9 +487,14 @@ impl Eras {
         let inner_proof = proof_storage.get_proof_for_authority_set_id(authority_set_id)?;
 
         let instant = Instant::now();
-        let proof =
-            prover_interface::prove_final(&gear_api, inner_proof, self.genesis_config, block)
-                .await?;
+        let proof = prover_interface::prove_final(
+            &gear_api,
+            &self.prover,
+            inner_proof,
+            self.genesis_config,
+            block,
+        )
+        .await?;
         let elapsed_proof = instant.elapsed();
         log::info!("prover_interface::prove_final took {elapsed_proof:?} for block_number = #{block_number}, authority_set_id = #{authority_set_id}");
 
@@ -485,6 +511,7 @@ impl Eras {
             era: authority_set_id,
             merkle_root_block: block_number,
             tx_hash,
+            submitted_at: Instant::now(),
             proof,
         });
 
@@ -521,7 +548,12 @@ impl SealedNotFinalizedEra {
 
         match tx_status {
             TxStatus::Finalized => Ok(true),
-            TxStatus::Pending => Ok(false),
+            TxStatus::Pending => {
+                replace_stuck_transaction(eth_api, &mut self.tx_hash, &mut self.submitted_at)
+                    .await?;
+
+                Ok(false)
+            }
             TxStatus::Failed => {
                 let root_exists = eth_api
                     .read_finalized_merkle_root(self.merkle_root_block)
@@ -537,6 +569,7 @@ impl SealedNotFinalizedEra {
                 log::warn!("Re-trying era #{} finalization", self.era);
 
                 self.tx_hash = submit_merkle_root_to_ethereum(eth_api, self.proof.clone()).await?;
+                self.submitted_at = Instant::now();
                 Ok(false)
             }
         }
@@ -2,9 +2,7 @@ use anyhow::{anyhow, Result as AnyResult};
 use checkpoint_light_client::WASM_BINARY;
 use checkpoint_light_client_client::{checkpoint_light_client_factory, traits::*};
 use checkpoint_light_client_io::{
-    ethereum_common::{
-        base_types::BytesFixed, network::Network, tree_hash::TreeHash, utils as eth_utils,
-    },
+    ethereum_common::{base_types::BytesFixed, network::Network, tree_hash::TreeHash},
     Init, G2,
 };
 use clap::Parser;
@@ -12,7 +10,9 @@ use ethereum_beacon_client::{utils, BeaconClient};
 use gclient::{GearApi, WSAddress};
 use parity_scale_codec::Encode;
 use sails_rs::{calls::*, gclient::calls::*, prelude::*};
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
+
+mod network_config;
 
 const GEAR_API_RETRIES: u8 = 3;
 
@@ -51,14 +51,24 @@ struct Cli {
     #[arg(long, default_value = "120", env = "BEACON_TIMEOUT")]
     beacon_timeout: u64,
 
-    /// Specify the Ethereum network (Mainnet, Holesky or Sepolia)
+    /// Specify the Ethereum network (Mainnet, Holesky, Sepolia, Hoodi or Custom)
     #[arg(long, default_value = "Mainnet", env = "NETWORK")]
     network: String,
 
+    /// Path to the JSON file with parameters of the custom network. Required
+    /// if the network is Custom
+    #[arg(long, env = "NETWORK_CONFIG")]
+    network_config: Option<PathBuf>,
+
     /// Specify the checkpoint slot for bootstrapping. If it is None then the header from
     /// the latest finality update is used to get the slot.
     #[arg(long, env = "SLOT_CHECKPOINT")]
     slot_checkpoint: Option<u64>,
+
+    /// Specify the maximum amount of checkpoints stored by the program. If it is None
+    /// then the default window is used.
+    #[arg(long, env = "CHECKPOINTS_WINDOW")]
+    checkpoints_window: Option<u32>,
 }
 
 #[tokio::main]
@@ -75,6 +85,12 @@ async fn main() -> AnyResult<()> {
         Network::Sepolia
     } else if network == "hoodi" {
         Network::Hoodi
+    } else if network == "custom" {
+        let path = cli
+            .network_config
+            .ok_or_else(|| anyhow!("Custom network requires --network-config"))?;
+
+        Network::Custom(network_config::load(&path)?)
     } else {
         return Err(anyhow!("Network '{network}' is not supported"));
     };
@@ -93,7 +109,7 @@ async fn main() -> AnyResult<()> {
             update.finalized_header.slot
         }
     };
-    let current_period = eth_utils::calculate_period(slot);
+    let current_period = network.calculate_period(slot);
     let mut updates = beacon_client.get_updates(current_period, 1).await?;
 
     println!(
@@ -137,6 +153,7 @@ async fn main() -> AnyResult<()> {
             .collect(),
         update: sync_update,
         sync_aggregate_encoded,
+        checkpoints_window: cli.checkpoints_window,
     };
 
     let api = GearApi::builder()
@@ -0,0 +1,116 @@
+//! Parameters of a custom Ethereum network (e.g. a private devnet) loaded from a JSON file:
+//!
+//! ```json
+//! {
+//!     "genesis_validators_root": "0x83431ec7fcf92cfc44947fc0418e831c25e1d0806590231c439830db7ad54fda",
+//!     "slots_per_epoch": 32,
+//!     "forks": [
+//!         { "name": "Deneb", "version": "0x50000038", "epoch": 0 },
+//!         { "name": "Electra", "version": "0x60000038", "epoch": 10 }
+//!     ]
+//! }
+//! ```
+//!
+//! Only the mainnet preset is supported. Sync committee parameters may be specified with
+//! `sync_committee_size` and `epochs_per_sync_committee` fields to make sure the network
+//! uses the preset, configs with other values are rejected.
+
+use anyhow::{anyhow, Result as AnyResult};
+use checkpoint_light_client_io::ethereum_common::{
+    network::{CustomNetwork, Fork, ForkName},
+    EPOCHS_PER_SYNC_COMMITTEE, SLOTS_PER_EPOCH, SYNC_COMMITTEE_SIZE,
+};
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+#[derive(Deserialize)]
+struct NetworkConfig {
+    genesis_validators_root: String,
+    #[serde(default = "default_slots_per_epoch")]
+    slots_per_epoch: u64,
+    #[serde(default = "default_sync_committee_size")]
+    sync_committee_size: usize,
+    #[serde(default = "default_epochs_per_sync_committee")]
+    epochs_per_sync_committee: u64,
+    forks: Vec<ForkConfig>,
+}
+
+#[derive(Deserialize)]
+struct ForkConfig {
+    name: String,
+    version: String,
+    epoch: u64,
+}
+
+fn default_slots_per_epoch() -> u64 {
+    SLOTS_PER_EPOCH
+}
+
+fn default_sync_committee_size() -> usize {
+    SYNC_COMMITTEE_SIZE
+}
+
+fn default_epochs_per_sync_committee() -> u64 {
+    EPOCHS_PER_SYNC_COMMITTEE
+}
+
+pub fn load(path: &Path) -> AnyResult<CustomNetwork> {
+    let config = fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read network config {path:?}: {e}"))?;
+    let config: NetworkConfig = serde_json::from_str(&config)
+        .map_err(|e| anyhow!("Failed to parse network config {path:?}: {e}"))?;
+
+    if config.sync_committee_size != SYNC_COMMITTEE_SIZE
+        || config.epochs_per_sync_committee != EPOCHS_PER_SYNC_COMMITTEE
+    {
+        return Err(anyhow!(
+            "Only the mainnet preset is supported: sync committee size should be \
+            {SYNC_COMMITTEE_SIZE} and sync committee period should be {EPOCHS_PER_SYNC_COMMITTEE} epochs"
+        ));
+    }
+
+    let forks = config
+        .forks
+        .into_iter()
+        .map(|fork| {
+            Ok(Fork {
+                name: parse_fork_name(&fork.name)?,
+                version: decode_hex(&fork.version)?,
+                epoch: fork.epoch,
+            })
+        })
+        .collect::<AnyResult<_>>()?;
+
+    let network = CustomNetwork {
+        genesis_validators_root: decode_hex(&config.genesis_validators_root)?,
+        forks,
+        slots_per_epoch: config.slots_per_epoch,
+    };
+
+    if !network.is_valid() {
+        return Err(anyhow!(
+            "Network config should have non-zero slots per epoch and non-empty fork schedule \
+            ordered by the activation epoch"
+        ));
+    }
+
+    Ok(network)
+}
+
+fn parse_fork_name(name: &str) -> AnyResult<ForkName> {
+    match name.to_lowercase().as_str() {
+        "deneb" => Ok(ForkName::Deneb),
+        "electra" => Ok(ForkName::Electra),
+        "fulu" => Ok(ForkName::Fulu),
+        _ => Err(anyhow!("Fork '{name}' is not supported")),
+    }
+}
+
+fn decode_hex<const N: usize>(value: &str) -> AnyResult<[u8; N]> {
+    let bytes = hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| anyhow!("Failed to decode '{value}': {e}"))?;
+
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("Expected {N} bytes in '{value}'"))
+}